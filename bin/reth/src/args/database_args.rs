@@ -1,8 +1,11 @@
 //! clap [Args](clap::Args) for database configuration
 
 use clap::Args;
+use reth_db::mdbx::DatabaseGeometry;
 use reth_interfaces::db::LogLevel;
 
+const GIGABYTE: usize = 1024 * 1024 * 1024;
+
 /// Parameters for database configuration
 #[derive(Debug, Args, PartialEq, Default, Clone, Copy)]
 #[command(next_help_heading = "Database")]
@@ -10,4 +13,61 @@ pub struct DatabaseArgs {
     /// Database logging level. Levels higher than "notice" require a debug build.
     #[arg(long = "db.log-level", value_enum)]
     pub log_level: Option<LogLevel>,
+
+    /// The maximum size of the database in gigabytes. [default: 4096]
+    ///
+    /// The space is reserved in virtual memory and only allocated on disk as the database grows,
+    /// provided the filesystem supports sparse files. It must not be lower than the current size
+    /// of the database.
+    #[arg(long = "db.max-size", value_name = "GB")]
+    pub max_size: Option<usize>,
+
+    /// The step in gigabytes the database file grows by once it is full. [default: 4]
+    #[arg(long = "db.growth-step", value_name = "GB")]
+    pub growth_step: Option<usize>,
+}
+
+impl DatabaseArgs {
+    /// Returns the configured [DatabaseGeometry], falling back to the default values.
+    pub fn geometry(&self) -> DatabaseGeometry {
+        let default = DatabaseGeometry::default();
+        DatabaseGeometry {
+            max_size: self.max_size.map_or(default.max_size, |size| size.saturating_mul(GIGABYTE)),
+            growth_step: self
+                .growth_step
+                .map_or(default.growth_step, |step| step.saturating_mul(GIGABYTE)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    /// A helper type to parse Args more easily
+    #[derive(Parser)]
+    struct CommandParser<T: Args> {
+        #[clap(flatten)]
+        args: T,
+    }
+
+    #[test]
+    fn test_parse_database_geometry() {
+        let args = CommandParser::<DatabaseArgs>::parse_from(["reth"]).args;
+        assert_eq!(args.geometry(), DatabaseGeometry::default());
+
+        let args = CommandParser::<DatabaseArgs>::parse_from([
+            "reth",
+            "--db.max-size",
+            "8192",
+            "--db.growth-step",
+            "16",
+        ])
+        .args;
+        assert_eq!(
+            args.geometry(),
+            DatabaseGeometry { max_size: 8192 * GIGABYTE, growth_step: 16 * GIGABYTE }
+        );
+    }
 }
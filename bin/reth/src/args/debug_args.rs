@@ -57,4 +57,9 @@ pub struct DebugArgs {
         conflicts_with = "hook_transaction"
     )]
     pub hook_all: bool,
+
+    /// Unwind all stages to the last consistent block on startup if their checkpoints are
+    /// inconsistent, instead of refusing to start.
+    #[arg(long = "debug.unwind-inconsistent", help_heading = "Debug")]
+    pub unwind_inconsistent: bool,
 }
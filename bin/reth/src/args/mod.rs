@@ -35,4 +35,8 @@ pub use gas_price_oracle_args::GasPriceOracleArgs;
 mod txpool_args;
 pub use txpool_args::TxPoolArgs;
 
+/// TreeArgs for configuring the blockchain tree
+mod tree_args;
+pub use tree_args::TreeArgs;
+
 pub mod utils;
@@ -22,7 +22,9 @@ pub struct NetworkArgs {
     #[arg(long)]
     pub trusted_peers: Vec<NodeRecord>,
 
-    /// Connect only to trusted peers
+    /// Connect only to trusted peers.
+    ///
+    /// This disables discovery, and incoming connections from untrusted peers are rejected.
     #[arg(long)]
     pub trusted_only: bool,
 
@@ -89,7 +91,10 @@ impl NetworkArgs {
         network_config_builder = network_config_builder
             .hello_message(HelloMessage::builder(peer_id).client_version(&self.identity).build());
 
-        self.discovery.apply_to_builder(network_config_builder)
+        // discovered peers are never connected to if only trusted peers are allowed
+        self.discovery
+            .apply_to_builder(network_config_builder)
+            .disable_discovery_if(self.trusted_only)
     }
 }
 
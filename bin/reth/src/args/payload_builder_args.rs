@@ -3,7 +3,10 @@ use clap::{
     builder::{RangedU64ValueParser, TypedValueParser},
     Arg, Args, Command,
 };
-use reth_primitives::{bytes::BytesMut, constants::MAXIMUM_EXTRA_DATA_SIZE};
+use reth_primitives::{
+    bytes::BytesMut,
+    constants::{MAXIMUM_EXTRA_DATA_SIZE, MINIMUM_GAS_LIMIT},
+};
 use reth_rlp::Encodable;
 use std::{ffi::OsStr, time::Duration};
 
@@ -14,14 +17,18 @@ pub struct PayloadBuilderArgs {
     #[arg(long = "builder.extradata", help_heading = "Builder", value_parser=ExtradataValueParser::default(),  default_value_t = default_extradata())]
     pub extradata: String,
 
-    /// Target gas ceiling for built blocks.
+    /// Target gas limit for built blocks.
+    ///
+    /// The gas limit of each built block moves from the parent's gas limit towards this target by
+    /// at most the protocol bound of parent_gas_limit / 1024.
     #[arg(
         long = "builder.gaslimit",
         help_heading = "Builder",
         default_value = "30000000",
-        value_name = "GAS_LIMIT"
+        value_name = "GAS_LIMIT",
+        value_parser = RangedU64ValueParser::<u64>::new().range(MINIMUM_GAS_LIMIT..)
     )]
-    pub max_gas_limit: u64,
+    pub desired_gas_limit: u64,
 
     /// The interval at which the job should build a new payload after the last (in seconds).
     #[arg(long = "builder.interval", help_heading = "Builder", value_parser = parse_duration_from_secs, default_value = "1", value_name = "SECONDS")]
@@ -102,6 +109,16 @@ mod tests {
         .is_err());
     }
 
+    #[test]
+    fn test_args_with_invalid_gas_limit() {
+        assert!(CommandParser::<PayloadBuilderArgs>::try_parse_from([
+            "reth",
+            "--builder.gaslimit",
+            "4999"
+        ])
+        .is_err());
+    }
+
     #[test]
     fn test_default_extradata() {
         let extradata = default_extradata();
@@ -8,8 +8,8 @@ use clap::{
 use futures::TryFutureExt;
 use reth_network_api::{NetworkInfo, Peers};
 use reth_provider::{
-    BlockReaderIdExt, CanonStateSubscriptions, ChainSpecProvider, EvmEnvProvider, HeaderProvider,
-    StateProviderFactory,
+    BlockReaderIdExt, CanonStateSubscriptions, ChainSpecProvider, EvmEnvProvider,
+    ForkchoiceSubscriptions, HeaderProvider, StateProviderFactory,
 };
 use reth_rpc::{
     eth::{
@@ -17,6 +17,7 @@ use reth_rpc::{
             DEFAULT_BLOCK_CACHE_MAX_LEN, DEFAULT_ENV_CACHE_MAX_LEN, DEFAULT_RECEIPT_CACHE_MAX_LEN,
         },
         gas_oracle::GasPriceOracleConfig,
+        EstimateGasConfig, DEFAULT_ESTIMATE_GAS_MAX_ITERATIONS,
     },
     JwtError, JwtSecret,
 };
@@ -47,6 +48,12 @@ pub(crate) const RPC_DEFAULT_MAX_RESPONSE_SIZE_MB: u32 = 100;
 pub(crate) const RPC_DEFAULT_MAX_CONNECTIONS: u32 = 100;
 /// Default number of incoming connections.
 pub(crate) const RPC_DEFAULT_MAX_TRACING_REQUESTS: u32 = 25;
+/// Default max number of logs returned by `eth_getLogs`.
+pub(crate) const RPC_DEFAULT_MAX_LOGS_PER_RESPONSE: usize = 20_000;
+/// Default max block range of `eth_getLogs`.
+pub(crate) const RPC_DEFAULT_MAX_BLOCKS_PER_FILTER: u64 = 100_000;
+/// Default max block range of `trace_filter`.
+pub(crate) const RPC_DEFAULT_MAX_TRACE_FILTER_BLOCKS: u64 = 100;
 
 /// Parameters for configuring the rpc more granularity via CLI
 #[derive(Debug, Args, PartialEq, Eq, Default)]
@@ -132,6 +139,38 @@ pub struct RpcServerArgs {
     #[arg(long, value_name = "COUNT", default_value_t = RPC_DEFAULT_MAX_TRACING_REQUESTS)]
     pub rpc_max_tracing_requests: u32,
 
+    /// Maximum number of logs that can be returned in a single `eth_getLogs` response.
+    ///
+    /// Set to 0 to disable the limit.
+    #[arg(long, value_name = "COUNT", default_value_t = RPC_DEFAULT_MAX_LOGS_PER_RESPONSE)]
+    pub rpc_max_logs_per_response: usize,
+
+    /// Maximum number of blocks that can be queried in a single `eth_getLogs` call.
+    ///
+    /// Set to 0 to disable the limit.
+    #[arg(long, value_name = "COUNT", default_value_t = RPC_DEFAULT_MAX_BLOCKS_PER_FILTER)]
+    pub rpc_max_blocks_per_filter: u64,
+
+    /// Maximum number of blocks that can be traced in a single `trace_filter` call.
+    ///
+    /// Set to 0 to disable the limit.
+    #[arg(long, value_name = "COUNT", default_value_t = RPC_DEFAULT_MAX_TRACE_FILTER_BLOCKS)]
+    pub rpc_max_trace_filter_blocks: u64,
+
+    /// Percentage that is added to the `eth_estimateGas` estimate, e.g. 10 for a 10% higher
+    /// estimate.
+    #[arg(long, value_name = "PERCENT", default_value_t = 0)]
+    pub rpc_gas_estimate_padding: u64,
+
+    /// Maximum number of executions of the `eth_estimateGas` binary search.
+    #[arg(long, value_name = "COUNT", default_value_t = DEFAULT_ESTIMATE_GAS_MAX_ITERATIONS)]
+    pub rpc_gas_estimate_max_iterations: u64,
+
+    /// Start the `eth_estimateGas` binary search of requests without an access list from the gas
+    /// used with the access list created by `eth_createAccessList`.
+    #[arg(long)]
+    pub rpc_gas_estimate_with_access_list: bool,
+
     /// Gas price oracle configuration.
     #[clap(flatten)]
     pub gas_price_oracle: GasPriceOracleArgs,
@@ -174,7 +213,20 @@ impl RpcServerArgs {
     pub fn eth_config(&self) -> EthConfig {
         EthConfig::default()
             .max_tracing_requests(self.rpc_max_tracing_requests)
+            .max_logs_per_response(self.rpc_max_logs_per_response)
+            .max_blocks_per_filter(self.rpc_max_blocks_per_filter)
+            .max_trace_filter_blocks(self.rpc_max_trace_filter_blocks)
             .gpo_config(self.gas_price_oracle_config())
+            .estimate_gas_config(self.estimate_gas_config())
+    }
+
+    /// Extracts the `eth_estimateGas` config from the args.
+    pub fn estimate_gas_config(&self) -> EstimateGasConfig {
+        EstimateGasConfig {
+            padding_percent: self.rpc_gas_estimate_padding,
+            max_iterations: self.rpc_gas_estimate_max_iterations,
+            with_access_list: self.rpc_gas_estimate_with_access_list,
+        }
     }
 
     /// Convenience function that returns whether ipc is enabled
@@ -237,6 +289,7 @@ impl RpcServerArgs {
             + StateProviderFactory
             + EvmEnvProvider
             + ChainSpecProvider
+            + ForkchoiceSubscriptions
             + Clone
             + Unpin
             + 'static,
@@ -298,6 +351,7 @@ impl RpcServerArgs {
             + StateProviderFactory
             + EvmEnvProvider
             + ChainSpecProvider
+            + ForkchoiceSubscriptions
             + Clone
             + Unpin
             + 'static,
@@ -0,0 +1,91 @@
+//! clap [Args](clap::Args) for blockchain tree configuration
+
+use clap::{builder::RangedU64ValueParser, Args};
+use reth_blockchain_tree::{BlockchainTreeConfig, StateRootValidation};
+
+/// Parameters for configuring the in-memory blockchain tree
+#[derive(Debug, Args, PartialEq, Eq, Clone, Copy)]
+#[command(next_help_heading = "Blockchain tree")]
+pub struct TreeArgs {
+    /// The number of blocks below the canonical tip that can be reorged.
+    ///
+    /// Side chains that fork off deeper than this are discarded.
+    #[arg(long = "tree.max-reorg-depth", value_name = "BLOCKS", default_value_t = 64, value_parser = RangedU64ValueParser::<u64>::new().range(1..))]
+    pub max_reorg_depth: u64,
+
+    /// Maximum number of side chains kept in memory.
+    ///
+    /// If exceeded, the side chains with the lowest tips are evicted first.
+    #[arg(long = "tree.max-side-chains", value_name = "COUNT", default_value_t = 64, value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
+    pub max_side_chains: usize,
+
+    /// Maximum number of buffered blocks whose parent is not known yet.
+    ///
+    /// If exceeded, the least recently inserted blocks are evicted first.
+    #[arg(long = "tree.max-buffered-blocks", value_name = "COUNT", default_value_t = 200, value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
+    pub max_buffered_blocks: usize,
+
+    /// When the state root of new blocks is verified: `every-block`, `final`, or an interval in
+    /// blocks.
+    ///
+    /// With `final`, only the tip of a chain is verified when it is made canonical. With an
+    /// interval, blocks whose number is a multiple of it are verified as well.
+    ///
+    /// UNSAFE: deferring the verification reports blocks as valid before their state root is
+    /// checked. Only use it when syncing from a trusted consensus client.
+    #[arg(long = "tree.state-root-validation", value_name = "MODE", default_value_t = StateRootValidation::EveryBlock)]
+    pub state_root_validation: StateRootValidation,
+}
+
+impl TreeArgs {
+    /// Returns the blockchain tree configuration for the arguments.
+    pub fn tree_config(&self) -> BlockchainTreeConfig {
+        let default = BlockchainTreeConfig::default();
+        BlockchainTreeConfig::new(
+            self.max_reorg_depth,
+            self.max_reorg_depth + 1,
+            default.num_of_additional_canonical_block_hashes(),
+            self.max_buffered_blocks,
+        )
+        .with_max_side_chains(self.max_side_chains)
+        .with_state_root_validation(self.state_root_validation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    /// A helper type to parse Args more easily
+    #[derive(Parser)]
+    struct CommandParser<T: Args> {
+        #[clap(flatten)]
+        args: T,
+    }
+
+    #[test]
+    fn test_default_tree_args() {
+        let config = CommandParser::<TreeArgs>::parse_from(["reth"]).args.tree_config();
+        let default = BlockchainTreeConfig::default();
+        assert_eq!(config.max_reorg_depth(), default.max_reorg_depth());
+        assert_eq!(config.max_blocks_in_chain(), default.max_blocks_in_chain());
+        assert_eq!(config.max_unconnected_blocks(), default.max_unconnected_blocks());
+        assert_eq!(config.max_side_chains(), default.max_side_chains());
+        assert_eq!(config.state_root_validation(), StateRootValidation::EveryBlock);
+    }
+
+    #[test]
+    fn test_state_root_validation_args() {
+        let args =
+            CommandParser::<TreeArgs>::parse_from(["reth", "--tree.state-root-validation", "1000"])
+                .args;
+        assert_eq!(args.state_root_validation, StateRootValidation::Checkpoints(1000));
+    }
+
+    #[test]
+    fn test_invalid_tree_args() {
+        assert!(CommandParser::<TreeArgs>::try_parse_from(["reth", "--tree.max-side-chains", "0"])
+            .is_err());
+    }
+}
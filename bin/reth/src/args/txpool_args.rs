@@ -1,10 +1,11 @@
 //! Transaction pool arguments
 
-use clap::Args;
+use clap::{builder::RangedU64ValueParser, Args};
 use reth_transaction_pool::{
     PoolConfig, SubPoolLimit, TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
     TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT, TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
 };
+use std::path::PathBuf;
 
 /// Parameters for debugging purposes
 #[derive(Debug, Args, PartialEq, Default)]
@@ -15,6 +16,14 @@ pub struct TxPoolArgs {
     /// Max size of the pending sub-pool in megabytes.
     #[arg(long = "txpool.pending_max_size", help_heading = "TxPool", default_value_t = TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT)]
     pub pending_max_size: usize,
+    /// Max number of transactions of a single sender in the pending sub-pool. Unlimited by
+    /// default.
+    ///
+    /// If exceeded, the sender's transactions with the highest nonces are evicted. Remote senders
+    /// can't have more than `--txpool.max_account_slots` transactions across all sub-pools, so a
+    /// higher value only limits local transactions.
+    #[arg(long = "txpool.pending_max_per_sender", help_heading = "TxPool", value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
+    pub pending_max_per_sender: Option<usize>,
 
     /// Max number of transaction in the basefee sub-pool
     #[arg(long = "txpool.basefee_max_count", help_heading = "TxPool", default_value_t = TXPOOL_SUBPOOL_MAX_TXS_DEFAULT)]
@@ -29,10 +38,28 @@ pub struct TxPoolArgs {
     /// Max size of the queued sub-pool in megabytes.
     #[arg(long = "txpool.queued_max_size", help_heading = "TxPool", default_value_t = TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT)]
     pub queued_max_size: usize,
+    /// Max number of transactions of a single sender in the queued sub-pool. Unlimited by
+    /// default.
+    ///
+    /// If exceeded, the sender's transactions with the highest nonces are evicted. Remote senders
+    /// can't have more than `--txpool.max_account_slots` transactions across all sub-pools, so a
+    /// higher value only limits local transactions.
+    #[arg(long = "txpool.queued_max_per_sender", help_heading = "TxPool", value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
+    pub queued_max_per_sender: Option<usize>,
 
     /// Max number of executable transaction slots guaranteed per account
+    ///
+    /// New remote transactions of a sender that already has this many transactions in the pool
+    /// are rejected. The per-sender limits of the sub-pools apply on top of this.
     #[arg(long = "txpool.max_account_slots", help_heading = "TxPool", default_value_t = TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER)]
     pub max_account_slots: usize,
+
+    /// Imports the RLP encoded signed transactions in the given file into the pool on startup.
+    ///
+    /// Transactions are validated like any other external transaction, invalid transactions are
+    /// skipped.
+    #[arg(long = "txpool.import", value_name = "FILE", help_heading = "TxPool")]
+    pub import: Option<PathBuf>,
 }
 
 impl TxPoolArgs {
@@ -42,16 +69,60 @@ impl TxPoolArgs {
             pending_limit: SubPoolLimit {
                 max_txs: self.pending_max_count,
                 max_size: self.pending_max_size * 1024 * 1024,
+                max_txs_per_sender: self.pending_max_per_sender,
             },
             basefee_limit: SubPoolLimit {
                 max_txs: self.basefee_max_count,
                 max_size: self.basefee_max_size * 1024 * 1024,
+                max_txs_per_sender: None,
             },
             queued_limit: SubPoolLimit {
                 max_txs: self.queued_max_count,
                 max_size: self.queued_max_size * 1024 * 1024,
+                max_txs_per_sender: self.queued_max_per_sender,
             },
             max_account_slots: self.max_account_slots,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    /// A helper type to parse Args more easily
+    #[derive(Parser)]
+    struct CommandParser<T: Args> {
+        #[clap(flatten)]
+        args: T,
+    }
+
+    #[test]
+    fn test_parse_max_per_sender() {
+        let args = CommandParser::<TxPoolArgs>::parse_from(["reth"]).args;
+        let config = args.pool_config();
+        assert_eq!(config.pending_limit.max_txs_per_sender, None);
+        assert_eq!(config.queued_limit.max_txs_per_sender, None);
+
+        let args = CommandParser::<TxPoolArgs>::parse_from([
+            "reth",
+            "--txpool.pending_max_per_sender",
+            "4",
+            "--txpool.queued_max_per_sender",
+            "8",
+        ])
+        .args;
+        let config = args.pool_config();
+        assert_eq!(config.pending_limit.max_txs_per_sender, Some(4));
+        assert_eq!(config.queued_limit.max_txs_per_sender, Some(8));
+
+        // a limit of zero would evict all transactions of every sender
+        assert!(CommandParser::<TxPoolArgs>::try_parse_from([
+            "reth",
+            "--txpool.pending_max_per_sender",
+            "0"
+        ])
+        .is_err());
+    }
+}
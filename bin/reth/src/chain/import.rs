@@ -12,7 +12,7 @@ use reth_provider::{ProviderFactory, StageCheckpointReader};
 
 use crate::args::{utils::genesis_value_parser, DatabaseArgs};
 use reth_config::Config;
-use reth_db::{database::Database, init_db};
+use reth_db::{database::Database, init_db_with_geometry};
 use reth_downloaders::{
     bodies::bodies::BodiesDownloaderBuilder,
     headers::reverse_headers::ReverseHeadersDownloaderBuilder, test_utils::FileClient,
@@ -90,7 +90,7 @@ impl ImportCommand {
         let db_path = data_dir.db_path();
 
         info!(target: "reth::cli", path = ?db_path, "Opening database");
-        let db = Arc::new(init_db(db_path, self.db.log_level)?);
+        let db = Arc::new(init_db_with_geometry(db_path, self.db.log_level, self.db.geometry())?);
         info!(target: "reth::cli", "Database opened");
 
         debug!(target: "reth::cli", chain=%self.chain.chain, genesis=?self.chain.genesis_hash(), "Initializing genesis");
@@ -175,18 +175,20 @@ impl ImportCommand {
                     TotalDifficultyStage::new(consensus.clone())
                         .with_commit_threshold(config.stages.total_difficulty.commit_threshold),
                 )
-                .set(SenderRecoveryStage {
-                    commit_threshold: config.stages.sender_recovery.commit_threshold,
-                })
+                .set(
+                    SenderRecoveryStage::new(config.stages.sender_recovery.commit_threshold)
+                        .with_threads(config.stages.sender_recovery.threads),
+                )
                 .set(ExecutionStage::new(
                     factory,
                     ExecutionStageThresholds {
                         max_blocks: config.stages.execution.max_blocks,
                         max_changes: config.stages.execution.max_changes,
+                        max_cumulative_gas: config.stages.execution.max_cumulative_gas,
                     },
                 )),
             )
-            .build(db, self.chain.clone());
+            .try_build(db, self.chain.clone())?;
 
         let events = pipeline.events().map(Into::into);
 
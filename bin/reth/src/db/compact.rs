@@ -0,0 +1,161 @@
+//! Offline compaction of the database.
+
+use eyre::WrapErr;
+use human_bytes::human_bytes;
+use reth_db::{
+    mdbx::{
+        Environment, EnvironmentFlags, EnvironmentKind, Error as MdbxError, Mode, SyncMode,
+        WriteMap, DATA_FILE_NAME,
+    },
+    open_db_read_only, Tables,
+};
+use reth_interfaces::db::LogLevel;
+use std::{borrow::Cow, fs, path::Path};
+use tracing::info;
+
+/// Copies the database at `db_path` to a new data file without its free pages, verifies every
+/// entry of the copy and atomically replaces the original data file with it.
+///
+/// The database is opened exclusively and stays open until the data file is replaced, so this
+/// fails if it is in use by another process and no other process can open it during the swap.
+pub(crate) fn compact(db_path: &Path, log_level: Option<LogLevel>) -> eyre::Result<()> {
+    let data_file = db_path.join(DATA_FILE_NAME);
+    let size_before = fs::metadata(&data_file)
+        .wrap_err_with(|| format!("Could not find database file {}", data_file.display()))?
+        .len();
+
+    let compact_dir = db_path.with_extension("compact");
+    if compact_dir.exists() {
+        eyre::bail!(
+            "{} already exists, remove the leftovers of a previous compaction first",
+            compact_dir.display()
+        )
+    }
+
+    let env = Environment::<WriteMap>::new()
+        .set_max_dbs(Tables::ALL.len())
+        .set_flags(EnvironmentFlags {
+            mode: Mode::ReadWrite { sync_mode: SyncMode::Durable },
+            exclusive: true,
+            ..Default::default()
+        })
+        .open(db_path)
+        .map_err(|err| match err {
+            MdbxError::Busy => eyre::eyre!(
+                "Database is in use by another process, stop the node before compacting"
+            ),
+            err => eyre::eyre!("Could not open database exclusively: {err}"),
+        })?;
+
+    info!(target: "reth::cli", path = ?compact_dir, "Copying database");
+    fs::create_dir_all(&compact_dir)?;
+    let compact_file = compact_dir.join(DATA_FILE_NAME);
+    env.copy(&compact_file, true)?;
+
+    info!(target: "reth::cli", "Verifying copy");
+    {
+        let copy = open_db_read_only(&compact_dir, log_level)?;
+        if let Some(table) = first_mismatch(&env, &copy.inner)? {
+            eyre::bail!(
+                "Table {} of the copy at {} doesn't match the database, the original is left untouched",
+                table.name(),
+                compact_dir.display()
+            )
+        }
+    }
+
+    // The rename replaces the original data file in a single step, while the exclusive environment
+    // keeps other processes from opening the database.
+    fs::rename(&compact_file, &data_file)?;
+    drop(env);
+    fs::remove_dir_all(&compact_dir)?;
+
+    let size_after = fs::metadata(&data_file)?.len();
+    println!(
+        "Compacted database from {} to {}, reclaimed {}",
+        human_bytes(size_before as f64),
+        human_bytes(size_after as f64),
+        human_bytes(size_before.saturating_sub(size_after) as f64)
+    );
+
+    Ok(())
+}
+
+/// Compares the raw entries of every table of both environments and returns the first table that
+/// differs.
+fn first_mismatch<E: EnvironmentKind, F: EnvironmentKind>(
+    original: &Environment<E>,
+    copy: &Environment<F>,
+) -> eyre::Result<Option<Tables>> {
+    let original_tx = original.begin_ro_txn()?;
+    let copy_tx = copy.begin_ro_txn()?;
+    for table in Tables::ALL {
+        let original_db = original_tx.open_db(Some(table.name())).wrap_err("Could not open db.")?;
+        let copy_db = copy_tx.open_db(Some(table.name())).wrap_err("Could not open db.")?;
+        let mut original_cursor = original_tx.cursor(&original_db)?;
+        let mut copy_cursor = copy_tx.cursor(&copy_db)?;
+        let mut original_entries = original_cursor.iter_start::<Cow<'_, [u8]>, Cow<'_, [u8]>>();
+        let mut copy_entries = copy_cursor.iter_start::<Cow<'_, [u8]>, Cow<'_, [u8]>>();
+        loop {
+            match (original_entries.next().transpose()?, copy_entries.next().transpose()?) {
+                (None, None) => break,
+                (original, copy) if original == copy => {}
+                _ => return Ok(Some(table)),
+            }
+        }
+    }
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_db::{database::Database, init_db, table::Table, tables, transaction::DbTxMut};
+    use reth_primitives::H256;
+
+    #[test]
+    fn compact_database() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let db_path = dir.path().join("db");
+        {
+            let db = init_db(&db_path, None).unwrap();
+            db.update(|tx| {
+                for number in 0..1000 {
+                    tx.put::<tables::CanonicalHeaders>(number, Default::default()).unwrap();
+                }
+            })
+            .unwrap();
+            db.update(|tx| tx.clear::<tables::CanonicalHeaders>().unwrap()).unwrap();
+            db.update(|tx| tx.put::<tables::CanonicalHeaders>(0, Default::default()).unwrap())
+                .unwrap();
+        }
+
+        compact(&db_path, None).unwrap();
+        assert!(!db_path.with_extension("compact").exists());
+
+        let db = open_db_read_only(&db_path, None).unwrap();
+        let tx = db.begin_ro_txn().unwrap();
+        let table = tx.open_db(Some(tables::CanonicalHeaders::NAME)).unwrap();
+        assert_eq!(tx.db_stat(&table).unwrap().entries(), 1);
+    }
+
+    #[test]
+    fn compare_table_contents() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let original = init_db(dir.path().join("original"), None).unwrap();
+        let copy = init_db(dir.path().join("copy"), None).unwrap();
+        for db in [&original, &copy] {
+            db.update(|tx| tx.put::<tables::CanonicalHeaders>(0, Default::default()).unwrap())
+                .unwrap();
+        }
+        assert_eq!(first_mismatch(&original.inner, &copy.inner).unwrap(), None);
+
+        // same number of entries, different value
+        copy.update(|tx| tx.put::<tables::CanonicalHeaders>(0, H256::repeat_byte(1)).unwrap())
+            .unwrap();
+        assert_eq!(
+            first_mismatch(&original.inner, &copy.inner).unwrap(),
+            Some(Tables::CanonicalHeaders)
+        );
+    }
+}
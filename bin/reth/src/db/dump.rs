@@ -0,0 +1,222 @@
+use super::get::maybe_json_value_parser;
+use crate::utils::DbTool;
+use clap::Parser;
+use eyre::WrapErr;
+use reth_db::{database::Database, table::Table, transaction::DbTx, TableViewer, Tables};
+use serde::Serialize;
+use std::{
+    cell::RefCell,
+    fs::File,
+    io::{self, BufWriter, Write},
+    ops::Bound,
+    path::PathBuf,
+};
+
+/// The arguments for the `reth db dump` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// The table name
+    #[arg(long, short)]
+    table: Tables,
+
+    /// The first key to dump, inclusive
+    #[arg(long, value_parser = maybe_json_value_parser)]
+    from: Option<String>,
+
+    /// The last key to dump, inclusive
+    #[arg(long, value_parser = maybe_json_value_parser)]
+    to: Option<String>,
+
+    /// The maximum number of rows to dump
+    #[arg(long, short)]
+    limit: Option<usize>,
+
+    /// The output format
+    #[arg(long, value_enum, default_value_t = DumpFormat::Json)]
+    format: DumpFormat,
+
+    /// The file to write the rows to. Defaults to stdout.
+    #[arg(long, short, value_name = "FILE")]
+    output: Option<PathBuf>,
+}
+
+/// The output format of `reth db dump`.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, clap::ValueEnum)]
+pub enum DumpFormat {
+    /// One JSON object with a `key` and a `value` field per line.
+    Json,
+    /// A `key` and a `value` column. Strings are written as is, other values as JSON.
+    Csv,
+}
+
+impl Command {
+    /// Execute `db dump` command
+    pub fn execute<DB: Database>(self, tool: &DbTool<'_, DB>) -> eyre::Result<()> {
+        match &self.output {
+            Some(path) => {
+                let file = File::create(path)
+                    .wrap_err_with(|| format!("Could not create {}", path.display()))?;
+                self.dump(tool, BufWriter::new(file))?;
+            }
+            None => self.dump(tool, BufWriter::new(io::stdout().lock()))?,
+        }
+
+        Ok(())
+    }
+
+    /// Writes the rows of the table to the writer and returns the number of rows.
+    fn dump<DB: Database, W: Write>(
+        &self,
+        tool: &DbTool<'_, DB>,
+        writer: W,
+    ) -> eyre::Result<usize> {
+        let viewer = DumpViewer { tool, args: self, writer: RefCell::new(writer) };
+        self.table.view(&viewer)
+    }
+
+    /// Parses the key of the given bound for the table.
+    fn table_key<T: Table>(key: &Option<String>) -> eyre::Result<Bound<T::Key>> {
+        Ok(match key {
+            Some(key) => Bound::Included(serde_json::from_str::<T::Key>(key)?),
+            None => Bound::Unbounded,
+        })
+    }
+}
+
+struct DumpViewer<'a, DB: Database, W> {
+    tool: &'a DbTool<'a, DB>,
+    args: &'a Command,
+    writer: RefCell<W>,
+}
+
+impl<DB: Database, W: Write> TableViewer<usize> for DumpViewer<'_, DB, W> {
+    type Error = eyre::Report;
+
+    fn view<T: Table>(&self) -> Result<usize, Self::Error> {
+        let range =
+            (Command::table_key::<T>(&self.args.from)?, Command::table_key::<T>(&self.args.to)?);
+        let limit = self.args.limit.unwrap_or(usize::MAX);
+        let mut writer = self.writer.borrow_mut();
+
+        if self.args.format == DumpFormat::Csv {
+            writeln!(writer, "key,value")?;
+        }
+
+        let rows = self.tool.db.view(|tx| {
+            let mut rows = 0;
+            for entry in tx.walk_range::<T>(range)?.take(limit) {
+                let (key, value) = entry?;
+                match self.args.format {
+                    DumpFormat::Json => {
+                        let row = serde_json::json!({ "key": key, "value": value });
+                        writeln!(writer, "{row}")?;
+                    }
+                    DumpFormat::Csv => {
+                        writeln!(writer, "{},{}", csv_field(&key)?, csv_field(&value)?)?
+                    }
+                }
+                rows += 1;
+            }
+            Ok::<_, eyre::Report>(rows)
+        })??;
+        writer.flush()?;
+
+        Ok(rows)
+    }
+}
+
+/// Encodes the value as a CSV field.
+///
+/// Strings are written as is, other values as JSON. The field is quoted if it contains a
+/// separator, quote or line break.
+fn csv_field<T: Serialize>(value: &T) -> eyre::Result<String> {
+    let field = match serde_json::to_value(value)? {
+        serde_json::Value::String(field) => field,
+        value => value.to_string(),
+    };
+    if field.contains([',', '"', '\n', '\r']) {
+        Ok(format!("\"{}\"", field.replace('"', "\"\"")))
+    } else {
+        Ok(field)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::{Args, Parser};
+    use reth_db::{tables, test_utils::create_test_rw_db, transaction::DbTxMut};
+    use reth_primitives::{H256, MAINNET};
+
+    /// A helper type to parse Args more easily
+    #[derive(Parser)]
+    struct CommandParser<T: Args> {
+        #[clap(flatten)]
+        args: T,
+    }
+
+    #[test]
+    fn dump_range() {
+        let db = create_test_rw_db();
+        db.update(|tx| {
+            for number in 0..10 {
+                tx.put::<tables::CanonicalHeaders>(number, H256::from_low_u64_be(number)).unwrap();
+            }
+        })
+        .unwrap();
+        let tool = DbTool::new(db.as_ref(), MAINNET.clone()).unwrap();
+
+        let args = CommandParser::<Command>::parse_from([
+            "reth",
+            "--table",
+            "CanonicalHeaders",
+            "--from",
+            "2",
+            "--to",
+            "8",
+            "--limit",
+            "3",
+        ])
+        .args;
+        let mut out = Vec::new();
+        assert_eq!(args.dump(&tool, &mut out).unwrap(), 3);
+        let rows = String::from_utf8(out).unwrap();
+        let rows = rows.lines().map(|row| serde_json::from_str(row).unwrap()).collect::<Vec<_>>();
+        assert_eq!(
+            rows,
+            (2..5)
+                .map(|number| serde_json::json!({
+                    "key": number,
+                    "value": H256::from_low_u64_be(number),
+                }))
+                .collect::<Vec<serde_json::Value>>()
+        );
+
+        let args = CommandParser::<Command>::parse_from([
+            "reth",
+            "--table",
+            "CanonicalHeaders",
+            "--from",
+            "9",
+            "--format",
+            "csv",
+        ])
+        .args;
+        let mut out = Vec::new();
+        assert_eq!(args.dump(&tool, &mut out).unwrap(), 1);
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            format!("key,value\n9,{:?}\n", H256::from_low_u64_be(9))
+        );
+    }
+
+    #[test]
+    fn csv_quoting() {
+        assert_eq!(csv_field(&123).unwrap(), "123");
+        assert_eq!(csv_field(&"a,b").unwrap(), r#""a,b""#);
+        assert_eq!(
+            csv_field(&serde_json::json!({ "a": 1, "b": "c" })).unwrap(),
+            r#""{""a"":1,""b"":""c""}""#
+        );
+    }
+}
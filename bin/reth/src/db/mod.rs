@@ -10,13 +10,16 @@ use eyre::WrapErr;
 use human_bytes::human_bytes;
 use reth_db::{
     database::Database,
-    open_db, open_db_read_only,
+    open_db_read_only, open_db_with_geometry,
     version::{get_db_version, DatabaseVersionError, DB_VERSION},
     Tables,
 };
 use reth_primitives::ChainSpec;
+use reth_provider::ProviderFactory;
 use std::sync::Arc;
 
+mod compact;
+mod dump;
 mod get;
 mod list;
 /// DB List TUI
@@ -69,12 +72,19 @@ pub enum Subcommands {
     List(list::Command),
     /// Gets the content of a table for the given key
     Get(get::Command),
+    /// Dumps the decoded entries of a table within a key range as JSON or CSV
+    Dump(dump::Command),
     /// Deletes all database entries
     Drop,
     /// Lists current and local database versions
     Version,
     /// Returns the full database path
     Path,
+    /// Checks the stage checkpoints for inconsistencies
+    Check,
+    /// Rewrites the database without its free pages to return them to the OS. The node must
+    /// not be running.
+    Compact,
 }
 
 impl Command {
@@ -147,8 +157,13 @@ impl Command {
                 let tool = DbTool::new(&db, self.chain.clone())?;
                 command.execute(&tool)?;
             }
+            Subcommands::Dump(command) => {
+                let db = open_db_read_only(&db_path, self.db.log_level)?;
+                let tool = DbTool::new(&db, self.chain.clone())?;
+                command.execute(&tool)?;
+            }
             Subcommands::Drop => {
-                let db = open_db(&db_path, self.db.log_level)?;
+                let db = open_db_with_geometry(&db_path, self.db.log_level, self.db.geometry())?;
                 let mut tool = DbTool::new(&db, self.chain.clone())?;
                 tool.drop(db_path)?;
             }
@@ -170,6 +185,25 @@ impl Command {
             Subcommands::Path => {
                 println!("{}", db_path.display());
             }
+            Subcommands::Check => {
+                let db = open_db_read_only(&db_path, self.db.log_level)?;
+                let report = ProviderFactory::new(&db, self.chain.clone())
+                    .provider()?
+                    .check_consistency()?;
+                if let Some(unwind_target) = report.unwind_target() {
+                    for violation in &report.violations {
+                        println!("{violation}");
+                    }
+                    eyre::bail!(
+                        "Found {} inconsistencies, the last consistent block is #{unwind_target}",
+                        report.violations.len()
+                    )
+                }
+                println!("Stage checkpoints are consistent");
+            }
+            Subcommands::Compact => {
+                compact::compact(&db_path, self.db.log_level)?;
+            }
         }
 
         Ok(())
@@ -11,7 +11,7 @@ use clap::Parser;
 use futures::{stream::select as stream_select, StreamExt};
 use reth_beacon_consensus::BeaconConsensus;
 use reth_config::Config;
-use reth_db::{database::Database, init_db, DatabaseEnv};
+use reth_db::{database::Database, init_db_with_geometry, DatabaseEnv};
 use reth_discv4::DEFAULT_DISCOVERY_PORT;
 use reth_downloaders::{
     bodies::bodies::BodiesDownloaderBuilder,
@@ -136,15 +136,20 @@ impl Command {
                     TotalDifficultyStage::new(consensus)
                         .with_commit_threshold(stage_conf.total_difficulty.commit_threshold),
                 )
-                .set(SenderRecoveryStage {
-                    commit_threshold: stage_conf.sender_recovery.commit_threshold,
-                })
+                .set(
+                    SenderRecoveryStage::new(stage_conf.sender_recovery.commit_threshold)
+                        .with_threads(stage_conf.sender_recovery.threads),
+                )
                 .set(ExecutionStage::new(
                     factory,
-                    ExecutionStageThresholds { max_blocks: None, max_changes: None },
+                    ExecutionStageThresholds {
+                        max_blocks: None,
+                        max_changes: None,
+                        max_cumulative_gas: None,
+                    },
                 )),
             )
-            .build(db, self.chain.clone());
+            .try_build(db, self.chain.clone())?;
 
         Ok(pipeline)
     }
@@ -204,7 +209,7 @@ impl Command {
         let data_dir = self.datadir.unwrap_or_chain_default(self.chain.chain);
         let db_path = data_dir.db_path();
         fs::create_dir_all(&db_path)?;
-        let db = Arc::new(init_db(db_path, self.db.log_level)?);
+        let db = Arc::new(init_db_with_geometry(db_path, self.db.log_level, self.db.geometry())?);
 
         debug!(target: "reth::cli", chain=%self.chain.chain, genesis=?self.chain.genesis_hash(), "Initializing genesis");
         init_genesis(db.clone(), self.chain.clone())?;
@@ -4,7 +4,7 @@ use crate::{
     dirs::{DataDirPath, MaybePlatformPath},
 };
 use clap::Parser;
-use reth_db::{cursor::DbCursorRO, init_db, tables, transaction::DbTx};
+use reth_db::{cursor::DbCursorRO, init_db_with_geometry, tables, transaction::DbTx};
 use reth_primitives::{
     fs,
     stage::{StageCheckpoint, StageId},
@@ -70,7 +70,7 @@ impl Command {
         let db_path = data_dir.db_path();
         fs::create_dir_all(&db_path)?;
 
-        let db = Arc::new(init_db(db_path, self.db.log_level)?);
+        let db = Arc::new(init_db_with_geometry(db_path, self.db.log_level, self.db.geometry())?);
         let factory = ProviderFactory::new(&db, self.chain.clone());
         let provider_rw = factory.provider_rw().map_err(PipelineError::Interface)?;
 
@@ -95,7 +95,11 @@ impl Command {
         let factory = reth_revm::Factory::new(self.chain.clone());
         let mut execution_stage = ExecutionStage::new(
             factory,
-            ExecutionStageThresholds { max_blocks: Some(1), max_changes: None },
+            ExecutionStageThresholds {
+                max_blocks: Some(1),
+                max_changes: None,
+                max_cumulative_gas: None,
+            },
         );
 
         let mut account_hashing_stage = AccountHashingStage::default();
@@ -7,7 +7,7 @@ use reth_interfaces::consensus::ForkchoiceState;
 use reth_network::{NetworkEvent, NetworkHandle};
 use reth_network_api::PeersInfo;
 use reth_primitives::{
-    stage::{EntitiesCheckpoint, StageCheckpoint, StageId},
+    stage::{StageCheckpoint, StageId},
     BlockNumber,
 };
 use reth_stages::{ExecOutput, PipelineEvent};
@@ -76,9 +76,10 @@ impl NodeState {
                 pipeline_total,
                 stage_id,
                 result: ExecOutput { checkpoint, done },
+                eta,
             } => {
                 self.current_checkpoint = checkpoint;
-                self.eta.update(self.current_checkpoint);
+                self.eta.update(eta);
 
                 info!(
                     pipeline_stages = %format!("{pipeline_position}/{pipeline_total}"),
@@ -130,6 +131,13 @@ impl NodeState {
             BeaconConsensusEngineEvent::ForkBlockAdded(block) => {
                 info!(number=block.number, hash=?block.hash, "Block added to fork chain");
             }
+            BeaconConsensusEngineEvent::InvalidPayload {
+                block_hash,
+                latest_valid_hash,
+                reason,
+            } => {
+                warn!(?block_hash, ?latest_valid_hash, %reason, "Received invalid payload");
+            }
         }
     }
 
@@ -266,36 +274,22 @@ where
     }
 }
 
-/// A container calculating the estimated time that a stage will complete in, based on stage
-/// checkpoints reported by the pipeline.
+/// A container for the estimated time that a stage will complete in, as reported by the pipeline
+/// with each stage checkpoint.
 ///
 /// One `Eta` is only valid for a single stage.
 #[derive(Default)]
 struct Eta {
-    /// The last stage checkpoint
-    last_checkpoint: EntitiesCheckpoint,
     /// The last time the stage reported its checkpoint
     last_checkpoint_time: Option<Instant>,
-    /// The current ETA
+    /// The ETA reported by the pipeline at the last checkpoint
     eta: Option<Duration>,
 }
 
 impl Eta {
-    /// Update the ETA given the checkpoint, if possible.
-    fn update(&mut self, checkpoint: StageCheckpoint) {
-        let Some(current) = checkpoint.entities() else { return };
-
-        if let Some(last_checkpoint_time) = &self.last_checkpoint_time {
-            let processed_since_last = current.processed - self.last_checkpoint.processed;
-            let elapsed = last_checkpoint_time.elapsed();
-            let per_second = processed_since_last as f64 / elapsed.as_secs_f64();
-
-            self.eta = Some(Duration::from_secs_f64(
-                (current.total - current.processed) as f64 / per_second,
-            ));
-        }
-
-        self.last_checkpoint = current;
+    /// Update the ETA with the one reported by the pipeline for the latest checkpoint.
+    fn update(&mut self, eta: Option<Duration>) {
+        self.eta = eta;
         self.last_checkpoint_time = Some(Instant::now());
     }
 }
@@ -332,7 +326,6 @@ mod tests {
                     37 * 1000 + // Seconds
                     999, // Milliseconds
             )),
-            ..Default::default()
         }
         .to_string();
 
@@ -2,7 +2,7 @@
 //!
 //! Starts the client
 use crate::{
-    args::{get_secret_key, DebugArgs, NetworkArgs, RpcServerArgs, TxPoolArgs},
+    args::{get_secret_key, DebugArgs, NetworkArgs, RpcServerArgs, TreeArgs, TxPoolArgs},
     dirs::DataDirPath,
     init::init_genesis,
     prometheus_exporter,
@@ -17,11 +17,9 @@ use futures::{future::Either, pin_mut, stream, stream_select, StreamExt};
 use reth_auto_seal_consensus::{AutoSealBuilder, AutoSealConsensus};
 use reth_basic_payload_builder::{BasicPayloadJobGenerator, BasicPayloadJobGeneratorConfig};
 use reth_beacon_consensus::{BeaconConsensus, BeaconConsensusEngine, MIN_BLOCKS_FOR_PIPELINE_RUN};
-use reth_blockchain_tree::{
-    config::BlockchainTreeConfig, externals::TreeExternals, BlockchainTree, ShareableBlockchainTree,
-};
+use reth_blockchain_tree::{externals::TreeExternals, BlockchainTree, ShareableBlockchainTree};
 use reth_config::Config;
-use reth_db::{database::Database, init_db, DatabaseEnv};
+use reth_db::{database::Database, init_db_with_geometry, DatabaseEnv};
 use reth_discv4::DEFAULT_DISCOVERY_PORT;
 use reth_downloaders::{
     bodies::bodies::BodiesDownloaderBuilder,
@@ -36,9 +34,10 @@ use reth_interfaces::{
     },
 };
 use reth_network::{error::NetworkError, NetworkConfig, NetworkHandle, NetworkManager};
-use reth_network_api::NetworkInfo;
+use reth_network_api::{NetworkInfo, PeersInfo};
 use reth_primitives::{
-    stage::StageId, BlockHashOrNumber, BlockNumber, ChainSpec, Head, SealedHeader, H256,
+    stage::StageId, BlockHashOrNumber, BlockNumber, ChainSpec, FromRecoveredTransaction, Head,
+    SealedHeader, TransactionSigned, H256,
 };
 use reth_provider::{
     BlockHashReader, BlockReader, CanonStateSubscriptions, HeaderProvider, ProviderFactory,
@@ -46,29 +45,31 @@ use reth_provider::{
 };
 use reth_revm::Factory;
 use reth_revm_inspectors::stack::Hook;
+use reth_rlp::Decodable;
 use reth_rpc_engine_api::EngineApi;
 use reth_stages::{
     prelude::*,
     stages::{
-        ExecutionStage, ExecutionStageThresholds, HeaderSyncMode, SenderRecoveryStage,
-        TotalDifficultyStage,
+        BatchSizeRamp, ExecutionStage, ExecutionStageThresholds, HeaderSyncMode,
+        SenderRecoveryStage, TotalDifficultyStage,
     },
     MetricEventsSender, MetricsListener,
 };
-use reth_tasks::TaskExecutor;
+use reth_tasks::{shutdown::GracefulShutdown, TaskExecutor};
 use reth_transaction_pool::{EthTransactionValidator, TransactionPool};
 use secp256k1::SecretKey;
 use std::{
     net::{Ipv4Addr, SocketAddr, SocketAddrV4},
-    path::PathBuf,
+    path::{Path, PathBuf},
     sync::Arc,
+    time::Duration,
 };
 use tokio::sync::{mpsc::unbounded_channel, oneshot, watch};
 use tracing::*;
 
 use crate::{
     args::{
-        utils::{genesis_value_parser, parse_socket_address},
+        utils::{genesis_value_parser, parse_duration_from_secs, parse_socket_address},
         DatabaseArgs, PayloadBuilderArgs,
     },
     dirs::MaybePlatformPath,
@@ -77,7 +78,7 @@ use crate::{
 use reth_interfaces::p2p::headers::client::HeadersClient;
 use reth_payload_builder::PayloadBuilderService;
 use reth_primitives::DisplayHardforks;
-use reth_provider::providers::BlockchainProvider;
+use reth_provider::providers::{BlockchainProvider, DEFAULT_BLOCK_NUMBER_CACHE_LEN};
 use reth_stages::stages::{
     AccountHashingStage, IndexAccountHistoryStage, IndexStorageHistoryStage, MerkleStage,
     StorageHashingStage, TransactionLookupStage,
@@ -126,6 +127,13 @@ pub struct Command {
     #[arg(long, value_name = "SOCKET", value_parser = parse_socket_address, help_heading = "Metrics")]
     metrics: Option<SocketAddr>,
 
+    /// The minimum interval between two collections of database table statistics (in seconds).
+    ///
+    /// Table statistics are collected when the metrics endpoint is scraped, but at most once per
+    /// interval, because collecting them can be expensive on large databases.
+    #[arg(long = "metrics.db-stats-interval", value_name = "SECONDS", value_parser = parse_duration_from_secs, default_value = "10", help_heading = "Metrics")]
+    metrics_db_stats_interval: Duration,
+
     #[clap(flatten)]
     network: NetworkArgs,
 
@@ -138,12 +146,21 @@ pub struct Command {
     #[clap(flatten)]
     builder: PayloadBuilderArgs,
 
+    #[clap(flatten)]
+    tree: TreeArgs,
+
     #[clap(flatten)]
     debug: DebugArgs,
 
     #[clap(flatten)]
     db: DatabaseArgs,
 
+    /// Maximum number of cached block hash to number lookups, including unknown hashes.
+    ///
+    /// Set to 0 to disable the cache.
+    #[arg(long = "db.block-number-cache-len", default_value_t = DEFAULT_BLOCK_NUMBER_CACHE_LEN, help_heading = "Database")]
+    block_number_cache_len: u32,
+
     /// Automatically mine blocks for new transactions
     #[arg(long)]
     auto_mine: bool,
@@ -169,7 +186,7 @@ impl Command {
 
         let db_path = data_dir.db_path();
         info!(target: "reth::cli", path = ?db_path, "Opening database");
-        let db = Arc::new(init_db(&db_path, self.db.log_level)?);
+        let db = Arc::new(init_db_with_geometry(&db_path, self.db.log_level, self.db.geometry())?);
         info!(target: "reth::cli", "Database opened");
 
         self.start_metrics_endpoint(Arc::clone(&db)).await?;
@@ -201,7 +218,7 @@ impl Command {
             Factory::new(self.chain.clone()),
             Arc::clone(&self.chain),
         );
-        let tree_config = BlockchainTreeConfig::default();
+        let tree_config = self.tree.tree_config();
         // The size of the broadcast is twice the maximum reorg depth, because at maximum reorg
         // depth at least N blocks must be sent at once.
         let (canon_state_notification_sender, _receiver) =
@@ -217,7 +234,8 @@ impl Command {
 
         // setup the blockchain provider
         let factory = ProviderFactory::new(Arc::clone(&db), Arc::clone(&self.chain));
-        let blockchain_db = BlockchainProvider::new(factory, blockchain_tree.clone())?;
+        let blockchain_db = BlockchainProvider::new(factory, blockchain_tree.clone())?
+            .with_block_number_cache(self.block_number_cache_len);
 
         let transaction_pool = reth_transaction_pool::Pool::eth_pool(
             EthTransactionValidator::with_additional_tasks(
@@ -246,6 +264,10 @@ impl Command {
             debug!(target: "reth::cli", "Spawned txpool maintenance task");
         }
 
+        if let Some(import_path) = &self.txpool.import {
+            import_transactions(&transaction_pool, import_path).await?;
+        }
+
         info!(target: "reth::cli", "Connecting to P2P network");
         let network_secret_path =
             self.network.p2p_secret_key.clone().unwrap_or_else(|| data_dir.p2p_secret_path());
@@ -284,7 +306,7 @@ impl Command {
                 .deadline(self.builder.deadline)
                 .max_payload_tasks(self.builder.max_payload_tasks)
                 .extradata(self.builder.extradata_bytes())
-                .max_gas_limit(self.builder.max_gas_limit),
+                .desired_gas_limit(self.builder.desired_gas_limit),
             Arc::clone(&self.chain),
         );
         let (payload_service, payload_builder) = PayloadBuilderService::new(payload_generator);
@@ -345,6 +367,22 @@ impl Command {
             (pipeline, EitherDownloader::Right(network_client))
         };
 
+        let report = ProviderFactory::new(Arc::clone(&db), Arc::clone(&self.chain))
+            .provider()?
+            .check_consistency()?;
+        if let Some(unwind_target) = report.unwind_target() {
+            for violation in &report.violations {
+                error!(target: "reth::cli", %violation, "Inconsistent stage checkpoint");
+            }
+            if !self.debug.unwind_inconsistent {
+                eyre::bail!(
+                    "Stage checkpoints are inconsistent, restart with --debug.unwind-inconsistent to unwind to block #{unwind_target}"
+                )
+            }
+            warn!(target: "reth::cli", unwind_target, "Unwinding inconsistent stages");
+            pipeline.unwind(unwind_target, None).await?;
+        }
+
         let pipeline_events = pipeline.events();
 
         let initial_target = if let Some(tip) = self.debug.tip {
@@ -477,6 +515,7 @@ impl Command {
                 max_block,
                 self.debug.continuous,
                 metrics_tx,
+                task_executor.on_graceful_shutdown_signal(),
             )
             .await?;
 
@@ -503,8 +542,13 @@ impl Command {
     async fn start_metrics_endpoint(&self, db: Arc<DatabaseEnv>) -> eyre::Result<()> {
         if let Some(listen_addr) = self.metrics {
             info!(target: "reth::cli", addr = %listen_addr, "Starting metrics endpoint");
-            prometheus_exporter::initialize(listen_addr, db, metrics_process::Collector::default())
-                .await?;
+            prometheus_exporter::initialize(
+                listen_addr,
+                db,
+                self.metrics_db_stats_interval,
+                metrics_process::Collector::default(),
+            )
+            .await?;
         }
 
         Ok(())
@@ -534,9 +578,7 @@ impl Command {
         task_executor.spawn_critical("p2p eth request handler", eth);
 
         let known_peers_file = self.network.persistent_peers_file(default_peers_path);
-        task_executor.spawn_critical_with_signal("p2p network task", |shutdown| {
-            run_network_until_shutdown(shutdown, network, known_peers_file)
-        });
+        spawn_network_task(task_executor, network, known_peers_file);
 
         Ok(handle)
     }
@@ -658,6 +700,7 @@ impl Command {
         max_block: Option<u64>,
         continuous: bool,
         metrics_tx: MetricEventsSender,
+        graceful_shutdown: GracefulShutdown,
     ) -> eyre::Result<Pipeline<DB>>
     where
         DB: Database + Clone + 'static,
@@ -666,7 +709,7 @@ impl Command {
     {
         let stage_config = &config.stages;
 
-        let mut builder = Pipeline::builder();
+        let mut builder = Pipeline::builder().with_graceful_shutdown(graceful_shutdown);
 
         if let Some(max_block) = max_block {
             debug!(target: "reth::cli", max_block, "Configuring builder to use max block");
@@ -709,16 +752,25 @@ impl Command {
                     TotalDifficultyStage::new(consensus)
                         .with_commit_threshold(stage_config.total_difficulty.commit_threshold),
                 )
-                .set(SenderRecoveryStage {
-                    commit_threshold: stage_config.sender_recovery.commit_threshold,
-                })
-                .set(ExecutionStage::new(
-                    factory,
-                    ExecutionStageThresholds {
-                        max_blocks: stage_config.execution.max_blocks,
-                        max_changes: stage_config.execution.max_changes,
-                    },
-                ))
+                .set(
+                    SenderRecoveryStage::new(stage_config.sender_recovery.commit_threshold)
+                        .with_threads(stage_config.sender_recovery.threads),
+                )
+                .set(
+                    ExecutionStage::new(
+                        factory,
+                        ExecutionStageThresholds {
+                            max_blocks: stage_config.execution.max_blocks,
+                            max_changes: stage_config.execution.max_changes,
+                            max_cumulative_gas: stage_config.execution.max_cumulative_gas,
+                        },
+                    )
+                    .with_incremental_state_root(stage_config.execution.incremental_state_root)
+                    .with_batch_size_ramp(BatchSizeRamp {
+                        initial_percent: stage_config.execution.batch_ramp_initial_percent,
+                        batches: stage_config.execution.batch_ramp_batches,
+                    }),
+                )
                 .set(AccountHashingStage::new(
                     stage_config.account_hashing.clean_threshold,
                     stage_config.account_hashing.commit_threshold,
@@ -729,21 +781,102 @@ impl Command {
                 ))
                 .set(MerkleStage::new_execution(stage_config.merkle.clean_threshold))
                 .set(TransactionLookupStage::new(stage_config.transaction_lookup.commit_threshold))
-                .set(IndexAccountHistoryStage::new(
-                    stage_config.index_account_history.commit_threshold,
-                ))
-                .set(IndexStorageHistoryStage::new(
-                    stage_config.index_storage_history.commit_threshold,
-                )),
+                .set(
+                    IndexAccountHistoryStage::new(
+                        stage_config.index_account_history.commit_threshold,
+                    )
+                    .with_max_changesets_per_commit(
+                        stage_config.index_account_history.max_changesets_per_commit,
+                    ),
+                )
+                .set(
+                    IndexStorageHistoryStage::new(
+                        stage_config.index_storage_history.commit_threshold,
+                    )
+                    .with_max_changesets_per_commit(
+                        stage_config.index_storage_history.max_changesets_per_commit,
+                    ),
+                ),
             )
-            .build(db, self.chain.clone());
+            .try_build(db, self.chain.clone())?;
 
         Ok(pipeline)
     }
 }
 
+/// Imports the RLP encoded transactions in the given file into the pool.
+///
+/// The file is expected to contain the concatenated network encodings of signed transactions. Each
+/// transaction is inserted as an external transaction, transactions that can't be recovered or are
+/// rejected by the pool are skipped.
+async fn import_transactions<Pool>(pool: &Pool, path: &Path) -> eyre::Result<()>
+where
+    Pool: TransactionPool,
+{
+    info!(target: "reth::cli", path = ?path, "Importing transactions into the pool");
+    let contents = std::fs::read(path)
+        .wrap_err_with(|| format!("Could not read transactions from {}", path.display()))?;
+
+    let mut buf = contents.as_slice();
+    let mut transactions = Vec::new();
+    while !buf.is_empty() {
+        let tx = match TransactionSigned::decode(&mut buf) {
+            Ok(tx) => tx,
+            Err(err) => {
+                // the remaining data can't be split into transactions anymore
+                warn!(target: "reth::cli", %err, remaining = buf.len(), "Failed to decode imported transaction, skipping the rest of the file");
+                break
+            }
+        };
+        let hash = tx.hash();
+        match tx.into_ecrecovered() {
+            Some(recovered) => {
+                transactions.push(Pool::Transaction::from_recovered_transaction(recovered))
+            }
+            None => {
+                warn!(target: "reth::cli", ?hash, "Rejected imported transaction: invalid signature")
+            }
+        }
+    }
+
+    let total = transactions.len();
+    let mut imported = 0;
+    for res in pool.add_external_transactions(transactions).await? {
+        match res {
+            Ok(_) => imported += 1,
+            Err(err) => {
+                warn!(target: "reth::cli", hash = ?err.hash(), %err, "Rejected imported transaction")
+            }
+        }
+    }
+    info!(target: "reth::cli", imported, rejected = total - imported, "Imported transactions into the pool");
+
+    Ok(())
+}
+
+/// Spawns the [NetworkManager] as a critical task that runs until the shutdown signal, see
+/// [run_network_until_shutdown].
+///
+/// The task holds a [GracefulShutdownGuard](reth_tasks::shutdown::GracefulShutdownGuard) until it
+/// finished, so that the runtime isn't torn down while the sessions are still draining.
+fn spawn_network_task<C>(
+    task_executor: &TaskExecutor,
+    network: NetworkManager<C>,
+    persistent_peers_file: Option<PathBuf>,
+) where
+    C: BlockReader + HeaderProvider + Clone + Unpin + 'static,
+{
+    let guard = task_executor.on_graceful_shutdown_signal().try_guard();
+    task_executor.spawn_critical_with_signal("p2p network task", |shutdown| async move {
+        run_network_until_shutdown(shutdown, network, persistent_peers_file).await;
+        drop(guard);
+    });
+}
+
 /// Drives the [NetworkManager] future until a [Shutdown](reth_tasks::shutdown::Shutdown) signal is
-/// received. If configured, this writes known peers to `persistent_peers_file` afterwards.
+/// received. On shutdown, the network keeps running until all sessions have answered their
+/// in-flight requests and disconnected, which is bounded by the session drain timeout.
+/// If configured, this writes known peers to `persistent_peers_file` afterwards.
 async fn run_network_until_shutdown<C>(
     shutdown: reth_tasks::shutdown::Shutdown,
     network: NetworkManager<C>,
@@ -753,9 +886,37 @@ async fn run_network_until_shutdown<C>(
 {
     pin_mut!(network, shutdown);
 
-    tokio::select! {
-        _ = &mut network => {},
-        _ = shutdown => {},
+    let shutdown_requested = tokio::select! {
+        _ = &mut network => false,
+        _ = shutdown => true,
+    };
+
+    if shutdown_requested {
+        let handle = network.handle().clone();
+        // sessions enforce the drain timeout themselves, this only bounds the time it takes to
+        // process their disconnects
+        let timeout = network.shutdown_drain_timeout() + Duration::from_secs(1);
+        let drain = async {
+            let mut events = handle.event_listener();
+            if handle.shutdown().await.is_err() {
+                return
+            }
+            while handle.num_connected_peers() > 0 {
+                if events.next().await.is_none() {
+                    break
+                }
+            }
+        };
+
+        debug!(target: "reth::cli", ?timeout, "Draining network sessions");
+        tokio::select! {
+            _ = &mut network => {},
+            res = tokio::time::timeout(timeout, drain) => {
+                if res.is_err() {
+                    warn!(target: "reth::cli", "Timed out draining network sessions");
+                }
+            },
+        }
     }
 
     if let Some(file_path) = persistent_peers_file {
@@ -778,6 +939,8 @@ async fn run_network_until_shutdown<C>(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use reth_network::config::rng_secret_key;
+    use reth_primitives::NodeRecord;
     use std::{net::IpAddr, path::Path};
 
     #[test]
@@ -848,4 +1011,48 @@ mod tests {
         let db_path = data_dir.db_path();
         assert_eq!(db_path, Path::new("my/custom/path/db"));
     }
+
+    #[test]
+    fn network_task_delays_runner_shutdown() {
+        let dir = tempfile::tempdir().unwrap();
+        let peers_file = dir.path().join("known-peers.json");
+        let (peer_tx, peer_rx) = std::sync::mpsc::channel();
+
+        let task_peers_file = peers_file.clone();
+        crate::runner::CliRunner::default()
+            .run_command_until_exit(|ctx| async move {
+                let config = |secret_key| {
+                    reth_network::NetworkConfigBuilder::new(secret_key)
+                        .listener_addr(SocketAddr::new(Ipv4Addr::LOCALHOST.into(), 0))
+                        .disable_discovery()
+                        .build(reth_provider::test_utils::NoopProvider::default())
+                };
+                let network = NetworkManager::new(config(rng_secret_key())).await?;
+                let other = NetworkManager::new(config(rng_secret_key())).await?;
+
+                let handle = network.handle().clone();
+                let other_handle = other.handle().clone();
+                let mut events = handle.event_listener();
+                ctx.task_executor.spawn(other);
+                spawn_network_task(&ctx.task_executor, network, Some(task_peers_file));
+
+                handle.add_peer(*other_handle.peer_id(), other_handle.local_addr());
+                while let Some(event) = events.next().await {
+                    if let reth_network::NetworkEvent::SessionEstablished { .. } = event {
+                        break
+                    }
+                }
+                peer_tx.send((handle, *other_handle.peer_id())).unwrap();
+                Ok::<_, eyre::Error>(())
+            })
+            .unwrap();
+
+        // the runner only returns after the network task drained its sessions and persisted its
+        // peers
+        let (handle, other_peer) = peer_rx.recv().unwrap();
+        assert_eq!(handle.num_connected_peers(), 0);
+        let known_peers: Vec<NodeRecord> =
+            serde_json::from_str(&std::fs::read_to_string(&peers_file).unwrap()).unwrap();
+        assert!(known_peers.iter().any(|peer| peer.id == other_peer));
+    }
 }
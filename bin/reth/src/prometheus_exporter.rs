@@ -7,8 +7,15 @@ use hyper::{
 use metrics_exporter_prometheus::{PrometheusBuilder, PrometheusHandle};
 use metrics_util::layers::{PrefixLayer, Stack};
 use reth_db::{database::Database, tables, DatabaseEnv};
-use reth_metrics::metrics::{self, absolute_counter, describe_counter, Unit};
-use std::{convert::Infallible, net::SocketAddr, sync::Arc};
+use reth_metrics::metrics::{
+    self, absolute_counter, describe_counter, describe_gauge, gauge, Unit,
+};
+use std::{
+    convert::Infallible,
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
 
 pub(crate) trait Hook: Fn() + Send + Sync {}
 impl<T: Fn() + Send + Sync> Hook for T {}
@@ -67,12 +74,26 @@ async fn start_endpoint<F: Hook + 'static>(
 
 /// Installs Prometheus as the metrics recorder and serves it over HTTP with database and process
 /// metrics.
+///
+/// Database table statistics are collected at most once per `db_stats_interval`, a zero interval
+/// collects them on every request.
 pub(crate) async fn initialize(
     listen_addr: SocketAddr,
     db: Arc<DatabaseEnv>,
+    db_stats_interval: Duration,
     process: metrics_process::Collector,
 ) -> eyre::Result<()> {
+    let last_db_stats_collection = Mutex::new(None::<Instant>);
     let db_stats = move || {
+        {
+            let mut last_collection =
+                last_db_stats_collection.lock().unwrap_or_else(|err| err.into_inner());
+            if last_collection.map_or(false, |last| last.elapsed() < db_stats_interval) {
+                return
+            }
+            *last_collection = Some(Instant::now());
+        }
+
         // TODO: A generic stats abstraction for other DB types to deduplicate this and `reth db
         //  stats`
         let _ = db.view(|tx| {
@@ -96,6 +117,11 @@ pub(crate) async fn initialize(
                 absolute_counter!("db.table_pages", leaf_pages as u64, "table" => table, "type" => "leaf");
                 absolute_counter!("db.table_pages", branch_pages as u64, "table" => table, "type" => "branch");
                 absolute_counter!("db.table_pages", overflow_pages as u64, "table" => table, "type" => "overflow");
+
+                gauge!("db.table.size_bytes", table_size as f64, "table" => table);
+                gauge!("db.table.pages", leaf_pages as f64, "table" => table, "type" => "leaf");
+                gauge!("db.table.pages", branch_pages as f64, "table" => table, "type" => "branch");
+                gauge!("db.table.pages", overflow_pages as f64, "table" => table, "type" => "overflow");
             }
 
             Ok::<(), eyre::Report>(())
@@ -115,6 +141,8 @@ pub(crate) async fn initialize(
     // registered
     describe_counter!("db.table_size", Unit::Bytes, "The size of a database table (in bytes)");
     describe_counter!("db.table_pages", "The number of database pages for a table");
+    describe_gauge!("db.table.size_bytes", Unit::Bytes, "The size of a database table (in bytes)");
+    describe_gauge!("db.table.pages", "The number of database pages for a table");
     process.describe();
     describe_memory_stats();
 
@@ -2,8 +2,12 @@
 
 use futures::pin_mut;
 use reth_tasks::{TaskExecutor, TaskManager};
-use std::future::Future;
-use tracing::trace;
+use std::{future::Future, time::Duration};
+use tracing::{debug, trace, warn};
+
+/// How long to wait for tasks to finish their current unit of work after the exit signal, e.g. for
+/// the pipeline to commit the batch it's executing.
+const GRACEFUL_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(30);
 
 /// Used to execute cli commands
 #[derive(Default, Debug)]
@@ -17,7 +21,9 @@ impl CliRunner {
     /// until the process receives a `SIGINT` or `SIGTERM` signal.
     ///
     /// Tasks spawned by the command via the [TaskExecutor] are shut down and an attempt is made to
-    /// drive their shutdown to completion after the command has finished.
+    /// drive their shutdown to completion after the command has finished. Tasks that hold a
+    /// [GracefulShutdownGuard](reth_tasks::shutdown::GracefulShutdownGuard) are waited for up to
+    /// 30 seconds.
     pub fn run_command_until_exit<F, E>(
         self,
         command: impl FnOnce(CliContext) -> F,
@@ -33,9 +39,13 @@ impl CliRunner {
             task_manager,
             run_until_ctrl_c(command(context)),
         ))?;
-        // after the command has finished or exit signal was received we drop the task manager which
-        // fires the shutdown signal to all tasks spawned via the task executor
-        drop(task_manager);
+        // after the command has finished or exit signal was received we fire the shutdown signal
+        // to all tasks spawned via the task executor, and wait for the tasks that need to finish
+        // their current work, e.g. the pipeline committing its current batch
+        debug!(target: "reth::cli", "Shutting down tasks");
+        if !task_manager.graceful_shutdown_with_timeout(GRACEFUL_SHUTDOWN_TIMEOUT) {
+            warn!(target: "reth::cli", "Tasks didn't shut down in time");
+        }
 
         // drop the tokio runtime on a separate thread because drop blocks until its pools
         // (including blocking pool) are shutdown. In other words `drop(tokio_runtime)` would block
@@ -6,7 +6,12 @@ use crate::{
     utils::DbTool,
 };
 use clap::Parser;
-use reth_db::{database::Database, open_db, tables, transaction::DbTxMut, DatabaseEnv};
+use reth_db::{
+    database::{Database, DatabaseGAT},
+    open_db_with_geometry, tables,
+    transaction::DbTxMut,
+    DatabaseEnv,
+};
 use reth_primitives::{fs, stage::StageId, ChainSpec};
 use std::sync::Arc;
 use tracing::info;
@@ -55,117 +60,103 @@ impl Command {
         let db_path = data_dir.db_path();
         fs::create_dir_all(&db_path)?;
 
-        let db = open_db(db_path.as_ref(), self.db.log_level)?;
+        let db = open_db_with_geometry(db_path.as_ref(), self.db.log_level, self.db.geometry())?;
 
         let tool = DbTool::new(&db, self.chain.clone())?;
 
-        tool.db.update(|tx| {
-            match &self.stage {
-                StageEnum::Bodies => {
-                    tx.clear::<tables::BlockBodyIndices>()?;
-                    tx.clear::<tables::Transactions>()?;
-                    tx.clear::<tables::TransactionBlock>()?;
-                    tx.clear::<tables::BlockOmmers>()?;
-                    tx.clear::<tables::BlockWithdrawals>()?;
-                    tx.put::<tables::SyncStage>(StageId::Bodies.to_string(), Default::default())?;
-                    insert_genesis_header::<DatabaseEnv>(tx, self.chain)?;
-                }
-                StageEnum::Senders => {
-                    tx.clear::<tables::TxSenders>()?;
-                    tx.put::<tables::SyncStage>(
-                        StageId::SenderRecovery.to_string(),
-                        Default::default(),
-                    )?;
-                }
-                StageEnum::Execution => {
-                    tx.clear::<tables::PlainAccountState>()?;
-                    tx.clear::<tables::PlainStorageState>()?;
-                    tx.clear::<tables::AccountChangeSet>()?;
-                    tx.clear::<tables::StorageChangeSet>()?;
-                    tx.clear::<tables::Bytecodes>()?;
-                    tx.clear::<tables::Receipts>()?;
-                    tx.put::<tables::SyncStage>(
-                        StageId::Execution.to_string(),
-                        Default::default(),
-                    )?;
-                    insert_genesis_state::<DatabaseEnv>(tx, self.chain.genesis())?;
-                }
-                StageEnum::AccountHashing => {
-                    tx.clear::<tables::HashedAccount>()?;
-                    tx.put::<tables::SyncStage>(
-                        StageId::AccountHashing.to_string(),
-                        Default::default(),
-                    )?;
-                }
-                StageEnum::StorageHashing => {
-                    tx.clear::<tables::HashedStorage>()?;
-                    tx.put::<tables::SyncStage>(
-                        StageId::StorageHashing.to_string(),
-                        Default::default(),
-                    )?;
-                }
-                StageEnum::Hashing => {
-                    // Clear hashed accounts
-                    tx.clear::<tables::HashedAccount>()?;
-                    tx.put::<tables::SyncStage>(
-                        StageId::AccountHashing.to_string(),
-                        Default::default(),
-                    )?;
-
-                    // Clear hashed storages
-                    tx.clear::<tables::HashedStorage>()?;
-                    tx.put::<tables::SyncStage>(
-                        StageId::StorageHashing.to_string(),
-                        Default::default(),
-                    )?;
-                }
-                StageEnum::Merkle => {
-                    tx.clear::<tables::AccountsTrie>()?;
-                    tx.clear::<tables::StoragesTrie>()?;
-                    tx.put::<tables::SyncStage>(
-                        StageId::MerkleExecute.to_string(),
-                        Default::default(),
-                    )?;
-                    tx.put::<tables::SyncStage>(
-                        StageId::MerkleUnwind.to_string(),
-                        Default::default(),
-                    )?;
-                    tx.delete::<tables::SyncStageProgress>(
-                        StageId::MerkleExecute.to_string(),
-                        None,
-                    )?;
-                }
-                StageEnum::History => {
-                    tx.clear::<tables::AccountHistory>()?;
-                    tx.clear::<tables::StorageHistory>()?;
-                    tx.put::<tables::SyncStage>(
-                        StageId::IndexAccountHistory.to_string(),
-                        Default::default(),
-                    )?;
-                    tx.put::<tables::SyncStage>(
-                        StageId::IndexStorageHistory.to_string(),
-                        Default::default(),
-                    )?;
-                }
-                StageEnum::TotalDifficulty => {
-                    tx.clear::<tables::HeaderTD>()?;
-                    tx.put::<tables::SyncStage>(
-                        StageId::TotalDifficulty.to_string(),
-                        Default::default(),
-                    )?;
-                    insert_genesis_header::<DatabaseEnv>(tx, self.chain)?;
-                }
-                _ => {
-                    info!("Nothing to do for stage {:?}", self.stage);
-                    return Ok(())
-                }
+        let stages: &[StageId] = match &self.stage {
+            StageEnum::Bodies => &[StageId::Bodies],
+            StageEnum::Senders => &[StageId::SenderRecovery],
+            StageEnum::Execution => &[StageId::Execution],
+            StageEnum::AccountHashing => &[StageId::AccountHashing],
+            StageEnum::StorageHashing => &[StageId::StorageHashing],
+            StageEnum::Hashing => &[StageId::AccountHashing, StageId::StorageHashing],
+            StageEnum::Merkle => &[StageId::MerkleExecute, StageId::MerkleUnwind],
+            StageEnum::History => &[StageId::IndexAccountHistory, StageId::IndexStorageHistory],
+            StageEnum::TotalDifficulty => &[StageId::TotalDifficulty],
+            _ => {
+                info!("Nothing to do for stage {:?}", self.stage);
+                return Ok(())
             }
+        };
 
-            tx.put::<tables::SyncStage>(StageId::Finish.to_string(), Default::default())?;
+        // The node refuses to start if a stage is ahead of one of its dependencies, so the stages
+        // depending on the dropped ones are dropped as well. `StageId::ALL` lists every stage after
+        // its dependencies, so a single pass collects all of them.
+        let mut dropped = stages.to_vec();
+        for stage in StageId::ALL {
+            if !dropped.contains(&stage) &&
+                stage.dependencies().iter().any(|dependency| dropped.contains(dependency))
+            {
+                dropped.push(stage);
+            }
+        }
 
+        tool.db.update(|tx| {
+            for stage in StageId::ALL.into_iter().filter(|stage| dropped.contains(stage)) {
+                info!(target: "reth::cli", ?stage, "Dropping stage");
+                drop_stage(tx, stage, self.chain.clone())?;
+            }
             Ok::<_, eyre::Error>(())
         })??;
 
         Ok(())
     }
 }
+
+/// Clears the tables written by the given stage and resets its checkpoint.
+fn drop_stage(
+    tx: &<DatabaseEnv as DatabaseGAT<'_>>::TXMut,
+    stage: StageId,
+    chain: Arc<ChainSpec>,
+) -> eyre::Result<()> {
+    match stage {
+        StageId::TotalDifficulty => {
+            tx.clear::<tables::HeaderTD>()?;
+            insert_genesis_header::<DatabaseEnv>(tx, chain)?;
+        }
+        StageId::Bodies => {
+            tx.clear::<tables::BlockBodyIndices>()?;
+            tx.clear::<tables::Transactions>()?;
+            tx.clear::<tables::TransactionBlock>()?;
+            tx.clear::<tables::BlockOmmers>()?;
+            tx.clear::<tables::BlockWithdrawals>()?;
+            insert_genesis_header::<DatabaseEnv>(tx, chain)?;
+        }
+        StageId::SenderRecovery => {
+            tx.clear::<tables::TxSenders>()?;
+        }
+        StageId::TransactionLookup => {
+            tx.clear::<tables::TxHashNumber>()?;
+        }
+        StageId::Execution => {
+            tx.clear::<tables::PlainAccountState>()?;
+            tx.clear::<tables::PlainStorageState>()?;
+            tx.clear::<tables::AccountChangeSet>()?;
+            tx.clear::<tables::StorageChangeSet>()?;
+            tx.clear::<tables::Bytecodes>()?;
+            tx.clear::<tables::Receipts>()?;
+            insert_genesis_state::<DatabaseEnv>(tx, chain.genesis())?;
+        }
+        StageId::AccountHashing => {
+            tx.clear::<tables::HashedAccount>()?;
+        }
+        StageId::StorageHashing => {
+            tx.clear::<tables::HashedStorage>()?;
+        }
+        StageId::MerkleExecute => {
+            tx.clear::<tables::AccountsTrie>()?;
+            tx.clear::<tables::StoragesTrie>()?;
+            tx.delete::<tables::SyncStageProgress>(StageId::MerkleExecute.to_string(), None)?;
+        }
+        StageId::IndexAccountHistory => {
+            tx.clear::<tables::AccountHistory>()?;
+        }
+        StageId::IndexStorageHistory => {
+            tx.clear::<tables::StorageHistory>()?;
+        }
+        StageId::Headers | StageId::MerkleUnwind | StageId::Finish | StageId::Other(_) => {}
+    }
+    tx.put::<tables::SyncStage>(stage.to_string(), Default::default())?;
+    Ok(())
+}
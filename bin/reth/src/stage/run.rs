@@ -10,10 +10,10 @@ use crate::{
 use clap::Parser;
 use reth_beacon_consensus::BeaconConsensus;
 use reth_config::Config;
-use reth_db::init_db;
+use reth_db::init_db_with_geometry;
 use reth_downloaders::bodies::bodies::BodiesDownloaderBuilder;
 use reth_primitives::ChainSpec;
-use reth_provider::{ProviderFactory, StageCheckpointReader};
+use reth_provider::{ProviderFactory, StageCheckpointReader, StageCheckpointWriter};
 use reth_stages::{
     stages::{
         AccountHashingStage, BodyStage, ExecutionStage, ExecutionStageThresholds,
@@ -22,7 +22,7 @@ use reth_stages::{
     },
     ExecInput, ExecOutput, PipelineError, Stage, UnwindInput,
 };
-use std::{any::Any, net::SocketAddr, path::PathBuf, sync::Arc};
+use std::{any::Any, net::SocketAddr, path::PathBuf, sync::Arc, time::Duration};
 use tracing::*;
 
 /// `reth stage` command
@@ -122,7 +122,7 @@ impl Command {
         let db_path = data_dir.db_path();
 
         info!(target: "reth::cli", path = ?db_path, "Opening database");
-        let db = Arc::new(init_db(db_path, self.db.log_level)?);
+        let db = Arc::new(init_db_with_geometry(db_path, self.db.log_level, self.db.geometry())?);
         info!(target: "reth::cli", "Database opened");
 
         let factory = ProviderFactory::new(&db, self.chain.clone());
@@ -133,6 +133,7 @@ impl Command {
             prometheus_exporter::initialize(
                 listen_addr,
                 Arc::clone(&db),
+                Duration::ZERO,
                 metrics_process::Collector::default(),
             )
             .await?;
@@ -201,6 +202,7 @@ impl Command {
                             ExecutionStageThresholds {
                                 max_blocks: Some(batch_size),
                                 max_changes: None,
+                                max_cumulative_gas: None,
                             },
                         )),
                         None,
@@ -252,15 +254,25 @@ impl Command {
             checkpoint: Some(checkpoint.with_block_number(self.from)),
         };
 
-        while let ExecOutput { checkpoint: stage_progress, done: false } =
-            exec_stage.execute(&provider_rw, input).await?
-        {
+        loop {
+            let ExecOutput { checkpoint: stage_progress, done } =
+                exec_stage.execute(&provider_rw, input).await?;
             input.checkpoint = Some(stage_progress);
 
+            // If the range directly follows the stage checkpoint, advance the checkpoint, so the
+            // range is not processed again by the next run of the stage.
+            if done && (self.from..self.to).contains(&checkpoint.block_number) {
+                provider_rw.save_stage_checkpoint(exec_stage.id(), stage_progress)?;
+            }
+
             if self.commit {
                 provider_rw.commit()?;
                 provider_rw = factory.provider_rw().map_err(PipelineError::Interface)?;
             }
+
+            if done {
+                break
+            }
         }
 
         Ok(())
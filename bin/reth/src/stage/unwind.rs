@@ -5,7 +5,9 @@ use crate::{
     dirs::{DataDirPath, MaybePlatformPath},
 };
 use clap::{Parser, Subcommand};
-use reth_db::{cursor::DbCursorRO, database::Database, open_db, tables, transaction::DbTx};
+use reth_db::{
+    cursor::DbCursorRO, database::Database, open_db_with_geometry, tables, transaction::DbTx,
+};
 use reth_primitives::{BlockHashOrNumber, ChainSpec};
 use reth_provider::{BlockExecutionWriter, ProviderFactory};
 use std::{ops::RangeInclusive, sync::Arc};
@@ -58,7 +60,7 @@ impl Command {
             eyre::bail!("Database {db_path:?} does not exist.")
         }
 
-        let db = open_db(db_path.as_ref(), self.db.log_level)?;
+        let db = open_db_with_geometry(db_path.as_ref(), self.db.log_level, self.db.geometry())?;
 
         let range = self.command.unwind_range(&db)?;
 
@@ -68,6 +68,7 @@ impl BlockBuffer {
             if let Some(evicted_block) = self.remove_from_blocks(&evicted_num_hash) {
                 // evict the block if limit is hit
                 self.remove_from_parent(evicted_block.parent_hash, &evicted_num_hash);
+                self.metrics.evicted_blocks.increment(1);
             }
         }
         self.metrics.blocks.set(self.len() as f64);
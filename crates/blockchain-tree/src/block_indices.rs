@@ -232,6 +232,26 @@ impl BlockIndices {
         lose_chains
     }
 
+    /// Remove a side chain from indices, including the link from its fork block to its first
+    /// block.
+    ///
+    /// Returns the ids of the chains that fork off the removed chain.
+    pub(crate) fn remove_side_chain(&mut self, chain: &Chain) -> BTreeSet<BlockChainId> {
+        if let Some((_, first)) = chain.blocks().first_key_value() {
+            if let hash_map::Entry::Occupied(mut entry) =
+                self.fork_to_child.entry(first.parent_hash)
+            {
+                let set = entry.get_mut();
+                set.remove(&first.hash());
+                // remove set if empty
+                if set.is_empty() {
+                    entry.remove();
+                }
+            }
+        }
+        self.remove_chain(chain)
+    }
+
     /// Remove Blocks from indices.
     fn remove_block(
         &mut self,
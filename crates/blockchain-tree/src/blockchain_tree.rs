@@ -3,7 +3,8 @@ use crate::{
     canonical_chain::CanonicalChain,
     chain::{BlockChainId, BlockKind},
     metrics::TreeMetrics,
-    AppendableChain, BlockBuffer, BlockIndices, BlockchainTreeConfig, PostStateData, TreeExternals,
+    AppendableChain, BlockBuffer, BlockIndices, BlockchainTreeConfig, PostStateData,
+    StateRootValidation, TreeExternals,
 };
 use reth_db::{cursor::DbCursorRO, database::Database, tables, transaction::DbTx};
 use reth_interfaces::{
@@ -24,11 +25,11 @@ use reth_provider::{
     post_state::PostState,
     BlockExecutionWriter, BlockNumReader, BlockWriter, CanonStateNotification,
     CanonStateNotificationSender, CanonStateNotifications, Chain, DatabaseProvider,
-    DisplayBlocksChain, ExecutorFactory, HeaderProvider,
+    DisplayBlocksChain, ExecutorFactory, HeaderProvider, LatestStateProviderRef, StateRootProvider,
 };
 use reth_stages::{MetricEvent, MetricEventsSender};
 use std::{
-    collections::{BTreeMap, HashMap},
+    collections::{BTreeMap, HashMap, HashSet},
     sync::Arc,
 };
 use tracing::{debug, error, info, instrument, trace, warn};
@@ -87,6 +88,11 @@ pub struct BlockchainTree<DB: Database, C: Consensus, EF: ExecutorFactory> {
     externals: TreeExternals<DB, C, EF>,
     /// Tree configuration
     config: BlockchainTreeConfig,
+    /// The blocks whose state root verification was deferred, see [StateRootValidation].
+    ///
+    /// The state root of a chain that contains any of them is verified before it is made
+    /// canonical.
+    deferred_state_roots: HashMap<BlockHash, BlockNumber>,
     /// Broadcast channel for canon state changes notifications.
     canon_state_notification_sender: CanonStateNotificationSender,
     /// Metrics for the blockchain tree.
@@ -112,6 +118,9 @@ impl<DB: Database, C: Consensus, EF: ExecutorFactory> BlockchainTree<DB, C, EF>
         config: BlockchainTreeConfig,
     ) -> Result<Self, Error> {
         let max_reorg_depth = config.max_reorg_depth();
+        if !config.state_root_validation().is_every_block() {
+            warn!(target: "blockchain_tree", mode = %config.state_root_validation(), "State root verification of new blocks is deferred, only use this with a trusted consensus client");
+        }
 
         let last_canonical_hashes = externals
             .db
@@ -142,6 +151,7 @@ impl<DB: Database, C: Consensus, EF: ExecutorFactory> BlockchainTree<DB, C, EF>
                 BTreeMap::from_iter(last_canonical_hashes),
             ),
             config,
+            deferred_state_roots: Default::default(),
             canon_state_notification_sender,
             metrics: Default::default(),
             sync_metrics_tx: None,
@@ -421,13 +431,21 @@ impl<DB: Database, C: Consensus, EF: ExecutorFactory> BlockchainTree<DB, C, EF>
             let canonical_chain = self.canonical_chain();
 
             if block.parent_hash == canonical_chain.tip().hash {
-                let chain = AppendableChain::new_canonical_head_fork(
+                let block_kind = BlockKind::canonical_head_descendant(
+                    self.config.state_root_validation(),
+                    block.number,
+                );
+                let chain = AppendableChain::new_canonical_head_fork_with_kind(
                     block,
                     &parent_header,
                     canonical_chain.inner(),
                     parent,
                     &self.externals,
+                    block_kind,
                 )?;
+                if block_kind.defers_state_root() {
+                    self.deferred_state_roots.insert(block_num_hash.hash, block_num_hash.number);
+                }
                 (BlockStatus::Valid, chain)
             } else {
                 let chain = AppendableChain::new_canonical_fork(
@@ -490,7 +508,10 @@ impl<DB: Database, C: Consensus, EF: ExecutorFactory> BlockchainTree<DB, C, EF>
         let status = if chain_tip == block.parent_hash {
             // check if the chain extends the currently tracked canonical head
             let block_kind = if canonical_fork.hash == canonical_chain.tip().hash {
-                BlockKind::ExtendsCanonicalHead
+                BlockKind::canonical_head_descendant(
+                    self.config.state_root_validation(),
+                    block.number,
+                )
             } else {
                 BlockKind::ForksHistoricalBlock
             };
@@ -508,6 +529,9 @@ impl<DB: Database, C: Consensus, EF: ExecutorFactory> BlockchainTree<DB, C, EF>
             )?;
 
             self.block_indices.insert_non_fork_block(block_number, block_hash, chain_id);
+            if block_kind.defers_state_root() {
+                self.deferred_state_roots.insert(block_hash, block_number);
+            }
 
             if block_kind.extends_canonical_head() {
                 // if the block can be traced back to the canonical head, we were able to fully
@@ -598,9 +622,67 @@ impl<DB: Database, C: Consensus, EF: ExecutorFactory> BlockchainTree<DB, C, EF>
         self.block_indices.insert_chain(chain_id, &chain);
         // add chain_id -> chain index
         self.chains.insert(chain_id, chain);
+        self.evict_side_chains(chain_id);
         Some(chain_id)
     }
 
+    /// Evicts side chains until the number of chains is within the configured limit.
+    ///
+    /// Chains with the lowest tips are evicted first, oldest first on equal tips, together with all
+    /// chains that fork off them. The given chain, the chain of the pending block and the chains
+    /// they build on are never evicted.
+    fn evict_side_chains(&mut self, keep: BlockChainId) {
+        if self.chains.len() <= self.config.max_side_chains() {
+            return
+        }
+
+        let pending = self
+            .block_indices
+            .pending_block_num_hash()
+            .and_then(|pending| self.block_indices.get_blocks_chain_id(&pending.hash));
+
+        // collect the chains and all chains they fork off from
+        let mut protected = HashSet::new();
+        for mut chain_id in std::iter::once(keep).chain(pending) {
+            protected.insert(chain_id);
+            while let Some(fork_chain_id) = self
+                .chains
+                .get(&chain_id)
+                .and_then(|chain| self.block_indices.get_blocks_chain_id(&chain.fork_block_hash()))
+            {
+                protected.insert(fork_chain_id);
+                chain_id = fork_chain_id;
+            }
+        }
+
+        while self.chains.len() > self.config.max_side_chains() {
+            let Some(evict) = self
+                .chains
+                .iter()
+                .filter(|(id, _)| !protected.contains(id))
+                .min_by_key(|(id, chain)| (chain.tip().number, **id))
+                .map(|(id, _)| *id)
+            else {
+                break
+            };
+
+            let Some(chain) = self.chains.remove(&evict) else { break };
+            debug!(target: "blockchain_tree", chain_id = ?evict, tip = chain.tip().number, "Evicting side chain");
+            self.metrics.evicted_sidechains.increment(1);
+            // the evicted chain is unlinked from its fork block, its descendants are dropped with
+            // it
+            let mut remove_chains = self.block_indices.remove_side_chain(&chain);
+            while let Some(chain_id) = remove_chains.pop_first() {
+                if let Some(chain) = self.chains.remove(&chain_id) {
+                    debug!(target: "blockchain_tree", ?chain_id, tip = chain.tip().number, "Evicting side chain");
+                    self.metrics.evicted_sidechains.increment(1);
+                    remove_chains.extend(self.block_indices.remove_chain(&chain));
+                }
+            }
+        }
+        self.metrics.sidechains.set(self.chains.len() as f64);
+    }
+
     /// Checks the block buffer for the given block.
     pub fn get_buffered_block(&self, hash: &BlockHash) -> Option<&SealedBlockWithSenders> {
         self.buffered_blocks.block_by_hash(hash)
@@ -738,6 +820,8 @@ impl<DB: Database, C: Consensus, EF: ExecutorFactory> BlockchainTree<DB, C, EF>
         }
         // clean block buffer.
         self.buffered_blocks.clean_old_blocks(finalized_block);
+        // blocks that can't be made canonical anymore don't need to be verified
+        self.deferred_state_roots.retain(|_, number| *number > finalized_block);
     }
 
     /// Reads the last `N` canonical hashes from the database and updates the block indices of the
@@ -942,6 +1026,15 @@ impl<DB: Database, C: Consensus, EF: ExecutorFactory> BlockchainTree<DB, C, EF>
             new_canon_chain.append_chain(chain).expect("We have just build the chain.");
         }
 
+        // verify the state root of the tip if the verification of any block was deferred
+        if new_canon_chain
+            .blocks()
+            .values()
+            .any(|block| self.deferred_state_roots.contains_key(&block.hash()))
+        {
+            self.verify_deferred_state_root(&new_canon_chain, old_tip)?;
+        }
+
         // update canonical index
         self.block_indices.canonicalize_blocks(new_canon_chain.blocks());
 
@@ -987,11 +1080,14 @@ impl<DB: Database, C: Consensus, EF: ExecutorFactory> BlockchainTree<DB, C, EF>
             self.commit_canonical(new_canon_chain.clone())?;
 
             if let Some(old_canon_chain) = old_canon_chain {
+                self.metrics.reorgs.increment(1);
+                self.metrics.latest_reorg_depth.set(old_canon_chain.blocks().len() as f64);
+
                 // state action
-                chain_notification = CanonStateNotification::Reorg {
-                    old: Arc::new(old_canon_chain.clone()),
-                    new: Arc::new(new_canon_chain.clone()),
-                };
+                chain_notification = CanonStateNotification::reorg(
+                    Arc::new(old_canon_chain.clone()),
+                    Arc::new(new_canon_chain.clone()),
+                );
                 // insert old canon chain
                 self.insert_chain(AppendableChain::new(old_canon_chain));
             } else {
@@ -1012,6 +1108,61 @@ impl<DB: Database, C: Consensus, EF: ExecutorFactory> BlockchainTree<DB, C, EF>
         Ok(CanonicalOutcome::Committed { head })
     }
 
+    /// Verifies the state root of the tip of a chain that is about to be made canonical.
+    ///
+    /// If the chain forks off a block below the canonical tip, the canonical blocks above the fork
+    /// block are reverted in a database transaction that is never committed, to compute the state
+    /// root on top of the fork block.
+    ///
+    /// If the state root doesn't match, the verification of some blocks of the chain was deferred
+    /// and at least one of them is invalid. Since it's unknown which one, the entire chain and all
+    /// chains forking off it are discarded, and the state root of every block is verified from now
+    /// on.
+    fn verify_deferred_state_root(
+        &mut self,
+        chain: &Chain,
+        canonical_tip: BlockNumHash,
+    ) -> Result<(), Error> {
+        let tip = chain.tip();
+        let state_root = if chain.fork_block_hash() == canonical_tip.hash {
+            self.externals.database().latest()?.state_root(chain.state().clone())?
+        } else {
+            let provider = DatabaseProvider::new_rw(
+                self.externals.db.tx_mut()?,
+                self.externals.chain_spec.clone(),
+            );
+            provider
+                .take_block_and_execution_range(
+                    self.externals.chain_spec.as_ref(),
+                    (chain.fork_block_number() + 1)..=canonical_tip.number,
+                )
+                .map_err(|e| BlockExecutionError::CanonicalRevert { inner: e.to_string() })?;
+            // the transaction is dropped without committing the revert
+            LatestStateProviderRef::new(provider.tx_ref()).state_root(chain.state().clone())?
+        };
+
+        for block in chain.blocks().values() {
+            self.deferred_state_roots.remove(&block.hash());
+        }
+        if state_root == tip.state_root {
+            return Ok(())
+        }
+
+        error!(target: "blockchain_tree", number = tip.number, hash = ?tip.hash(), got = ?state_root, expected = ?tip.state_root, "Deferred state root verification failed, discarding chain and verifying the state root of every block");
+        self.config =
+            self.config.clone().with_state_root_validation(StateRootValidation::EveryBlock);
+
+        let mut remove_chains = self.block_indices.remove_side_chain(chain);
+        while let Some(chain_id) = remove_chains.pop_first() {
+            if let Some(chain) = self.chains.remove(&chain_id) {
+                remove_chains.extend(self.block_indices.remove_chain(&chain));
+            }
+        }
+        self.metrics.sidechains.set(self.chains.len() as f64);
+
+        Err(ConsensusError::BodyStateRootDiff { got: state_root, expected: tip.state_root }.into())
+    }
+
     /// Subscribe to new blocks events.
     ///
     /// Note: Only canonical blocks are send.
@@ -1401,7 +1552,7 @@ mod tests {
         assert!(tree.make_canonical(&block2a_hash).is_ok());
         // check notification.
         assert_matches!(canon_notif.try_recv(),
-            Ok(CanonStateNotification::Reorg{ old, new})
+            Ok(CanonStateNotification::Reorg{ old, new, .. })
             if *old.blocks() == BTreeMap::from([(block2.number,block2.clone())])
                 && *new.blocks() == BTreeMap::from([(block2a.number,block2a.clone())]));
 
@@ -1450,7 +1601,7 @@ mod tests {
 
         // check notification.
         assert_matches!(canon_notif.try_recv(),
-            Ok(CanonStateNotification::Reorg{ old, new})
+            Ok(CanonStateNotification::Reorg{ old, new, .. })
             if *old.blocks() == BTreeMap::from([(block1.number,block1.clone()),(block2a.number,block2a.clone())])
                 && *new.blocks() == BTreeMap::from([(block1a.number,block1a.clone())]));
 
@@ -1485,7 +1636,7 @@ mod tests {
 
         // check notification.
         assert_matches!(canon_notif.try_recv(),
-            Ok(CanonStateNotification::Reorg{ old, new})
+            Ok(CanonStateNotification::Reorg{ old, new, .. })
             if *old.blocks() == BTreeMap::from([(block1a.number,block1a.clone())])
                 && *new.blocks() == BTreeMap::from([(block1.number,block1.clone()),(block2.number,block2.clone())]));
 
@@ -1593,4 +1744,265 @@ mod tests {
             .with_buffered_blocks(BTreeMap::from([]))
             .assert(&tree);
     }
+
+    #[tokio::test]
+    async fn evict_side_chains() {
+        let data = BlockChainTestData::default_with_numbers(11, 12);
+        let (block1, exec1) = data.blocks[0].clone();
+        let (block2, exec2) = data.blocks[1].clone();
+        let genesis = data.genesis;
+
+        let side_block = |block: &SealedBlockWithSenders, hash: u8, parent_hash: H256| {
+            let mut block = block.clone();
+            block.hash = H256([hash; 32]);
+            block.parent_hash = parent_hash;
+            block
+        };
+        let block1a = side_block(&block1, 0x33, block1.parent_hash);
+        let block2a = side_block(&block2, 0x34, block1a.hash);
+        let block1b = side_block(&block1, 0x35, block1.parent_hash);
+        let block1c = side_block(&block1, 0x36, block1.parent_hash);
+        let block2d = side_block(&block2, 0x37, block1a.hash);
+        let block1e = side_block(&block1, 0x38, block1.parent_hash);
+
+        // test pops execution results from vector, so order is from last to first.
+        let externals = setup_externals(vec![
+            exec1.clone(),
+            exec2.clone(),
+            exec1.clone(),
+            exec1.clone(),
+            exec2,
+            exec1.clone(),
+            exec1,
+        ]);
+
+        setup_genesis(externals.db.clone(), genesis);
+
+        // make tree that keeps at most 3 side chains
+        let config = BlockchainTreeConfig::new(1, 2, 3, 2).with_max_side_chains(3);
+        let (sender, _) = tokio::sync::broadcast::channel(10);
+        let mut tree =
+            BlockchainTree::new(externals, sender, config).expect("failed to create tree");
+
+        assert!(tree.make_canonical(&H256::zero()).is_ok());
+        tree.finalize_block(10);
+
+        // b1 is the pending block
+        assert_eq!(
+            tree.insert_block(block1.clone()).unwrap(),
+            InsertPayloadOk::Inserted(BlockStatus::Valid)
+        );
+        assert_eq!(
+            tree.insert_block(block1a.clone()).unwrap(),
+            InsertPayloadOk::Inserted(BlockStatus::Valid)
+        );
+        assert_eq!(
+            tree.insert_block(block2a.clone()).unwrap(),
+            InsertPayloadOk::Inserted(BlockStatus::Valid)
+        );
+        assert_eq!(
+            tree.insert_block(block1b.clone()).unwrap(),
+            InsertPayloadOk::Inserted(BlockStatus::Valid)
+        );
+
+        // Trie state:
+        //      b2a
+        //      |
+        // b1   b1a  b1b
+        // |   /    /
+        // | /   /
+        // g1 (10)
+        TreeTester::default()
+            .with_chain_num(3)
+            .with_block_to_chain(HashMap::from([
+                (block1.hash, 0),
+                (block1a.hash, 1),
+                (block2a.hash, 1),
+                (block1b.hash, 2),
+            ]))
+            .with_pending_blocks((
+                block1.number,
+                HashSet::from([block1.hash, block1a.hash, block1b.hash]),
+            ))
+            .assert(&tree);
+
+        // exceeding the limit evicts b1b, the lowest tip that is neither the new nor the pending
+        // chain
+        assert_eq!(
+            tree.insert_block(block1c.clone()).unwrap(),
+            InsertPayloadOk::Inserted(BlockStatus::Valid)
+        );
+
+        // Trie state:
+        //      b2a
+        //      |
+        // b1   b1a  b1c
+        // |   /    /
+        // | /   /
+        // g1 (10)
+        TreeTester::default()
+            .with_chain_num(3)
+            .with_block_to_chain(HashMap::from([
+                (block1.hash, 0),
+                (block1a.hash, 1),
+                (block2a.hash, 1),
+                (block1c.hash, 3),
+            ]))
+            .with_fork_to_child(HashMap::from([(
+                block1.parent_hash,
+                HashSet::from([block1.hash, block1a.hash, block1c.hash]),
+            )]))
+            .with_pending_blocks((
+                block1.number,
+                HashSet::from([block1.hash, block1a.hash, block1c.hash]),
+            ))
+            .assert(&tree);
+
+        // forking b1a evicts b1c, the chain of b1a is kept as the new chain builds on it
+        assert_eq!(
+            tree.insert_block(block2d.clone()).unwrap(),
+            InsertPayloadOk::Inserted(BlockStatus::Accepted)
+        );
+
+        // Trie state:
+        //      b2a  b2d
+        //      |   /
+        // b1   b1a
+        // |   /
+        // | /
+        // g1 (10)
+        TreeTester::default()
+            .with_chain_num(3)
+            .with_block_to_chain(HashMap::from([
+                (block1.hash, 0),
+                (block1a.hash, 1),
+                (block2a.hash, 1),
+                (block2d.hash, 4),
+            ]))
+            .with_fork_to_child(HashMap::from([
+                (block1.parent_hash, HashSet::from([block1.hash, block1a.hash])),
+                (block1a.hash, HashSet::from([block2d.hash])),
+            ]))
+            .assert(&tree);
+
+        // the chains of b2a and b2d have the same tip, the older chain is evicted together with
+        // the chain that forks off it
+        assert_eq!(
+            tree.insert_block(block1e.clone()).unwrap(),
+            InsertPayloadOk::Inserted(BlockStatus::Valid)
+        );
+
+        // Trie state:
+        // b1   b1e
+        // |   /
+        // | /
+        // g1 (10)
+        TreeTester::default()
+            .with_chain_num(2)
+            .with_block_to_chain(HashMap::from([(block1.hash, 0), (block1e.hash, 5)]))
+            .with_fork_to_child(HashMap::from([(
+                block1.parent_hash,
+                HashSet::from([block1.hash, block1e.hash]),
+            )]))
+            .with_pending_blocks((block1.number, HashSet::from([block1.hash, block1e.hash])))
+            .assert(&tree);
+
+        // the canonical chain is untouched
+        assert_eq!(tree.block_indices.canonical_tip().number, 10);
+        assert!(tree.is_block_hash_canonical(&H256::zero()).unwrap());
+        assert!(tree.make_canonical(&block1.hash).is_ok());
+        assert!(tree.is_block_hash_canonical(&block1.hash).unwrap());
+    }
+
+    #[tokio::test]
+    async fn deferred_state_root() {
+        let data = BlockChainTestData::default_with_numbers(11, 12);
+        let (block1, exec1) = data.blocks[0].clone();
+        let genesis = data.genesis;
+
+        let mut block1a = block1.clone();
+        block1a.hash = H256([0x33; 32]);
+        let mut block1b = block1.clone();
+        block1b.hash = H256([0x34; 32]);
+        block1b.header.header.state_root = H256([0x35; 32]);
+
+        // test pops execution results from vector, so order is from last to first.
+        let externals = setup_externals(vec![exec1.clone(), exec1.clone(), exec1]);
+
+        setup_genesis(externals.db.clone(), genesis);
+
+        // make tree that only verifies the state root of chains that are made canonical
+        let config = BlockchainTreeConfig::new(1, 2, 3, 2)
+            .with_state_root_validation(StateRootValidation::Final);
+        let (sender, _) = tokio::sync::broadcast::channel(10);
+        let mut tree =
+            BlockchainTree::new(externals, sender, config).expect("failed to create tree");
+
+        assert!(tree.make_canonical(&H256::zero()).is_ok());
+        tree.finalize_block(10);
+
+        // all blocks extend the canonical head, so they are valid without verifying their state
+        // root, including b1b whose state root is wrong
+        for block in [&block1, &block1a, &block1b] {
+            assert_eq!(
+                tree.insert_block(block.clone()).unwrap(),
+                InsertPayloadOk::Inserted(BlockStatus::Valid)
+            );
+        }
+        assert_eq!(
+            tree.deferred_state_roots,
+            HashMap::from([
+                (block1.hash, block1.number),
+                (block1a.hash, block1a.number),
+                (block1b.hash, block1b.number),
+            ])
+        );
+
+        // the state root of b1 is verified when it's committed on top of the canonical head
+        assert!(tree.make_canonical(&block1.hash).is_ok());
+        assert!(!tree.deferred_state_roots.contains_key(&block1.hash));
+
+        // Trie state:
+        // b1 (canon)  b1a  b1b
+        // |         /    /
+        // |      /    /
+        // g1 (10)
+        TreeTester::default()
+            .with_chain_num(2)
+            .with_block_to_chain(HashMap::from([(block1a.hash, 1), (block1b.hash, 2)]))
+            .with_fork_to_child(HashMap::from([(
+                block1.parent_hash,
+                HashSet::from([block1a.hash, block1b.hash]),
+            )]))
+            .assert(&tree);
+
+        // b1b no longer extends the canonical head, its state root is still verified before the
+        // reorg and the mismatch discards it
+        assert_matches!(
+            tree.make_canonical(&block1b.hash),
+            Err(Error::Consensus(ConsensusError::BodyStateRootDiff { .. }))
+        );
+        assert!(tree.config.state_root_validation().is_every_block());
+        assert!(tree.is_block_hash_canonical(&block1.hash).unwrap());
+
+        // Trie state:
+        // b1 (canon)  b1a
+        // |         /
+        // |      /
+        // g1 (10)
+        TreeTester::default()
+            .with_chain_num(1)
+            .with_block_to_chain(HashMap::from([(block1a.hash, 1)]))
+            .with_fork_to_child(HashMap::from([(
+                block1.parent_hash,
+                HashSet::from([block1a.hash]),
+            )]))
+            .assert(&tree);
+
+        // the deferred verification of b1a passes and it replaces b1
+        assert!(tree.make_canonical(&block1a.hash).is_ok());
+        assert!(tree.is_block_hash_canonical(&block1a.hash).unwrap());
+        assert!(!tree.is_block_hash_canonical(&block1.hash).unwrap());
+        assert!(tree.deferred_state_roots.is_empty());
+    }
 }
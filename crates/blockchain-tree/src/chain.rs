@@ -3,7 +3,7 @@
 //! A [`Chain`] contains the state of accounts for the chain after execution of its constituent
 //! blocks, as well as a list of the blocks the chain is composed of.
 use super::externals::TreeExternals;
-use crate::{post_state::PostState, PostStateDataRef};
+use crate::{post_state::PostState, PostStateDataRef, StateRootValidation};
 use reth_db::database::Database;
 use reth_interfaces::{
     blockchain_tree::error::{BlockchainTreeError, InsertBlockError},
@@ -67,6 +67,33 @@ impl AppendableChain {
         canonical_fork: ForkBlock,
         externals: &TreeExternals<DB, C, EF>,
     ) -> Result<Self, InsertBlockError>
+    where
+        DB: Database,
+        C: Consensus,
+        EF: ExecutorFactory,
+    {
+        Self::new_canonical_head_fork_with_kind(
+            block,
+            parent_header,
+            canonical_block_hashes,
+            canonical_fork,
+            externals,
+            BlockKind::ExtendsCanonicalHead,
+        )
+    }
+
+    /// Create a new chain that forks off the canonical.
+    ///
+    /// This will also verify the state root of the block extending the canonical chain, unless the
+    /// verification is deferred by the given [BlockKind].
+    pub(crate) fn new_canonical_head_fork_with_kind<DB, C, EF>(
+        block: SealedBlockWithSenders,
+        parent_header: &SealedHeader,
+        canonical_block_hashes: &BTreeMap<BlockNumber, BlockHash>,
+        canonical_fork: ForkBlock,
+        externals: &TreeExternals<DB, C, EF>,
+        block_kind: BlockKind,
+    ) -> Result<Self, InsertBlockError>
     where
         DB: Database,
         C: Consensus,
@@ -82,11 +109,12 @@ impl AppendableChain {
             canonical_fork,
         };
 
-        let changeset = Self::validate_and_execute_canonical_head_descendant(
+        let changeset = Self::validate_and_execute(
             block.clone(),
             parent_header,
             state_provider,
             externals,
+            block_kind,
         )
         .map_err(|err| InsertBlockError::new(block.block.clone(), err.into()))?;
 
@@ -207,7 +235,7 @@ impl AppendableChain {
         let post_state = executor.execute_and_verify_receipt(&block, U256::MAX, Some(senders))?;
 
         // check state root if the block extends the canonical chain.
-        if block_kind.extends_canonical_head() {
+        if block_kind.verifies_state_root() {
             // check state root
             let state_root = provider.state_root(post_state.clone())?;
             if block.state_root != state_root {
@@ -222,29 +250,6 @@ impl AppendableChain {
         Ok(post_state)
     }
 
-    /// Validate and execute the given block that _extends the canonical chain_, validating its
-    /// state root after execution.
-    fn validate_and_execute_canonical_head_descendant<PSDP, DB, C, EF>(
-        block: SealedBlockWithSenders,
-        parent_block: &SealedHeader,
-        post_state_data_provider: PSDP,
-        externals: &TreeExternals<DB, C, EF>,
-    ) -> Result<PostState, Error>
-    where
-        PSDP: PostStateDataProvider,
-        DB: Database,
-        C: Consensus,
-        EF: ExecutorFactory,
-    {
-        Self::validate_and_execute(
-            block,
-            parent_block,
-            post_state_data_provider,
-            externals,
-            BlockKind::ExtendsCanonicalHead,
-        )
-    }
-
     /// Validate and execute the given sidechain block, skipping state root validation.
     fn validate_and_execute_sidechain<PSDP, DB, C, EF>(
         block: SealedBlockWithSenders,
@@ -326,15 +331,46 @@ pub(crate) enum BlockKind {
     ///
     ///    [`head..(block.parent)*,block`]
     ExtendsCanonicalHead,
+    /// The `block` is a descendant of the canonical head, but its state root is only verified once
+    /// its chain is made canonical, see [StateRootValidation].
+    ExtendsCanonicalHeadDeferred,
     /// The block can be traced back to an ancestor of the canonical head: a historical block, but
     /// this chain does __not__ include the canonical head.
     ForksHistoricalBlock,
 }
 
 impl BlockKind {
+    /// Returns the kind of a block that is a descendant of the canonical head, depending on
+    /// whether its state root must be verified on insertion.
+    pub(crate) fn canonical_head_descendant(
+        state_root_validation: StateRootValidation,
+        block_number: BlockNumber,
+    ) -> Self {
+        if state_root_validation.is_required(block_number) {
+            BlockKind::ExtendsCanonicalHead
+        } else {
+            BlockKind::ExtendsCanonicalHeadDeferred
+        }
+    }
+
     /// Returns `true` if the block is a descendant of the canonical head.
     #[inline]
     pub(crate) fn extends_canonical_head(&self) -> bool {
+        matches!(self, BlockKind::ExtendsCanonicalHead | BlockKind::ExtendsCanonicalHeadDeferred)
+    }
+
+    /// Returns `true` if the block is a descendant of the canonical head whose state root
+    /// verification is deferred.
+    #[inline]
+    pub(crate) fn defers_state_root(&self) -> bool {
+        matches!(self, BlockKind::ExtendsCanonicalHeadDeferred)
+    }
+
+    /// Returns `true` if the state root of the block is verified after execution.
+    ///
+    /// This is only possible if the block is a descendant of the canonical head.
+    #[inline]
+    pub(crate) fn verifies_state_root(&self) -> bool {
         matches!(self, BlockKind::ExtendsCanonicalHead)
     }
 }
@@ -1,5 +1,8 @@
 //! Blockchain tree configuration
 
+use reth_primitives::BlockNumber;
+use std::{fmt, str::FromStr};
+
 /// The configuration for the blockchain tree.
 #[derive(Clone, Debug)]
 pub struct BlockchainTreeConfig {
@@ -11,10 +14,16 @@ pub struct BlockchainTreeConfig {
     max_reorg_depth: u64,
     /// The number of unconnected blocks that we are buffering
     max_unconnected_blocks: usize,
+    /// The number of side chains that are kept in memory.
+    ///
+    /// If exceeded, the side chains with the lowest tips are evicted.
+    max_side_chains: usize,
     /// For EVM's "BLOCKHASH" opcode we require last 256 block hashes. So we need to specify
     /// at least `additional_canonical_block_hashes`+`max_reorg_depth`, for eth that would be
     /// 256+64.
     num_of_additional_canonical_block_hashes: u64,
+    /// When the state root of blocks that extend the canonical head is verified.
+    state_root_validation: StateRootValidation,
 }
 
 impl Default for BlockchainTreeConfig {
@@ -29,6 +38,10 @@ impl Default for BlockchainTreeConfig {
             num_of_additional_canonical_block_hashes: 256,
             // max unconnected blocks.
             max_unconnected_blocks: 200,
+            // max side chains.
+            max_side_chains: 64,
+            // verify the state root of every block.
+            state_root_validation: StateRootValidation::EveryBlock,
         }
     }
 }
@@ -49,9 +62,33 @@ impl BlockchainTreeConfig {
             max_reorg_depth,
             num_of_additional_canonical_block_hashes,
             max_unconnected_blocks,
+            max_side_chains: Self::default().max_side_chains,
+            state_root_validation: StateRootValidation::EveryBlock,
         }
     }
 
+    /// Sets the maximum number of side chains that are kept in memory.
+    ///
+    /// # Panics
+    ///
+    /// If `max_side_chains` is 0.
+    pub fn with_max_side_chains(mut self, max_side_chains: usize) -> Self {
+        assert!(max_side_chains > 0, "max_side_chains must be greater than 0");
+        self.max_side_chains = max_side_chains;
+        self
+    }
+
+    /// Sets when the state root of blocks that extend the canonical head is verified.
+    ///
+    /// See [StateRootValidation] for the risks of deferring the verification.
+    pub fn with_state_root_validation(
+        mut self,
+        state_root_validation: StateRootValidation,
+    ) -> Self {
+        self.state_root_validation = state_root_validation;
+        self
+    }
+
     /// Return the maximum reorg depth.
     pub fn max_reorg_depth(&self) -> u64 {
         self.max_reorg_depth
@@ -72,4 +109,112 @@ impl BlockchainTreeConfig {
     pub fn max_unconnected_blocks(&self) -> usize {
         self.max_unconnected_blocks
     }
+
+    /// Return max number of side chains that are kept in memory
+    pub fn max_side_chains(&self) -> usize {
+        self.max_side_chains
+    }
+
+    /// Return when the state root of blocks that extend the canonical head is verified
+    pub fn state_root_validation(&self) -> StateRootValidation {
+        self.state_root_validation
+    }
+}
+
+/// Determines when the state root of blocks that extend the canonical head is verified.
+///
+/// Computing the state root is the most expensive part of inserting a block. When syncing from a
+/// trusted source, the verification can be deferred: blocks are then executed and reported as
+/// valid without checking their state root, and only the tip of the chain that is made canonical
+/// is verified before it is committed to the database. If that verification fails, the chain is
+/// discarded and the tree falls back to [StateRootValidation::EveryBlock].
+///
+/// Deferring the verification is unsafe against untrusted peers or consensus clients, since an
+/// invalid block is reported as valid until its chain is made canonical.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum StateRootValidation {
+    /// Verify the state root of every block.
+    #[default]
+    EveryBlock,
+    /// Verify the state root of every block whose number is a multiple of the given interval, and
+    /// of the tip of every chain that is made canonical.
+    ///
+    /// An interval of 0 is treated as 1.
+    Checkpoints(u64),
+    /// Only verify the state root of the tip of every chain that is made canonical.
+    Final,
+}
+
+impl StateRootValidation {
+    /// Returns `true` if the state root of every block is verified.
+    pub fn is_every_block(&self) -> bool {
+        matches!(self, StateRootValidation::EveryBlock)
+    }
+
+    /// Returns `true` if the state root of the block with the given number must be verified when
+    /// the block is inserted.
+    pub fn is_required(&self, block_number: BlockNumber) -> bool {
+        match self {
+            StateRootValidation::EveryBlock => true,
+            StateRootValidation::Checkpoints(interval) => block_number % (*interval).max(1) == 0,
+            StateRootValidation::Final => false,
+        }
+    }
+}
+
+impl fmt::Display for StateRootValidation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StateRootValidation::EveryBlock => f.write_str("every-block"),
+            StateRootValidation::Checkpoints(interval) => write!(f, "{interval}"),
+            StateRootValidation::Final => f.write_str("final"),
+        }
+    }
+}
+
+impl FromStr for StateRootValidation {
+    type Err = String;
+
+    /// Parses `every-block`, `final`, or the checkpoint interval in blocks.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "every-block" => Ok(StateRootValidation::EveryBlock),
+            "final" => Ok(StateRootValidation::Final),
+            interval => match interval.parse::<u64>() {
+                Ok(0) => Err("checkpoint interval must be greater than 0".to_string()),
+                Ok(interval) => Ok(StateRootValidation::Checkpoints(interval)),
+                Err(_) => Err(format!(
+                    "invalid state root validation mode {s:?}, expected `every-block`, `final` or a checkpoint interval"
+                )),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_state_root_validation() {
+        for mode in [
+            StateRootValidation::EveryBlock,
+            StateRootValidation::Checkpoints(1000),
+            StateRootValidation::Final,
+        ] {
+            assert_eq!(mode.to_string().parse::<StateRootValidation>(), Ok(mode));
+        }
+        assert!("0".parse::<StateRootValidation>().is_err());
+        assert!("sometimes".parse::<StateRootValidation>().is_err());
+    }
+
+    #[test]
+    fn state_root_checkpoints() {
+        let mode = StateRootValidation::Checkpoints(10);
+        assert!(mode.is_required(0));
+        assert!(!mode.is_required(9));
+        assert!(mode.is_required(10));
+        assert!(StateRootValidation::EveryBlock.is_required(9));
+        assert!(!StateRootValidation::Final.is_required(10));
+    }
 }
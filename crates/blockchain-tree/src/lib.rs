@@ -35,7 +35,7 @@ pub mod chain;
 pub use chain::AppendableChain;
 
 pub mod config;
-pub use config::BlockchainTreeConfig;
+pub use config::{BlockchainTreeConfig, StateRootValidation};
 
 pub mod externals;
 pub use externals::TreeExternals;
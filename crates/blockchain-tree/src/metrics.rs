@@ -1,5 +1,5 @@
 use reth_metrics::{
-    metrics::{self, Gauge},
+    metrics::{self, Counter, Gauge},
     Metrics,
 };
 
@@ -11,6 +11,12 @@ pub struct TreeMetrics {
     pub sidechains: Gauge,
     /// The highest block number in the canonical chain
     pub canonical_chain_height: Gauge,
+    /// The number of reorgs
+    pub reorgs: Counter,
+    /// The number of blocks reverted by the latest reorg
+    pub latest_reorg_depth: Gauge,
+    /// The number of sidechains that were evicted because the sidechain limit was exceeded
+    pub evicted_sidechains: Counter,
 }
 
 /// Metrics for the blockchain tree block buffer
@@ -19,4 +25,6 @@ pub struct TreeMetrics {
 pub struct BlockBufferMetrics {
     /// Total blocks in the block buffer
     pub blocks: Gauge,
+    /// The number of blocks that were evicted because the buffer limit was exceeded
+    pub evicted_blocks: Counter,
 }
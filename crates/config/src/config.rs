@@ -5,9 +5,9 @@ use reth_downloaders::{
     headers::reverse_headers::ReverseHeadersDownloaderBuilder,
 };
 use reth_network::{NetworkConfigBuilder, PeersConfig, SessionsConfig};
-use reth_primitives::PruneMode;
+use reth_primitives::{constants::MINIMUM_PRUNING_DISTANCE, BlockNumHash, PruneMode};
 use secp256k1::SecretKey;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 use std::path::PathBuf;
 
 /// Configuration for the reth node.
@@ -96,6 +96,13 @@ pub struct HeadersConfig {
     pub downloader_request_limit: u64,
     /// The maximum number of headers to download before committing progress to the database.
     pub commit_threshold: u64,
+    /// Optional trusted checkpoint (block number and hash).
+    ///
+    /// Headers at or below the checkpoint are only checked for their link to the parent, the
+    /// consensus rules are skipped for them. The checkpoint must be known to be canonical.
+    ///
+    /// Default: None
+    pub trusted_checkpoint: Option<BlockNumHash>,
 }
 
 impl Default for HeadersConfig {
@@ -106,6 +113,7 @@ impl Default for HeadersConfig {
             downloader_max_concurrent_requests: 100,
             downloader_min_concurrent_requests: 5,
             downloader_max_buffered_responses: 100,
+            trusted_checkpoint: None,
         }
     }
 }
@@ -118,6 +126,7 @@ impl From<HeadersConfig> for ReverseHeadersDownloaderBuilder {
             .max_concurrent_requests(config.downloader_max_concurrent_requests)
             .max_buffered_responses(config.downloader_max_buffered_responses)
             .stream_batch_size(config.commit_threshold as usize)
+            .trusted_checkpoint(config.trusted_checkpoint)
     }
 }
 
@@ -194,11 +203,15 @@ impl From<BodiesConfig> for BodiesDownloaderBuilder {
 pub struct SenderRecoveryConfig {
     /// The maximum number of transactions to process before committing progress to the database.
     pub commit_threshold: u64,
+    /// The number of threads used to recover senders.
+    ///
+    /// Defaults to the number of physical cores if not set.
+    pub threads: Option<usize>,
 }
 
 impl Default for SenderRecoveryConfig {
     fn default() -> Self {
-        Self { commit_threshold: 5_000_000 }
+        Self { commit_threshold: 5_000_000, threads: None }
     }
 }
 
@@ -210,11 +223,33 @@ pub struct ExecutionConfig {
     pub max_blocks: Option<u64>,
     /// The maximum amount of state changes to keep in memory before the execution stage commits.
     pub max_changes: Option<u64>,
+    /// The maximum amount of gas to process before the execution stage commits.
+    pub max_cumulative_gas: Option<u64>,
+    /// Whether to compute and validate the state root of each batch in the execution stage,
+    /// instead of only in the merkle stage.
+    pub incremental_state_root: bool,
+    /// The commit thresholds of the first batch after startup, in percent of the configured
+    /// thresholds.
+    ///
+    /// Default: 1
+    pub batch_ramp_initial_percent: u64,
+    /// The number of batches until the commit thresholds grow to the configured values.
+    /// `0` disables the ramp.
+    ///
+    /// Default: 5
+    pub batch_ramp_batches: u64,
 }
 
 impl Default for ExecutionConfig {
     fn default() -> Self {
-        Self { max_blocks: Some(500_000), max_changes: Some(5_000_000) }
+        Self {
+            max_blocks: Some(500_000),
+            max_changes: Some(5_000_000),
+            max_cumulative_gas: None,
+            incremental_state_root: false,
+            batch_ramp_initial_percent: 1,
+            batch_ramp_batches: 5,
+        }
     }
 }
 
@@ -270,11 +305,15 @@ impl Default for TransactionLookupConfig {
 pub struct IndexHistoryConfig {
     /// The maximum number of blocks to process before committing progress to the database.
     pub commit_threshold: u64,
+    /// The maximum number of changesets to process before committing progress to the database.
+    ///
+    /// Default: None (only `commit_threshold` applies)
+    pub max_changesets_per_commit: Option<u64>,
 }
 
 impl Default for IndexHistoryConfig {
     fn default() -> Self {
-        Self { commit_threshold: 100_000 }
+        Self { commit_threshold: 100_000, max_changesets_per_commit: None }
     }
 }
 
@@ -295,29 +334,68 @@ impl Default for PruneConfig {
 }
 
 /// Pruning configuration for every part of the data that can be pruned.
+///
+/// Each part is pruned independently according to its own [PruneMode]. Distances smaller than
+/// [MINIMUM_PRUNING_DISTANCE] are rejected, because the blockchain tree needs that many recent
+/// blocks to unwind a reorg.
 #[derive(Debug, Clone, Default, Copy, Deserialize, PartialEq, Serialize)]
 #[serde(default)]
 pub struct PruneParts {
     /// Sender Recovery pruning configuration.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_opt_prune_mode_with_min_distance"
+    )]
     pub sender_recovery: Option<PruneMode>,
     /// Transaction Lookup pruning configuration.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_opt_prune_mode_with_min_distance"
+    )]
     pub transaction_lookup: Option<PruneMode>,
     /// Receipts pruning configuration.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_opt_prune_mode_with_min_distance"
+    )]
     pub receipts: Option<PruneMode>,
     /// Account History pruning configuration.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_opt_prune_mode_with_min_distance"
+    )]
     pub account_history: Option<PruneMode>,
     /// Storage History pruning configuration.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_opt_prune_mode_with_min_distance"
+    )]
     pub storage_history: Option<PruneMode>,
 }
 
+/// Deserializes an optional [PruneMode], rejecting [PruneMode::Distance] values smaller than
+/// [MINIMUM_PRUNING_DISTANCE].
+fn deserialize_opt_prune_mode_with_min_distance<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<PruneMode>, D::Error> {
+    let prune_mode = Option::<PruneMode>::deserialize(deserializer)?;
+
+    match prune_mode {
+        Some(PruneMode::Distance(distance)) if distance < MINIMUM_PRUNING_DISTANCE => {
+            Err(serde::de::Error::invalid_value(
+                serde::de::Unexpected::Unsigned(distance),
+                &format!("prune mode distance not less than {MINIMUM_PRUNING_DISTANCE} blocks")
+                    .as_str(),
+            ))
+        }
+        _ => Ok(prune_mode),
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::Config;
+    use super::{Config, PruneParts};
+    use reth_primitives::{constants::MINIMUM_PRUNING_DISTANCE, PruneMode};
 
     const EXTENSION: &str = "toml";
 
@@ -348,4 +426,22 @@ mod tests {
             assert_eq!(config, loaded_config);
         })
     }
+
+    #[test]
+    fn prune_parts_min_distance() {
+        let parts: PruneParts = serde_json::from_str(&format!(
+            r#"{{"receipts": {{"distance": {MINIMUM_PRUNING_DISTANCE}}}, "sender_recovery": "full"}}"#
+        ))
+        .unwrap();
+        assert_eq!(parts.receipts, Some(PruneMode::Distance(MINIMUM_PRUNING_DISTANCE)));
+        assert_eq!(parts.sender_recovery, Some(PruneMode::Full));
+        assert_eq!(parts.transaction_lookup, None);
+
+        let err = serde_json::from_str::<PruneParts>(&format!(
+            r#"{{"receipts": {{"distance": {}}}}}"#,
+            MINIMUM_PRUNING_DISTANCE - 1
+        ))
+        .unwrap_err();
+        assert!(err.to_string().contains("not less than"));
+    }
 }
@@ -1,6 +1,10 @@
 use crate::engine::forkchoice::ForkchoiceStatus;
-use reth_interfaces::consensus::ForkchoiceState;
-use reth_primitives::SealedBlock;
+use reth_interfaces::{
+    blockchain_tree::error::{BlockchainTreeError, InsertBlockErrorKind},
+    consensus::{ConsensusError, ForkchoiceState},
+    executor::{BlockExecutionError, BlockValidationError},
+};
+use reth_primitives::{SealedBlock, H256};
 use std::sync::Arc;
 
 /// Events emitted by [crate::BeaconConsensusEngine].
@@ -12,4 +16,108 @@ pub enum BeaconConsensusEngineEvent {
     CanonicalBlockAdded(Arc<SealedBlock>),
     /// A block was added to the fork chain.
     ForkBlockAdded(Arc<SealedBlock>),
+    /// A new payload was rejected as invalid.
+    InvalidPayload {
+        /// The hash of the rejected payload.
+        block_hash: H256,
+        /// The latest valid hash that was returned to the consensus layer.
+        latest_valid_hash: Option<H256>,
+        /// Why the payload is invalid.
+        reason: InvalidPayloadReason,
+    },
+}
+
+/// The reason a payload was rejected as invalid.
+///
+/// The display output is returned as the `validationError` of the engine API response.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum InvalidPayloadReason {
+    /// The payload could not be converted into a block, e.g. because of a block hash mismatch.
+    #[error("{0}")]
+    Malformed(String),
+    /// The payload descends from a payload that was previously rejected.
+    #[error("links to previously rejected block")]
+    InvalidAncestor,
+    /// The senders of the payload's transactions could not be recovered.
+    #[error("Failed to recover senders for block")]
+    SenderRecovery,
+    /// The state root after execution differs from the state root of the header.
+    #[error("Block state root ({got:?}) is different from expected: ({expected:?})")]
+    StateRootMismatch {
+        /// The state root after execution.
+        got: H256,
+        /// The state root of the header.
+        expected: H256,
+    },
+    /// The gas used by the execution differs from the gas used of the header.
+    #[error("Block gas used {got} is different from expected gas used {expected}.")]
+    GasUsedMismatch {
+        /// The gas used by the execution.
+        got: u64,
+        /// The gas used of the header.
+        expected: u64,
+    },
+    /// The receipts root after execution differs from the receipts root of the header.
+    #[error("Receipt root {got:?} is different than expected {expected:?}.")]
+    ReceiptsRootMismatch {
+        /// The receipts root after execution.
+        got: H256,
+        /// The receipts root of the header.
+        expected: H256,
+    },
+    /// A transaction of the payload is invalid.
+    #[error("EVM reported invalid transaction ({hash:?}): {message}")]
+    InvalidTransaction {
+        /// The hash of the invalid transaction.
+        hash: H256,
+        /// The error reported by the EVM.
+        message: String,
+    },
+    /// The payload violates another consensus rule.
+    #[error(transparent)]
+    Consensus(ConsensusError),
+    /// The payload failed another execution check.
+    #[error(transparent)]
+    Execution(BlockValidationError),
+    /// The payload violates a rule of the blockchain tree, e.g. it's below the finalized block.
+    #[error(transparent)]
+    Tree(BlockchainTreeError),
+}
+
+impl InvalidPayloadReason {
+    /// Returns the reason for an error that occurred while inserting a payload.
+    ///
+    /// Returns `None` if the error is not caused by an invalid payload, see
+    /// [InsertBlockErrorKind::is_invalid_block].
+    pub fn from_insert_error(error: &InsertBlockErrorKind) -> Option<Self> {
+        if !error.is_invalid_block() {
+            return None
+        }
+
+        let reason = match error {
+            InsertBlockErrorKind::SenderRecovery => Self::SenderRecovery,
+            InsertBlockErrorKind::Consensus(ConsensusError::BodyStateRootDiff {
+                got,
+                expected,
+            }) => Self::StateRootMismatch { got: *got, expected: *expected },
+            InsertBlockErrorKind::Consensus(error) => Self::Consensus(error.clone()),
+            InsertBlockErrorKind::Execution(BlockExecutionError::Validation(error)) => {
+                match error {
+                    BlockValidationError::BlockGasUsed { got, expected } => {
+                        Self::GasUsedMismatch { got: *got, expected: *expected }
+                    }
+                    BlockValidationError::ReceiptRootDiff { got, expected } => {
+                        Self::ReceiptsRootMismatch { got: *got, expected: *expected }
+                    }
+                    BlockValidationError::EVM { hash, message } => {
+                        Self::InvalidTransaction { hash: *hash, message: message.clone() }
+                    }
+                    error => Self::Execution(error.clone()),
+                }
+            }
+            InsertBlockErrorKind::Tree(error) => Self::Tree(*error),
+            InsertBlockErrorKind::Execution(_) | InsertBlockErrorKind::Internal(_) => return None,
+        };
+        Some(reason)
+    }
 }
@@ -11,6 +11,9 @@ pub(crate) struct EngineMetrics {
     pub(crate) pipeline_runs: Counter,
     /// The total count of forkchoice updated messages received.
     pub(crate) forkchoice_updated_messages: Counter,
+    /// The total count of forkchoice updated messages that were superseded by a queued update to
+    /// a descendant head and answered without a separate canonicalization.
+    pub(crate) coalesced_forkchoice_updated_messages: Counter,
     /// The total count of new payload messages received.
     pub(crate) new_payload_messages: Counter,
 }
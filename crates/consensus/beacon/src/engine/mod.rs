@@ -2,7 +2,7 @@ use crate::{
     engine::{message::OnForkChoiceUpdated, metrics::EngineMetrics},
     sync::{EngineSyncController, EngineSyncEvent},
 };
-use futures::{Future, StreamExt, TryFutureExt};
+use futures::{stream::Peekable, Future, StreamExt, TryFutureExt};
 use reth_db::database::Database;
 use reth_interfaces::{
     blockchain_tree::{
@@ -60,7 +60,7 @@ mod forkchoice;
 pub(crate) mod sync;
 
 use crate::engine::forkchoice::{ForkchoiceStateHash, ForkchoiceStateTracker};
-pub use event::BeaconConsensusEngineEvent;
+pub use event::{BeaconConsensusEngineEvent, InvalidPayloadReason};
 use reth_interfaces::blockchain_tree::InsertPayloadOk;
 use reth_primitives::constants::EPOCH_SLOTS;
 
@@ -229,7 +229,10 @@ where
     /// Used for emitting updates about whether the engine is syncing or not.
     sync_state_updater: Box<dyn NetworkSyncUpdater>,
     /// The Engine API message receiver.
-    engine_message_rx: UnboundedReceiverStream<BeaconEngineMessage>,
+    ///
+    /// This is peekable so that queued forkchoice updates can be coalesced, see
+    /// [Self::on_forkchoice_updated_batch].
+    engine_message_rx: Peekable<UnboundedReceiverStream<BeaconEngineMessage>>,
     /// A clone of the handle
     handle: BeaconConsensusEngineHandle,
     /// Tracks the received forkchoice state updates received by the CL.
@@ -333,7 +336,7 @@ where
             sync,
             blockchain,
             sync_state_updater,
-            engine_message_rx: UnboundedReceiverStream::new(rx),
+            engine_message_rx: UnboundedReceiverStream::new(rx).peekable(),
             handle: handle.clone(),
             forkchoice_state_tracker: Default::default(),
             payload_builder,
@@ -614,6 +617,102 @@ where
         false
     }
 
+    /// Handles the given forkchoice update together with the forkchoice updates that are queued
+    /// right behind it, if they only advance the head.
+    ///
+    /// After a connectivity issue, the CL can send a burst of forkchoice updates whose heads are
+    /// descendants of each other. Canonicalizing each of them would walk the same chain of the tree
+    /// multiple times, so instead only the last update of such a sequence is processed and the
+    /// superseded updates are answered once their head is part of the canonical chain.
+    ///
+    /// Only updates without payload attributes are superseded, and only if their successor's head
+    /// is a block of the tree that descends from their head. If the last update doesn't make its
+    /// head canonical, the superseded updates are dropped: applying them afterwards would move the
+    /// head back to an older state than the latest one received.
+    ///
+    /// Returns `true` if the engine should terminate, see [Self::on_forkchoice_updated].
+    fn on_forkchoice_updated_batch(
+        &mut self,
+        cx: &mut Context<'_>,
+        mut state: ForkchoiceState,
+        mut attrs: Option<PayloadAttributes>,
+        mut tx: oneshot::Sender<Result<OnForkChoiceUpdated, reth_interfaces::Error>>,
+    ) -> bool {
+        let mut superseded = Vec::new();
+        while attrs.is_none() && self.sync.is_pipeline_idle() {
+            let next_head = match Pin::new(&mut self.engine_message_rx).poll_peek(cx) {
+                Poll::Ready(Some(BeaconEngineMessage::ForkchoiceUpdated {
+                    state: next, ..
+                })) => next.head_block_hash,
+                _ => break,
+            };
+            if !self.is_tree_descendant(next_head, state.head_block_hash) {
+                break
+            }
+
+            let Poll::Ready(Some(BeaconEngineMessage::ForkchoiceUpdated {
+                state: next_state,
+                payload_attrs,
+                tx: next_tx,
+            })) = self.engine_message_rx.poll_next_unpin(cx)
+            else {
+                unreachable!("message was peeked")
+            };
+            superseded.push((
+                std::mem::replace(&mut state, next_state),
+                std::mem::replace(&mut tx, next_tx),
+            ));
+            attrs = payload_attrs;
+        }
+
+        if !superseded.is_empty() {
+            trace!(target: "consensus::engine", superseded = superseded.len(), head = ?state.head_block_hash, "Coalescing forkchoice updates");
+        }
+
+        let terminate = self.on_forkchoice_updated(state, attrs, tx);
+
+        for (state, tx) in superseded {
+            self.metrics.forkchoice_updated_messages.increment(1);
+            self.metrics.coalesced_forkchoice_updated_messages.increment(1);
+            let _ = tx.send(self.superseded_forkchoice_updated(state));
+        }
+
+        terminate
+    }
+
+    /// Returns the response to a forkchoice update that was superseded by a later update to a
+    /// descendant head.
+    fn superseded_forkchoice_updated(
+        &self,
+        state: ForkchoiceState,
+    ) -> Result<OnForkChoiceUpdated, reth_interfaces::Error> {
+        if self.blockchain.block_number(state.head_block_hash)?.is_none() {
+            // the head didn't become canonical because the later update failed. The update is not
+            // applied out of order, instead the CL is told to retry.
+            return Ok(OnForkChoiceUpdated::syncing())
+        }
+
+        if !self.is_consistent_forkchoice_state(&state)? {
+            return Ok(OnForkChoiceUpdated::invalid_state())
+        }
+        Ok(OnForkChoiceUpdated::valid(PayloadStatus::new(
+            PayloadStatusEnum::Valid,
+            Some(state.head_block_hash),
+        )))
+    }
+
+    /// Returns `true` if `hash` is the hash of a block in the tree that descends from, or is, the
+    /// block with the given `ancestor` hash.
+    fn is_tree_descendant(&self, mut hash: H256, ancestor: H256) -> bool {
+        while hash != ancestor {
+            match self.blockchain.header_by_hash(hash) {
+                Some(header) => hash = header.parent_hash,
+                None => return false,
+            }
+        }
+        true
+    }
+
     /// Called to resolve chain forks and ensure that the Execution layer is working with the latest
     /// valid chain.
     ///
@@ -637,6 +736,18 @@ where
             return Ok(OnForkChoiceUpdated::with_invalid(status))
         }
 
+        // the state can't be valid if it references a safe or finalized block that is known to be
+        // invalid, regardless of whether we know the head
+        for hash in [state.safe_block_hash, state.finalized_block_hash] {
+            if hash.is_zero() {
+                continue
+            }
+            if let Some(status) = self.check_invalid_ancestor(hash) {
+                debug!(target: "consensus::engine", ?hash, "Forkchoice state references invalid block");
+                return Ok(OnForkChoiceUpdated::with_invalid(status))
+            }
+        }
+
         if self.sync.is_pipeline_active() {
             // We can only process new forkchoice updates if the pipeline is idle, since it requires
             // exclusive access to the database
@@ -646,6 +757,15 @@ where
 
         let status = match self.blockchain.make_canonical(&state.head_block_hash) {
             Ok(outcome) => {
+                // the head is VALID, so the safe and finalized blocks must be part of its chain
+                if !self.is_consistent_forkchoice_state(&state)? {
+                    debug!(target: "consensus::engine", ?state, "Safe or finalized block is not part of the canonical chain");
+                    if !outcome.is_already_canonical() {
+                        self.blockchain.set_canonical_head(outcome.into_header());
+                    }
+                    return Ok(OnForkChoiceUpdated::invalid_state())
+                }
+
                 if !outcome.is_already_canonical() {
                     debug!(target: "consensus::engine", hash=?state.head_block_hash, number=outcome.header().number, "canonicalized new head");
 
@@ -685,6 +805,23 @@ where
         Ok(OnForkChoiceUpdated::valid(status))
     }
 
+    /// Returns `true` if the safe and finalized blocks of the given state are canonical.
+    ///
+    /// This must only be called once the head of the state is canonical. If either block is not
+    /// part of the canonical chain, the state must be rejected with `-38002: Invalid forkchoice
+    /// state`, because the head is VALID but the state is inconsistent.
+    fn is_consistent_forkchoice_state(
+        &self,
+        state: &ForkchoiceState,
+    ) -> Result<bool, reth_interfaces::Error> {
+        for hash in [state.safe_block_hash, state.finalized_block_hash] {
+            if !hash.is_zero() && self.blockchain.block_number(hash)?.is_none() {
+                return Ok(false)
+            }
+        }
+        Ok(true)
+    }
+
     /// Sets the state of the canon chain tracker based to the given head.
     ///
     /// This expects the given head to be the new canonical head.
@@ -901,8 +1038,15 @@ where
         let block_hash = block.hash();
         let block_num_hash = block.num_hash();
 
+        // the configured max block is final, blocks above it are never inserted
+        if self.sync.is_beyond_max_block(block.number) {
+            trace!(target: "consensus::engine", ?block_num_hash, "Ignoring payload above max block");
+            return Ok(PayloadStatus::from_status(PayloadStatusEnum::Syncing))
+        }
+
         // now check the block itself
         if let Some(status) = self.check_invalid_ancestor_with_head(block.parent_hash, block.hash) {
+            self.notify_invalid_payload(block_hash, &status, InvalidPayloadReason::InvalidAncestor);
             return Ok(status)
         }
 
@@ -946,10 +1090,11 @@ where
     ///    - invalid extra data
     ///    - invalid transactions
     fn ensure_well_formed_payload(
-        &self,
+        &mut self,
         payload: ExecutionPayload,
     ) -> Result<SealedBlock, PayloadStatus> {
         let parent_hash = payload.parent_hash;
+        let block_hash = payload.block_hash;
         let block = match SealedBlock::try_from(payload) {
             Ok(block) => block,
             Err(error) => {
@@ -962,9 +1107,14 @@ where
                     latest_valid_hash =
                         self.latest_valid_hash_for_invalid_payload(parent_hash, None);
                 }
-                let status = PayloadStatusEnum::from(error);
-
-                return Err(PayloadStatus::new(status, latest_valid_hash))
+                let reason = InvalidPayloadReason::Malformed(error.to_string());
+                let status = PayloadStatus::new(
+                    PayloadStatusEnum::Invalid { validation_error: reason.to_string() },
+                    latest_valid_hash,
+                );
+                self.notify_invalid_payload(block_hash, &status, reason);
+
+                return Err(status)
             }
         };
 
@@ -1019,6 +1169,11 @@ where
                 if let Some(status) =
                     self.check_invalid_ancestor_with_head(block.parent_hash, block.hash)
                 {
+                    self.notify_invalid_payload(
+                        block_hash,
+                        &status,
+                        InvalidPayloadReason::InvalidAncestor,
+                    );
                     return Ok(status)
                 }
 
@@ -1047,22 +1202,42 @@ where
     ) -> Result<PayloadStatus, BeaconOnNewPayloadError> {
         let (block, error) = err.split();
 
-        if error.is_invalid_block() {
+        if let Some(reason) = InvalidPayloadReason::from_insert_error(&error) {
             // all of these occurred if the payload is invalid
             let parent_hash = block.parent_hash;
+            let block_hash = block.hash;
 
             // keep track of the invalid header
             self.invalid_headers.insert(block.header);
 
             let latest_valid_hash =
                 self.latest_valid_hash_for_invalid_payload(parent_hash, Some(&error));
-            let status = PayloadStatusEnum::Invalid { validation_error: error.to_string() };
-            Ok(PayloadStatus::new(status, latest_valid_hash))
+            let status = PayloadStatus::new(
+                PayloadStatusEnum::Invalid { validation_error: reason.to_string() },
+                latest_valid_hash,
+            );
+            self.notify_invalid_payload(block_hash, &status, reason);
+            Ok(status)
         } else {
             Err(BeaconOnNewPayloadError::Internal(Box::new(error)))
         }
     }
 
+    /// Notifies listeners that the payload with the given hash was rejected with the given
+    /// status.
+    fn notify_invalid_payload(
+        &mut self,
+        block_hash: H256,
+        status: &PayloadStatus,
+        reason: InvalidPayloadReason,
+    ) {
+        self.listeners.notify(BeaconConsensusEngineEvent::InvalidPayload {
+            block_hash,
+            latest_valid_hash: status.latest_valid_hash,
+            reason,
+        });
+    }
+
     /// Attempt to restore the tree with the given block hash.
     ///
     /// This is invoked after a full pipeline to update the tree with the most recent canonical
@@ -1420,7 +1595,7 @@ where
             match this.engine_message_rx.poll_next_unpin(cx) {
                 Poll::Ready(Some(msg)) => match msg {
                     BeaconEngineMessage::ForkchoiceUpdated { state, payload_attrs, tx } => {
-                        if this.on_forkchoice_updated(state, payload_attrs, tx) {
+                        if this.on_forkchoice_updated_batch(cx, state, payload_attrs, tx) {
                             return Poll::Ready(Ok(()))
                         }
                     }
@@ -1833,8 +2008,12 @@ mod tests {
 
     mod fork_choice_updated {
         use super::*;
-        use reth_db::{tables, transaction::DbTxMut};
+        use reth_db::{
+            tables,
+            transaction::{DbTx, DbTxMut},
+        };
         use reth_interfaces::test_utils::{generators, generators::random_block};
+        use reth_primitives::proofs::EMPTY_ROOT;
         use reth_rpc_types::engine::ForkchoiceUpdateError;
 
         #[tokio::test]
@@ -1915,6 +2094,163 @@ mod tests {
             assert_matches!(engine_rx.try_recv(), Err(TryRecvError::Empty));
         }
 
+        /// Sets the empty state root on the block, so that the tree accepts it when it's executed
+        /// with an empty post state.
+        fn with_empty_state_root(block: SealedBlock) -> SealedBlock {
+            let mut block = block.unseal();
+            block.header.state_root = EMPTY_ROOT;
+            block.seal_slow()
+        }
+
+        /// Returns an engine with blocks 0 and 1 in the database, and blocks 2 and 3 on top of
+        /// them that are only known to the tree, together with blocks 1 to 3.
+        fn superseded_forkchoice_fixture(
+        ) -> (TestBeaconConsensusEngine, TestEnv<Arc<DatabaseEnv>>, [SealedBlock; 3]) {
+            let mut rng = generators::rng();
+            let chain_spec = Arc::new(
+                ChainSpecBuilder::default()
+                    .chain(MAINNET.chain)
+                    .genesis(MAINNET.genesis.clone())
+                    .paris_activated()
+                    .build(),
+            );
+
+            let (consensus_engine, env) = TestConsensusEngineBuilder::new(chain_spec.clone())
+                .with_pipeline_exec_outputs(VecDeque::from([Ok(ExecOutput {
+                    checkpoint: StageCheckpoint::new(0),
+                    done: true,
+                })]))
+                .with_executor_results(vec![PostState::default(); 2])
+                .build();
+
+            let genesis = random_block(&mut rng, 0, None, None, Some(0));
+            let block1 = random_block(&mut rng, 1, Some(genesis.hash), None, Some(0));
+            insert_blocks(env.db.as_ref(), chain_spec.clone(), [&genesis, &block1].into_iter());
+            env.db
+                .update(|tx| {
+                    tx.put::<tables::SyncStage>(
+                        StageId::Finish.to_string(),
+                        StageCheckpoint::new(block1.number),
+                    )
+                })
+                .unwrap()
+                .unwrap();
+
+            let block2 = with_empty_state_root(random_block(
+                &mut rng,
+                2,
+                Some(block1.hash),
+                Some(0),
+                Some(0),
+            ));
+            let block3 = with_empty_state_root(random_block(
+                &mut rng,
+                3,
+                Some(block2.hash),
+                Some(0),
+                Some(0),
+            ));
+            consensus_engine.blockchain.restore_canonical_hashes(block1.number).unwrap();
+            for block in [&block2, &block3] {
+                assert_matches!(
+                    consensus_engine.blockchain.insert_block_without_senders(block.clone()),
+                    Ok(_)
+                );
+            }
+
+            (consensus_engine, env, [block1, block2, block3])
+        }
+
+        #[tokio::test]
+        async fn superseded_forkchoice_updates() {
+            let (consensus_engine, env, [block1, block2, block3]) = superseded_forkchoice_fixture();
+
+            // both updates are queued before the engine runs, so the first is superseded
+            let rx2 = env.engine_handle.send_fork_choice_updated(
+                ForkchoiceState {
+                    head_block_hash: block2.hash,
+                    finalized_block_hash: block1.hash,
+                    ..Default::default()
+                },
+                None,
+            );
+            let rx3 = env.engine_handle.send_fork_choice_updated(
+                ForkchoiceState {
+                    head_block_hash: block3.hash,
+                    finalized_block_hash: block1.hash,
+                    ..Default::default()
+                },
+                None,
+            );
+
+            let mut engine_rx = spawn_consensus_engine(consensus_engine);
+
+            let result3 = rx3.await.unwrap().unwrap().await.unwrap();
+            let expected_result = ForkchoiceUpdated::new(PayloadStatus::new(
+                PayloadStatusEnum::Valid,
+                Some(block3.hash),
+            ));
+            assert_eq!(result3, expected_result);
+
+            // the superseded update is valid, with its own head as the latest valid hash
+            let result2 = rx2.await.unwrap().unwrap().await.unwrap();
+            let expected_result = ForkchoiceUpdated::new(PayloadStatus::new(
+                PayloadStatusEnum::Valid,
+                Some(block2.hash),
+            ));
+            assert_eq!(result2, expected_result);
+
+            assert_matches!(engine_rx.try_recv(), Err(TryRecvError::Empty));
+        }
+
+        #[tokio::test]
+        async fn superseded_forkchoice_updates_dropped_on_failure() {
+            let (mut consensus_engine, env, [block1, block2, block3]) =
+                superseded_forkchoice_fixture();
+
+            // a known invalid block on top of block 1
+            let mut invalid = block2.clone().unseal();
+            invalid.header.timestamp += 1;
+            let invalid = invalid.seal_slow();
+            consensus_engine.invalid_headers.insert(invalid.header.clone());
+
+            // the later update fails, because it references the invalid block
+            let rx2 = env.engine_handle.send_fork_choice_updated(
+                ForkchoiceState {
+                    head_block_hash: block2.hash,
+                    finalized_block_hash: block1.hash,
+                    ..Default::default()
+                },
+                None,
+            );
+            let rx3 = env.engine_handle.send_fork_choice_updated(
+                ForkchoiceState {
+                    head_block_hash: block3.hash,
+                    finalized_block_hash: invalid.hash,
+                    ..Default::default()
+                },
+                None,
+            );
+
+            let mut engine_rx = spawn_consensus_engine(consensus_engine);
+
+            let result3 = rx3.await.unwrap().unwrap().await.unwrap();
+            assert_matches!(result3.payload_status.status, PayloadStatusEnum::Invalid { .. });
+
+            // the superseded update is not applied after the failed one
+            let result2 = rx2.await.unwrap().unwrap().await.unwrap();
+            assert_eq!(result2, ForkchoiceUpdated::from_status(PayloadStatusEnum::Syncing));
+            assert_eq!(
+                env.db
+                    .view(|tx| tx.get::<tables::CanonicalHeaders>(block2.number))
+                    .unwrap()
+                    .unwrap(),
+                None
+            );
+
+            assert_matches!(engine_rx.try_recv(), Err(TryRecvError::Empty));
+        }
+
         #[tokio::test]
         async fn unknown_head_hash() {
             let mut rng = generators::rng();
@@ -2003,6 +2339,128 @@ mod tests {
             drop(engine);
         }
 
+        #[tokio::test]
+        async fn valid_head_unknown_finalized_or_safe_hash() {
+            let mut rng = generators::rng();
+            let chain_spec = Arc::new(
+                ChainSpecBuilder::default()
+                    .chain(MAINNET.chain)
+                    .genesis(MAINNET.genesis.clone())
+                    .paris_activated()
+                    .build(),
+            );
+
+            let (consensus_engine, env) = TestConsensusEngineBuilder::new(chain_spec.clone())
+                .with_pipeline_exec_outputs(VecDeque::from([Ok(ExecOutput {
+                    checkpoint: StageCheckpoint::new(0),
+                    done: true,
+                })]))
+                .disable_blockchain_tree_sync()
+                .build();
+
+            let genesis = random_block(&mut rng, 0, None, None, Some(0));
+            let block1 = random_block(&mut rng, 1, Some(genesis.hash), None, Some(0));
+            insert_blocks(env.db.as_ref(), chain_spec.clone(), [&genesis, &block1].into_iter());
+            env.db
+                .update(|tx| {
+                    tx.put::<tables::SyncStage>(
+                        StageId::Finish.to_string(),
+                        StageCheckpoint::new(block1.number),
+                    )
+                })
+                .unwrap()
+                .unwrap();
+
+            let _engine = spawn_consensus_engine(consensus_engine);
+
+            // the head is VALID, but the finalized block is not part of its chain
+            let res = env
+                .send_forkchoice_updated(ForkchoiceState {
+                    head_block_hash: block1.hash,
+                    finalized_block_hash: H256::random(),
+                    ..Default::default()
+                })
+                .await;
+            assert_matches!(
+                res,
+                Err(BeaconForkChoiceUpdateError::ForkchoiceUpdateError(
+                    ForkchoiceUpdateError::InvalidState
+                ))
+            );
+
+            // same for the safe block
+            let res = env
+                .send_forkchoice_updated(ForkchoiceState {
+                    head_block_hash: block1.hash,
+                    safe_block_hash: H256::random(),
+                    finalized_block_hash: genesis.hash,
+                })
+                .await;
+            assert_matches!(
+                res,
+                Err(BeaconForkChoiceUpdateError::ForkchoiceUpdateError(
+                    ForkchoiceUpdateError::InvalidState
+                ))
+            );
+
+            // the state is accepted once it's consistent
+            let res = env
+                .send_forkchoice_updated(ForkchoiceState {
+                    head_block_hash: block1.hash,
+                    safe_block_hash: block1.hash,
+                    finalized_block_hash: genesis.hash,
+                })
+                .await;
+            let expected_result = ForkchoiceUpdated::from_status(PayloadStatusEnum::Valid)
+                .with_latest_valid_hash(block1.hash);
+            assert_matches!(res, Ok(result) => assert_eq!(result, expected_result));
+        }
+
+        #[tokio::test]
+        async fn invalid_finalized_hash() {
+            let mut rng = generators::rng();
+            let chain_spec = Arc::new(
+                ChainSpecBuilder::default()
+                    .chain(MAINNET.chain)
+                    .genesis(MAINNET.genesis.clone())
+                    .paris_activated()
+                    .build(),
+            );
+
+            let (mut consensus_engine, env) = TestConsensusEngineBuilder::new(chain_spec.clone())
+                .with_pipeline_exec_outputs(VecDeque::from([Ok(ExecOutput {
+                    checkpoint: StageCheckpoint::new(0),
+                    done: true,
+                })]))
+                .disable_blockchain_tree_sync()
+                .build();
+
+            let genesis = random_block(&mut rng, 0, None, None, Some(0));
+            let block1 = random_block(&mut rng, 1, Some(genesis.hash), None, Some(0));
+            insert_blocks(env.db.as_ref(), chain_spec.clone(), [&genesis, &block1].into_iter());
+
+            // a known invalid block on top of block 1
+            let invalid = random_block(&mut rng, 2, Some(block1.hash), None, Some(0));
+            consensus_engine.invalid_headers.insert(invalid.header.clone());
+
+            let _engine = spawn_consensus_engine(consensus_engine);
+
+            // the head is unknown, but the state references a known invalid block, so it's
+            // rejected instead of triggering a sync
+            let res = env
+                .send_forkchoice_updated(ForkchoiceState {
+                    head_block_hash: H256::random(),
+                    finalized_block_hash: invalid.hash,
+                    ..Default::default()
+                })
+                .await;
+            let expected_result = ForkchoiceUpdated::from_status(PayloadStatusEnum::Invalid {
+                validation_error: PayloadValidationError::LinksToRejectedPayload.to_string(),
+            })
+            .with_latest_valid_hash(block1.hash);
+            assert_matches!(res, Ok(result) => assert_eq!(result, expected_result));
+        }
+
         #[tokio::test]
         async fn forkchoice_updated_pre_merge() {
             let mut rng = generators::rng();
@@ -2289,6 +2747,7 @@ mod tests {
             assert_matches!(res, Ok(ForkchoiceUpdated { payload_status, .. }) => assert_eq!(payload_status, expected_result));
 
             // Send new payload
+            let mut events = env.engine_handle.event_listener();
             let result =
                 env.send_new_payload_retry_on_syncing(block2.clone().into()).await.unwrap();
 
@@ -2299,6 +2758,24 @@ mod tests {
             .with_latest_valid_hash(H256::zero());
             assert_eq!(result, expected_result);
 
+            // the rejection is reported to the event listeners with the same reason
+            let event = loop {
+                match events.next().await.expect("engine is running") {
+                    event @ BeaconConsensusEngineEvent::InvalidPayload { .. } => break event,
+                    _ => continue,
+                }
+            };
+            assert_matches!(event, BeaconConsensusEngineEvent::InvalidPayload { block_hash, latest_valid_hash, reason } => {
+                assert_eq!(block_hash, block2.hash);
+                assert_eq!(latest_valid_hash, Some(H256::zero()));
+                assert_eq!(
+                    reason,
+                    InvalidPayloadReason::Execution(BlockValidationError::BlockPreMerge {
+                        hash: block2.hash
+                    })
+                );
+            });
+
             assert_matches!(engine_rx.try_recv(), Err(TryRecvError::Empty));
         }
     }
@@ -170,6 +170,13 @@ where
         has_reached_max_block
     }
 
+    /// Returns `true` if the given block number is above the configured `max_block`, if any.
+    ///
+    /// Blocks above the `max_block` must not be inserted, so the node never advances past it.
+    pub(crate) fn is_beyond_max_block(&self, block_number: BlockNumber) -> bool {
+        self.max_block.map_or(false, |max_block| block_number > max_block)
+    }
+
     /// Advances the pipeline state.
     ///
     /// This checks for the result in the channel, or returns pending if the pipeline is idle.
@@ -211,7 +218,9 @@ where
                 let (tx, rx) = oneshot::channel();
 
                 let pipeline = pipeline.take().expect("exists");
-                self.pipeline_task_spawner.spawn_critical_blocking(
+                // The pipeline stops on its own once the shutdown signal is fired, after it has
+                // committed the batch it's executing.
+                self.pipeline_task_spawner.spawn_critical_blocking_graceful(
                     "pipeline task",
                     Box::pin(async move {
                         let result = pipeline.run_as_fut(target).await;
@@ -1,8 +1,8 @@
 //! Collection of methods for block validation.
 use reth_interfaces::{consensus::ConsensusError, Result as RethResult};
 use reth_primitives::{
-    constants, BlockNumber, ChainSpec, Hardfork, Header, InvalidTransactionError, SealedBlock,
-    SealedHeader, Transaction, TransactionSignedEcRecovered, TxEip1559, TxEip2930, TxLegacy,
+    BlockNumber, ChainSpec, Hardfork, Header, InvalidTransactionError, SealedBlock, SealedHeader,
+    Transaction, TransactionSignedEcRecovered, TxEip1559, TxEip2930, TxLegacy,
 };
 use reth_provider::{AccountReader, HeaderProvider, WithdrawalsProvider};
 use std::{
@@ -204,15 +204,10 @@ pub fn validate_block_standalone(
     if chain_spec.fork(Hardfork::Shanghai).active_at_timestamp(block.timestamp) {
         let withdrawals =
             block.withdrawals.as_ref().ok_or(ConsensusError::BodyWithdrawalsMissing)?;
-        let withdrawals_root = reth_primitives::proofs::calculate_withdrawals_root(withdrawals);
-        let header_withdrawals_root =
-            block.withdrawals_root.as_ref().ok_or(ConsensusError::WithdrawalsRootMissing)?;
-        if withdrawals_root != *header_withdrawals_root {
-            return Err(ConsensusError::BodyWithdrawalsRootDiff {
-                got: withdrawals_root,
-                expected: *header_withdrawals_root,
-            })
+        if block.withdrawals_root.is_none() {
+            return Err(ConsensusError::WithdrawalsRootMissing)
         }
+        block.header.validate_withdrawals_root(Some(withdrawals))?;
 
         // Validate that withdrawal index is monotonically increasing within a block.
         if let Some(first) = withdrawals.first() {
@@ -234,77 +229,14 @@ pub fn validate_block_standalone(
 }
 
 /// Validate block in regards to parent
+///
+/// See [SealedHeader::validate_against_parent].
 pub fn validate_header_regarding_parent(
     parent: &SealedHeader,
     child: &SealedHeader,
     chain_spec: &ChainSpec,
 ) -> Result<(), ConsensusError> {
-    // Parent number is consistent.
-    if parent.number + 1 != child.number {
-        return Err(ConsensusError::ParentBlockNumberMismatch {
-            parent_block_number: parent.number,
-            block_number: child.number,
-        })
-    }
-
-    if parent.hash != child.parent_hash {
-        return Err(ConsensusError::ParentHashMismatch {
-            expected_parent_hash: parent.hash,
-            got_parent_hash: child.parent_hash,
-        })
-    }
-
-    // timestamp in past check
-    if child.timestamp <= parent.timestamp {
-        return Err(ConsensusError::TimestampIsInPast {
-            parent_timestamp: parent.timestamp,
-            timestamp: child.timestamp,
-        })
-    }
-
-    // TODO Check difficulty increment between parent and child
-    // Ace age did increment it by some formula that we need to follow.
-
-    let mut parent_gas_limit = parent.gas_limit;
-
-    // By consensus, gas_limit is multiplied by elasticity (*2) on
-    // on exact block that hardfork happens.
-    if chain_spec.fork(Hardfork::London).transitions_at_block(child.number) {
-        parent_gas_limit = parent.gas_limit * constants::EIP1559_ELASTICITY_MULTIPLIER;
-    }
-
-    // Check gas limit, max diff between child/parent gas_limit should be  max_diff=parent_gas/1024
-    if child.gas_limit > parent_gas_limit {
-        if child.gas_limit - parent_gas_limit >= parent_gas_limit / 1024 {
-            return Err(ConsensusError::GasLimitInvalidIncrease {
-                parent_gas_limit,
-                child_gas_limit: child.gas_limit,
-            })
-        }
-    } else if parent_gas_limit - child.gas_limit >= parent_gas_limit / 1024 {
-        return Err(ConsensusError::GasLimitInvalidDecrease {
-            parent_gas_limit,
-            child_gas_limit: child.gas_limit,
-        })
-    }
-
-    // EIP-1559 check base fee
-    if chain_spec.fork(Hardfork::London).active_at_block(child.number) {
-        let base_fee = child.base_fee_per_gas.ok_or(ConsensusError::BaseFeeMissing)?;
-
-        let expected_base_fee =
-            if chain_spec.fork(Hardfork::London).transitions_at_block(child.number) {
-                constants::EIP1559_INITIAL_BASE_FEE
-            } else {
-                // This BaseFeeMissing will not happen as previous blocks are checked to have them.
-                parent.next_block_base_fee().ok_or(ConsensusError::BaseFeeMissing)?
-            };
-        if expected_base_fee != base_fee {
-            return Err(ConsensusError::BaseFeeDiff { expected: expected_base_fee, got: base_fee })
-        }
-    }
-
-    Ok(())
+    Ok(child.validate_against_parent(parent, chain_spec)?)
 }
 
 /// Validate block in regards to chain (parent)
@@ -377,7 +309,10 @@ pub fn full_validation<Provider: HeaderProvider + AccountReader + WithdrawalsPro
     let transactions = block
         .body
         .iter()
-        .map(|tx| tx.try_ecrecovered().ok_or(ConsensusError::TransactionSignerRecoveryError))
+        .map(|tx| {
+            tx.try_ecrecovered()
+                .ok_or(ConsensusError::TransactionSignerRecoveryError { hash: tx.hash })
+        })
         .collect::<Result<Vec<_>, _>>()?;
 
     validate_all_transaction_regarding_block_and_nonces(
@@ -1,6 +1,7 @@
 use async_trait::async_trait;
 use reth_primitives::{
-    BlockHash, BlockNumber, Header, InvalidTransactionError, SealedBlock, SealedHeader, H256, U256,
+    BlockHash, BlockNumber, Header, HeaderValidationError, InvalidTransactionError, SealedBlock,
+    SealedHeader, WithdrawalsValidationError, H256, U256,
 };
 use std::fmt::Debug;
 
@@ -95,8 +96,8 @@ pub enum ConsensusError {
     BaseFeeMissing,
     #[error("Block base fee ({got}) is different than expected: ({expected}).")]
     BaseFeeDiff { expected: u64, got: u64 },
-    #[error("Transaction signer recovery error.")]
-    TransactionSignerRecoveryError,
+    #[error("Transaction {hash:?} signer recovery error.")]
+    TransactionSignerRecoveryError { hash: H256 },
     #[error("Extra data {len} exceeds max length: ")]
     ExtraDataExceedsMax { len: usize },
     #[error("Difficulty after merge is not zero")]
@@ -117,3 +118,51 @@ pub enum ConsensusError {
     #[error(transparent)]
     InvalidTransaction(#[from] InvalidTransactionError),
 }
+
+impl From<HeaderValidationError> for ConsensusError {
+    fn from(error: HeaderValidationError) -> Self {
+        match error {
+            HeaderValidationError::ParentBlockNumberMismatch {
+                parent_block_number,
+                block_number,
+            } => ConsensusError::ParentBlockNumberMismatch { parent_block_number, block_number },
+            HeaderValidationError::ParentHashMismatch { expected_parent_hash, got_parent_hash } => {
+                ConsensusError::ParentHashMismatch { expected_parent_hash, got_parent_hash }
+            }
+            HeaderValidationError::TimestampIsInPast { parent_timestamp, timestamp } => {
+                ConsensusError::TimestampIsInPast { parent_timestamp, timestamp }
+            }
+            HeaderValidationError::GasLimitInvalidIncrease {
+                parent_gas_limit,
+                child_gas_limit,
+            } => ConsensusError::GasLimitInvalidIncrease { parent_gas_limit, child_gas_limit },
+            HeaderValidationError::GasLimitInvalidDecrease {
+                parent_gas_limit,
+                child_gas_limit,
+            } => ConsensusError::GasLimitInvalidDecrease { parent_gas_limit, child_gas_limit },
+            HeaderValidationError::BaseFeeMissing => ConsensusError::BaseFeeMissing,
+            HeaderValidationError::BaseFeeDiff { expected, got } => {
+                ConsensusError::BaseFeeDiff { expected, got }
+            }
+            HeaderValidationError::TheMergeDifficultyIsNotZero => {
+                ConsensusError::TheMergeDifficultyIsNotZero
+            }
+        }
+    }
+}
+
+impl From<WithdrawalsValidationError> for ConsensusError {
+    fn from(error: WithdrawalsValidationError) -> Self {
+        match error {
+            WithdrawalsValidationError::WithdrawalsRootDiff { got, expected } => {
+                ConsensusError::BodyWithdrawalsRootDiff { got, expected }
+            }
+            WithdrawalsValidationError::WithdrawalsRootUnexpected => {
+                ConsensusError::WithdrawalsRootUnexpected
+            }
+            WithdrawalsValidationError::BodyWithdrawalsMissing => {
+                ConsensusError::BodyWithdrawalsMissing
+            }
+        }
+    }
+}
@@ -34,6 +34,48 @@ pub enum DatabaseError {
     /// Failed to use the specified log level, as it's not available.
     #[error("Log level is not available: {0:?}")]
     LogLevelUnavailable(LogLevel),
+    /// Failed to start a write transaction, because the database is opened in read-only mode.
+    #[error("Database is opened in read-only mode and can't be written to")]
+    ReadOnly,
+    /// The configured database geometry is invalid.
+    #[error(transparent)]
+    InvalidGeometry(#[from] DatabaseGeometryError),
+}
+
+/// Errors of an invalid database geometry.
+#[derive(Debug, thiserror::Error, PartialEq, Eq, Clone)]
+pub enum DatabaseGeometryError {
+    /// The growth step is zero.
+    #[error("Database growth step must be greater than zero")]
+    GrowthStepZero,
+    /// The growth step is larger than the maximum size.
+    #[error(
+        "Database growth step of {growth_step} bytes exceeds the maximum size of {max_size} bytes"
+    )]
+    GrowthStepExceedsMaxSize {
+        /// The configured growth step.
+        growth_step: usize,
+        /// The configured maximum size.
+        max_size: usize,
+    },
+    /// The maximum size exceeds the number of pages the database can address.
+    #[error("Database maximum size of {max_size} bytes exceeds the limit of {limit} bytes for a page size of {page_size} bytes")]
+    MaxSizeTooLarge {
+        /// The configured maximum size.
+        max_size: usize,
+        /// The largest possible size.
+        limit: usize,
+        /// The page size of the database.
+        page_size: usize,
+    },
+    /// The maximum size is smaller than the existing database file.
+    #[error("Database maximum size of {max_size} bytes is below the current database size of {file_size} bytes")]
+    MaxSizeBelowFileSize {
+        /// The configured maximum size.
+        max_size: usize,
+        /// The size of the existing database file.
+        file_size: u64,
+    },
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
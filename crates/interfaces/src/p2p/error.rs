@@ -1,7 +1,7 @@
 use super::headers::client::HeadersRequest;
 use crate::{consensus, db};
 use reth_network_api::ReputationChangeKind;
-use reth_primitives::{BlockHashOrNumber, BlockNumber, Header, WithPeerId, H256};
+use reth_primitives::{BlockHashOrNumber, BlockNumber, Header, PeerId, WithPeerId, H256};
 use std::ops::RangeInclusive;
 use thiserror::Error;
 use tokio::sync::{mpsc, oneshot};
@@ -119,10 +119,16 @@ pub type DownloadResult<T> = Result<T, DownloadError>;
 pub enum DownloadError {
     /* ==================== HEADER ERRORS ==================== */
     /// Header validation failed
-    #[error("Failed to validate header {hash}. Details: {error}.")]
+    #[error(
+        "Failed to validate header #{number} ({hash}) from peer {peer_id:?}. Details: {error}."
+    )]
     HeaderValidation {
+        /// Number of header failing validation
+        number: BlockNumber,
         /// Hash of header failing validation
         hash: H256,
+        /// The peer that sent the header, if known
+        peer_id: Option<PeerId>,
         /// The details of validation failure
         #[source]
         error: consensus::ConsensusError,
@@ -159,6 +165,16 @@ pub enum DownloadError {
         /// How many headers we expected.
         expected: u64,
     },
+    /// Received a header at the trusted checkpoint height with an unexpected hash.
+    #[error("Header #{number} ({received}) does not match the trusted checkpoint {expected}.")]
+    TrustedCheckpointMismatch {
+        /// The block number of the trusted checkpoint
+        number: BlockNumber,
+        /// The hash of the received header
+        received: H256,
+        /// The hash of the trusted checkpoint
+        expected: H256,
+    },
     /* ==================== BODIES ERRORS ==================== */
     /// Block validation failed
     #[error("Failed to validate body for header {hash}. Details: {error}.")]
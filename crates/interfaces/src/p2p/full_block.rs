@@ -264,20 +264,7 @@ fn ensure_valid_body_response(
     }
 
     let withdrawals = block.withdrawals.as_deref().unwrap_or(&[]);
-    if let Some(header_withdrawals_root) = header.withdrawals_root {
-        let withdrawals_root = reth_primitives::proofs::calculate_withdrawals_root(withdrawals);
-        if withdrawals_root != header_withdrawals_root {
-            return Err(ConsensusError::BodyWithdrawalsRootDiff {
-                got: withdrawals_root,
-                expected: header_withdrawals_root,
-            })
-        }
-        return Ok(())
-    }
-
-    if !withdrawals.is_empty() {
-        return Err(ConsensusError::WithdrawalsRootUnexpected)
-    }
+    header.validate_withdrawals_root(Some(withdrawals))?;
 
     Ok(())
 }
@@ -1,6 +1,6 @@
 use super::error::HeadersDownloaderResult;
 use crate::{
-    consensus::Consensus,
+    consensus::{Consensus, ConsensusError},
     p2p::error::{DownloadError, DownloadResult},
 };
 use futures::Stream;
@@ -80,12 +80,43 @@ pub fn validate_header_download(
     parent: &SealedHeader,
 ) -> DownloadResult<()> {
     // validate header against parent
-    consensus
-        .validate_header_against_parent(header, parent)
-        .map_err(|error| DownloadError::HeaderValidation { hash: parent.hash(), error })?;
+    consensus.validate_header_against_parent(header, parent).map_err(|error| {
+        DownloadError::HeaderValidation {
+            number: header.number,
+            hash: header.hash(),
+            peer_id: None,
+            error,
+        }
+    })?;
     // validate header standalone
-    consensus
-        .validate_header(header)
-        .map_err(|error| DownloadError::HeaderValidation { hash: parent.hash(), error })?;
+    consensus.validate_header(header).map_err(|error| DownloadError::HeaderValidation {
+        number: header.number,
+        hash: header.hash(),
+        peer_id: None,
+        error,
+    })?;
+    Ok(())
+}
+
+/// Validate only that the header is the child of the given parent, by number and hash.
+///
+/// This skips all consensus rules and must only be used for headers that are already known to be
+/// part of a trusted chain, e.g. headers below a trusted checkpoint.
+pub fn validate_header_link(
+    header: &SealedHeader,
+    parent: &SealedHeader,
+) -> Result<(), ConsensusError> {
+    if parent.number + 1 != header.number {
+        return Err(ConsensusError::ParentBlockNumberMismatch {
+            parent_block_number: parent.number,
+            block_number: header.number,
+        })
+    }
+    if parent.hash != header.parent_hash {
+        return Err(ConsensusError::ParentHashMismatch {
+            expected_parent_hash: parent.hash,
+            got_parent_hash: header.parent_hash,
+        })
+    }
     Ok(())
 }
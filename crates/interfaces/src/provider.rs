@@ -61,12 +61,64 @@ pub enum ProviderError {
     /// Thrown when we failed to lookup a block for the pending state
     #[error("Unknown block hash: {0:}")]
     UnknownBlockHash(H256),
+    /// Thrown when the block hash is known, but the block is not part of the canonical chain
+    #[error("Block hash {hash:?} is not canonical")]
+    BlockHashNotCanonical {
+        /// The requested block hash
+        hash: BlockHash,
+        /// The number of the block, if it is known
+        number: Option<BlockNumber>,
+    },
+    /// Thrown when the history required for the state at the given block has been pruned
+    #[error("State history for block #{block_number} has been pruned, lowest available block is #{lowest_available}")]
+    StateHistoryPruned {
+        /// The requested block number
+        block_number: BlockNumber,
+        /// The lowest block number the history is available for
+        lowest_available: BlockNumber,
+    },
+    /// Thrown when the blocks of a batch write don't form a contiguous range
+    #[error("Block #{got} does not follow block #{expected_parent}")]
+    NonSequentialBlock {
+        /// The number of the block the inserted block is expected to follow
+        expected_parent: BlockNumber,
+        /// The number of the inserted block
+        got: BlockNumber,
+    },
+    /// Thrown when the senders of an inserted block don't match its transactions
+    #[error("Block #{block_number} has {transactions} transactions, but {senders} senders")]
+    BlockSendersMismatch {
+        /// The number of the inserted block
+        block_number: BlockNumber,
+        /// The number of transactions of the block
+        transactions: usize,
+        /// The number of senders of the block
+        senders: usize,
+    },
+    /// Thrown when upserted blocks don't reach the database tip
+    #[error("Upserted blocks end at block #{last}, below the database tip #{tip}")]
+    UpsertBelowTip {
+        /// The number of the last block in the database
+        tip: BlockNumber,
+        /// The number of the last upserted block
+        last: BlockNumber,
+    },
     /// Thrown when we were unable to find a state for a block hash
     #[error("No State found for block hash: {0:}")]
     StateForHashNotFound(H256),
     /// Unable to compute state root on top of historical block
     #[error("Unable to compute state root on top of historical block")]
     StateRootNotAvailableForHistoricalBlock,
+    /// Thrown when the trie doesn't reflect the state a proof is requested for
+    #[error("Proofs for block #{block_number} are unavailable, the trie is at block #{trie_block_number} and the hashed state at block #{hashed_block_number}")]
+    ProofNotAvailable {
+        /// The block the proof was requested for
+        block_number: BlockNumber,
+        /// The block the trie was computed for
+        trie_block_number: BlockNumber,
+        /// The block the hashed state was computed for
+        hashed_block_number: BlockNumber,
+    },
     /// Unable to find the block number for a given transaction index
     #[error("Unable to find the block number for a given transaction index")]
     BlockNumberForTransactionIndexNotFound,
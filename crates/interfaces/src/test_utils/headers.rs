@@ -160,7 +160,9 @@ impl Stream for TestDownload {
             if let Err(error) = this.consensus.validate_header_against_parent(&empty, &empty) {
                 this.done = true;
                 return Poll::Ready(Some(Err(DownloadError::HeaderValidation {
+                    number: empty.number,
                     hash: empty.hash(),
+                    peer_id: None,
                     error,
                 })))
             }
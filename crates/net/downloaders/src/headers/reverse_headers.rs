@@ -11,14 +11,17 @@ use reth_interfaces::{
         error::{DownloadError, DownloadResult, PeerRequestResult},
         headers::{
             client::{HeadersClient, HeadersRequest},
-            downloader::{validate_header_download, HeaderDownloader, SyncTarget},
+            downloader::{
+                validate_header_download, validate_header_link, HeaderDownloader, SyncTarget,
+            },
             error::{HeadersDownloaderError, HeadersDownloaderResult},
         },
         priority::Priority,
     },
 };
 use reth_primitives::{
-    BlockHashOrNumber, BlockNumber, Header, HeadersDirection, PeerId, SealedHeader, H256,
+    BlockHashOrNumber, BlockNumHash, BlockNumber, Header, HeadersDirection, PeerId, SealedHeader,
+    H256,
 };
 use reth_tasks::{TaskSpawner, TokioTaskExecutor};
 use std::{
@@ -83,6 +86,11 @@ pub struct ReverseHeadersDownloader<H: HeadersClient> {
     stream_batch_size: usize,
     /// Maximum amount of received headers to buffer internally.
     max_buffered_responses: usize,
+    /// Optional trusted checkpoint, headers at or below it are only checked for chain linkage.
+    trusted_checkpoint: Option<BlockNumHash>,
+    /// Whether the header at the trusted checkpoint was downloaded and matched the checkpoint
+    /// hash, which links all validated headers below it to the checkpoint.
+    trusted_checkpoint_verified: bool,
     /// Contains the request to retrieve the headers for the sync target
     ///
     /// This will give us the block number of the `sync_target`, after which we can send multiple
@@ -242,14 +250,22 @@ where
     ) -> Result<(), ReverseHeadersDownloaderError> {
         let mut validated = Vec::with_capacity(headers.len());
 
+        // Only committed once all headers are validated
+        let mut checkpoint_verified = self.trusted_checkpoint_verified;
+
         let sealed_headers = headers.into_par_iter().map(|h| h.seal_slow()).collect::<Vec<_>>();
         for parent in sealed_headers {
+            checkpoint_verified |=
+                self.validate_trusted_checkpoint(&parent, request.clone(), peer_id)?;
+
             // Validate that the header is the parent header of the last validated header.
             if let Some(validated_header) =
                 validated.last().or_else(|| self.lowest_validated_header())
             {
-                if let Err(error) = self.validate(validated_header, &parent) {
-                    trace!(target: "downloaders::headers", ?error ,"Failed to validate header");
+                let is_trusted = checkpoint_verified &&
+                    self.is_below_trusted_checkpoint(validated_header.number);
+                if let Err(error) = self.validate(validated_header, &parent, is_trusted, peer_id) {
+                    trace!(target: "downloaders::headers", ?error, ?peer_id, number = validated_header.number, "Failed to validate header");
                     return Err(
                         HeadersResponseError { request, peer_id: Some(peer_id), error }.into()
                     )
@@ -267,20 +283,35 @@ where
             .zip(self.local_head.as_ref())
             .filter(|(last, head)| last.number == head.number + 1)
         {
-            // Every header must be valid on its own
-            if let Err(error) = self.consensus.validate_header(last_header) {
-                trace!(target: "downloaders::headers", ?error, "Failed to validate header");
+            let is_trusted =
+                checkpoint_verified && self.is_below_trusted_checkpoint(last_header.number);
+
+            // Every header must be valid on its own, unless it's covered by the trusted checkpoint
+            let standalone_result =
+                if is_trusted { Ok(()) } else { self.consensus.validate_header(last_header) };
+            if let Err(error) = standalone_result {
+                trace!(target: "downloaders::headers", ?error, ?peer_id, number = last_header.number, "Failed to validate header");
                 return Err(HeadersResponseError {
                     request,
                     peer_id: Some(peer_id),
-                    error: DownloadError::HeaderValidation { hash: head.hash(), error },
+                    error: DownloadError::HeaderValidation {
+                        number: last_header.number,
+                        hash: last_header.hash(),
+                        peer_id: Some(peer_id),
+                        error,
+                    },
                 }
                 .into())
             }
 
             // If the header is valid on its own, but not against its parent, we return it as
             // detached head error.
-            if let Err(error) = self.consensus.validate_header_against_parent(last_header, head) {
+            let parent_result = if is_trusted {
+                validate_header_link(last_header, head)
+            } else {
+                self.consensus.validate_header_against_parent(last_header, head)
+            };
+            if let Err(error) = parent_result {
                 // Replace the last header with a detached variant
                 error!(target: "downloaders::headers", ?error, number = last_header.number, hash = ?last_header.hash, "Header cannot be attached to known canonical chain");
                 return Err(HeadersDownloaderError::DetachedHead {
@@ -296,6 +327,7 @@ where
         self.next_chain_tip_block_number =
             validated.last().expect("exists").number.saturating_sub(1);
         self.queued_validated_headers.extend(validated);
+        self.trusted_checkpoint_verified = checkpoint_verified;
 
         Ok(())
     }
@@ -593,13 +625,76 @@ where
     }
 
     /// Validate whether the header is valid in relation to it's parent
-    fn validate(&self, header: &SealedHeader, parent: &SealedHeader) -> DownloadResult<()> {
-        validate_header_download(&self.consensus, header, parent)
+    ///
+    /// Trusted headers are only checked for their link to the parent.
+    fn validate(
+        &self,
+        header: &SealedHeader,
+        parent: &SealedHeader,
+        is_trusted: bool,
+        peer_id: PeerId,
+    ) -> DownloadResult<()> {
+        let result = if is_trusted {
+            validate_header_link(header, parent).map_err(|error| DownloadError::HeaderValidation {
+                number: header.number,
+                hash: header.hash(),
+                peer_id: None,
+                error,
+            })
+        } else {
+            validate_header_download(&self.consensus, header, parent)
+        };
+        result.map_err(|mut error| {
+            if let DownloadError::HeaderValidation { peer_id: ref mut peer, .. } = error {
+                *peer = Some(peer_id);
+            }
+            error
+        })
+    }
+
+    /// Returns true if the given block number is at or below the configured trusted checkpoint.
+    ///
+    /// Headers covered by the checkpoint are only checked for chain linkage once the header at
+    /// the checkpoint was verified, see [ReverseHeadersDownloaderBuilder::trusted_checkpoint].
+    #[inline]
+    fn is_below_trusted_checkpoint(&self, number: BlockNumber) -> bool {
+        self.trusted_checkpoint.map_or(false, |checkpoint| number <= checkpoint.number)
+    }
+
+    /// Ensures that the given header matches the trusted checkpoint, if it's at its height.
+    ///
+    /// Returns `true` if the header is the trusted checkpoint.
+    fn validate_trusted_checkpoint(
+        &self,
+        header: &SealedHeader,
+        request: HeadersRequest,
+        peer_id: PeerId,
+    ) -> Result<bool, ReverseHeadersDownloaderError> {
+        match self.trusted_checkpoint {
+            Some(checkpoint)
+                if checkpoint.number == header.number && checkpoint.hash != header.hash() =>
+            {
+                trace!(target: "downloaders::headers", ?peer_id, number = header.number, received = ?header.hash(), expected = ?checkpoint.hash, "Header does not match trusted checkpoint");
+                Err(HeadersResponseError {
+                    request,
+                    peer_id: Some(peer_id),
+                    error: DownloadError::TrustedCheckpointMismatch {
+                        number: checkpoint.number,
+                        received: header.hash(),
+                        expected: checkpoint.hash,
+                    },
+                }
+                .into())
+            }
+            Some(checkpoint) => Ok(checkpoint.number == header.number),
+            None => Ok(false),
+        }
     }
 
     /// Clears all requests/responses.
     fn clear(&mut self) {
         self.lowest_validated_header.take();
+        self.trusted_checkpoint_verified = false;
         self.queued_validated_headers = Vec::new();
         self.buffered_responses = BinaryHeap::new();
         self.in_progress_queue.clear();
@@ -1086,6 +1181,8 @@ pub struct ReverseHeadersDownloaderBuilder {
     max_concurrent_requests: usize,
     /// How many responses to buffer
     max_buffered_responses: usize,
+    /// Optional trusted checkpoint
+    trusted_checkpoint: Option<BlockNumHash>,
 }
 
 impl Default for ReverseHeadersDownloaderBuilder {
@@ -1098,6 +1195,7 @@ impl Default for ReverseHeadersDownloaderBuilder {
             max_concurrent_requests: 100,
             min_concurrent_requests: 5,
             max_buffered_responses: 100,
+            trusted_checkpoint: None,
         }
     }
 }
@@ -1150,6 +1248,26 @@ impl ReverseHeadersDownloaderBuilder {
         self
     }
 
+    /// Set a trusted checkpoint.
+    ///
+    /// Headers at or below the checkpoint's block number are only checked for their link to the
+    /// parent (block number and parent hash), all consensus rules are skipped for them. The header
+    /// at the checkpoint's height must match the checkpoint's hash, otherwise the response is
+    /// rejected. Headers are only trusted once the header at the checkpoint has been downloaded
+    /// and matched, so a sync target below the checkpoint is validated as usual.
+    ///
+    /// **Trust assumption:** the checkpoint hash must come from a source that is trusted to be
+    /// part of the canonical chain (e.g. a weak subjectivity checkpoint). Since all headers below
+    /// it are linked to it by hash they are implied to be valid, but they are never checked
+    /// against the consensus rules by this downloader. A checkpoint that is not canonical makes
+    /// the downloader reject every peer's chain.
+    ///
+    /// Disabled by default.
+    pub fn trusted_checkpoint(mut self, trusted_checkpoint: Option<BlockNumHash>) -> Self {
+        self.trusted_checkpoint = trusted_checkpoint;
+        self
+    }
+
     /// Build [ReverseHeadersDownloader] with provided consensus
     /// and header client implementations
     pub fn build<H>(self, client: H, consensus: Arc<dyn Consensus>) -> ReverseHeadersDownloader<H>
@@ -1162,6 +1280,7 @@ impl ReverseHeadersDownloaderBuilder {
             min_concurrent_requests,
             max_concurrent_requests,
             max_buffered_responses,
+            trusted_checkpoint,
         } = self;
         ReverseHeadersDownloader {
             consensus,
@@ -1178,6 +1297,8 @@ impl ReverseHeadersDownloaderBuilder {
             max_concurrent_requests,
             stream_batch_size,
             max_buffered_responses,
+            trusted_checkpoint,
+            trusted_checkpoint_verified: false,
             sync_target_request: None,
             in_progress_queue: Default::default(),
             buffered_responses: Default::default(),
@@ -1421,6 +1542,111 @@ mod tests {
         assert!(downloader.next().await.is_none());
     }
 
+    #[tokio::test]
+    async fn download_below_trusted_checkpoint() {
+        reth_tracing::init_test_tracing();
+
+        let client = Arc::new(TestHeadersClient::default());
+
+        let p3 = SealedHeader::default();
+        let p2 = child_header(&p3);
+        let p1 = child_header(&p2);
+        let p0 = child_header(&p1);
+
+        let consensus = Arc::new(TestConsensus::default());
+        consensus.set_fail_validation(true);
+
+        let mut downloader = ReverseHeadersDownloaderBuilder::default()
+            .stream_batch_size(3)
+            .request_limit(3)
+            .trusted_checkpoint(Some(BlockNumHash::new(p0.number, p0.hash())))
+            .build(Arc::clone(&client), consensus);
+        downloader.update_local_head(p3.clone());
+        downloader.update_sync_target(SyncTarget::Tip(p0.hash()));
+
+        client
+            .extend(vec![
+                p0.as_ref().clone(),
+                p1.as_ref().clone(),
+                p2.as_ref().clone(),
+                p3.as_ref().clone(),
+            ])
+            .await;
+
+        let headers = downloader.next().await.unwrap();
+        assert_eq!(headers, Ok(vec![p0, p1, p2,]));
+        assert!(downloader.next().await.is_none());
+    }
+
+    #[test]
+    fn trusted_checkpoint_mismatch() {
+        let p3 = SealedHeader::default();
+        let p2 = child_header(&p3);
+        let p1 = child_header(&p2);
+        let p0 = child_header(&p1);
+
+        let expected = H256::repeat_byte(1);
+        let mut downloader = ReverseHeadersDownloaderBuilder::default()
+            .trusted_checkpoint(Some(BlockNumHash::new(p1.number, expected)))
+            .build(Arc::new(TestHeadersClient::default()), Arc::new(TestConsensus::default()));
+        downloader.lowest_validated_header = Some(p0);
+
+        let request = HeadersRequest {
+            start: p1.number.into(),
+            limit: 2,
+            direction: HeadersDirection::Falling,
+        };
+        let result = downloader.process_next_headers(
+            request,
+            vec![p1.as_ref().clone(), p2.as_ref().clone()],
+            PeerId::random(),
+        );
+        assert_matches!(
+            result,
+            Err(ReverseHeadersDownloaderError::Response(HeadersResponseError {
+                error: DownloadError::TrustedCheckpointMismatch { number, received, expected: hash },
+                ..
+            })) if number == p1.number && received == p1.hash() && hash == expected
+        );
+        assert!(downloader.queued_validated_headers.is_empty());
+    }
+
+    #[test]
+    fn trusted_checkpoint_not_downloaded() {
+        let p3 = SealedHeader::default();
+        let p2 = child_header(&p3);
+        let p1 = child_header(&p2);
+        let p0 = child_header(&p1);
+
+        let consensus = Arc::new(TestConsensus::default());
+        consensus.set_fail_validation(true);
+
+        // the checkpoint is above the sync target, so it's never downloaded and can't be verified
+        let mut downloader = ReverseHeadersDownloaderBuilder::default()
+            .trusted_checkpoint(Some(BlockNumHash::new(p0.number + 1, H256::repeat_byte(1))))
+            .build(Arc::new(TestHeadersClient::default()), consensus);
+        downloader.lowest_validated_header = Some(p0.clone());
+
+        let request = HeadersRequest {
+            start: p1.number.into(),
+            limit: 2,
+            direction: HeadersDirection::Falling,
+        };
+        let peer = PeerId::random();
+        let result = downloader.process_next_headers(
+            request,
+            vec![p1.as_ref().clone(), p2.as_ref().clone()],
+            peer,
+        );
+        assert_matches!(
+            result,
+            Err(ReverseHeadersDownloaderError::Response(HeadersResponseError {
+                error: DownloadError::HeaderValidation { number, hash, peer_id, .. },
+                ..
+            })) if number == p0.number && hash == p0.hash() && peer_id == Some(peer)
+        );
+    }
+
     #[tokio::test]
     async fn download_one_by_one() {
         reth_tracing::init_test_tracing();
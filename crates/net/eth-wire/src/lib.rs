@@ -23,6 +23,7 @@ mod disconnect;
 pub mod errors;
 mod ethstream;
 mod hello;
+mod metrics;
 mod p2pstream;
 mod pinger;
 pub use builder::*;
@@ -0,0 +1,119 @@
+//! Bandwidth metrics of the [`P2PStream`](crate::P2PStream).
+
+use crate::EthMessageID;
+use reth_metrics::{
+    metrics::{self, Counter},
+    Metrics,
+};
+
+/// The category of an eth message, used to label the bandwidth metrics.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub(crate) enum MessageCategory {
+    /// The `Status` handshake message.
+    Status,
+    /// `NewBlockHashes` and `NewBlock` announcements.
+    BlockPropagation,
+    /// `GetBlockHeaders` and `BlockHeaders`.
+    Headers,
+    /// `GetBlockBodies` and `BlockBodies`.
+    Bodies,
+    /// Transaction gossip: `Transactions`, `NewPooledTransactionHashes`, `GetPooledTransactions`
+    /// and `PooledTransactions`.
+    Transactions,
+    /// `GetNodeData` and `NodeData`.
+    NodeData,
+    /// `GetReceipts` and `Receipts`.
+    Receipts,
+}
+
+impl MessageCategory {
+    /// All categories, in the order of their discriminant.
+    const ALL: [MessageCategory; 7] = [
+        MessageCategory::Status,
+        MessageCategory::BlockPropagation,
+        MessageCategory::Headers,
+        MessageCategory::Bodies,
+        MessageCategory::Transactions,
+        MessageCategory::NodeData,
+        MessageCategory::Receipts,
+    ];
+
+    /// Returns the label value of the category.
+    fn as_str(&self) -> &'static str {
+        match self {
+            MessageCategory::Status => "status",
+            MessageCategory::BlockPropagation => "block_propagation",
+            MessageCategory::Headers => "headers",
+            MessageCategory::Bodies => "bodies",
+            MessageCategory::Transactions => "transactions",
+            MessageCategory::NodeData => "node_data",
+            MessageCategory::Receipts => "receipts",
+        }
+    }
+}
+
+impl From<EthMessageID> for MessageCategory {
+    fn from(id: EthMessageID) -> Self {
+        match id {
+            EthMessageID::Status => MessageCategory::Status,
+            EthMessageID::NewBlockHashes | EthMessageID::NewBlock => {
+                MessageCategory::BlockPropagation
+            }
+            EthMessageID::GetBlockHeaders | EthMessageID::BlockHeaders => MessageCategory::Headers,
+            EthMessageID::GetBlockBodies | EthMessageID::BlockBodies => MessageCategory::Bodies,
+            EthMessageID::Transactions |
+            EthMessageID::NewPooledTransactionHashes |
+            EthMessageID::GetPooledTransactions |
+            EthMessageID::PooledTransactions => MessageCategory::Transactions,
+            EthMessageID::GetNodeData | EthMessageID::NodeData => MessageCategory::NodeData,
+            EthMessageID::GetReceipts | EthMessageID::Receipts => MessageCategory::Receipts,
+        }
+    }
+}
+
+/// Bandwidth metrics of a single [MessageCategory].
+#[derive(Metrics)]
+#[metrics(scope = "network.bandwidth")]
+pub(crate) struct BandwidthMetrics {
+    /// Number of bytes of received eth messages
+    pub(crate) received_bytes: Counter,
+    /// Number of bytes of sent eth messages
+    pub(crate) sent_bytes: Counter,
+}
+
+/// Bandwidth metrics of the eth messages of a [`P2PStream`](crate::P2PStream), labeled by
+/// [MessageCategory].
+///
+/// The sizes are those of the snappy compressed messages including their message ID, as they are
+/// passed to and from the RLPx framing. Messages of the eth handshake are counted as well.
+#[derive(Debug)]
+pub(crate) struct P2PStreamMetrics {
+    categories: [BandwidthMetrics; 7],
+}
+
+impl P2PStreamMetrics {
+    /// Records a received message of the given size.
+    pub(crate) fn on_received(&self, id: EthMessageID, len: usize) {
+        self.get(id).received_bytes.increment(len as u64);
+    }
+
+    /// Records a sent message of the given size.
+    pub(crate) fn on_sent(&self, id: EthMessageID, len: usize) {
+        self.get(id).sent_bytes.increment(len as u64);
+    }
+
+    fn get(&self, id: EthMessageID) -> &BandwidthMetrics {
+        &self.categories[MessageCategory::from(id) as usize]
+    }
+}
+
+impl Default for P2PStreamMetrics {
+    fn default() -> Self {
+        Self {
+            categories: MessageCategory::ALL.map(|category| {
+                BandwidthMetrics::new_with_labels(&[("category", category.as_str())])
+            }),
+        }
+    }
+}
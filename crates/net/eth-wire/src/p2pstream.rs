@@ -3,8 +3,9 @@ use crate::{
     capability::{Capability, SharedCapability},
     disconnect::CanDisconnect,
     errors::{P2PHandshakeError, P2PStreamError},
+    metrics::P2PStreamMetrics,
     pinger::{Pinger, PingerEvent},
-    DisconnectReason, HelloMessage,
+    DisconnectReason, EthMessageID, HelloMessage,
 };
 use futures::{Sink, SinkExt, StreamExt};
 use pin_project::pin_project;
@@ -223,6 +224,9 @@ pub struct P2PStream<S> {
     /// Whether this stream is currently in the process of disconnecting by sending a disconnect
     /// message.
     disconnecting: bool,
+
+    /// Bandwidth metrics of the sent and received eth messages.
+    metrics: P2PStreamMetrics,
 }
 
 impl<S> P2PStream<S> {
@@ -239,6 +243,7 @@ impl<S> P2PStream<S> {
             outgoing_messages: VecDeque::new(),
             outgoing_message_buffer_capacity: MAX_P2P_CAPACITY,
             disconnecting: false,
+            metrics: Default::default(),
         }
     }
 
@@ -442,6 +447,10 @@ where
                     //
                     decompress_buf[0] = bytes[0] - this.shared_capability.offset();
 
+                    if let Some(id) = eth_message_id(&this.shared_capability, decompress_buf[0]) {
+                        this.metrics.on_received(id, bytes.len());
+                    }
+
                     return Poll::Ready(Some(Ok(decompress_buf)))
                 }
             }
@@ -520,6 +529,11 @@ where
         // all messages sent in this stream are subprotocol messages, so we need to switch the
         // message id based on the offset
         compressed[0] = item[0] + this.shared_capability.offset();
+
+        if let Some(id) = eth_message_id(this.shared_capability, item[0]) {
+            this.metrics.on_sent(id, compressed.len());
+        }
+
         this.outgoing_messages.push_back(compressed.freeze());
 
         Ok(())
@@ -551,6 +565,16 @@ where
     }
 }
 
+/// Returns the [`EthMessageID`] of a subprotocol message if the shared capability is `eth`.
+///
+/// The `id` is the message ID relative to the offset of the capability.
+fn eth_message_id(capability: &SharedCapability, id: u8) -> Option<EthMessageID> {
+    match capability {
+        SharedCapability::Eth { .. } => EthMessageID::try_from(id as usize).ok(),
+        SharedCapability::UnknownCapability { .. } => None,
+    }
+}
+
 /// Determines the offsets for each shared capability between the input list of peer
 /// capabilities and the input list of locally supported capabilities.
 ///
@@ -826,8 +850,14 @@ impl Decodable for ProtocolVersion {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{DisconnectReason, EthVersion};
+    use crate::{
+        BlockHashNumber, DisconnectReason, EthMessage, EthVersion, ProtocolMessage, Status,
+        UnauthedEthStream,
+    };
+    use ethers_core::types::Chain;
+    use metrics_util::debugging::{DebugValue, DebuggingRecorder};
     use reth_ecies::util::pk2id;
+    use reth_primitives::{ForkFilter, Head, H256, U256};
     use secp256k1::{SecretKey, SECP256K1};
     use tokio::net::{TcpListener, TcpStream};
     use tokio_util::codec::Decoder;
@@ -1049,4 +1079,78 @@ mod tests {
         pong.encode(&mut buf);
         assert_eq!(buf.as_ref(), &snappy_pong[..]);
     }
+
+    #[tokio::test]
+    async fn test_eth_bandwidth_metrics() {
+        let recorder = DebuggingRecorder::per_thread();
+        let snapshotter = recorder.snapshotter();
+        // the recorder may already be installed by another test
+        let _ = recorder.install();
+
+        let genesis = H256::random();
+        let fork_filter = ForkFilter::new(Head::default(), genesis, Vec::new());
+        let status = Status {
+            version: EthVersion::Eth67 as u8,
+            chain: Chain::Mainnet.into(),
+            total_difficulty: U256::ZERO,
+            blockhash: H256::random(),
+            genesis,
+            forkid: fork_filter.current(),
+        };
+        let message = EthMessage::NewBlockHashes(
+            vec![BlockHashNumber { hash: H256::random(), number: 5 }].into(),
+        );
+
+        let capability = SharedCapability::Eth {
+            version: EthVersion::Eth67,
+            offset: MAX_RESERVED_MESSAGE_ID + 1,
+        };
+        let (local, remote) = tokio::io::duplex(MAX_PAYLOAD_SIZE);
+        let local =
+            P2PStream::new(crate::PassthroughCodec::default().framed(local), capability.clone());
+        let remote = P2PStream::new(crate::PassthroughCodec::default().framed(remote), capability);
+
+        let (local, remote) = tokio::join!(
+            UnauthedEthStream::new(local).handshake(status, fork_filter.clone()),
+            UnauthedEthStream::new(remote).handshake(status, fork_filter),
+        );
+        let (mut local, _) = local.unwrap();
+        let (mut remote, _) = remote.unwrap();
+
+        local.send(message.clone()).await.unwrap();
+        assert_eq!(remote.next().await.unwrap().unwrap(), message);
+
+        // the sizes are those of the snappy compressed messages, including the message id
+        let compressed_len = |message: EthMessage| {
+            let mut bytes = BytesMut::new();
+            ProtocolMessage::from(message).encode(&mut bytes);
+            1 + snap::raw::Encoder::new().compress_vec(&bytes[1..]).unwrap().len() as u64
+        };
+        let counter = |name: &str, category: &str| {
+            snapshotter.snapshot().into_vec().into_iter().find_map(|(key, _, _, value)| {
+                let key = key.key();
+                match value {
+                    DebugValue::Counter(value)
+                        if key.name() == name &&
+                            key.labels().any(|label| label.value() == category) =>
+                    {
+                        Some(value)
+                    }
+                    _ => None,
+                }
+            })
+        };
+
+        // both sides sent and received a status during the handshake
+        let status_len = compressed_len(EthMessage::Status(status));
+        assert_eq!(counter("network.bandwidth.sent_bytes", "status"), Some(2 * status_len));
+        assert_eq!(counter("network.bandwidth.received_bytes", "status"), Some(2 * status_len));
+
+        let message_len = compressed_len(message);
+        assert_eq!(counter("network.bandwidth.sent_bytes", "block_propagation"), Some(message_len));
+        assert_eq!(
+            counter("network.bandwidth.received_bytes", "block_propagation"),
+            Some(message_len)
+        );
+    }
 }
@@ -24,6 +24,8 @@ pub enum ReputationChangeKind {
     AlreadySeenTransaction,
     /// Peer failed to respond in time.
     Timeout,
+    /// Peer sent more requests than allowed by the configured rate limits.
+    RateLimited,
     /// Peer does not adhere to network protocol rules.
     BadProtocol,
     /// Failed to establish a connection to the peer.
@@ -1,6 +1,6 @@
 //! Fetch data from the network.
 
-use crate::{message::BlockRequest, peers::PeersHandle};
+use crate::{message::BlockRequest, metrics::StateFetcherMetrics, peers::PeersHandle};
 use futures::StreamExt;
 use reth_eth_wire::{GetBlockBodies, GetBlockHeaders};
 use reth_interfaces::p2p::{
@@ -29,7 +29,9 @@ pub use client::FetchClient;
 /// This type is hooked into the staged sync pipeline and delegates download request to available
 /// peers and sends the response once ready.
 ///
-/// This type maintains a list of connected peers that are available for requests.
+/// This type maintains a list of connected peers that are available for requests. Each peer
+/// handles at most one request at a time, so requests are spread across all idle peers instead of
+/// piling up on a single fast peer.
 pub struct StateFetcher {
     /// Currently active [`GetBlockHeaders`] requests
     inflight_headers_requests:
@@ -49,6 +51,8 @@ pub struct StateFetcher {
     download_requests_rx: UnboundedReceiverStream<DownloadRequest>,
     /// Sender for download requests, used to detach a [`FetchClient`]
     download_requests_tx: UnboundedSender<DownloadRequest>,
+    /// Metrics for in-flight requests
+    metrics: StateFetcherMetrics,
 }
 
 // === impl StateSyncer ===
@@ -65,9 +69,16 @@ impl StateFetcher {
             queued_requests: Default::default(),
             download_requests_rx: UnboundedReceiverStream::new(download_requests_rx),
             download_requests_tx,
+            metrics: Default::default(),
         }
     }
 
+    /// Updates the metrics for in-flight requests.
+    fn update_inflight_metrics(&self) {
+        self.metrics.inflight_headers_requests.set(self.inflight_headers_requests.len() as f64);
+        self.metrics.inflight_bodies_requests.set(self.inflight_bodies_requests.len() as f64);
+    }
+
     /// Invoked when connected to a new peer.
     pub(crate) fn new_active_peer(
         &mut self,
@@ -94,6 +105,7 @@ impl StateFetcher {
         if let Some(req) = self.inflight_bodies_requests.remove(peer) {
             let _ = req.response.send(Err(RequestError::ConnectionDropped));
         }
+        self.update_inflight_metrics();
     }
 
     /// Updates the block information for the peer.
@@ -193,7 +205,7 @@ impl StateFetcher {
             peer.state = req.peer_state();
         }
 
-        match req {
+        let request = match req {
             DownloadRequest::GetBlockHeaders { request, response, .. } => {
                 let inflight = Request { request: request.clone(), response };
                 self.inflight_headers_requests.insert(peer_id, inflight);
@@ -210,7 +222,10 @@ impl StateFetcher {
                 self.inflight_bodies_requests.insert(peer_id, inflight);
                 BlockRequest::GetBlockBodies(GetBlockBodies(request))
             }
-        }
+        };
+        self.update_inflight_metrics();
+
+        request
     }
 
     /// Returns a new followup request for the peer.
@@ -236,6 +251,7 @@ impl StateFetcher {
         let maybe_reputation_change = res.reputation_change_err();
 
         let resp = self.inflight_headers_requests.remove(&peer_id);
+        self.update_inflight_metrics();
 
         let is_likely_bad_response = resp
             .as_ref()
@@ -270,6 +286,7 @@ impl StateFetcher {
         if let Some(resp) = self.inflight_bodies_requests.remove(&peer_id) {
             let _ = resp.response.send(res.map(|b| (peer_id, b).into()));
         }
+        self.update_inflight_metrics();
         if let Some(peer) = self.peers.get_mut(&peer_id) {
             if peer.state.on_request_finished() {
                 return self.followup_request(peer_id)
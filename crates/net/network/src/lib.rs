@@ -151,6 +151,6 @@ pub use manager::{NetworkEvent, NetworkManager};
 pub use message::PeerRequest;
 pub use network::NetworkHandle;
 pub use peers::PeersConfig;
-pub use session::{PeerInfo, SessionsConfig};
+pub use session::{MessageRateLimits, PeerInfo, SessionsConfig};
 
 pub use reth_eth_wire::{DisconnectReason, HelloBuilder, HelloMessage};
@@ -52,6 +52,7 @@ use std::{
         Arc,
     },
     task::{Context, Poll},
+    time::Duration,
 };
 use tokio::sync::mpsc::{self, error::TrySendError};
 use tokio_stream::wrappers::UnboundedReceiverStream;
@@ -299,6 +300,12 @@ where
         self.swarm.listener().local_address()
     }
 
+    /// Returns the maximum time active sessions wait for their in-flight requests to complete on
+    /// shutdown.
+    pub fn shutdown_drain_timeout(&self) -> Duration {
+        self.swarm.sessions().shutdown_drain_timeout()
+    }
+
     /// Returns the configured genesis hash
     pub fn genesis_hash(&self) -> H256 {
         self.swarm.state().genesis_hash()
@@ -551,8 +558,8 @@ where
                 // new incoming connections as well as sending connection requests to newly
                 // discovered nodes.
                 self.swarm.on_shutdown_requested();
-                // Disconnect all active connections
-                self.swarm.sessions_mut().disconnect_all(Some(DisconnectReason::ClientQuitting));
+                // Disconnect all active connections once their in-flight requests are answered
+                self.swarm.sessions_mut().drain_all(DisconnectReason::ClientQuitting);
                 // drop pending connections
                 self.swarm.sessions_mut().disconnect_all_pending();
                 let _ = tx.send(());
@@ -861,6 +868,12 @@ where
                             );
                             this.metrics.invalid_messages_received.increment(1);
                         }
+                        SwarmEvent::RateLimited { peer_id } => {
+                            this.swarm.state_mut().peers_mut().apply_reputation_change(
+                                &peer_id,
+                                ReputationChangeKind::RateLimited,
+                            );
+                        }
                         SwarmEvent::ProtocolBreach { peer_id } => {
                             this.swarm.state_mut().peers_mut().apply_reputation_change(
                                 &peer_id,
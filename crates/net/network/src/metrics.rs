@@ -3,6 +3,7 @@ use reth_metrics::{
     metrics::{self, Counter, Gauge},
     Metrics,
 };
+use reth_network_api::ReputationChangeKind;
 
 /// Scope for monitoring transactions sent from the manager to the tx manager
 pub(crate) const NETWORK_POOL_TRANSACTIONS_SCOPE: &str = "network.pool.transactions";
@@ -45,6 +46,19 @@ pub struct NetworkMetrics {
     pub(crate) total_dropped_eth_requests_at_full_capacity: Counter,
 }
 
+/// Metrics for the StateFetcher
+///
+/// Peers handle at most one request at a time, so these are also the number of peers currently
+/// busy with each kind of request.
+#[derive(Metrics)]
+#[metrics(scope = "network.fetch")]
+pub struct StateFetcherMetrics {
+    /// Number of in-flight `GetBlockHeaders` requests
+    pub(crate) inflight_headers_requests: Gauge,
+    /// Number of in-flight `GetBlockBodies` requests
+    pub(crate) inflight_bodies_requests: Gauge,
+}
+
 /// Metrics for the TransactionsManager
 #[derive(Metrics)]
 #[metrics(scope = "network")]
@@ -130,6 +144,80 @@ impl DisconnectMetrics {
     }
 }
 
+/// Metrics for peer bans, by the reason the peer was banned for
+#[derive(Metrics)]
+#[metrics(scope = "network.bans")]
+pub struct PeerBanMetrics {
+    /// Number of bans due to bad messages
+    pub(crate) bad_message: Counter,
+
+    /// Number of bans due to bad blocks
+    pub(crate) bad_block: Counter,
+
+    /// Number of bans due to bad transactions
+    pub(crate) bad_transactions: Counter,
+
+    /// Number of bans due to already seen transactions
+    pub(crate) already_seen_transactions: Counter,
+
+    /// Number of bans due to timeouts
+    pub(crate) timeout: Counter,
+
+    /// Number of bans due to exceeded request rate limits
+    pub(crate) rate_limited: Counter,
+
+    /// Number of bans due to protocol violations
+    pub(crate) bad_protocol: Counter,
+
+    /// Number of bans due to failed connection attempts
+    pub(crate) failed_to_connect: Counter,
+
+    /// Number of bans due to dropped connections
+    pub(crate) dropped: Counter,
+
+    /// Number of bans due to manual reputation changes
+    pub(crate) other: Counter,
+
+    /// Number of bans due to fatal protocol errors during session establishment
+    pub(crate) fatal_protocol_error: Counter,
+}
+
+impl PeerBanMetrics {
+    /// Increments the counter of the reputation change that caused a ban
+    pub(crate) fn increment(&self, kind: ReputationChangeKind) {
+        match kind {
+            ReputationChangeKind::BadMessage => self.bad_message.increment(1),
+            ReputationChangeKind::BadBlock => self.bad_block.increment(1),
+            ReputationChangeKind::BadTransactions => self.bad_transactions.increment(1),
+            ReputationChangeKind::AlreadySeenTransaction => {
+                self.already_seen_transactions.increment(1)
+            }
+            ReputationChangeKind::Timeout => self.timeout.increment(1),
+            ReputationChangeKind::RateLimited => self.rate_limited.increment(1),
+            ReputationChangeKind::BadProtocol => self.bad_protocol.increment(1),
+            ReputationChangeKind::FailedToConnect => self.failed_to_connect.increment(1),
+            ReputationChangeKind::Dropped => self.dropped.increment(1),
+            ReputationChangeKind::Reset | ReputationChangeKind::Other(_) => self.other.increment(1),
+        }
+    }
+}
+
+/// Metrics for requests that were dropped because they exceeded the per peer rate limits
+#[derive(Metrics)]
+#[metrics(scope = "network.rate_limited")]
+pub struct MessageRateLimitMetrics {
+    /// Number of rate limited `GetBlockHeaders` requests
+    pub(crate) get_block_headers: Counter,
+    /// Number of rate limited `GetBlockBodies` requests
+    pub(crate) get_block_bodies: Counter,
+    /// Number of rate limited `GetPooledTransactions` requests
+    pub(crate) get_pooled_transactions: Counter,
+    /// Number of rate limited `GetNodeData` requests
+    pub(crate) get_node_data: Counter,
+    /// Number of rate limited `GetReceipts` requests
+    pub(crate) get_receipts: Counter,
+}
+
 /// Metrics for the EthRequestHandler
 #[derive(Metrics)]
 #[metrics(scope = "network")]
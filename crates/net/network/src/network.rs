@@ -159,7 +159,9 @@ impl NetworkHandle {
     /// Send message to gracefully shutdown node.
     ///
     /// This will disconnect all active and pending sessions and prevent
-    /// new connections to be established.
+    /// new connections to be established. Active sessions stop accepting new requests and
+    /// disconnect once their in-flight requests are answered, or the
+    /// [`shutdown_drain_timeout`](crate::SessionsConfig::shutdown_drain_timeout) elapsed.
     pub async fn shutdown(&self) -> Result<(), oneshot::error::RecvError> {
         let (tx, rx) = oneshot::channel();
         self.send_message(NetworkHandleMessage::Shutdown(tx));
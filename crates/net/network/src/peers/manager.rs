@@ -1,7 +1,8 @@
 use crate::{
     error::{BackoffKind, SessionError},
+    metrics::PeerBanMetrics,
     peers::{
-        reputation::{is_banned_reputation, DEFAULT_REPUTATION},
+        reputation::{is_banned_reputation, BANNED_REPUTATION, DEFAULT_REPUTATION},
         ReputationChangeWeights, DEFAULT_MAX_PEERS_INBOUND, DEFAULT_MAX_PEERS_OUTBOUND,
     },
     session::{Direction, PendingSessionHandshakeError},
@@ -9,7 +10,7 @@ use crate::{
 use futures::StreamExt;
 use reth_eth_wire::{errors::EthStreamError, DisconnectReason};
 use reth_net_common::ban_list::BanList;
-use reth_network_api::{PeerKind, ReputationChangeKind};
+use reth_network_api::{PeerKind, Reputation, ReputationChangeKind};
 use reth_primitives::{ForkId, NodeRecord, PeerId};
 use std::{
     collections::{hash_map::Entry, HashMap, HashSet, VecDeque},
@@ -93,6 +94,10 @@ pub(crate) struct PeersManager {
     refill_slots_interval: Interval,
     /// How to weigh reputation changes
     reputation_weights: ReputationChangeWeights,
+    /// The reputation below which peers are banned.
+    banned_reputation: Reputation,
+    /// Counts the peer bans by reason.
+    ban_metrics: PeerBanMetrics,
     /// Tracks current slot stats.
     connection_info: ConnectionInfo,
     /// Tracks unwanted ips/peer ids.
@@ -119,13 +124,13 @@ impl PeersManager {
             refill_slots_interval,
             connection_info,
             reputation_weights,
+            banned_reputation,
             ban_list,
             ban_duration,
             backoff_durations,
             trusted_nodes,
             connect_trusted_nodes_only,
             basic_nodes,
-            ..
         } = config;
         let (manager_tx, handle_rx) = mpsc::unbounded_channel();
         let now = Instant::now();
@@ -149,6 +154,8 @@ impl PeersManager {
             handle_rx: UnboundedReceiverStream::new(handle_rx),
             queued_actions: Default::default(),
             reputation_weights,
+            banned_reputation,
+            ban_metrics: Default::default(),
             refill_slots_interval: tokio::time::interval_at(
                 now + refill_slots_interval,
                 refill_slots_interval,
@@ -250,8 +257,9 @@ impl PeersManager {
     ///
     /// This will update the state of the peer if not yet tracked.
     ///
-    /// If the reputation of the peer is below the `BANNED_REPUTATION` threshold, a disconnect will
-    /// be scheduled.
+    /// If the reputation of the peer is below the configured banned reputation threshold, a
+    /// disconnect will be scheduled. If only trusted peers should be connected to, see
+    /// [PeersConfig::connect_trusted_nodes_only], untrusted peers are disconnected as well.
     pub(crate) fn on_incoming_session_established(&mut self, peer_id: PeerId, addr: SocketAddr) {
         // we only need to check the peer id here as the ip address will have been checked at
         // on_inbound_pending_session. We also check if the peer is in the backoff list here.
@@ -266,12 +274,20 @@ impl PeersManager {
         match self.peers.entry(peer_id) {
             Entry::Occupied(mut entry) => {
                 let value = entry.get_mut();
-                if value.is_banned() {
+                if value.is_banned(self.banned_reputation) {
                     self.queued_actions.push_back(PeerAction::DisconnectBannedIncoming { peer_id });
                     return
                 }
+                if self.connect_trusted_nodes_only && !value.is_trusted() {
+                    self.queued_actions
+                        .push_back(PeerAction::DisconnectUntrustedIncoming { peer_id });
+                    return
+                }
                 value.state = PeerConnectionState::In;
             }
+            Entry::Vacant(_) if self.connect_trusted_nodes_only => {
+                self.queued_actions.push_back(PeerAction::DisconnectUntrustedIncoming { peer_id });
+            }
             Entry::Vacant(entry) => {
                 // peer is missing in the table, we add it but mark it as to be removed after
                 // disconnect, because we only know the outgoing port
@@ -345,7 +361,7 @@ impl PeersManager {
                 peer.reset_reputation()
             } else {
                 let reputation_change = self.reputation_weights.change(rep);
-                peer.apply_reputation(reputation_change.as_i32())
+                peer.apply_reputation(reputation_change.as_i32(), self.banned_reputation)
             }
         } else {
             return
@@ -354,6 +370,7 @@ impl PeersManager {
         match outcome {
             ReputationChangeOutcome::None => {}
             ReputationChangeOutcome::Ban => {
+                self.ban_metrics.increment(rep);
                 self.ban_peer(*peer_id);
             }
             ReputationChangeOutcome::Unban => self.unban_peer(*peer_id),
@@ -362,6 +379,7 @@ impl PeersManager {
                     peer_id: *peer_id,
                     reason: Some(DisconnectReason::DisconnectRequested),
                 });
+                self.ban_metrics.increment(rep);
                 self.ban_peer(*peer_id);
             }
         }
@@ -444,7 +462,21 @@ impl PeersManager {
     ) {
         trace!(target: "net::peers", ?remote_addr, ?peer_id, ?err, "handling failed connection");
 
-        if err.is_fatal_protocol_error() {
+        if err.is_fatal_protocol_error() &&
+            self.peers.get(peer_id).map_or(false, |peer| peer.is_trusted())
+        {
+            // trusted peers are never removed or banned, instead we try to reconnect to them after
+            // the highest backoff
+            trace!(target: "net::peers", ?remote_addr, ?peer_id, ?err, "fatal connection error with trusted peer");
+            let peer = self.peers.get_mut(peer_id).expect("peer exists");
+            peer.severe_backoff_counter += 1;
+            let backoff_until = self
+                .backoff_durations
+                .backoff_until(BackoffKind::High, peer.severe_backoff_counter);
+            self.connection_info.decr_state(peer.state);
+            peer.state = PeerConnectionState::Idle;
+            self.backoff_peer_until(*peer_id, backoff_until);
+        } else if err.is_fatal_protocol_error() {
             trace!(target: "net::peers", ?remote_addr, ?peer_id, ?err, "fatal connection error");
             // remove the peer to which we can't establish a connection due to protocol related
             // issues.
@@ -454,6 +486,7 @@ impl PeersManager {
             }
 
             // ban the peer
+            self.ban_metrics.fatal_protocol_error.increment(1);
             self.ban_peer(*peer_id);
 
             // If the error is caused by a peer that should be banned from discovery
@@ -630,7 +663,7 @@ impl PeersManager {
     fn best_unconnected(&mut self) -> Option<(PeerId, &mut Peer)> {
         let mut unconnected = self.peers.iter_mut().filter(|(_, peer)| {
             peer.state.is_unconnected() &&
-                !peer.is_banned() &&
+                !peer.is_banned(self.banned_reputation) &&
                 !peer.is_backed_off() &&
                 (!self.connect_trusted_nodes_only || peer.is_trusted())
         });
@@ -665,6 +698,7 @@ impl PeersManager {
         self.tick();
 
         // as long as there a slots available try to fill them with the best peers
+        let banned_reputation = self.banned_reputation;
         while self.connection_info.has_out_capacity() {
             let action = {
                 let (peer_id, peer) = match self.best_unconnected() {
@@ -673,7 +707,7 @@ impl PeersManager {
                 };
 
                 // If best peer does not meet reputation threshold exit immediately.
-                if peer.is_banned() {
+                if peer.is_banned(banned_reputation) {
                     break
                 }
 
@@ -885,23 +919,30 @@ impl Peer {
     }
 
     /// Applies a reputation change to the peer and returns what action should be taken.
-    fn apply_reputation(&mut self, reputation: i32) -> ReputationChangeOutcome {
+    ///
+    /// The peer is banned if its reputation falls below `banned_reputation`.
+    fn apply_reputation(
+        &mut self,
+        reputation: i32,
+        banned_reputation: Reputation,
+    ) -> ReputationChangeOutcome {
         let previous = self.reputation;
         // we add reputation since negative reputation change decrease total reputation
         self.reputation = previous.saturating_add(reputation);
 
-        trace!(target: "net::peers", reputation=%self.reputation, banned=%self.is_banned(), "applied reputation change");
+        let is_banned = self.is_banned(banned_reputation);
+        trace!(target: "net::peers", reputation=%self.reputation, banned=%is_banned, "applied reputation change");
 
-        if self.state.is_connected() && self.is_banned() {
+        if self.state.is_connected() && is_banned {
             self.state.disconnect();
             return ReputationChangeOutcome::DisconnectAndBan
         }
 
-        if self.is_banned() && !is_banned_reputation(previous) {
+        if is_banned && !is_banned_reputation(previous, banned_reputation) {
             return ReputationChangeOutcome::Ban
         }
 
-        if !self.is_banned() && is_banned_reputation(previous) {
+        if !is_banned && is_banned_reputation(previous, banned_reputation) {
             return ReputationChangeOutcome::Unban
         }
 
@@ -909,9 +950,11 @@ impl Peer {
     }
 
     /// Returns true if the peer's reputation is below the banned threshold.
+    ///
+    /// Trusted peers are never banned because of their reputation.
     #[inline]
-    fn is_banned(&self) -> bool {
-        is_banned_reputation(self.reputation)
+    fn is_banned(&self, banned_reputation: Reputation) -> bool {
+        !self.is_trusted() && is_banned_reputation(self.reputation, banned_reputation)
     }
 
     #[inline]
@@ -1026,6 +1069,12 @@ pub enum PeerAction {
         /// Peer id of the established connection.
         peer_id: PeerId,
     },
+    /// Disconnect an existing incoming connection, because only trusted peers should be connected
+    /// to and the peer is not trusted.
+    DisconnectUntrustedIncoming {
+        /// Peer id of the established connection.
+        peer_id: PeerId,
+    },
     /// Ban the peer in discovery.
     DiscoveryBanPeerId { peer_id: PeerId, ip_addr: IpAddr },
     /// Ban the IP in discovery.
@@ -1065,6 +1114,8 @@ pub struct PeersConfig {
     pub connection_info: ConnectionInfo,
     /// How to weigh reputation changes.
     pub reputation_weights: ReputationChangeWeights,
+    /// The reputation below which peers are disconnected and banned for the `ban_duration`.
+    pub banned_reputation: Reputation,
     /// How long to backoff peers that are we failed to connect to for non-fatal reasons, such as
     /// [`DisconnectReason::TooManyPeers`].
     pub backoff_durations: PeerBackoffDurations,
@@ -1076,6 +1127,7 @@ impl Default for PeersConfig {
             refill_slots_interval: Duration::from_millis(1_000),
             connection_info: Default::default(),
             reputation_weights: Default::default(),
+            banned_reputation: BANNED_REPUTATION,
             ban_list: Default::default(),
             // Ban peers for 12h
             ban_duration: Duration::from_secs(60 * 60 * 12),
@@ -1094,6 +1146,18 @@ impl PeersConfig {
         self
     }
 
+    /// How long to ban bad peers.
+    pub fn with_ban_duration(mut self, ban_duration: Duration) -> Self {
+        self.ban_duration = ban_duration;
+        self
+    }
+
+    /// The reputation below which peers are banned.
+    pub fn with_banned_reputation(mut self, banned_reputation: Reputation) -> Self {
+        self.banned_reputation = banned_reputation;
+        self
+    }
+
     /// Maximum occupied slots for outbound connections.
     pub fn with_max_pending_outbound(mut self, num_outbound: usize) -> Self {
         self.connection_info.num_outbound = num_outbound;
@@ -1715,13 +1779,13 @@ mod test {
 
         let p = peers.peers.get(&peer).unwrap();
         assert_eq!(p.state, PeerConnectionState::DisconnectingOut);
-        assert!(p.is_banned());
+        assert!(p.is_banned(BANNED_REPUTATION));
 
         peers.on_active_session_gracefully_closed(peer);
 
         let p = peers.peers.get(&peer).unwrap();
         assert_eq!(p.state, PeerConnectionState::Idle);
-        assert!(p.is_banned());
+        assert!(p.is_banned(BANNED_REPUTATION));
 
         match event!(peers) {
             PeerAction::Disconnect { peer_id, .. } => {
@@ -1746,6 +1810,22 @@ mod test {
         assert_eq!(peers.get_reputation(&peer), Some(0));
     }
 
+    #[tokio::test]
+    async fn test_banned_reputation_threshold() {
+        let peer = PeerId::random();
+        let socket_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2)), 8008);
+
+        let mut peers = PeersManager::default();
+        peers.add_peer(peer, socket_addr, None);
+        peers.apply_reputation_change(&peer, ReputationChangeKind::Timeout);
+        assert!(!peers.ban_list.is_banned_peer(&peer));
+
+        let mut peers = PeersManager::new(PeersConfig::default().with_banned_reputation(-1));
+        peers.add_peer(peer, socket_addr, None);
+        peers.apply_reputation_change(&peer, ReputationChangeKind::Timeout);
+        assert!(peers.ban_list.is_banned_peer(&peer));
+    }
+
     #[tokio::test]
     async fn test_remove_discovered_active() {
         let peer = PeerId::random();
@@ -1996,6 +2076,108 @@ mod test {
         .await;
     }
 
+    #[tokio::test]
+    async fn test_trusted_nodes_only_rejects_untrusted_incoming() {
+        let trusted_peer = PeerId::random();
+        let config = PeersConfig::default()
+            .with_trusted_nodes(HashSet::from([NodeRecord {
+                address: IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2)),
+                tcp_port: 8008,
+                udp_port: 8008,
+                id: trusted_peer,
+            }]))
+            .with_connect_trusted_nodes_only(true);
+        let mut peers = PeersManager::new(config);
+
+        let basic_peer = PeerId::random();
+        let basic_sock = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2)), 8009);
+        peers.on_incoming_pending_session(basic_sock.ip()).unwrap();
+        peers.on_incoming_session_established(basic_peer, basic_sock);
+
+        match event!(peers) {
+            PeerAction::DisconnectUntrustedIncoming { peer_id } => {
+                assert_eq!(peer_id, basic_peer);
+            }
+            _ => unreachable!(),
+        }
+        assert!(!peers.peers.contains_key(&basic_peer));
+    }
+
+    #[tokio::test]
+    async fn test_trusted_peer_not_banned() {
+        let trusted_peer = PeerId::random();
+        let config = PeersConfig::default().with_trusted_nodes(HashSet::from([NodeRecord {
+            address: IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2)),
+            tcp_port: 8008,
+            udp_port: 8008,
+            id: trusted_peer,
+        }]));
+        let mut peers = PeersManager::new(config);
+
+        peers.apply_reputation_change(&trusted_peer, ReputationChangeKind::BadProtocol);
+
+        let p = peers.peers.get(&trusted_peer).unwrap();
+        assert!(is_banned_reputation(p.reputation, BANNED_REPUTATION));
+        assert!(!p.is_banned(BANNED_REPUTATION));
+        assert!(!peers.ban_list.is_banned_peer(&trusted_peer));
+    }
+
+    #[tokio::test]
+    async fn test_reconnect_trusted_peer_after_fatal_error() {
+        let trusted_peer = PeerId::random();
+        let trusted_sock = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2)), 8008);
+        let backoff_durations =
+            PeerBackoffDurations { high: Duration::from_millis(100), ..Default::default() };
+        let config = PeersConfig {
+            backoff_durations,
+            ..PeersConfig::default()
+                .with_trusted_nodes(HashSet::from([NodeRecord {
+                    address: IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2)),
+                    tcp_port: 8008,
+                    udp_port: 8008,
+                    id: trusted_peer,
+                }]))
+                .with_connect_trusted_nodes_only(true)
+        };
+        let mut peers = PeersManager::new(config);
+
+        match event!(peers) {
+            PeerAction::Connect { peer_id, .. } => {
+                assert_eq!(peer_id, trusted_peer);
+            }
+            _ => unreachable!(),
+        }
+
+        peers.on_active_session_dropped(
+            &trusted_sock,
+            &trusted_peer,
+            &EthStreamError::P2PStreamError(P2PStreamError::Disconnected(
+                DisconnectReason::UselessPeer,
+            )),
+        );
+
+        // the trusted peer is neither removed nor banned, only backed off
+        poll_fn(|cx| {
+            assert!(peers.poll(cx).is_pending());
+            Poll::Ready(())
+        })
+        .await;
+        assert!(peers.peers.get(&trusted_peer).unwrap().is_backed_off());
+        assert!(!peers.ban_list.is_banned_peer(&trusted_peer));
+
+        // the first severe backoff lasts twice the high backoff duration
+        tokio::time::sleep(backoff_durations.high * 2).await;
+
+        match event!(peers) {
+            PeerAction::Connect { peer_id, remote_addr } => {
+                assert_eq!(peer_id, trusted_peer);
+                assert_eq!(remote_addr, trusted_sock);
+            }
+            _ => unreachable!(),
+        }
+        assert!(!peers.peers.get(&trusted_peer).unwrap().is_backed_off());
+    }
+
     #[tokio::test]
     async fn test_tick() {
         let ip = IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2));
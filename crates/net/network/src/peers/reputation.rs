@@ -8,7 +8,7 @@ pub(crate) const DEFAULT_REPUTATION: Reputation = 0;
 /// The minimal unit we're measuring reputation
 const REPUTATION_UNIT: i32 = -1024;
 
-/// The reputation value below which new connection from/to peers are rejected.
+/// The default reputation value below which new connection from/to peers are rejected.
 pub(crate) const BANNED_REPUTATION: i32 = 50 * REPUTATION_UNIT;
 
 /// The reputation change to apply to a peer that dropped the connection.
@@ -23,6 +23,12 @@ const TIMEOUT_REPUTATION_CHANGE: i32 = 4 * REPUTATION_UNIT;
 /// The reputation change to apply to a peer that sent a bad message.
 const BAD_MESSAGE_REPUTATION_CHANGE: i32 = 16 * REPUTATION_UNIT;
 
+/// The reputation change to apply to a peer that exceeded a request rate limit.
+///
+/// This is intentionally small, so that a peer is only banned after exceeding the limits many
+/// times.
+const RATE_LIMITED_REPUTATION_CHANGE: i32 = REPUTATION_UNIT;
+
 /// The reputation change applies to a peer that has sent a transaction (full or hash) that we
 /// already know about and have already previously received from that peer.
 ///
@@ -33,15 +39,16 @@ const ALREADY_SEEN_TRANSACTION_REPUTATION_CHANGE: i32 = 0;
 /// The reputation change to apply to a peer which violates protocol rules: minimal reputation
 const BAD_PROTOCOL_REPUTATION_CHANGE: i32 = i32::MIN;
 
-/// Returns `true` if the given reputation is below the [`BANNED_REPUTATION`] threshold
+/// Returns `true` if the given reputation is below the `banned_reputation` threshold
 #[inline]
-pub(crate) fn is_banned_reputation(reputation: i32) -> bool {
-    reputation < BANNED_REPUTATION
+pub(crate) fn is_banned_reputation(reputation: i32, banned_reputation: i32) -> bool {
+    reputation < banned_reputation
 }
 
 /// How the [`ReputationChangeKind`] are weighted.
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct ReputationChangeWeights {
     /// Weight for [`ReputationChangeKind::BadMessage`]
     pub bad_message: Reputation,
@@ -53,6 +60,8 @@ pub struct ReputationChangeWeights {
     pub already_seen_transactions: Reputation,
     /// Weight for [`ReputationChangeKind::Timeout`]
     pub timeout: Reputation,
+    /// Weight for [`ReputationChangeKind::RateLimited`]
+    pub rate_limited: Reputation,
     /// Weight for [`ReputationChangeKind::BadProtocol`]
     pub bad_protocol: Reputation,
     /// Weight for [`ReputationChangeKind::FailedToConnect`]
@@ -73,6 +82,7 @@ impl ReputationChangeWeights {
             ReputationChangeKind::BadTransactions => self.bad_transactions.into(),
             ReputationChangeKind::AlreadySeenTransaction => self.already_seen_transactions.into(),
             ReputationChangeKind::Timeout => self.timeout.into(),
+            ReputationChangeKind::RateLimited => self.rate_limited.into(),
             ReputationChangeKind::BadProtocol => self.bad_protocol.into(),
             ReputationChangeKind::FailedToConnect => self.failed_to_connect.into(),
             ReputationChangeKind::Dropped => self.dropped.into(),
@@ -90,6 +100,7 @@ impl Default for ReputationChangeWeights {
             already_seen_transactions: ALREADY_SEEN_TRANSACTION_REPUTATION_CHANGE,
             bad_message: BAD_MESSAGE_REPUTATION_CHANGE,
             timeout: TIMEOUT_REPUTATION_CHANGE,
+            rate_limited: RATE_LIMITED_REPUTATION_CHANGE,
             bad_protocol: BAD_PROTOCOL_REPUTATION_CHANGE,
             failed_to_connect: FAILED_TO_CONNECT_REPUTATION_CHANGE,
             dropped: REMOTE_DISCONNECT_REPUTATION_CHANGE,
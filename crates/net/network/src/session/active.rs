@@ -5,6 +5,7 @@ use crate::{
     session::{
         config::INITIAL_REQUEST_TIMEOUT,
         handle::{ActiveSessionMessage, SessionCommand},
+        rate_limit::{MessageRateLimiter, RateLimitedMessage},
         SessionId,
     },
 };
@@ -34,7 +35,7 @@ use std::{
 use tokio::{
     net::TcpStream,
     sync::{mpsc::error::TrySendError, oneshot},
-    time::Interval,
+    time::{Interval, Sleep},
 };
 use tokio_stream::wrappers::ReceiverStream;
 use tracing::{debug, info, trace};
@@ -94,6 +95,10 @@ pub(crate) struct ActiveSession {
     /// If an [ActiveSession] does not receive a response at all within this duration then it is
     /// considered a protocol violation and the session will initiate a drop.
     pub(crate) protocol_breach_request_timeout: Duration,
+    /// Enforces the rate limits of requests received from the remote peer.
+    pub(crate) rate_limiter: MessageRateLimiter,
+    /// Set if the session is draining its in-flight requests before it disconnects.
+    pub(crate) draining: Option<Draining>,
 }
 
 impl ActiveSession {
@@ -122,8 +127,20 @@ impl ActiveSession {
         /// A macro that handles an incoming request
         /// This creates a new channel and tries to send the sender half to the session while
         /// storing the receiver half internally so the pending response can be polled.
+        ///
+        /// Requests that exceed the rate limit are dropped and reported as rate limited. Requests
+        /// received while the session is draining are dropped.
         macro_rules! on_request {
             ($req:ident, $resp_item:ident, $req_item:ident) => {{
+                if self.draining.is_some() {
+                    trace!(target: "net::session", remote_peer_id=?self.remote_peer_id, request=stringify!($req_item), "ignoring request while draining");
+                    return OnIncomingMessageOutcome::Ok
+                }
+                if !self.rate_limiter.try_acquire(RateLimitedMessage::$req_item) {
+                    trace!(target: "net::session", remote_peer_id=?self.remote_peer_id, request=stringify!($req_item), "rate limited request");
+                    self.on_rate_limited();
+                    return OnIncomingMessageOutcome::Ok
+                }
                 let RequestPair { request_id, message: request } = $req;
                 let (tx, response) = oneshot::channel();
                 let received = ReceivedRequest {
@@ -235,6 +252,11 @@ impl ActiveSession {
 
     /// Handle an internal peer request that will be sent to the remote.
     fn on_internal_peer_request(&mut self, request: PeerRequest, deadline: Instant) {
+        if self.draining.is_some() {
+            // the session is about to be closed
+            request.send_err_response(RequestError::ConnectionDropped);
+            return
+        }
         let request_id = self.next_id();
         let msg = request.create_request_message(request_id);
         self.queued_outgoing.push_back(msg.into());
@@ -354,6 +376,13 @@ impl ActiveSession {
             .try_send(ActiveSessionMessage::BadMessage { peer_id: self.remote_peer_id });
     }
 
+    /// Notify the manager that the peer exceeded a request rate limit
+    fn on_rate_limited(&self) {
+        let _ = self
+            .to_session_manager
+            .try_send(ActiveSessionMessage::RateLimited { peer_id: self.remote_peer_id });
+    }
+
     /// Report back that this session has been closed.
     fn emit_disconnect(&self) {
         trace!(target: "net::session", remote_peer_id=?self.remote_peer_id, "emitting disconnect");
@@ -434,6 +463,14 @@ impl ActiveSession {
         false
     }
 
+    /// Returns `true` if there are no pending responses to received requests, no requests waiting
+    /// for a response from the peer, and no buffered outgoing messages.
+    fn is_drained(&self) -> bool {
+        self.received_requests_from_remote.is_empty() &&
+            self.queued_outgoing.is_empty() &&
+            !self.inflight_requests.values().any(InflightRequest::is_waiting)
+    }
+
     /// Updates the request timeout with a request's timestamps
     fn update_request_timeout(&mut self, sent: Instant, received: Instant) {
         let elapsed = received.saturating_duration_since(sent);
@@ -485,6 +522,15 @@ impl Future for ActiveSession {
 
                                 return this.try_disconnect(reason, cx)
                             }
+                            SessionCommand::Drain { reason, timeout } => {
+                                if this.draining.is_none() {
+                                    debug!(target: "net::session", ?reason, ?timeout, remote_peer_id=?this.remote_peer_id, "Draining session");
+                                    this.draining = Some(Draining {
+                                        reason,
+                                        deadline: Box::pin(tokio::time::sleep(timeout)),
+                                    });
+                                }
+                            }
                             SessionCommand::Message(msg) => {
                                 this.on_peer_message(msg);
                             }
@@ -535,6 +581,15 @@ impl Future for ActiveSession {
                 }
             }
 
+            // disconnect once all in-flight requests are done or the drain timeout elapsed
+            if let Some(draining) = this.draining.as_mut() {
+                let timed_out = draining.deadline.as_mut().poll(cx).is_ready();
+                let reason = draining.reason;
+                if timed_out || this.is_drained() {
+                    return this.try_disconnect(reason, cx)
+                }
+            }
+
             // read incoming messages from the wire
             'receive: loop {
                 // ensure we still have enough budget for another iteration
@@ -628,6 +683,14 @@ impl Future for ActiveSession {
     }
 }
 
+/// The state of a session that drains its in-flight requests before it disconnects.
+pub(crate) struct Draining {
+    /// The reason sent to the peer on disconnect.
+    reason: DisconnectReason,
+    /// The session disconnects when the deadline is reached, even if requests are still pending.
+    deadline: Pin<Box<Sleep>>,
+}
+
 /// Tracks a request received from the peer
 pub(crate) struct ReceivedRequest {
     /// Protocol Identifier
@@ -736,7 +799,7 @@ mod tests {
 
     use super::*;
     use crate::session::{
-        config::{INITIAL_REQUEST_TIMEOUT, PROTOCOL_BREACH_REQUEST_TIMEOUT},
+        config::{MessageRateLimits, INITIAL_REQUEST_TIMEOUT, PROTOCOL_BREACH_REQUEST_TIMEOUT},
         handle::PendingSessionEvent,
         start_pending_incoming_session,
     };
@@ -866,6 +929,8 @@ mod tests {
                             INITIAL_REQUEST_TIMEOUT.as_millis() as u64,
                         )),
                         protocol_breach_request_timeout: PROTOCOL_BREACH_REQUEST_TIMEOUT,
+                        rate_limiter: MessageRateLimiter::new(Default::default()),
+                        draining: None,
                     }
                 }
                 ev => {
@@ -1028,6 +1093,95 @@ mod tests {
         }
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_drain_timeout() {
+        let mut builder = SessionBuilder::default();
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let local_addr = listener.local_addr().unwrap();
+
+        let fut = builder.with_client_stream(local_addr, move |mut client_stream| async move {
+            // the request is never answered, so the session disconnects once the drain timed out
+            loop {
+                match client_stream.next().await.unwrap() {
+                    Ok(msg) => assert!(matches!(msg, EthMessage::GetBlockBodies(_))),
+                    Err(err) => {
+                        assert_eq!(
+                            err.as_disconnected().unwrap(),
+                            DisconnectReason::ClientQuitting
+                        );
+                        break
+                    }
+                }
+            }
+        });
+        let client = tokio::task::spawn(fut);
+
+        let (incoming, _) = listener.accept().await.unwrap();
+        let mut session = builder.connect_incoming(incoming).await;
+        let (tx, _rx) = oneshot::channel();
+        let req = PeerRequest::GetBlockBodies { request: GetBlockBodies(vec![]), response: tx };
+        let deadline = session.request_deadline();
+        session.on_internal_peer_request(req, deadline);
+
+        builder.to_sessions[0]
+            .send(SessionCommand::Drain {
+                reason: DisconnectReason::ClientQuitting,
+                timeout: Duration::from_millis(200),
+            })
+            .await
+            .unwrap();
+        tokio::spawn(session);
+
+        client.await.unwrap();
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_rate_limited_requests_keep_session() {
+        let mut builder = SessionBuilder::default();
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let local_addr = listener.local_addr().unwrap();
+
+        let num_requests = 10u64;
+
+        let fut = builder.with_client_stream(local_addr, move |mut client_stream| async move {
+            for request_id in 0..num_requests {
+                client_stream
+                    .send(EthMessage::GetBlockBodies(RequestPair {
+                        request_id,
+                        message: GetBlockBodies(vec![]),
+                    }))
+                    .await
+                    .unwrap();
+            }
+            let _ = tokio::time::timeout(Duration::from_secs(100), client_stream.next()).await;
+        });
+        tokio::task::spawn(fut);
+
+        let (incoming, _) = listener.accept().await.unwrap();
+        let mut session = builder.connect_incoming(incoming).await;
+        session.rate_limiter =
+            MessageRateLimiter::new(MessageRateLimits::default().with_get_block_bodies(1));
+        let session = tokio::task::spawn(session);
+
+        // over-limit requests are reported as rate limited, not as bad messages
+        let mut requests = Vec::new();
+        let mut rate_limited = 0;
+        while requests.len() as u64 + rate_limited < num_requests {
+            match builder.active_session_rx.next().await.unwrap() {
+                ActiveSessionMessage::ValidMessage { message, .. } => requests.push(message),
+                ActiveSessionMessage::RateLimited { .. } => rate_limited += 1,
+                ev => unreachable!("{ev:?}"),
+            }
+        }
+        assert!(rate_limited >= num_requests - 2, "{rate_limited} requests were rate limited");
+
+        // the session stays open
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert!(!session.is_finished());
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn test_keep_alive() {
         let mut builder = SessionBuilder::default();
@@ -16,6 +16,9 @@ pub const INITIAL_REQUEST_TIMEOUT: Duration = Duration::from_secs(20);
 /// This is the time a peer has to answer a response.
 pub const PROTOCOL_BREACH_REQUEST_TIMEOUT: Duration = Duration::from_secs(2 * 60);
 
+/// Default time sessions wait for in-flight requests to complete on shutdown.
+pub const SHUTDOWN_DRAIN_TIMEOUT: Duration = Duration::from_secs(5);
+
 /// Configuration options when creating a [SessionManager](crate::session::SessionManager).
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -38,6 +41,13 @@ pub struct SessionsConfig {
     /// `PROTOCOL_BREACH_REQUEST_TIMEOUT`) this is considered a protocol violation and results in a
     /// dropped session.
     pub protocol_breach_request_timeout: Duration,
+    /// Per peer rate limits for requests received from the peer.
+    ///
+    /// By default, no limits will be enforced.
+    pub message_rate_limits: MessageRateLimits,
+    /// The maximum time sessions wait for in-flight requests to complete on shutdown, before they
+    /// disconnect.
+    pub shutdown_drain_timeout: Duration,
 }
 
 impl Default for SessionsConfig {
@@ -56,6 +66,8 @@ impl Default for SessionsConfig {
             limits: Default::default(),
             initial_internal_request_timeout: INITIAL_REQUEST_TIMEOUT,
             protocol_breach_request_timeout: PROTOCOL_BREACH_REQUEST_TIMEOUT,
+            message_rate_limits: Default::default(),
+            shutdown_drain_timeout: SHUTDOWN_DRAIN_TIMEOUT,
         }
     }
 }
@@ -70,6 +82,70 @@ impl SessionsConfig {
         self.session_event_buffer = n;
         self
     }
+
+    /// Sets the per peer rate limits for requests received from the peer.
+    pub fn with_message_rate_limits(mut self, limits: MessageRateLimits) -> Self {
+        self.message_rate_limits = limits;
+        self
+    }
+
+    /// Sets the maximum time sessions wait for in-flight requests to complete on shutdown.
+    pub fn with_shutdown_drain_timeout(mut self, timeout: Duration) -> Self {
+        self.shutdown_drain_timeout = timeout;
+        self
+    }
+}
+
+/// Per peer rate limits for requests received from the peer, in requests per second.
+///
+/// Requests that exceed the limit are dropped and the peer's reputation is reduced, but the session
+/// is kept alive. By default, no limits will be enforced.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct MessageRateLimits {
+    /// Limit for `GetBlockHeaders` requests.
+    pub get_block_headers: Option<u32>,
+    /// Limit for `GetBlockBodies` requests.
+    pub get_block_bodies: Option<u32>,
+    /// Limit for `GetPooledTransactions` requests.
+    pub get_pooled_transactions: Option<u32>,
+    /// Limit for `GetNodeData` requests.
+    pub get_node_data: Option<u32>,
+    /// Limit for `GetReceipts` requests.
+    pub get_receipts: Option<u32>,
+}
+
+impl MessageRateLimits {
+    /// Sets the limit for `GetBlockHeaders` requests.
+    pub fn with_get_block_headers(mut self, limit: u32) -> Self {
+        self.get_block_headers = Some(limit);
+        self
+    }
+
+    /// Sets the limit for `GetBlockBodies` requests.
+    pub fn with_get_block_bodies(mut self, limit: u32) -> Self {
+        self.get_block_bodies = Some(limit);
+        self
+    }
+
+    /// Sets the limit for `GetPooledTransactions` requests.
+    pub fn with_get_pooled_transactions(mut self, limit: u32) -> Self {
+        self.get_pooled_transactions = Some(limit);
+        self
+    }
+
+    /// Sets the limit for `GetNodeData` requests.
+    pub fn with_get_node_data(mut self, limit: u32) -> Self {
+        self.get_node_data = Some(limit);
+        self
+    }
+
+    /// Sets the limit for `GetReceipts` requests.
+    pub fn with_get_receipts(mut self, limit: u32) -> Self {
+        self.get_receipts = Some(limit);
+        self
+    }
 }
 
 /// Limits for sessions.
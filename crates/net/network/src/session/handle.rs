@@ -11,7 +11,12 @@ use reth_eth_wire::{
 };
 use reth_net_common::bandwidth_meter::MeteredStream;
 use reth_primitives::PeerId;
-use std::{io, net::SocketAddr, sync::Arc, time::Instant};
+use std::{
+    io,
+    net::SocketAddr,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 use tokio::{
     net::TcpStream,
     sync::{mpsc, oneshot},
@@ -75,6 +80,13 @@ impl ActiveSessionHandle {
         // Note: we clone the sender which ensures the channel has capacity to send the message
         let _ = self.commands_to_session.clone().try_send(SessionCommand::Disconnect { reason });
     }
+
+    /// Sends a drain command to the session.
+    pub(crate) fn drain(&self, reason: DisconnectReason, timeout: Duration) {
+        // Note: we clone the sender which ensures the channel has capacity to send the message
+        let _ =
+            self.commands_to_session.clone().try_send(SessionCommand::Drain { reason, timeout });
+    }
 }
 
 /// Info about an active peer session.
@@ -143,6 +155,14 @@ pub(crate) enum SessionCommand {
         /// Why the disconnect was initiated
         reason: Option<DisconnectReason>,
     },
+    /// Stop accepting new requests and disconnect once all in-flight requests are answered, or
+    /// the timeout elapsed.
+    Drain {
+        /// Why the disconnect was initiated
+        reason: DisconnectReason,
+        /// The maximum time to wait for in-flight requests.
+        timeout: Duration,
+    },
     /// Sends a message to the peer
     Message(PeerMessage),
 }
@@ -182,6 +202,11 @@ pub(crate) enum ActiveSessionMessage {
         /// Identifier of the remote peer.
         peer_id: PeerId,
     },
+    /// Received a request that exceeds the configured rate limits from the peer.
+    RateLimited {
+        /// Identifier of the remote peer.
+        peer_id: PeerId,
+    },
     /// Remote peer is considered in protocol violation
     ProtocolBreach {
         /// Identifier of the remote peer.
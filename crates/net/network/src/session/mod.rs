@@ -3,11 +3,12 @@ use crate::{
     message::PeerMessage,
     session::{
         active::ActiveSession,
-        config::SessionCounter,
+        config::{MessageRateLimits, SessionCounter},
         handle::{
             ActiveSessionHandle, ActiveSessionMessage, PendingSessionEvent, PendingSessionHandle,
             SessionCommand,
         },
+        rate_limit::MessageRateLimiter,
     },
 };
 pub use crate::{message::PeerRequestSender, session::handle::PeerInfo};
@@ -46,7 +47,8 @@ use tracing::{instrument, trace};
 mod active;
 mod config;
 mod handle;
-pub use config::SessionsConfig;
+mod rate_limit;
+pub use config::{MessageRateLimits, SessionsConfig};
 
 /// Internal identifier for active sessions.
 #[derive(Debug, Clone, Copy, PartialOrd, PartialEq, Eq, Hash)]
@@ -66,6 +68,10 @@ pub(crate) struct SessionManager {
     /// If an [ActiveSession] does not receive a response at all within this duration then it is
     /// considered a protocol violation and the session will initiate a drop.
     protocol_breach_request_timeout: Duration,
+    /// Per peer rate limits for requests received from the peer.
+    message_rate_limits: MessageRateLimits,
+    /// The maximum time sessions wait for in-flight requests to complete on shutdown.
+    shutdown_drain_timeout: Duration,
     /// The secret key used for authenticating sessions.
     secret_key: SecretKey,
     /// The `Status` message to send to peers.
@@ -124,6 +130,8 @@ impl SessionManager {
             counter: SessionCounter::new(config.limits),
             initial_internal_request_timeout: config.initial_internal_request_timeout,
             protocol_breach_request_timeout: config.protocol_breach_request_timeout,
+            message_rate_limits: config.message_rate_limits,
+            shutdown_drain_timeout: config.shutdown_drain_timeout,
             secret_key,
             status,
             hello_message,
@@ -291,14 +299,21 @@ impl SessionManager {
 
     /// Initiates a shutdown of all sessions.
     ///
-    /// It will trigger the disconnect on all the session tasks to gracefully terminate. The result
-    /// will be picked by the receiver.
-    pub(crate) fn disconnect_all(&self, reason: Option<DisconnectReason>) {
+    /// All active sessions stop accepting new requests and disconnect with the given reason once
+    /// their in-flight requests are answered, or the
+    /// [shutdown_drain_timeout](SessionsConfig::shutdown_drain_timeout) elapsed. The result will be
+    /// picked by the receiver.
+    pub(crate) fn drain_all(&self, reason: DisconnectReason) {
         for (_, session) in self.active_sessions.iter() {
-            session.disconnect(reason);
+            session.drain(reason, self.shutdown_drain_timeout);
         }
     }
 
+    /// Returns the maximum time sessions wait for in-flight requests to complete on shutdown.
+    pub(crate) fn shutdown_drain_timeout(&self) -> Duration {
+        self.shutdown_drain_timeout
+    }
+
     /// Disconnects all pending sessions.
     pub(crate) fn disconnect_all_pending(&mut self) {
         for (_, session) in self.pending_sessions.iter_mut() {
@@ -370,6 +385,9 @@ impl SessionManager {
                     ActiveSessionMessage::BadMessage { peer_id } => {
                         Poll::Ready(SessionEvent::BadMessage { peer_id })
                     }
+                    ActiveSessionMessage::RateLimited { peer_id } => {
+                        Poll::Ready(SessionEvent::RateLimited { peer_id })
+                    }
                     ActiveSessionMessage::ProtocolBreach { peer_id } => {
                         Poll::Ready(SessionEvent::ProtocolBreach { peer_id })
                     }
@@ -453,6 +471,8 @@ impl SessionManager {
                     ),
                     internal_request_timeout: Arc::clone(&timeout),
                     protocol_breach_request_timeout: self.protocol_breach_request_timeout,
+                    rate_limiter: MessageRateLimiter::new(self.message_rate_limits),
+                    draining: None,
                 };
 
                 self.spawn(session);
@@ -626,6 +646,11 @@ pub(crate) enum SessionEvent {
         /// Identifier of the remote peer.
         peer_id: PeerId,
     },
+    /// Received a request that exceeds the configured rate limits from the peer.
+    RateLimited {
+        /// Identifier of the remote peer.
+        peer_id: PeerId,
+    },
     /// Remote peer is considered in protocol violation
     ProtocolBreach {
         /// Identifier of the remote peer.
@@ -0,0 +1,139 @@
+//! Rate limiting of requests received from a peer.
+
+use crate::{metrics::MessageRateLimitMetrics, session::config::MessageRateLimits};
+use std::time::Instant;
+
+/// The kinds of messages that are rate limited per peer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum RateLimitedMessage {
+    GetBlockHeaders,
+    GetBlockBodies,
+    GetPooledTransactions,
+    GetNodeData,
+    GetReceipts,
+}
+
+/// Enforces the [MessageRateLimits] of a single session.
+pub(crate) struct MessageRateLimiter {
+    get_block_headers: Option<TokenBucket>,
+    get_block_bodies: Option<TokenBucket>,
+    get_pooled_transactions: Option<TokenBucket>,
+    get_node_data: Option<TokenBucket>,
+    get_receipts: Option<TokenBucket>,
+    metrics: MessageRateLimitMetrics,
+}
+
+impl MessageRateLimiter {
+    /// Creates a new rate limiter with the given limits.
+    pub(crate) fn new(limits: MessageRateLimits) -> Self {
+        let now = Instant::now();
+        let bucket = |rate: Option<u32>| rate.map(|rate| TokenBucket::new(rate, now));
+        Self {
+            get_block_headers: bucket(limits.get_block_headers),
+            get_block_bodies: bucket(limits.get_block_bodies),
+            get_pooled_transactions: bucket(limits.get_pooled_transactions),
+            get_node_data: bucket(limits.get_node_data),
+            get_receipts: bucket(limits.get_receipts),
+            metrics: Default::default(),
+        }
+    }
+
+    /// Returns `true` if the message is within the limits and should be handled.
+    pub(crate) fn try_acquire(&mut self, message: RateLimitedMessage) -> bool {
+        let (bucket, hits) = match message {
+            RateLimitedMessage::GetBlockHeaders => {
+                (&mut self.get_block_headers, &self.metrics.get_block_headers)
+            }
+            RateLimitedMessage::GetBlockBodies => {
+                (&mut self.get_block_bodies, &self.metrics.get_block_bodies)
+            }
+            RateLimitedMessage::GetPooledTransactions => {
+                (&mut self.get_pooled_transactions, &self.metrics.get_pooled_transactions)
+            }
+            RateLimitedMessage::GetNodeData => {
+                (&mut self.get_node_data, &self.metrics.get_node_data)
+            }
+            RateLimitedMessage::GetReceipts => (&mut self.get_receipts, &self.metrics.get_receipts),
+        };
+
+        let Some(bucket) = bucket else { return true };
+        if bucket.try_acquire(Instant::now()) {
+            return true
+        }
+        hits.increment(1);
+        false
+    }
+}
+
+/// A token bucket that is refilled with `rate` tokens per second and holds up to one second worth
+/// of tokens.
+#[derive(Debug)]
+struct TokenBucket {
+    /// Tokens per second.
+    rate: f64,
+    /// Currently available tokens.
+    tokens: f64,
+    /// When the bucket was last refilled.
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn new(rate: u32, now: Instant) -> Self {
+        let rate = rate.max(1) as f64;
+        Self { rate, tokens: rate, last_refill: now }
+    }
+
+    /// Takes a token out of the bucket, returns `false` if the bucket is empty.
+    fn try_acquire(&mut self, now: Instant) -> bool {
+        let elapsed = now.saturating_duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.rate).min(self.rate);
+        self.last_refill = now;
+
+        if self.tokens < 1.0 {
+            return false
+        }
+        self.tokens -= 1.0;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn token_bucket_refills() {
+        let now = Instant::now();
+        let mut bucket = TokenBucket::new(2, now);
+
+        assert!(bucket.try_acquire(now));
+        assert!(bucket.try_acquire(now));
+        assert!(!bucket.try_acquire(now));
+
+        // half a second refills a single token
+        let now = now + Duration::from_millis(500);
+        assert!(bucket.try_acquire(now));
+        assert!(!bucket.try_acquire(now));
+
+        // the bucket holds at most one second worth of tokens
+        let now = now + Duration::from_secs(10);
+        assert!(bucket.try_acquire(now));
+        assert!(bucket.try_acquire(now));
+        assert!(!bucket.try_acquire(now));
+    }
+
+    #[test]
+    fn unlimited_by_default() {
+        let mut limiter = MessageRateLimiter::new(MessageRateLimits::default());
+        for _ in 0..100 {
+            assert!(limiter.try_acquire(RateLimitedMessage::GetBlockHeaders));
+        }
+
+        let mut limiter =
+            MessageRateLimiter::new(MessageRateLimits::default().with_get_block_headers(1));
+        assert!(limiter.try_acquire(RateLimitedMessage::GetBlockHeaders));
+        assert!(!limiter.try_acquire(RateLimitedMessage::GetBlockHeaders));
+        assert!(limiter.try_acquire(RateLimitedMessage::GetBlockBodies));
+    }
+}
@@ -180,6 +180,7 @@ where
                 Some(SwarmEvent::OutgoingConnectionError { peer_id, remote_addr, error })
             }
             SessionEvent::BadMessage { peer_id } => Some(SwarmEvent::BadMessage { peer_id }),
+            SessionEvent::RateLimited { peer_id } => Some(SwarmEvent::RateLimited { peer_id }),
             SessionEvent::ProtocolBreach { peer_id } => {
                 Some(SwarmEvent::ProtocolBreach { peer_id })
             }
@@ -362,6 +363,11 @@ pub(crate) enum SwarmEvent {
         /// Identifier of the remote peer.
         peer_id: PeerId,
     },
+    /// Received a request that exceeds the configured rate limits from the peer.
+    RateLimited {
+        /// Identifier of the remote peer.
+        peer_id: PeerId,
+    },
     /// Remote peer is considered in protocol violation
     ProtocolBreach {
         /// Identifier of the remote peer.
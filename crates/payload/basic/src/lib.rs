@@ -24,10 +24,11 @@ use reth_primitives::{
     bytes::{Bytes, BytesMut},
     constants::{
         BEACON_NONCE, EMPTY_RECEIPTS, EMPTY_TRANSACTIONS, EMPTY_WITHDRAWALS,
-        ETHEREUM_BLOCK_GAS_LIMIT, RETH_CLIENT_VERSION, SLOT_DURATION,
+        ETHEREUM_BLOCK_GAS_LIMIT, GAS_LIMIT_BOUND_DIVISOR, MINIMUM_GAS_LIMIT, RETH_CLIENT_VERSION,
+        SLOT_DURATION,
     },
     proofs, Block, BlockNumberOrTag, ChainSpec, Header, IntoRecoveredTransaction, Receipt,
-    SealedBlock, Withdrawal, EMPTY_OMMER_ROOT, H256, U256,
+    SealedBlock, Withdrawal, Withdrawals, EMPTY_OMMER_ROOT, H256, U256,
 };
 use reth_provider::{BlockReaderIdExt, BlockSource, PostState, StateProviderFactory};
 use reth_revm::{
@@ -50,7 +51,7 @@ use std::{
     pin::Pin,
     sync::{atomic::AtomicBool, Arc},
     task::{Context, Poll},
-    time::Duration,
+    time::{Duration, Instant},
 };
 use tokio::{
     sync::{oneshot, Semaphore},
@@ -131,9 +132,17 @@ where
         };
 
         // configure evm env based on parent block
-        let (initialized_cfg, initialized_block_env) =
+        let (initialized_cfg, mut initialized_block_env) =
             attributes.cfg_and_block_env(&self.chain_spec, &parent_block);
 
+        // move the gas limit towards the desired gas limit
+        let gas_limit = calculate_gas_limit(parent_block.gas_limit, self.config.desired_gas_limit);
+        debug!(target: "payload_builder", parent_gas_limit = parent_block.gas_limit, desired_gas_limit = self.config.desired_gas_limit, gas_limit, "Selected gas limit for payload");
+        initialized_block_env.gas_limit = U256::from(gas_limit);
+
+        let until = tokio::time::Instant::now() + self.config.deadline;
+        let deadline = Box::pin(tokio::time::sleep_until(until));
+
         let config = PayloadConfig {
             initialized_block_env,
             initialized_cfg,
@@ -141,11 +150,9 @@ where
             extra_data: self.config.extradata.clone(),
             attributes,
             chain_spec: Arc::clone(&self.chain_spec),
+            build_deadline: BuildDeadline::new(until.into_std()),
         };
 
-        let until = tokio::time::Instant::now() + self.config.deadline;
-        let deadline = Box::pin(tokio::time::sleep_until(until));
-
         Ok(BasicPayloadJob {
             config,
             client: self.client.clone(),
@@ -179,11 +186,14 @@ impl PayloadTaskGuard {
 pub struct BasicPayloadJobGeneratorConfig {
     /// Data to include in the block's extra data field.
     extradata: Bytes,
-    /// Target gas ceiling for built blocks, defaults to [ETHEREUM_BLOCK_GAS_LIMIT] gas.
-    max_gas_limit: u64,
+    /// The gas limit built blocks should move towards, defaults to [ETHEREUM_BLOCK_GAS_LIMIT] gas.
+    desired_gas_limit: u64,
     /// The interval at which the job should build a new payload after the last.
     interval: Duration,
     /// The deadline for when the payload builder job should resolve.
+    ///
+    /// This is also the hard deadline for building: an in progress build attempt stops including
+    /// transactions once it is reached and returns the block assembled so far.
     deadline: Duration,
     /// Maximum number of tasks to spawn for building a payload.
     max_payload_tasks: usize,
@@ -223,11 +233,15 @@ impl BasicPayloadJobGeneratorConfig {
         self
     }
 
-    /// Sets the target gas ceiling for mined blocks.
+    /// Sets the gas limit built blocks should move towards.
+    ///
+    /// The gas limit of each block is adjusted from the parent's gas limit by at most the protocol
+    /// bound, see [calculate_gas_limit].
     ///
-    /// Defaults to [ETHEREUM_BLOCK_GAS_LIMIT] gas.
-    pub fn max_gas_limit(mut self, max_gas_limit: u64) -> Self {
-        self.max_gas_limit = max_gas_limit;
+    /// Defaults to [ETHEREUM_BLOCK_GAS_LIMIT] gas. Values below [MINIMUM_GAS_LIMIT] are raised to
+    /// the minimum.
+    pub fn desired_gas_limit(mut self, desired_gas_limit: u64) -> Self {
+        self.desired_gas_limit = desired_gas_limit.max(MINIMUM_GAS_LIMIT);
         self
     }
 }
@@ -238,7 +252,7 @@ impl Default for BasicPayloadJobGeneratorConfig {
         RETH_CLIENT_VERSION.as_bytes().encode(&mut extradata);
         Self {
             extradata: extradata.freeze(),
-            max_gas_limit: ETHEREUM_BLOCK_GAS_LIMIT,
+            desired_gas_limit: ETHEREUM_BLOCK_GAS_LIMIT,
             interval: Duration::from_secs(1),
             // 12s slot time
             deadline: SLOT_DURATION,
@@ -247,6 +261,21 @@ impl Default for BasicPayloadJobGeneratorConfig {
     }
 }
 
+/// Returns the gas limit of a block that moves from the parent's gas limit towards the desired gas
+/// limit.
+///
+/// The gas limit can change by less than `parent_gas_limit / 1024` per block, and never drops below
+/// [MINIMUM_GAS_LIMIT].
+pub fn calculate_gas_limit(parent_gas_limit: u64, desired_gas_limit: u64) -> u64 {
+    let delta = (parent_gas_limit / GAS_LIMIT_BOUND_DIVISOR).saturating_sub(1);
+    let desired_gas_limit = desired_gas_limit.max(MINIMUM_GAS_LIMIT);
+    if parent_gas_limit < desired_gas_limit {
+        parent_gas_limit.saturating_add(delta).min(desired_gas_limit)
+    } else {
+        parent_gas_limit.saturating_sub(delta).max(desired_gas_limit)
+    }
+}
+
 /// A basic payload job that continuously builds a payload with the best transactions from the pool.
 pub struct BasicPayloadJob<Client, Pool, Tasks> {
     /// The configuration for how the payload will be created.
@@ -306,6 +335,7 @@ where
                 let guard = this.payload_task_guard.clone();
                 let payload_config = this.config.clone();
                 let best_payload = this.best_payload.clone();
+                let metrics = this.metrics.clone();
                 this.metrics.inc_initiated_payload_builds();
                 let cached_reads = this.cached_reads.take().unwrap_or_default();
                 this.executor.spawn_blocking(Box::pin(async move {
@@ -318,6 +348,7 @@ where
                         payload_config,
                         cancel,
                         best_payload,
+                        metrics,
                         tx,
                     )
                 }));
@@ -384,6 +415,10 @@ where
     }
 
     fn resolve(&mut self) -> (Self::ResolvePayloadFuture, KeepPayloadJobAlive) {
+        // the payload is needed now, so the in progress build attempt should finish with what it
+        // has assembled so far instead of including more transactions
+        self.config.build_deadline.fire();
+
         let best_payload = self.best_payload.take();
         let maybe_better = self.pending_block.take();
         let mut empty_payload = None;
@@ -499,6 +534,54 @@ impl Drop for Cancelled {
     }
 }
 
+/// The hard deadline of the build attempts of a job.
+///
+/// Once reached, a build attempt stops at the next transaction boundary and seals the block
+/// assembled so far.
+#[derive(Clone, Debug)]
+struct BuildDeadline {
+    /// The point in time the job's deadline is reached.
+    at: Instant,
+    /// Set if the payload was requested before the deadline was reached.
+    fired: Arc<AtomicBool>,
+}
+
+// === impl BuildDeadline ===
+
+impl BuildDeadline {
+    fn new(at: Instant) -> Self {
+        Self { at, fired: Default::default() }
+    }
+
+    /// Marks the deadline as reached.
+    fn fire(&self) {
+        self.fired.store(true, std::sync::atomic::Ordering::Relaxed);
+    }
+
+    /// Returns true if the deadline was reached.
+    fn is_reached(&self) -> bool {
+        self.fired.load(std::sync::atomic::Ordering::Relaxed) || Instant::now() >= self.at
+    }
+}
+
+/// Returns the next best transaction, or `None` if there are no transactions left or the deadline
+/// is reached.
+///
+/// Building only counts as truncated if the deadline is reached while transactions are left.
+fn next_best_transaction<I: Iterator>(
+    best_txs: &mut I,
+    deadline: &BuildDeadline,
+    metrics: &PayloadBuilderMetrics,
+) -> Option<I::Item> {
+    let tx = best_txs.next()?;
+    if deadline.is_reached() {
+        debug!(target: "payload_builder", "payload building deadline reached, sealing block");
+        metrics.inc_truncated_payload_builds();
+        return None
+    }
+    Some(tx)
+}
+
 /// Static config for how to build a payload.
 #[derive(Clone)]
 struct PayloadConfig {
@@ -514,6 +597,8 @@ struct PayloadConfig {
     attributes: PayloadBuilderAttributes,
     /// The chain spec.
     chain_spec: Arc<ChainSpec>,
+    /// The hard deadline for building the payload.
+    build_deadline: BuildDeadline,
 }
 
 #[derive(Debug)]
@@ -543,6 +628,7 @@ fn build_payload<Pool, Client>(
     config: PayloadConfig,
     cancel: Cancelled,
     best_payload: Option<Arc<BuiltPayload>>,
+    metrics: PayloadBuilderMetrics,
     to_job: oneshot::Sender<Result<BuildOutcome, PayloadBuilderError>>,
 ) where
     Client: StateProviderFactory,
@@ -556,6 +642,7 @@ fn build_payload<Pool, Client>(
         config: PayloadConfig,
         cancel: Cancelled,
         best_payload: Option<Arc<BuiltPayload>>,
+        metrics: PayloadBuilderMetrics,
     ) -> Result<BuildOutcome, PayloadBuilderError>
     where
         Client: StateProviderFactory,
@@ -568,6 +655,7 @@ fn build_payload<Pool, Client>(
             extra_data,
             attributes,
             chain_spec,
+            build_deadline,
         } = config;
 
         debug!(parent_hash=?parent_block.hash, parent_number=parent_block.number, "building new payload");
@@ -587,7 +675,9 @@ fn build_payload<Pool, Client>(
 
         let block_number = initialized_block_env.number.to::<u64>();
 
-        while let Some(pool_tx) = best_txs.next() {
+        // stop including transactions once the deadline is reached and seal the block with the
+        // transactions executed so far
+        while let Some(pool_tx) = next_best_transaction(&mut best_txs, &build_deadline, &metrics) {
             // ensure we still have capacity for this transaction
             if cumulative_gas_used + pool_tx.gas_limit() > block_gas_limit {
                 // we can't fit this transaction into the block, so we need to mark it as invalid
@@ -725,7 +815,8 @@ fn build_payload<Pool, Client>(
             cached_reads,
         })
     }
-    let _ = to_job.send(try_build(client, pool, cached_reads, config, cancel, best_payload));
+    let _ =
+        to_job.send(try_build(client, pool, cached_reads, config, cancel, best_payload, metrics));
 }
 
 /// Builds an empty payload without any transactions.
@@ -843,11 +934,12 @@ where
         increment_account_balance(db, post_state, block_number, address, increment)?;
     }
 
-    let withdrawals_root = proofs::calculate_withdrawals_root(&withdrawals);
-
     // calculate withdrawals root
+    let withdrawals = Withdrawals::new(withdrawals);
+    let withdrawals_root = withdrawals.root();
+
     Ok(WithdrawalsOutcome {
-        withdrawals: Some(withdrawals),
+        withdrawals: Some(withdrawals.into_inner()),
         withdrawals_root: Some(withdrawals_root),
     })
 }
@@ -863,3 +955,52 @@ fn is_better_payload(best_payload: Option<&BuiltPayload>, new_fees: U256) -> boo
         true
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gas_limit_moves_towards_desired() {
+        let parent = 30_000_000;
+        let delta = parent / GAS_LIMIT_BOUND_DIVISOR - 1;
+
+        assert_eq!(calculate_gas_limit(parent, parent), parent);
+        assert_eq!(calculate_gas_limit(parent, parent + 1), parent + 1);
+        assert_eq!(calculate_gas_limit(parent, 36_000_000), parent + delta);
+        assert_eq!(calculate_gas_limit(parent, parent - 1), parent - 1);
+        assert_eq!(calculate_gas_limit(parent, 25_000_000), parent - delta);
+
+        // never below the minimum gas limit
+        assert_eq!(calculate_gas_limit(MINIMUM_GAS_LIMIT + 1, 0), MINIMUM_GAS_LIMIT);
+    }
+
+    #[test]
+    fn transaction_loop_stops_at_deadline() {
+        let deadline = BuildDeadline::new(Instant::now() + Duration::from_millis(100));
+        let metrics = PayloadBuilderMetrics::default();
+
+        let mut best_txs = 0..10;
+        let mut included = Vec::new();
+        while let Some(tx) = next_best_transaction(&mut best_txs, &deadline, &metrics) {
+            included.push(tx);
+            if tx == 2 {
+                std::thread::sleep(Duration::from_millis(100));
+            }
+        }
+        assert_eq!(included, vec![0, 1, 2]);
+        // only the transaction after the deadline was taken
+        assert_eq!(best_txs.next(), Some(4));
+
+        // resolving the payload stops building before the deadline
+        let deadline = BuildDeadline::new(Instant::now() + Duration::from_secs(60));
+        deadline.fire();
+        assert_eq!(next_best_transaction(&mut (0..10), &deadline, &metrics), None);
+    }
+
+    #[test]
+    fn desired_gas_limit_is_clamped() {
+        let config = BasicPayloadJobGeneratorConfig::default().desired_gas_limit(0);
+        assert_eq!(config.desired_gas_limit, MINIMUM_GAS_LIMIT);
+    }
+}
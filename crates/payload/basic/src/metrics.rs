@@ -6,7 +6,7 @@ use reth_metrics::{
 };
 
 /// Transaction pool metrics
-#[derive(Metrics)]
+#[derive(Clone, Metrics)]
 #[metrics(scope = "payloads")]
 pub(crate) struct PayloadBuilderMetrics {
     /// Number of active jobs
@@ -15,6 +15,8 @@ pub(crate) struct PayloadBuilderMetrics {
     pub(crate) initiated_payload_builds: Counter,
     /// Total number of failed payload build attempts
     pub(crate) failed_payload_builds: Counter,
+    /// Total number of payload build attempts that were cut short by the deadline
+    pub(crate) truncated_payload_builds: Counter,
 }
 
 impl PayloadBuilderMetrics {
@@ -29,4 +31,8 @@ impl PayloadBuilderMetrics {
     pub(crate) fn inc_failed_payload_builds(&self) {
         self.failed_payload_builds.increment(1);
     }
+
+    pub(crate) fn inc_truncated_payload_builds(&self) {
+        self.truncated_payload_builds.increment(1);
+    }
 }
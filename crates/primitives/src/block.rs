@@ -9,7 +9,14 @@ use serde::{
     ser::SerializeStruct,
     Deserialize, Deserializer, Serialize, Serializer,
 };
-use std::{fmt, fmt::Formatter, num::ParseIntError, ops::Deref, str::FromStr};
+use std::{
+    fmt,
+    fmt::Formatter,
+    io::{self, Read},
+    num::ParseIntError,
+    ops::Deref,
+    str::FromStr,
+};
 
 /// Ethereum full block.
 ///
@@ -688,7 +695,7 @@ impl AsRef<H256> for RpcBlockHash {
 }
 
 /// Block number and hash.
-#[derive(Clone, Copy, Hash, Default, PartialEq, Eq)]
+#[derive(Clone, Copy, Hash, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct BlockNumHash {
     /// Block number
     pub number: BlockNumber,
@@ -774,6 +781,112 @@ impl BlockBody {
             withdrawals: self.withdrawals.clone(),
         }
     }
+
+    /// Returns an iterator that decodes consecutive RLP encoded block bodies from the reader.
+    ///
+    /// Only one body is buffered at a time, so arbitrarily large inputs can be processed with
+    /// bounded memory. The iterator ends once the input ends at a body boundary. If the input ends
+    /// within a body, [BlockBodyStreamError::Truncated] is returned. The iterator ends after the
+    /// first error.
+    pub fn decode_stream<R: Read>(reader: R) -> BlockBodyStream<R> {
+        BlockBodyStream { reader, offset: 0, done: false }
+    }
+}
+
+/// An iterator over the RLP encoded block bodies of a reader, see [BlockBody::decode_stream].
+#[derive(Debug)]
+pub struct BlockBodyStream<R> {
+    reader: R,
+    /// The byte offset of the next body.
+    offset: u64,
+    /// Whether the end of the input or an error was reached.
+    done: bool,
+}
+
+impl<R: Read> BlockBodyStream<R> {
+    /// Reads exactly `len` bytes into the buffer, or returns a truncation error.
+    fn read_exact(&mut self, buf: &mut Vec<u8>, len: usize) -> Result<(), BlockBodyStreamError> {
+        let read = (&mut self.reader).take(len as u64).read_to_end(buf)?;
+        if read < len {
+            return Err(BlockBodyStreamError::Truncated { offset: self.offset, missing: len - read })
+        }
+        Ok(())
+    }
+
+    /// Reads and decodes the next body, returns `None` if the input ends before it.
+    fn next_body(&mut self) -> Result<Option<BlockBody>, BlockBodyStreamError> {
+        let mut first = [0u8; 1];
+        loop {
+            match self.reader.read(&mut first) {
+                Ok(0) => return Ok(None),
+                Ok(_) => break,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err.into()),
+            }
+        }
+
+        let mut buf = vec![first[0]];
+        let payload_length = match first[0] {
+            0xc0..=0xf7 => (first[0] - 0xc0) as usize,
+            0xf8..=0xff => {
+                let len_of_len = (first[0] - 0xf7) as usize;
+                self.read_exact(&mut buf, len_of_len)?;
+                let mut len = [0u8; 8];
+                len[8 - len_of_len..].copy_from_slice(&buf[1..]);
+                usize::try_from(u64::from_be_bytes(len))
+                    .map_err(|_| self.decode_error(DecodeError::Overflow))?
+            }
+            _ => return Err(self.decode_error(DecodeError::UnexpectedString)),
+        };
+        self.read_exact(&mut buf, payload_length)?;
+
+        let body = BlockBody::decode(&mut buf.as_slice()).map_err(|err| self.decode_error(err))?;
+        self.offset += buf.len() as u64;
+        Ok(Some(body))
+    }
+
+    fn decode_error(&self, error: DecodeError) -> BlockBodyStreamError {
+        BlockBodyStreamError::Decode { offset: self.offset, error }
+    }
+}
+
+impl<R: Read> Iterator for BlockBodyStream<R> {
+    type Item = Result<BlockBody, BlockBodyStreamError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None
+        }
+        let next = self.next_body().transpose();
+        if !matches!(next, Some(Ok(_))) {
+            self.done = true;
+        }
+        next
+    }
+}
+
+/// An error returned by [BlockBodyStream].
+#[derive(Debug, thiserror::Error)]
+pub enum BlockBodyStreamError {
+    /// Reading from the input failed.
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    /// The body at the given offset is not a valid RLP encoded block body.
+    #[error("invalid block body at byte {offset}: {error}")]
+    Decode {
+        /// The byte offset of the body in the input.
+        offset: u64,
+        /// The decoding error.
+        error: DecodeError,
+    },
+    /// The input ended within the body at the given offset.
+    #[error("input ended within the block body at byte {offset}, {missing} bytes are missing")]
+    Truncated {
+        /// The byte offset of the body in the input.
+        offset: u64,
+        /// The number of bytes that are missing to complete the body header or payload.
+        missing: usize,
+    },
 }
 
 #[cfg(test)]
@@ -932,4 +1045,36 @@ mod test {
         let err = serde_json::from_str::<BlockNumberOrTag>(s).unwrap_err();
         assert_eq!(err.to_string(), HexStringMissingPrefixError::default().to_string());
     }
+
+    #[test]
+    fn decode_block_body_stream() {
+        let bodies = vec![
+            BlockBody::default(),
+            BlockBody {
+                ommers: vec![Header { number: 1, ..Default::default() }],
+                withdrawals: Some(vec![Withdrawal { index: 1, ..Default::default() }]),
+                ..Default::default()
+            },
+            BlockBody { withdrawals: Some(vec![]), ..Default::default() },
+        ];
+        let mut encoded = Vec::new();
+        for body in &bodies {
+            body.encode(&mut encoded);
+        }
+
+        let decoded = BlockBody::decode_stream(encoded.as_slice()).collect::<Result<Vec<_>, _>>();
+        assert_eq!(decoded.unwrap(), bodies);
+
+        // the last body is cut off
+        let truncated = &encoded[..encoded.len() - 1];
+        let mut stream = BlockBody::decode_stream(truncated);
+        assert_eq!(stream.next().unwrap().unwrap(), bodies[0]);
+        assert_eq!(stream.next().unwrap().unwrap(), bodies[1]);
+        let offset = (bodies[0].length() + bodies[1].length()) as u64;
+        assert_matches::assert_matches!(
+            stream.next(),
+            Some(Err(BlockBodyStreamError::Truncated { offset: got, missing: 1 })) if got == offset
+        );
+        assert!(stream.next().is_none());
+    }
 }
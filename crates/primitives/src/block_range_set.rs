@@ -0,0 +1,227 @@
+use crate::BlockNumber;
+use std::{
+    collections::BTreeMap,
+    ops::{Bound, RangeInclusive},
+};
+
+/// A set of block numbers, stored as disjoint ranges.
+///
+/// Overlapping and adjacent ranges are merged on insertion, e.g. inserting `1..=5` and `6..=9`
+/// results in the single range `1..=9`. This makes it cheap to track which blocks are available,
+/// and to find the [gaps](BlockRangeSet::gaps) that are missing.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BlockRangeSet {
+    /// The inclusive end of every range, keyed by its start.
+    ranges: BTreeMap<BlockNumber, BlockNumber>,
+}
+
+impl BlockRangeSet {
+    /// Creates an empty set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns `true` if the set contains no blocks.
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Returns the number of disjoint ranges in the set.
+    pub fn num_ranges(&self) -> usize {
+        self.ranges.len()
+    }
+
+    /// Returns the lowest block number in the set.
+    pub fn first(&self) -> Option<BlockNumber> {
+        self.ranges.keys().next().copied()
+    }
+
+    /// Returns the highest block number in the set.
+    pub fn last(&self) -> Option<BlockNumber> {
+        self.ranges.values().next_back().copied()
+    }
+
+    /// Returns an iterator over the disjoint ranges of the set, in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = RangeInclusive<BlockNumber>> + '_ {
+        self.ranges.iter().map(|(start, end)| *start..=*end)
+    }
+
+    /// Inserts a single block number.
+    pub fn insert_block(&mut self, block: BlockNumber) {
+        self.insert(block..=block)
+    }
+
+    /// Inserts all block numbers of the range, merging it with overlapping and adjacent ranges.
+    ///
+    /// Empty ranges are ignored.
+    pub fn insert(&mut self, range: RangeInclusive<BlockNumber>) {
+        let (mut start, mut end) = range.into_inner();
+        if start > end {
+            return
+        }
+
+        // merge with the range that starts before and overlaps or touches the new range
+        if let Some((&prev_start, &prev_end)) = self.ranges.range(..=start).next_back() {
+            if prev_end.saturating_add(1) >= start {
+                start = prev_start;
+                end = end.max(prev_end);
+            }
+        }
+
+        // merge with all ranges that start within or right after the new range
+        let merged = self
+            .ranges
+            .range(start..=end.saturating_add(1))
+            .map(|(start, end)| (*start, *end))
+            .collect::<Vec<_>>();
+        for (merged_start, merged_end) in merged {
+            self.ranges.remove(&merged_start);
+            end = end.max(merged_end);
+        }
+
+        self.ranges.insert(start, end);
+    }
+
+    /// Inserts all ranges of the other set.
+    pub fn extend(&mut self, other: &BlockRangeSet) {
+        for range in other.iter() {
+            self.insert(range);
+        }
+    }
+
+    /// Returns `true` if the set contains the block number.
+    pub fn contains(&self, block: BlockNumber) -> bool {
+        self.ranges.range(..=block).next_back().map_or(false, |(_, end)| block <= *end)
+    }
+
+    /// Returns `true` if the set contains all block numbers of the range.
+    ///
+    /// Empty ranges are always contained.
+    pub fn contains_range(&self, range: RangeInclusive<BlockNumber>) -> bool {
+        let (start, end) = range.into_inner();
+        start > end ||
+            self.ranges
+                .range(..=start)
+                .next_back()
+                .map_or(false, |(_, range_end)| end <= *range_end)
+    }
+
+    /// Returns an iterator over the ranges of the given bounds that are not in the set, in
+    /// ascending order.
+    pub fn gaps(
+        &self,
+        bounds: RangeInclusive<BlockNumber>,
+    ) -> impl Iterator<Item = RangeInclusive<BlockNumber>> + '_ {
+        let (bounds_start, bounds_end) = bounds.into_inner();
+        let mut next = (bounds_start <= bounds_end).then_some(bounds_start);
+
+        // the range that contains the start of the bounds can begin before it
+        let ranges = self
+            .ranges
+            .range(..=bounds_start)
+            .next_back()
+            .into_iter()
+            .chain(self.ranges.range((
+                Bound::Excluded(bounds_start),
+                Bound::Included(bounds_end.max(bounds_start)),
+            )))
+            .map(|(start, end)| (*start, *end));
+
+        ranges.map(Some).chain(std::iter::once(None)).filter_map(move |range| {
+            let from = next?;
+            match range {
+                Some((start, end)) => {
+                    if end < from {
+                        return None
+                    }
+                    next = end.checked_add(1).filter(|next| *next <= bounds_end);
+                    (start > from).then(|| from..=start - 1)
+                }
+                None => {
+                    next = None;
+                    Some(from..=bounds_end)
+                }
+            }
+        })
+    }
+}
+
+impl FromIterator<RangeInclusive<BlockNumber>> for BlockRangeSet {
+    fn from_iter<T: IntoIterator<Item = RangeInclusive<BlockNumber>>>(iter: T) -> Self {
+        let mut set = Self::new();
+        for range in iter {
+            set.insert(range);
+        }
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn insert_merges_adjacent_and_overlapping() {
+        let mut set = BlockRangeSet::new();
+        set.insert(1..=5);
+        set.insert(6..=9);
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![1..=9]);
+
+        set.insert(20..=30);
+        set.insert(12..=14);
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![1..=9, 12..=14, 20..=30]);
+
+        // bridges the two last ranges
+        set.insert(13..=25);
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![1..=9, 12..=30]);
+
+        // contained ranges and empty ranges don't change the set
+        set.insert(2..=3);
+        #[allow(clippy::reversed_empty_ranges)]
+        set.insert(5..=4);
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![1..=9, 12..=30]);
+
+        set.insert_block(10);
+        set.insert_block(11);
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![1..=30]);
+        assert_eq!(set.first(), Some(1));
+        assert_eq!(set.last(), Some(30));
+    }
+
+    #[test]
+    fn contains() {
+        let set = BlockRangeSet::from_iter([1..=5, 10..=15]);
+        assert!(!set.contains(0));
+        assert!(set.contains(1));
+        assert!(set.contains(5));
+        assert!(!set.contains(6));
+        assert!(set.contains(12));
+        assert!(!set.contains(16));
+
+        assert!(set.contains_range(10..=15));
+        assert!(!set.contains_range(4..=10));
+        assert!(!set.contains_range(14..=16));
+    }
+
+    #[test]
+    fn gaps() {
+        let set = BlockRangeSet::from_iter([5..=10, 15..=20]);
+        assert_eq!(set.gaps(0..=25).collect::<Vec<_>>(), vec![0..=4, 11..=14, 21..=25]);
+        assert_eq!(set.gaps(7..=17).collect::<Vec<_>>(), vec![11..=14]);
+        assert_eq!(set.gaps(5..=20).collect::<Vec<_>>(), vec![11..=14]);
+        assert_eq!(set.gaps(6..=9).count(), 0);
+        assert_eq!(set.gaps(21..=21).collect::<Vec<_>>(), vec![21..=21]);
+        assert_eq!(BlockRangeSet::new().gaps(0..=3).collect::<Vec<_>>(), vec![0..=3]);
+
+        let set = BlockRangeSet::from_iter([0..=u64::MAX]);
+        assert_eq!(set.gaps(0..=u64::MAX).count(), 0);
+    }
+
+    #[test]
+    fn extend() {
+        let mut set = BlockRangeSet::from_iter([1..=3]);
+        set.extend(&BlockRangeSet::from_iter([4..=6, 10..=12]));
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![1..=6, 10..=12]);
+        assert_eq!(set.num_ranges(), 2);
+    }
+}
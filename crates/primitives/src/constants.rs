@@ -34,6 +34,13 @@ pub const BEACON_NONCE: u64 = 0u64;
 /// See <https://github.com/paradigmxyz/reth/issues/3233>.
 pub const ETHEREUM_BLOCK_GAS_LIMIT: u64 = 30_000_000;
 
+/// The minimum gas limit of a block.
+pub const MINIMUM_GAS_LIMIT: u64 = 5000;
+
+/// The bound divisor of the gas limit, the gas limit of a block may differ from its parent by
+/// less than `parent_gas_limit / GAS_LIMIT_BOUND_DIVISOR`.
+pub const GAS_LIMIT_BOUND_DIVISOR: u64 = 1024;
+
 /// The minimal value the basefee can decrease to.
 ///
 /// The `BASE_FEE_MAX_CHANGE_DENOMINATOR` <https://eips.ethereum.org/EIPS/eip-1559> is `8`, or 12.5%.
@@ -108,6 +115,13 @@ pub const EMPTY_WITHDRAWALS: H256 = EMPTY_SET_HASH;
 /// the database.
 pub const BEACON_CONSENSUS_REORG_UNWIND_DEPTH: u64 = 3;
 
+/// The minimum number of most recent blocks that must be kept when pruning with
+/// [PruneMode::Distance](crate::PruneMode::Distance).
+///
+/// Matches the maximum reorg depth of the blockchain tree, so that the data required to unwind a
+/// reorg is never pruned.
+pub const MINIMUM_PRUNING_DISTANCE: u64 = 64;
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,8 +1,10 @@
 use crate::{
     basefee::calculate_next_block_base_fee,
+    constants::{EIP1559_ELASTICITY_MULTIPLIER, EIP1559_INITIAL_BASE_FEE, GAS_LIMIT_BOUND_DIVISOR},
     keccak256,
-    proofs::{EMPTY_LIST_HASH, EMPTY_ROOT},
-    BlockHash, BlockNumHash, BlockNumber, Bloom, Bytes, H160, H256, H64, U256,
+    proofs::{calculate_withdrawals_root, EMPTY_LIST_HASH, EMPTY_ROOT},
+    BlockHash, BlockNumHash, BlockNumber, Bloom, Bytes, ChainSpec, Hardfork, Withdrawal,
+    WithdrawalsValidationError, H160, H256, H64, U256,
 };
 use bytes::{Buf, BufMut, BytesMut};
 
@@ -151,6 +153,31 @@ impl Header {
         self.transactions_root == EMPTY_ROOT
     }
 
+    /// Validates the withdrawals root of the header against the withdrawals of the block body.
+    ///
+    /// If the header has a withdrawals root, the body must contain the withdrawals it commits to,
+    /// an empty body committing to the empty root. Otherwise the body must not contain any
+    /// withdrawals.
+    pub fn validate_withdrawals_root(
+        &self,
+        withdrawals: Option<&[Withdrawal]>,
+    ) -> Result<(), WithdrawalsValidationError> {
+        match (self.withdrawals_root, withdrawals) {
+            (Some(expected), Some(withdrawals)) => {
+                let got = calculate_withdrawals_root(withdrawals);
+                if got != expected {
+                    return Err(WithdrawalsValidationError::WithdrawalsRootDiff { got, expected })
+                }
+                Ok(())
+            }
+            (Some(_), None) => Err(WithdrawalsValidationError::BodyWithdrawalsMissing),
+            (None, Some(withdrawals)) if !withdrawals.is_empty() => {
+                Err(WithdrawalsValidationError::WithdrawalsRootUnexpected)
+            }
+            (None, _) => Ok(()),
+        }
+    }
+
     /// Calculate base fee for next block according to the EIP-1559 spec.
     ///
     /// Returns a `None` if no base fee is set, no EIP-1559 support
@@ -322,6 +349,122 @@ impl SealedHeader {
     pub fn num_hash(&self) -> BlockNumHash {
         BlockNumHash::new(self.number, self.hash)
     }
+
+    /// Validates the header against its parent.
+    ///
+    /// Checks that:
+    ///  - the number and parent hash link to the parent
+    ///  - the timestamp is greater than the parent timestamp
+    ///  - the gas limit is within the bounds of the parent gas limit
+    ///  - the base fee is the expected base fee, once London is active
+    ///  - the difficulty is zero, if the parent is a post-merge block
+    pub fn validate_against_parent(
+        &self,
+        parent: &SealedHeader,
+        chain_spec: &ChainSpec,
+    ) -> Result<(), HeaderValidationError> {
+        // Parent number is consistent.
+        if parent.number + 1 != self.number {
+            return Err(HeaderValidationError::ParentBlockNumberMismatch {
+                parent_block_number: parent.number,
+                block_number: self.number,
+            })
+        }
+
+        if parent.hash != self.parent_hash {
+            return Err(HeaderValidationError::ParentHashMismatch {
+                expected_parent_hash: parent.hash,
+                got_parent_hash: self.parent_hash,
+            })
+        }
+
+        // timestamp in past check
+        if self.timestamp <= parent.timestamp {
+            return Err(HeaderValidationError::TimestampIsInPast {
+                parent_timestamp: parent.timestamp,
+                timestamp: self.timestamp,
+            })
+        }
+
+        // once the chain is merged, it stays merged
+        if parent.difficulty.is_zero() && !self.difficulty.is_zero() {
+            return Err(HeaderValidationError::TheMergeDifficultyIsNotZero)
+        }
+
+        let mut parent_gas_limit = parent.gas_limit;
+
+        // By consensus, gas_limit is multiplied by elasticity (*2) on
+        // on exact block that hardfork happens.
+        if chain_spec.fork(Hardfork::London).transitions_at_block(self.number) {
+            parent_gas_limit = parent.gas_limit * EIP1559_ELASTICITY_MULTIPLIER;
+        }
+
+        // Check gas limit, max diff between child/parent gas_limit should be
+        // max_diff=parent_gas/1024
+        if self.gas_limit > parent_gas_limit {
+            if self.gas_limit - parent_gas_limit >= parent_gas_limit / GAS_LIMIT_BOUND_DIVISOR {
+                return Err(HeaderValidationError::GasLimitInvalidIncrease {
+                    parent_gas_limit,
+                    child_gas_limit: self.gas_limit,
+                })
+            }
+        } else if parent_gas_limit - self.gas_limit >= parent_gas_limit / GAS_LIMIT_BOUND_DIVISOR {
+            return Err(HeaderValidationError::GasLimitInvalidDecrease {
+                parent_gas_limit,
+                child_gas_limit: self.gas_limit,
+            })
+        }
+
+        // EIP-1559 check base fee
+        if chain_spec.fork(Hardfork::London).active_at_block(self.number) {
+            let base_fee = self.base_fee_per_gas.ok_or(HeaderValidationError::BaseFeeMissing)?;
+
+            let expected_base_fee =
+                if chain_spec.fork(Hardfork::London).transitions_at_block(self.number) {
+                    EIP1559_INITIAL_BASE_FEE
+                } else {
+                    // This BaseFeeMissing will not happen as previous blocks are checked to have
+                    // them.
+                    parent.next_block_base_fee().ok_or(HeaderValidationError::BaseFeeMissing)?
+                };
+            if expected_base_fee != base_fee {
+                return Err(HeaderValidationError::BaseFeeDiff {
+                    expected: expected_base_fee,
+                    got: base_fee,
+                })
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// An error returned by [SealedHeader::validate_against_parent].
+#[allow(missing_docs)]
+#[derive(thiserror::Error, Debug, PartialEq, Eq, Clone)]
+pub enum HeaderValidationError {
+    #[error(
+        "Block number {block_number} does not match parent block number {parent_block_number}"
+    )]
+    ParentBlockNumberMismatch { parent_block_number: BlockNumber, block_number: BlockNumber },
+    #[error(
+        "Parent hash {got_parent_hash:?} does not match the expected {expected_parent_hash:?}"
+    )]
+    ParentHashMismatch { expected_parent_hash: H256, got_parent_hash: H256 },
+    #[error(
+        "Block timestamp {timestamp} is in the past compared to the parent timestamp {parent_timestamp}."
+    )]
+    TimestampIsInPast { parent_timestamp: u64, timestamp: u64 },
+    #[error("Child gas_limit {child_gas_limit} max increase is {parent_gas_limit}/1024.")]
+    GasLimitInvalidIncrease { parent_gas_limit: u64, child_gas_limit: u64 },
+    #[error("Child gas_limit {child_gas_limit} max decrease is {parent_gas_limit}/1024.")]
+    GasLimitInvalidDecrease { parent_gas_limit: u64, child_gas_limit: u64 },
+    #[error("Base fee missing.")]
+    BaseFeeMissing,
+    #[error("Block base fee ({got}) is different than expected: ({expected}).")]
+    BaseFeeDiff { expected: u64, got: u64 },
+    #[error("Difficulty after merge is not zero")]
+    TheMergeDifficultyIsNotZero,
 }
 
 #[cfg(any(test, feature = "arbitrary"))]
@@ -513,7 +656,9 @@ mod ethers_compat {
 
 #[cfg(test)]
 mod tests {
-    use super::{Bytes, Decodable, Encodable, Header, H256};
+    use super::{
+        Bytes, Decodable, Encodable, Header, HeaderValidationError, GAS_LIMIT_BOUND_DIVISOR, H256,
+    };
     use crate::{Address, HeadersDirection, U256};
     use ethers_core::utils::hex::{self, FromHex};
     use std::str::FromStr;
@@ -663,4 +808,60 @@ mod tests {
         direction.encode(&mut buf);
         assert_eq!(direction, HeadersDirection::decode(&mut buf.as_slice()).unwrap());
     }
+
+    #[test]
+    fn validate_header_against_parent() {
+        let parent = Header {
+            number: 17_000_000,
+            timestamp: 1_000,
+            gas_limit: 30_000_000,
+            gas_used: 15_000_000,
+            base_fee_per_gas: Some(10_000_000_000),
+            ..Default::default()
+        }
+        .seal_slow();
+        let child = Header {
+            parent_hash: parent.hash,
+            number: parent.number + 1,
+            timestamp: parent.timestamp + 12,
+            gas_limit: parent.gas_limit,
+            base_fee_per_gas: parent.next_block_base_fee(),
+            ..Default::default()
+        };
+        let validate = |header: &Header| {
+            header.clone().seal_slow().validate_against_parent(&parent, &crate::MAINNET)
+        };
+        assert_eq!(validate(&child), Ok(()));
+
+        let header = Header { timestamp: parent.timestamp, ..child.clone() };
+        assert_eq!(
+            validate(&header),
+            Err(HeaderValidationError::TimestampIsInPast {
+                parent_timestamp: parent.timestamp,
+                timestamp: parent.timestamp
+            })
+        );
+
+        let header = Header { difficulty: U256::from(1), ..child.clone() };
+        assert_eq!(validate(&header), Err(HeaderValidationError::TheMergeDifficultyIsNotZero));
+
+        let gas_limit = parent.gas_limit + parent.gas_limit / GAS_LIMIT_BOUND_DIVISOR;
+        let header = Header { gas_limit, ..child.clone() };
+        assert_eq!(
+            validate(&header),
+            Err(HeaderValidationError::GasLimitInvalidIncrease {
+                parent_gas_limit: parent.gas_limit,
+                child_gas_limit: gas_limit
+            })
+        );
+
+        let header = Header { base_fee_per_gas: Some(1), ..child };
+        assert_eq!(
+            validate(&header),
+            Err(HeaderValidationError::BaseFeeDiff {
+                expected: parent.next_block_base_fee().unwrap(),
+                got: 1
+            })
+        );
+    }
 }
@@ -24,6 +24,7 @@ mod account;
 pub mod basefee;
 mod bits;
 mod block;
+mod block_range_set;
 pub mod bloom;
 mod chain;
 mod compression;
@@ -54,9 +55,11 @@ pub mod proofs;
 pub use account::{Account, Bytecode};
 pub use bits::H512;
 pub use block::{
-    Block, BlockBody, BlockHashOrNumber, BlockId, BlockNumHash, BlockNumberOrTag, BlockWithSenders,
-    ForkBlock, SealedBlock, SealedBlockWithSenders,
+    Block, BlockBody, BlockBodyStream, BlockBodyStreamError, BlockHashOrNumber, BlockId,
+    BlockNumHash, BlockNumberOrTag, BlockWithSenders, ForkBlock, SealedBlock,
+    SealedBlockWithSenders,
 };
+pub use block_range_set::BlockRangeSet;
 pub use bloom::Bloom;
 pub use chain::{
     AllGenesisFormats, Chain, ChainInfo, ChainSpec, ChainSpecBuilder, DisplayHardforks,
@@ -69,7 +72,7 @@ pub use constants::{
 pub use forkid::{ForkFilter, ForkHash, ForkId, ForkTransition, ValidationError};
 pub use genesis::{Genesis, GenesisAccount};
 pub use hardfork::Hardfork;
-pub use header::{Head, Header, HeadersDirection, SealedHeader};
+pub use header::{Head, Header, HeaderValidationError, HeadersDirection, SealedHeader};
 pub use hex_bytes::Bytes;
 pub use integer_list::IntegerList;
 pub use log::Log;
@@ -78,8 +81,8 @@ pub use net::{
     SEPOLIA_BOOTNODES,
 };
 pub use peer::{PeerId, WithPeerId};
-pub use prune::{PruneCheckpoint, PruneMode};
-pub use receipt::{Receipt, ReceiptWithBloom, ReceiptWithBloomRef};
+pub use prune::{PruneCheckpoint, PruneMode, PruneSegment};
+pub use receipt::{Receipt, ReceiptWithBloom, ReceiptWithBloomRef, ReceiptWithMeta};
 pub use revm_primitives::JumpMap;
 pub use serde_helper::JsonU256;
 pub use storage::StorageEntry;
@@ -91,7 +94,7 @@ pub use transaction::{
     TxEip1559, TxEip2930, TxLegacy, TxType, EIP1559_TX_TYPE_ID, EIP2930_TX_TYPE_ID,
     LEGACY_TX_TYPE_ID,
 };
-pub use withdrawal::Withdrawal;
+pub use withdrawal::{Withdrawal, Withdrawals, WithdrawalsValidationError};
 
 /// A block hash.
 pub type BlockHash = H256;
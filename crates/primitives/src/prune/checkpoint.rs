@@ -1,4 +1,4 @@
-use crate::{prune::PruneMode, BlockNumber};
+use crate::{prune::PruneMode, BlockNumber, TxNumber};
 use reth_codecs::{main_codec, Compact};
 
 /// Saves the pruning progress of a stage.
@@ -7,7 +7,9 @@ use reth_codecs::{main_codec, Compact};
 #[cfg_attr(test, derive(Default))]
 pub struct PruneCheckpoint {
     /// Highest pruned block number.
-    block_number: BlockNumber,
+    pub block_number: BlockNumber,
+    /// Highest pruned transaction number, if applicable to the segment.
+    pub tx_number: Option<TxNumber>,
     /// Prune mode.
-    prune_mode: PruneMode,
+    pub prune_mode: PruneMode,
 }
@@ -1,5 +1,7 @@
 mod checkpoint;
 mod mode;
+mod segment;
 
 pub use checkpoint::PruneCheckpoint;
 pub use mode::PruneMode;
+pub use segment::PruneSegment;
@@ -0,0 +1,35 @@
+use reth_codecs::{main_codec, Compact};
+
+/// Segment of the data that can be pruned.
+#[main_codec]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum PruneSegment {
+    /// Prune segment responsible for the `TxSenders` table.
+    SenderRecovery,
+    /// Prune segment responsible for the `TxHashNumber` table.
+    TransactionLookup,
+    /// Prune segment responsible for the `Receipts` table.
+    Receipts,
+    /// Prune segment responsible for the `AccountChangeSet` and `AccountHistory` tables.
+    AccountHistory,
+    /// Prune segment responsible for the `StorageChangeSet` and `StorageHistory` tables.
+    StorageHistory,
+}
+
+impl PruneSegment {
+    /// All prune segments.
+    pub const ALL: [PruneSegment; 5] = [
+        PruneSegment::SenderRecovery,
+        PruneSegment::TransactionLookup,
+        PruneSegment::Receipts,
+        PruneSegment::AccountHistory,
+        PruneSegment::StorageHistory,
+    ];
+}
+
+impl std::fmt::Display for PruneSegment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
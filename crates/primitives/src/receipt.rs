@@ -1,7 +1,7 @@
 use crate::{
     bloom::logs_bloom,
     compression::{RECEIPT_COMPRESSOR, RECEIPT_DECOMPRESSOR},
-    Bloom, Log, TxType,
+    BlockNumHash, Bloom, Log, TransactionMeta, TxHash, TxType,
 };
 use bytes::{Buf, BufMut, BytesMut};
 use reth_codecs::{main_codec, Compact, CompactZstd};
@@ -159,6 +159,60 @@ impl Decodable for ReceiptWithBloom {
     }
 }
 
+/// [`Receipt`] with the position of its transaction and logs in the block.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReceiptWithMeta {
+    /// The receipt.
+    pub receipt: Receipt,
+    /// The position of the receipt's transaction in the block.
+    pub meta: TransactionMeta,
+    /// The index of the receipt's first log in the block.
+    ///
+    /// Log indices are counted across all receipts of a block, so this is the total number of
+    /// logs of all previous receipts.
+    pub first_log_index: u64,
+}
+
+impl ReceiptWithMeta {
+    /// Attaches the block position to all receipts of a block.
+    ///
+    /// The receipts must be given in the order of the block's transactions, paired with the hash
+    /// of their transaction.
+    pub fn from_block_receipts(
+        block: BlockNumHash,
+        base_fee: Option<u64>,
+        receipts: impl IntoIterator<Item = (TxHash, Receipt)>,
+    ) -> Vec<Self> {
+        let mut next_log_index = 0;
+        receipts
+            .into_iter()
+            .enumerate()
+            .map(|(index, (tx_hash, receipt))| {
+                let first_log_index = next_log_index;
+                next_log_index += receipt.logs.len() as u64;
+                let meta = TransactionMeta {
+                    tx_hash,
+                    index: index as u64,
+                    block_hash: block.hash,
+                    block_number: block.number,
+                    base_fee,
+                };
+                Self { receipt, meta, first_log_index }
+            })
+            .collect()
+    }
+
+    /// Returns the logs of the receipt together with their index in the block.
+    pub fn logs_with_index(&self) -> impl Iterator<Item = (u64, &Log)> + '_ {
+        let first_log_index = self.first_log_index;
+        self.receipt
+            .logs
+            .iter()
+            .enumerate()
+            .map(move |(idx, log)| (first_log_index + idx as u64, log))
+    }
+}
+
 /// [`Receipt`] reference type with calculated bloom filter.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct ReceiptWithBloomRef<'a> {
@@ -390,4 +444,30 @@ mod tests {
         let (decoded, _) = Receipt::from_compact(&data[..], data.len());
         assert_eq!(decoded, receipt);
     }
+
+    #[test]
+    fn receipts_with_meta_log_indices() {
+        let log = Log::default();
+        let receipt = |logs: usize| Receipt { logs: vec![log.clone(); logs], ..Default::default() };
+        let block = BlockNumHash::new(1, H256::random());
+        let receipts = ReceiptWithMeta::from_block_receipts(
+            block,
+            Some(7),
+            [
+                (H256::random(), receipt(2)),
+                (H256::random(), receipt(0)),
+                (H256::random(), receipt(3)),
+            ],
+        );
+
+        assert_eq!(receipts.iter().map(|r| r.first_log_index).collect::<Vec<_>>(), vec![0, 2, 2]);
+        assert_eq!(receipts.iter().map(|r| r.meta.index).collect::<Vec<_>>(), vec![0, 1, 2]);
+        assert!(receipts
+            .iter()
+            .all(|r| r.meta.block_hash == block.hash && r.meta.base_fee == Some(7)));
+        assert_eq!(
+            receipts[2].logs_with_index().map(|(idx, _)| idx).collect::<Vec<_>>(),
+            vec![2, 3, 4]
+        );
+    }
 }
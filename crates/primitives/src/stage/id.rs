@@ -67,6 +67,31 @@ impl StageId {
     pub fn is_finish(&self) -> bool {
         matches!(self, StageId::Finish)
     }
+
+    /// Returns the stages this stage reads the data of.
+    ///
+    /// The checkpoint of a stage must never be ahead of the checkpoints of its dependencies.
+    /// Custom stages ([StageId::Other]) have no known dependencies.
+    pub fn dependencies(&self) -> &'static [StageId] {
+        match self {
+            StageId::Headers | StageId::Other(_) => &[],
+            StageId::TotalDifficulty | StageId::Bodies => &[StageId::Headers],
+            StageId::SenderRecovery | StageId::TransactionLookup => &[StageId::Bodies],
+            StageId::Execution => &[StageId::TotalDifficulty, StageId::SenderRecovery],
+            StageId::MerkleUnwind |
+            StageId::AccountHashing |
+            StageId::StorageHashing |
+            StageId::IndexStorageHistory |
+            StageId::IndexAccountHistory => &[StageId::Execution],
+            StageId::MerkleExecute => &[StageId::AccountHashing, StageId::StorageHashing],
+            StageId::Finish => &[
+                StageId::MerkleExecute,
+                StageId::TransactionLookup,
+                StageId::IndexStorageHistory,
+                StageId::IndexAccountHistory,
+            ],
+        }
+    }
 }
 
 impl std::fmt::Display for StageId {
@@ -105,4 +130,16 @@ mod tests {
 
         assert!(!StageId::Execution.is_downloading_stage());
     }
+
+    #[test]
+    fn dependencies_precede_stage() {
+        for (idx, stage) in StageId::ALL.iter().enumerate() {
+            for dependency in stage.dependencies() {
+                assert!(
+                    StageId::ALL[..idx].contains(dependency),
+                    "{stage} depends on {dependency}"
+                );
+            }
+        }
+    }
 }
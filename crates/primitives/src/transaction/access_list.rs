@@ -58,4 +58,7 @@ pub struct AccessListWithGasUsed {
     pub access_list: AccessList,
     /// Estimated gas used with access list.
     pub gas_used: U256,
+    /// The error message if the transaction reverted or halted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
 }
@@ -980,6 +980,19 @@ impl TransactionSigned {
     /// Output the length of the encode_inner(out, true). Note to assume that `with_header` is only
     /// `true`.
     pub(crate) fn payload_len_inner(&self) -> usize {
+        let len = self.encoded_2718_len();
+        match self.transaction {
+            Transaction::Legacy(_) => len,
+            // 'string header length' + 'enveloped length'
+            _ => length_of_length(len) + len,
+        }
+    }
+
+    /// Returns the length of the EIP-2718 encoding of the transaction, see
+    /// [TransactionSigned::encode_enveloped].
+    ///
+    /// This is computed from the fields without encoding the transaction.
+    pub fn encoded_2718_len(&self) -> usize {
         match self.transaction {
             Transaction::Legacy(TxLegacy { chain_id, .. }) => {
                 let payload_length = self.transaction.fields_len() +
@@ -990,8 +1003,7 @@ impl TransactionSigned {
             _ => {
                 let payload_length = self.transaction.fields_len() + self.signature.payload_len();
                 // 'transaction type byte length' + 'header length' + 'payload length'
-                let len = 1 + length_of_length(payload_length) + payload_length;
-                length_of_length(len) + len
+                1 + length_of_length(payload_length) + payload_length
             }
         }
     }
@@ -1610,6 +1622,17 @@ mod tests {
         assert_eq!(encoded, input);
     }
 
+    proptest::proptest! {
+        #[test]
+        fn test_encoded_2718_len(tx: TransactionSigned) {
+            proptest::prop_assert_eq!(tx.encoded_2718_len(), tx.envelope_encoded().len());
+
+            let mut buf = Vec::new();
+            tx.encode(&mut buf);
+            proptest::prop_assert_eq!(tx.length(), buf.len());
+        }
+    }
+
     #[test]
     fn test_envelop_decode() {
         // random tx: <https://etherscan.io/getRawTx?tx=0x9448608d36e721ef403c53b00546068a6474d6cbab6816c3926de449898e7bce>
@@ -2,8 +2,11 @@ use super::{
     nodes::{rlp_hash, BranchNode, ExtensionNode, LeafNode},
     BranchNodeCompact, Nibbles, TrieMask,
 };
-use crate::{keccak256, proofs::EMPTY_ROOT, H256};
-use std::{collections::HashMap, fmt::Debug};
+use crate::{keccak256, proofs::EMPTY_ROOT, Bytes, H256};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Debug,
+};
 
 mod state;
 pub use state::HashBuilderState;
@@ -47,10 +50,20 @@ pub struct HashBuilder {
     stored_in_database: bool,
 
     updated_branch_nodes: Option<HashMap<Nibbles, BranchNodeCompact>>,
+    proof_retainer: Option<ProofRetainer>,
 
     rlp_buf: Vec<u8>,
 }
 
+/// Collects the RLP encoded nodes on the paths to a set of target keys.
+#[derive(Debug, Default)]
+struct ProofRetainer {
+    /// The keys to retain the proofs for.
+    targets: Vec<Nibbles>,
+    /// The retained nodes, keyed by their path.
+    nodes: BTreeMap<Nibbles, Bytes>,
+}
+
 impl From<HashBuilderState> for HashBuilder {
     fn from(state: HashBuilderState) -> Self {
         Self {
@@ -62,6 +75,7 @@ impl From<HashBuilderState> for HashBuilder {
             hash_masks: state.hash_masks,
             stored_in_database: state.stored_in_database,
             updated_branch_nodes: None,
+            proof_retainer: None,
             rlp_buf: Vec::with_capacity(32),
         }
     }
@@ -105,6 +119,25 @@ impl HashBuilder {
         (self, updates.unwrap_or_default())
     }
 
+    /// Enables the Hash Builder to retain the nodes on the paths to the given keys.
+    ///
+    /// Call [HashBuilder::take_proof_nodes] to get the retained nodes.
+    pub fn with_proof_retainer(mut self, targets: Vec<Nibbles>) -> Self {
+        self.proof_retainer = Some(ProofRetainer { targets, nodes: BTreeMap::default() });
+        self
+    }
+
+    /// Takes the RLP encoded nodes retained on the paths to the proof targets, keyed by their
+    /// path. Ordering by path puts every node before its descendants.
+    ///
+    /// Returns an empty map if [Self::with_proof_retainer] was not called.
+    pub fn take_proof_nodes(&mut self) -> BTreeMap<Nibbles, Bytes> {
+        self.proof_retainer
+            .as_mut()
+            .map(|retainer| std::mem::take(&mut retainer.nodes))
+            .unwrap_or_default()
+    }
+
     /// The number of total updates accrued.
     /// Returns `0` if [Self::with_updates] was not called.
     pub fn updates_len(&self) -> usize {
@@ -252,6 +285,7 @@ impl HashBuilder {
 
                         self.rlp_buf.clear();
                         self.stack.push(leaf_node.rlp(&mut self.rlp_buf));
+                        self.retain_proof_node(current.slice(0, len_from));
                     }
                     HashBuilderValue::Hash(hash) => {
                         tracing::debug!(target: "trie::hash_builder", ?hash, "pushing branch node hash");
@@ -281,6 +315,7 @@ impl HashBuilder {
                 }, "extension node rlp");
                 self.rlp_buf.clear();
                 self.stack.push(extension_node.rlp(&mut self.rlp_buf));
+                self.retain_proof_node(current.slice(0, len_from));
                 self.resize_masks(len_from);
             }
 
@@ -292,7 +327,7 @@ impl HashBuilder {
             // Insert branch nodes in the stack
             if !succeeding.is_empty() || preceding_exists {
                 // Pushes the corresponding branch node to the stack
-                let children = self.push_branch_node(len);
+                let children = self.push_branch_node(&current, len);
                 // Need to store the branch node in an efficient format
                 // outside of the hash builder
                 self.store_branch_node(&current, len, children);
@@ -323,7 +358,7 @@ impl HashBuilder {
     /// Given the size of the longest common prefix, it proceeds to create a branch node
     /// from the state mask and existing stack state, and store its RLP to the top of the stack,
     /// after popping all the relevant elements from the stack.
-    fn push_branch_node(&mut self, len: usize) -> Vec<H256> {
+    fn push_branch_node(&mut self, current: &Nibbles, len: usize) -> Vec<H256> {
         let state_mask = self.groups[len];
         let hash_mask = self.hash_masks[len];
         let branch_node = BranchNode::new(&self.stack);
@@ -345,9 +380,20 @@ impl HashBuilder {
         tracing::debug!(target: "trie::hash_builder", "pushing branch node with {:?} mask from stack", state_mask);
         tracing::trace!(target: "trie::hash_builder", rlp = hex::encode(&rlp), "branch node rlp");
         self.stack.push(rlp);
+        self.retain_proof_node(current.slice(0, len));
         children
     }
 
+    /// Retains the node that was last encoded into the RLP buffer if its path leads to any of the
+    /// proof targets.
+    fn retain_proof_node(&mut self, path: Nibbles) {
+        if let Some(retainer) = self.proof_retainer.as_mut() {
+            if retainer.targets.iter().any(|target| target.has_prefix(&path)) {
+                retainer.nodes.insert(path, Bytes::from(self.rlp_buf.clone()));
+            }
+        }
+    }
+
     /// Given the current nibble prefix and the highest common prefix length, proceeds
     /// to update the masks for the next level and store the branch node and the
     /// masks in the database. We will use that when consuming the intermediate nodes
@@ -475,6 +521,34 @@ mod tests {
         assert_eq!(HashBuilder::default().root(), EMPTY_ROOT);
     }
 
+    #[test]
+    fn retains_proof_nodes() {
+        let data = (0..50u64)
+            .map(|i| (keccak256(i.to_be_bytes()), vec![0xab; 40]))
+            .collect::<BTreeMap<_, _>>();
+        let target = Nibbles::unpack(keccak256(7u64.to_be_bytes()));
+
+        let mut hb = HashBuilder::default().with_proof_retainer(vec![target.clone()]);
+        for (key, value) in &data {
+            hb.add_leaf(Nibbles::unpack(key), value);
+        }
+        let root = hb.root();
+        assert_eq!(root, trie_root(data.iter()));
+
+        let nodes = hb.take_proof_nodes();
+        assert!(nodes.keys().all(|path| target.has_prefix(path)));
+
+        // every node is referenced by its parent, starting from the root
+        let proof = nodes.into_values().collect::<Vec<_>>();
+        assert_eq!(keccak256(&proof[0]), root);
+        for (parent, child) in proof.iter().zip(proof.iter().skip(1)) {
+            let reference = keccak256(child);
+            assert!(parent.windows(32).any(|window| window == reference.as_bytes()));
+        }
+        // the last node is the leaf holding the target value
+        assert!(proof.last().unwrap().ends_with(&[0xab; 40]));
+    }
+
     #[test]
     fn arbitrary_hashed_root() {
         proptest!(|(state: BTreeMap<H256, U256>)| {
@@ -1,6 +1,10 @@
-use crate::{constants::GWEI_TO_WEI, serde_helper::u64_hex, Address, U256};
+use crate::{
+    constants::GWEI_TO_WEI, proofs::calculate_withdrawals_root, serde_helper::u64_hex, Address,
+    H256, U256,
+};
 use reth_codecs::{main_codec, Compact};
 use reth_rlp::{RlpDecodable, RlpEncodable};
+use std::ops::{Deref, DerefMut};
 
 /// Withdrawal represents a validator withdrawal from the consensus layer.
 #[main_codec]
@@ -26,9 +30,81 @@ impl Withdrawal {
     }
 }
 
+/// The withdrawals of a block.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Hash)]
+pub struct Withdrawals(Vec<Withdrawal>);
+
+impl Withdrawals {
+    /// Create a new list of withdrawals.
+    pub fn new(withdrawals: Vec<Withdrawal>) -> Self {
+        Self(withdrawals)
+    }
+
+    /// Calculate the withdrawals root.
+    ///
+    /// An empty list of withdrawals has the root of the empty trie.
+    pub fn root(&self) -> H256 {
+        calculate_withdrawals_root(&self.0)
+    }
+
+    /// Consumes the type and returns the inner withdrawals.
+    pub fn into_inner(self) -> Vec<Withdrawal> {
+        self.0
+    }
+}
+
+impl From<Vec<Withdrawal>> for Withdrawals {
+    fn from(withdrawals: Vec<Withdrawal>) -> Self {
+        Self(withdrawals)
+    }
+}
+
+impl From<Withdrawals> for Vec<Withdrawal> {
+    fn from(withdrawals: Withdrawals) -> Self {
+        withdrawals.0
+    }
+}
+
+impl Deref for Withdrawals {
+    type Target = Vec<Withdrawal>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for Withdrawals {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl IntoIterator for Withdrawals {
+    type Item = Withdrawal;
+    type IntoIter = std::vec::IntoIter<Withdrawal>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+/// Errors of [Header::validate_withdrawals_root](crate::Header::validate_withdrawals_root).
+#[derive(thiserror::Error, Debug, PartialEq, Eq, Clone)]
+#[allow(missing_docs)]
+pub enum WithdrawalsValidationError {
+    #[error("Block withdrawals root {got:?} is different from expected {expected:?}")]
+    WithdrawalsRootDiff { got: H256, expected: H256 },
+    #[error("Unexpected withdrawals in the block body")]
+    WithdrawalsRootUnexpected,
+    #[error("Missing withdrawals in the block body")]
+    BodyWithdrawalsMissing,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{hex_literal::hex, proofs::EMPTY_ROOT, Block, Header};
+    use reth_rlp::Decodable;
 
     // <https://github.com/paradigmxyz/reth/issues/1614>
     #[test]
@@ -39,4 +115,51 @@ mod tests {
         let s = serde_json::to_string(&withdrawals).unwrap();
         assert_eq!(input, s);
     }
+
+    #[test]
+    fn empty_withdrawals_root() {
+        assert_eq!(Withdrawals::default().root(), EMPTY_ROOT);
+
+        // post shanghai header without any withdrawals
+        let header = Header { withdrawals_root: Some(EMPTY_ROOT), ..Default::default() };
+        assert_eq!(header.validate_withdrawals_root(Some(&[])), Ok(()));
+        assert_eq!(
+            header.validate_withdrawals_root(None),
+            Err(WithdrawalsValidationError::BodyWithdrawalsMissing)
+        );
+
+        // pre shanghai header
+        let header = Header::default();
+        assert_eq!(header.validate_withdrawals_root(None), Ok(()));
+        assert_eq!(header.validate_withdrawals_root(Some(&[])), Ok(()));
+        assert_eq!(
+            header.validate_withdrawals_root(Some(&[Withdrawal::default()])),
+            Err(WithdrawalsValidationError::WithdrawalsRootUnexpected)
+        );
+    }
+
+    #[test]
+    fn validate_withdrawals_root() {
+        // Single withdrawal, amount 0
+        // https://github.com/ethereum/tests/blob/9760400e667eba241265016b02644ef62ab55de2/BlockchainTests/EIPTests/bc4895-withdrawals/amountIs0.json
+        let data = &hex!("f90238f90219a0151934ad9b654c50197f37018ee5ee9bb922dec0a1b5e24a6d679cb111cdb107a01dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347942adc25665018aa1fe0e6bc666dac8fc2697ff9baa0046119afb1ab36aaa8f66088677ed96cd62762f6d3e65642898e189fbe702d51a056e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421a056e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421b90100000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000008001887fffffffffffffff8082079e42a056e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b42188000000000000000009a048a703da164234812273ea083e4ec3d09d028300cd325b46a6a75402e5a7ab95c0c0d9d8808094c94f5374fce5edbc8e2a8697c15331677e6ebf0b80");
+        let block = Block::decode(&mut data.as_slice()).unwrap();
+        let withdrawals = Withdrawals::new(block.withdrawals.clone().unwrap());
+        assert_eq!(
+            withdrawals.root(),
+            H256(hex!("48a703da164234812273ea083e4ec3d09d028300cd325b46a6a75402e5a7ab95"))
+        );
+        assert_eq!(block.header.validate_withdrawals_root(Some(&withdrawals)), Ok(()));
+
+        // a different amount changes the root
+        let mut modified = withdrawals.clone();
+        modified[0].amount = 1;
+        assert_eq!(
+            block.header.validate_withdrawals_root(Some(&modified)),
+            Err(WithdrawalsValidationError::WithdrawalsRootDiff {
+                got: modified.root(),
+                expected: withdrawals.root(),
+            })
+        );
+    }
 }
@@ -4,7 +4,7 @@ use crate::tracing::{
     types::{CallTraceNode, CallTraceStepStackItem},
     TracingInspectorConfig,
 };
-use reth_primitives::{Address, Bytes, H256};
+use reth_primitives::{Address, Bytes, H256, U256};
 use reth_rpc_types::trace::geth::*;
 use std::collections::{BTreeMap, HashMap, VecDeque};
 
@@ -106,7 +106,10 @@ impl GethTraceBuilder {
     /// Generate a geth-style traces for the call tracer.
     ///
     /// This decodes all call frames from the recorded traces.
-    pub fn geth_call_traces(&self, opts: CallConfig) -> CallFrame {
+    ///
+    /// The `gas_used` of the transaction is set as the gas used of the root call frame, since the
+    /// recorded root trace doesn't include the intrinsic gas of the transaction.
+    pub fn geth_call_traces(&self, opts: CallConfig, gas_used: u64) -> CallFrame {
         if self.nodes.is_empty() {
             return Default::default()
         }
@@ -114,7 +117,8 @@ impl GethTraceBuilder {
         let include_logs = opts.with_log.unwrap_or_default();
         // first fill up the root
         let main_trace_node = &self.nodes[0];
-        let root_call_frame = main_trace_node.geth_empty_call_frame(include_logs);
+        let mut root_call_frame = main_trace_node.geth_empty_call_frame(include_logs);
+        root_call_frame.gas_used = U256::from(gas_used);
 
         if opts.only_top_call.unwrap_or_default() {
             return root_call_frame
@@ -148,3 +152,37 @@ impl GethTraceBuilder {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tracing::TracingInspector;
+    use revm::{
+        primitives::{AccountInfo, Bytecode, ExecutionResult, TransactTo, B160},
+        InMemoryDB, EVM,
+    };
+
+    #[test]
+    fn call_frame_gas_used_includes_refund() {
+        let contract = B160::from_low_u64_be(0x1000);
+        let mut db = InMemoryDB::default();
+        // clears storage slot 0, which is refunded: PUSH1 0 PUSH1 0 SSTORE STOP
+        let code = Bytecode::new_raw(vec![0x60, 0x00, 0x60, 0x00, 0x55, 0x00].into());
+        db.insert_account_info(contract, AccountInfo { code: Some(code), ..Default::default() });
+        db.insert_account_storage(contract, U256::ZERO, U256::from(1)).unwrap();
+
+        let mut evm = EVM::new();
+        evm.database(db);
+        evm.env.tx.transact_to = TransactTo::Call(contract);
+
+        let mut inspector = TracingInspector::new(TracingInspectorConfig::default_geth());
+        let res = evm.inspect(&mut inspector).unwrap();
+        let ExecutionResult::Success { gas_used, gas_refunded, .. } = res.result else {
+            panic!("call failed: {:?}", res.result)
+        };
+        assert!(gas_refunded > 0);
+
+        let frame = inspector.into_geth_builder().geth_call_traces(CallConfig::default(), gas_used);
+        assert_eq!(frame.gas_used, U256::from(gas_used));
+    }
+}
@@ -1,6 +1,8 @@
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
 use reth_primitives::Address;
-use reth_rpc_types::txpool::{TxpoolContent, TxpoolContentFrom, TxpoolInspect, TxpoolStatus};
+use reth_rpc_types::txpool::{
+    TxpoolContent, TxpoolContentFrom, TxpoolContentPage, TxpoolInspect, TxpoolStatus,
+};
 
 /// Txpool rpc interface.
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "txpool"))]
@@ -34,4 +36,17 @@ pub trait TxPoolApi {
     /// See [here](https://geth.ethereum.org/docs/rpc/ns-txpool#txpool_content) for more details
     #[method(name = "content")]
     async fn txpool_content(&self) -> RpcResult<TxpoolContent>;
+
+    /// Same as `txpool_content`, but returns the transactions of at most `limit` senders, starting
+    /// at sender `from`.
+    ///
+    /// The returned page contains the first sender of the next page, which can be passed as `from`
+    /// to fetch the next page. Senders that enter the pool after their position in the iteration
+    /// has been passed are not included.
+    #[method(name = "contentPaged")]
+    async fn txpool_content_paged(
+        &self,
+        limit: usize,
+        from: Option<Address>,
+    ) -> RpcResult<TxpoolContentPage>;
 }
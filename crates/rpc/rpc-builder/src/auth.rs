@@ -1,7 +1,7 @@
 use crate::{
     constants,
     error::{RpcError, ServerKind},
-    eth::DEFAULT_MAX_LOGS_PER_RESPONSE,
+    eth::{DEFAULT_MAX_BLOCKS_PER_FILTER, DEFAULT_MAX_LOGS_PER_RESPONSE},
 };
 use hyper::header::AUTHORIZATION;
 pub use jsonrpsee::server::ServerBuilder;
@@ -61,6 +61,7 @@ where
         network,
         eth_cache.clone(),
         gas_oracle,
+        Default::default(),
         Box::new(executor.clone()),
     );
     let eth_filter = EthFilter::new(
@@ -68,6 +69,7 @@ where
         pool,
         eth_cache.clone(),
         DEFAULT_MAX_LOGS_PER_RESPONSE,
+        DEFAULT_MAX_BLOCKS_PER_FILTER,
         Box::new(executor.clone()),
     );
     launch_with_eth_api(eth_api, eth_filter, engine_api, socket_addr, secret).await
@@ -2,6 +2,7 @@ use reth_rpc::{
     eth::{
         cache::{EthStateCache, EthStateCacheConfig},
         gas_oracle::GasPriceOracleConfig,
+        EstimateGasConfig,
     },
     EthApi, EthFilter, EthPubSub,
 };
@@ -10,6 +11,12 @@ use serde::{Deserialize, Serialize};
 /// The default maximum of logs in a single response.
 pub(crate) const DEFAULT_MAX_LOGS_PER_RESPONSE: usize = 20_000;
 
+/// The default maximum number of blocks of a single `eth_getLogs` query.
+pub(crate) const DEFAULT_MAX_BLOCKS_PER_FILTER: u64 = 100_000;
+
+/// The default maximum number of blocks of a single `trace_filter` call.
+pub(crate) const DEFAULT_MAX_TRACE_FILTER_BLOCKS: u64 = 100;
+
 /// The default maximum number of concurrently executed tracing calls
 pub(crate) const DEFAULT_MAX_TRACING_REQUESTS: u32 = 25;
 
@@ -33,10 +40,16 @@ pub struct EthConfig {
     pub cache: EthStateCacheConfig,
     /// Settings for the gas price oracle
     pub gas_oracle: GasPriceOracleConfig,
+    /// Settings for `eth_estimateGas`
+    pub estimate_gas: EstimateGasConfig,
     /// The maximum number of tracing calls that can be executed in concurrently.
     pub max_tracing_requests: u32,
     /// Maximum number of logs that can be returned in a single response in `eth_getLogs` calls.
     pub max_logs_per_response: usize,
+    /// Maximum number of blocks that can be queried in a single `eth_getLogs` call.
+    pub max_blocks_per_filter: u64,
+    /// Maximum number of blocks that can be traced in a single `trace_filter` call.
+    pub max_trace_filter_blocks: u64,
 }
 
 impl Default for EthConfig {
@@ -44,8 +57,11 @@ impl Default for EthConfig {
         Self {
             cache: EthStateCacheConfig::default(),
             gas_oracle: GasPriceOracleConfig::default(),
+            estimate_gas: EstimateGasConfig::default(),
             max_tracing_requests: DEFAULT_MAX_TRACING_REQUESTS,
             max_logs_per_response: DEFAULT_MAX_LOGS_PER_RESPONSE,
+            max_blocks_per_filter: DEFAULT_MAX_BLOCKS_PER_FILTER,
+            max_trace_filter_blocks: DEFAULT_MAX_TRACE_FILTER_BLOCKS,
         }
     }
 }
@@ -63,6 +79,12 @@ impl EthConfig {
         self
     }
 
+    /// Configures the `eth_estimateGas` settings
+    pub fn estimate_gas_config(mut self, estimate_gas: EstimateGasConfig) -> Self {
+        self.estimate_gas = estimate_gas;
+        self
+    }
+
     /// Configures the maximum number of tracing requests
     pub fn max_tracing_requests(mut self, max_requests: u32) -> Self {
         self.max_tracing_requests = max_requests;
@@ -74,4 +96,16 @@ impl EthConfig {
         self.max_logs_per_response = max_logs;
         self
     }
+
+    /// Configures the maximum block range of a single log query
+    pub fn max_blocks_per_filter(mut self, max_blocks: u64) -> Self {
+        self.max_blocks_per_filter = max_blocks;
+        self
+    }
+
+    /// Configures the maximum block range of a single `trace_filter` call
+    pub fn max_trace_filter_blocks(mut self, max_blocks: u64) -> Self {
+        self.max_trace_filter_blocks = max_blocks;
+        self
+    }
 }
@@ -31,13 +31,13 @@
 //!
 //! ```
 //! use reth_network_api::{NetworkInfo, Peers};
-//! use reth_provider::{BlockReaderIdExt, ChainSpecProvider, CanonStateSubscriptions, StateProviderFactory, EvmEnvProvider};
+//! use reth_provider::{BlockReaderIdExt, ChainSpecProvider, CanonStateSubscriptions, ForkchoiceSubscriptions, StateProviderFactory, EvmEnvProvider};
 //! use reth_rpc_builder::{RethRpcModule, RpcModuleBuilder, RpcServerConfig, ServerBuilder, TransportRpcModuleConfig};
 //! use reth_tasks::TokioTaskExecutor;
 //! use reth_transaction_pool::TransactionPool;
 //! pub async fn launch<Provider, Pool, Network, Events>(provider: Provider, pool: Pool, network: Network, events: Events)
 //! where
-//!     Provider: BlockReaderIdExt + ChainSpecProvider + StateProviderFactory + EvmEnvProvider + Clone + Unpin + 'static,
+//!     Provider: BlockReaderIdExt + ChainSpecProvider + ForkchoiceSubscriptions + StateProviderFactory + EvmEnvProvider + Clone + Unpin + 'static,
 //!     Pool: TransactionPool + Clone + 'static,
 //!     Network: NetworkInfo + Peers + Clone + 'static,
 //!     Events: CanonStateSubscriptions +  Clone + 'static,
@@ -64,7 +64,7 @@
 //! ```
 //! use tokio::try_join;
 //! use reth_network_api::{NetworkInfo, Peers};
-//! use reth_provider::{BlockReaderIdExt, ChainSpecProvider, CanonStateSubscriptions, StateProviderFactory, EvmEnvProvider};
+//! use reth_provider::{BlockReaderIdExt, ChainSpecProvider, CanonStateSubscriptions, ForkchoiceSubscriptions, StateProviderFactory, EvmEnvProvider};
 //! use reth_rpc::JwtSecret;
 //! use reth_rpc_builder::{RethRpcModule, RpcModuleBuilder, RpcServerConfig, TransportRpcModuleConfig};
 //! use reth_tasks::TokioTaskExecutor;
@@ -73,7 +73,7 @@
 //! use reth_rpc_builder::auth::AuthServerConfig;
 //! pub async fn launch<Provider, Pool, Network, Events, EngineApi>(provider: Provider, pool: Pool, network: Network, events: Events, engine_api: EngineApi)
 //! where
-//!     Provider: BlockReaderIdExt + ChainSpecProvider + StateProviderFactory + EvmEnvProvider + Clone + Unpin + 'static,
+//!     Provider: BlockReaderIdExt + ChainSpecProvider + ForkchoiceSubscriptions + StateProviderFactory + EvmEnvProvider + Clone + Unpin + 'static,
 //!     Pool: TransactionPool + Clone + 'static,
 //!     Network: NetworkInfo + Peers + Clone + 'static,
 //!     Events: CanonStateSubscriptions +  Clone + 'static,
@@ -114,7 +114,7 @@ use reth_ipc::server::IpcServer;
 use reth_network_api::{NetworkInfo, Peers};
 use reth_provider::{
     BlockReader, BlockReaderIdExt, CanonStateSubscriptions, ChainSpecProvider, EvmEnvProvider,
-    StateProviderFactory,
+    ForkchoiceSubscriptions, StateProviderFactory,
 };
 use reth_rpc::{
     eth::{
@@ -174,6 +174,7 @@ where
         + StateProviderFactory
         + EvmEnvProvider
         + ChainSpecProvider
+        + ForkchoiceSubscriptions
         + Clone
         + Unpin
         + 'static,
@@ -279,6 +280,7 @@ where
         + StateProviderFactory
         + EvmEnvProvider
         + ChainSpecProvider
+        + ForkchoiceSubscriptions
         + Clone
         + Unpin
         + 'static,
@@ -502,6 +504,7 @@ impl RpcModuleSelection {
             + StateProviderFactory
             + EvmEnvProvider
             + ChainSpecProvider
+            + ForkchoiceSubscriptions
             + Clone
             + Unpin
             + 'static,
@@ -714,6 +717,7 @@ where
         + StateProviderFactory
         + EvmEnvProvider
         + ChainSpecProvider
+        + ForkchoiceSubscriptions
         + Clone
         + Unpin
         + 'static,
@@ -757,6 +761,7 @@ where
                 eth.cache,
                 Box::new(self.executor.clone()),
                 self.tracing_call_guard.clone(),
+                self.config.eth.max_trace_filter_blocks,
             )
             .into_rpc()
             .into(),
@@ -862,6 +867,7 @@ where
                             eth_cache.clone(),
                             Box::new(self.executor.clone()),
                             self.tracing_call_guard.clone(),
+                            self.config.eth.max_trace_filter_blocks,
                         )
                         .into_rpc()
                         .into(),
@@ -923,6 +929,7 @@ where
                 self.network.clone(),
                 cache.clone(),
                 gas_oracle,
+                self.config.eth.estimate_gas.clone(),
                 executor.clone(),
             );
             let filter = EthFilter::new(
@@ -930,6 +937,7 @@ where
                 self.pool.clone(),
                 cache.clone(),
                 self.config.eth.max_logs_per_response,
+                self.config.eth.max_blocks_per_filter,
                 executor.clone(),
             );
 
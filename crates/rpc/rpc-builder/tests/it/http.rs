@@ -69,6 +69,7 @@ where
     EthApiClient::balance(client, address, None).await.unwrap();
     EthApiClient::transaction_count(client, address, None).await.unwrap();
     EthApiClient::storage_at(client, address, U256::default().into(), None).await.unwrap();
+    EthApiClient::get_proof(client, address, vec![], None).await.unwrap();
     EthApiClient::block_by_hash(client, hash, false).await.unwrap();
     EthApiClient::block_by_number(client, block_number, false).await.unwrap();
     EthApiClient::block_transaction_count_by_number(client, block_number).await.unwrap();
@@ -101,9 +102,6 @@ where
     EthApiClient::max_priority_fee_per_gas(client).await.unwrap_err();
 
     // Unimplemented
-    assert!(is_unimplemented(
-        EthApiClient::get_proof(client, address, vec![], None).await.err().unwrap()
-    ));
     assert!(is_unimplemented(EthApiClient::author(client).await.err().unwrap()));
     assert!(is_unimplemented(EthApiClient::is_mining(client).await.err().unwrap()));
     assert!(is_unimplemented(EthApiClient::get_work(client).await.err().unwrap()));
@@ -150,6 +148,7 @@ where
         to_block: None,
         from_address: None,
         to_address: None,
+        mode: None,
         after: None,
         count: None,
     };
@@ -167,9 +166,7 @@ where
     TraceApiClient::trace_block(client, block_id).await.unwrap();
     TraceApiClient::replay_block_transactions(client, block_id, HashSet::default()).await.unwrap();
 
-    assert!(is_unimplemented(
-        TraceApiClient::trace_filter(client, trace_filter).await.err().unwrap()
-    ));
+    TraceApiClient::trace_filter(client, trace_filter).await.unwrap();
 }
 
 async fn test_basic_web3_calls<C>(client: &C)
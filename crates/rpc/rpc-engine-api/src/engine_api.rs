@@ -175,18 +175,14 @@ where
             return Err(EngineApiError::InvalidBodiesRange { start, count })
         }
 
-        let mut result = Vec::with_capacity(count as usize);
-
-        let end = start.saturating_add(count);
-        for num in start..end {
-            let block = self
-                .provider
-                .block(BlockHashOrNumber::Number(num))
-                .map_err(|err| EngineApiError::Internal(Box::new(err)))?;
-            result.push(block.map(Into::into));
-        }
+        // read all bodies of the range at once instead of loading every full block
+        let end = start.saturating_add(count - 1);
+        let bodies = self
+            .provider
+            .block_body_range(start..=end)
+            .map_err(|err| EngineApiError::Internal(Box::new(err)))?;
 
-        Ok(result)
+        Ok(bodies.into_iter().map(|body| body.map(Into::into)).collect())
     }
 
     /// Called to retrieve execution payload bodies by hashes.
@@ -1,8 +1,8 @@
 use reth_primitives::{
     constants::{MAXIMUM_EXTRA_DATA_SIZE, MIN_PROTOCOL_BASE_FEE_U256},
     proofs::{self, EMPTY_LIST_HASH},
-    Address, Block, Bloom, Bytes, Header, SealedBlock, TransactionSigned, UintTryTo, Withdrawal,
-    H256, H64, U256, U64,
+    Address, Block, BlockBody, Bloom, Bytes, Header, SealedBlock, TransactionSigned, UintTryTo,
+    Withdrawal, H256, H64, U256, U64,
 };
 use reth_rlp::{Decodable, Encodable};
 use serde::{ser::SerializeMap, Deserialize, Serialize, Serializer};
@@ -227,7 +227,14 @@ pub struct ExecutionPayloadBody {
 
 impl From<Block> for ExecutionPayloadBody {
     fn from(value: Block) -> Self {
-        let transactions = value.body.into_iter().map(|tx| {
+        BlockBody { transactions: value.body, ommers: value.ommers, withdrawals: value.withdrawals }
+            .into()
+    }
+}
+
+impl From<BlockBody> for ExecutionPayloadBody {
+    fn from(value: BlockBody) -> Self {
+        let transactions = value.transactions.into_iter().map(|tx| {
             let mut out = Vec::new();
             tx.encode(&mut out);
             out.into()
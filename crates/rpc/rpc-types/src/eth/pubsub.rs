@@ -1,6 +1,6 @@
 //! Ethereum types for pub-sub
 
-use crate::{eth::Filter, Log, RichHeader};
+use crate::{eth::Filter, Log, RichHeader, Transaction};
 use reth_primitives::H256;
 use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
 
@@ -14,6 +14,8 @@ pub enum SubscriptionResult {
     Log(Box<Log>),
     /// Transaction hash
     TransactionHash(H256),
+    /// Full transaction
+    FullTransaction(Box<Transaction>),
     /// SyncStatus
     SyncState(PubSubSyncStatus),
 }
@@ -49,6 +51,7 @@ impl Serialize for SubscriptionResult {
             SubscriptionResult::Header(ref header) => header.serialize(serializer),
             SubscriptionResult::Log(ref log) => log.serialize(serializer),
             SubscriptionResult::TransactionHash(ref hash) => hash.serialize(serializer),
+            SubscriptionResult::FullTransaction(ref tx) => tx.serialize(serializer),
             SubscriptionResult::SyncState(ref sync) => sync.serialize(serializer),
         }
     }
@@ -80,6 +83,9 @@ pub enum SubscriptionKind {
     /// with a key that is available in the node. When a transaction that was previously part of
     /// the canonical chain isn't part of the new canonical chain after a reorganization its again
     /// emitted.
+    ///
+    /// If the `true` parameter is passed, the full transactions including the recovered senders
+    /// are returned instead of the hashes.
     NewPendingTransactions,
     /// Node syncing status subscription.
     ///
@@ -87,6 +93,16 @@ pub enum SubscriptionKind {
     /// indicating that the synchronization has started (true), finished (false) or an object with
     /// various progress indicators.
     Syncing,
+    /// Finalized block headers subscription.
+    ///
+    /// Fires a notification with the current finalized header right away, and each time the
+    /// consensus layer finalizes a new block via a forkchoice update.
+    FinalizedHeaders,
+    /// Safe block headers subscription.
+    ///
+    /// Fires a notification with the current safe header right away, and each time the
+    /// consensus layer marks a new block as safe via a forkchoice update.
+    SafeHeaders,
 }
 
 /// Subscription kind.
@@ -97,6 +113,8 @@ pub enum Params {
     None,
     /// Log parameters.
     Logs(Box<Filter>),
+    /// Boolean parameter, e.g. whether full transactions should be returned.
+    Bool(bool),
 }
 
 impl Serialize for Params {
@@ -107,6 +125,7 @@ impl Serialize for Params {
         match self {
             Params::None => (&[] as &[serde_json::Value]).serialize(serializer),
             Params::Logs(logs) => logs.serialize(serializer),
+            Params::Bool(full) => full.serialize(serializer),
         }
     }
 }
@@ -122,8 +141,30 @@ impl<'a> Deserialize<'a> for Params {
             return Ok(Params::None)
         }
 
+        if let Some(full) = v.as_bool() {
+            return Ok(Params::Bool(full))
+        }
+
         serde_json::from_value(v)
             .map(|f| Params::Logs(Box::new(f)))
             .map_err(|e| D::Error::custom(format!("Invalid Pub-Sub parameters: {e}")))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn params_serde() {
+        let params: Params = serde_json::from_str("true").unwrap();
+        assert_eq!(params, Params::Bool(true));
+        assert_eq!(serde_json::to_string(&params).unwrap(), "true");
+
+        let params: Params = serde_json::from_str("null").unwrap();
+        assert_eq!(params, Params::None);
+
+        let params: Params = serde_json::from_str(r#"{"fromBlock":"latest"}"#).unwrap();
+        assert!(matches!(params, Params::Logs(_)));
+    }
+}
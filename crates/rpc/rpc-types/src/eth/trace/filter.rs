@@ -1,6 +1,8 @@
 //! `trace_filter` types and support
+use crate::trace::parity::{Action, TraceOutput, TraceResult, TransactionTrace};
 use reth_primitives::{Address, BlockNumber};
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 
 /// Trace filter.
 #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -15,8 +17,155 @@ pub struct TraceFilter {
     pub from_address: Option<Vec<Address>>,
     /// To address
     pub to_address: Option<Vec<Address>>,
+    /// How the from and to addresses are combined, union by default
+    pub mode: Option<TraceFilterMode>,
     /// Output offset
     pub after: Option<usize>,
     /// Output amount
     pub count: Option<usize>,
 }
+
+// === impl TraceFilter ===
+
+impl TraceFilter {
+    /// Returns a matcher for the addresses of the filter.
+    pub fn matcher(&self) -> TraceFilterMatcher {
+        TraceFilterMatcher {
+            mode: self.mode.unwrap_or_default(),
+            from_addresses: self.from_address.iter().flatten().copied().collect(),
+            to_addresses: self.to_address.iter().flatten().copied().collect(),
+        }
+    }
+}
+
+/// How the from and to addresses of a [TraceFilter] are combined.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TraceFilterMode {
+    /// A trace matches if its sender matches the from addresses _or_ its receiver matches the to
+    /// addresses.
+    #[default]
+    Union,
+    /// A trace matches if its sender matches the from addresses _and_ its receiver matches the to
+    /// addresses.
+    Intersection,
+}
+
+/// Matches traces against the addresses of a [TraceFilter].
+///
+/// An empty address set matches all traces.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceFilterMatcher {
+    mode: TraceFilterMode,
+    from_addresses: HashSet<Address>,
+    to_addresses: HashSet<Address>,
+}
+
+// === impl TraceFilterMatcher ===
+
+impl TraceFilterMatcher {
+    /// Returns `true` if the trace matches the filter.
+    ///
+    /// The sender of a trace is the caller of a call or create, or the destroyed contract of a
+    /// selfdestruct. The receiver is the callee of a call, the created contract of a create, the
+    /// refund address of a selfdestruct, or the author of a reward.
+    pub fn matches(&self, trace: &TransactionTrace) -> bool {
+        let (from, to) = match &trace.action {
+            Action::Call(call) => (Some(call.from), Some(call.to)),
+            Action::Create(create) => {
+                let created = match &trace.result {
+                    Some(TraceResult::Success { result: TraceOutput::Create(output) }) => {
+                        Some(output.address)
+                    }
+                    _ => None,
+                };
+                (Some(create.from), created)
+            }
+            Action::Selfdestruct(selfdestruct) => {
+                (Some(selfdestruct.address), Some(selfdestruct.refund_address))
+            }
+            Action::Reward(reward) => (None, Some(reward.author)),
+        };
+
+        let from_matches = from.map_or(false, |from| self.from_addresses.contains(&from));
+        let to_matches = to.map_or(false, |to| self.to_addresses.contains(&to));
+
+        match self.mode {
+            TraceFilterMode::Union => {
+                (self.from_addresses.is_empty() && self.to_addresses.is_empty()) ||
+                    from_matches ||
+                    to_matches
+            }
+            TraceFilterMode::Intersection => {
+                (self.from_addresses.is_empty() || from_matches) &&
+                    (self.to_addresses.is_empty() || to_matches)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trace::parity::{CallAction, RewardAction, RewardType};
+
+    fn call(from: Address, to: Address) -> TransactionTrace {
+        TransactionTrace {
+            action: Action::Call(CallAction {
+                from,
+                to,
+                value: Default::default(),
+                gas: Default::default(),
+                input: Default::default(),
+                call_type: Default::default(),
+            }),
+            result: None,
+            subtraces: 0,
+            trace_address: vec![],
+        }
+    }
+
+    fn filter(from: Vec<Address>, to: Vec<Address>, mode: TraceFilterMode) -> TraceFilter {
+        TraceFilter {
+            from_block: None,
+            to_block: None,
+            from_address: Some(from),
+            to_address: Some(to),
+            mode: Some(mode),
+            after: None,
+            count: None,
+        }
+    }
+
+    #[test]
+    fn match_trace_addresses() {
+        let (a, b, c) = (Address::random(), Address::random(), Address::random());
+        let trace = call(a, b);
+
+        assert!(filter(vec![], vec![], TraceFilterMode::Union).matcher().matches(&trace));
+        assert!(filter(vec![a], vec![], TraceFilterMode::Union).matcher().matches(&trace));
+        assert!(!filter(vec![b], vec![], TraceFilterMode::Union).matcher().matches(&trace));
+        assert!(filter(vec![c], vec![b], TraceFilterMode::Union).matcher().matches(&trace));
+        assert!(!filter(vec![c], vec![b], TraceFilterMode::Intersection).matcher().matches(&trace));
+        assert!(filter(vec![a], vec![b], TraceFilterMode::Intersection).matcher().matches(&trace));
+
+        let reward = TransactionTrace {
+            action: Action::Reward(RewardAction {
+                author: c,
+                value: Default::default(),
+                reward_type: RewardType::Block,
+            }),
+            ..trace
+        };
+        assert!(filter(vec![], vec![c], TraceFilterMode::Union).matcher().matches(&reward));
+        assert!(!filter(vec![c], vec![], TraceFilterMode::Union).matcher().matches(&reward));
+    }
+
+    #[test]
+    fn deserialize_filter() {
+        let s = r#"{"fromBlock":1,"toBlock":2,"fromAddress":["0x0000000000000000000000000000000000000001"],"mode":"intersection","count":10}"#;
+        let filter: TraceFilter = serde_json::from_str(s).unwrap();
+        assert_eq!(filter.mode, Some(TraceFilterMode::Intersection));
+        assert_eq!(filter.count, Some(10));
+    }
+}
@@ -144,6 +144,20 @@ pub struct TxpoolContentFrom {
     pub queued: BTreeMap<String, Transaction>,
 }
 
+/// A page of [TxpoolContent], returned by `txpool_contentPaged`.
+///
+/// Pages are keyed by sender: all transactions of a sender are contained in the same page, and the
+/// senders of a page are in ascending order.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TxpoolContentPage {
+    /// pending tx
+    pub pending: BTreeMap<Address, BTreeMap<String, Transaction>>,
+    /// queued tx
+    pub queued: BTreeMap<Address, BTreeMap<String, Transaction>>,
+    /// The first sender of the next page, `None` if this is the last page.
+    pub next: Option<Address>,
+}
+
 /// Transaction Pool Inspect
 ///
 /// The inspect inspection property can be queried to list a textual summary
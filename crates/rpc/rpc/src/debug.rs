@@ -295,13 +295,15 @@ where
                                 .set_record_logs(call_config.with_log.unwrap_or_default()),
                         );
 
-                        let _ = self
+                        let (res, _) = self
                             .inner
                             .eth_api
                             .inspect_call_at(call, at, overrides, &mut inspector)
                             .await?;
 
-                        let frame = inspector.into_geth_builder().geth_call_traces(call_config);
+                        let frame = inspector
+                            .into_geth_builder()
+                            .geth_call_traces(call_config, res.result.gas_used());
 
                         return Ok(frame.into())
                     }
@@ -387,7 +389,9 @@ where
 
                         let (res, _) = inspect(db, env, &mut inspector)?;
 
-                        let frame = inspector.into_geth_builder().geth_call_traces(call_config);
+                        let frame = inspector
+                            .into_geth_builder()
+                            .geth_call_traces(call_config, res.result.gas_used());
 
                         return Ok((frame.into(), res.state))
                     }
@@ -2,7 +2,7 @@
 
 use crate::{
     eth::{
-        api::transactions::build_transaction_receipt_with_block_receipts,
+        api::transactions::build_transaction_receipt,
         error::{EthApiError, EthResult},
     },
     EthApi,
@@ -51,6 +51,9 @@ where
 
     /// Returns all transaction receipts in the block.
     ///
+    /// The receipts are built in a single pass over the block's receipts, tracking the gas used and
+    /// the log index of the previous transactions.
+    ///
     /// Returns `None` if the block wasn't found.
     pub(crate) async fn block_receipts(
         &self,
@@ -68,10 +71,12 @@ where
             let block_number = block.number;
             let base_fee = block.base_fee_per_gas;
             let block_hash = block.hash;
+            let mut prev_cumulative_gas_used = 0;
+            let mut next_log_index = 0;
             let receipts = block
                 .body
                 .into_iter()
-                .zip(receipts.clone())
+                .zip(receipts)
                 .enumerate()
                 .map(|(idx, (tx, receipt))| {
                     let meta = TransactionMeta {
@@ -81,7 +86,11 @@ where
                         block_number,
                         base_fee,
                     };
-                    build_transaction_receipt_with_block_receipts(tx, meta, receipt, &receipts)
+                    let gas_used = receipt.cumulative_gas_used - prev_cumulative_gas_used;
+                    let first_log_index = next_log_index;
+                    prev_cumulative_gas_used = receipt.cumulative_gas_used;
+                    next_log_index += receipt.logs.len();
+                    build_transaction_receipt(tx, meta, receipt, gas_used, first_log_index)
                 })
                 .collect::<EthResult<Vec<_>>>();
             return receipts.map(Some)
@@ -13,7 +13,7 @@ use crate::{
 };
 use ethers_core::utils::get_contract_address;
 use reth_network_api::NetworkInfo;
-use reth_primitives::{AccessList, BlockId, BlockNumberOrTag, Bytes, U256};
+use reth_primitives::{AccessList, AccessListWithGasUsed, BlockId, BlockNumberOrTag, Bytes, U256};
 use reth_provider::{BlockReaderIdExt, EvmEnvProvider, StateProvider, StateProviderFactory};
 use reth_revm::{
     access_list::AccessListInspector,
@@ -25,11 +25,50 @@ use revm::{
     db::{CacheDB, DatabaseRef},
     primitives::{BlockEnv, CfgEnv, Env, ExecutionResult, Halt, TransactTo},
 };
+use serde::{Deserialize, Serialize};
 use tracing::trace;
 
 // Gas per transaction not creating a contract.
 const MIN_TRANSACTION_GAS: u64 = 21_000u64;
 const MIN_CREATE_GAS: u64 = 53_000u64;
+// Gas per address and storage key of an access list.
+const ACCESS_LIST_ADDRESS_GAS: u64 = 2_400u64;
+const ACCESS_LIST_STORAGE_KEY_GAS: u64 = 1_900u64;
+
+/// The default maximum number of executions of the `eth_estimateGas` binary search.
+pub const DEFAULT_ESTIMATE_GAS_MAX_ITERATIONS: u64 = 64;
+
+/// Settings for `eth_estimateGas`
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EstimateGasConfig {
+    /// The percentage that is added to the estimate once the binary search converged.
+    ///
+    /// The padded estimate is capped by the highest gas limit the transaction can be executed
+    /// with.
+    pub padding_percent: u64,
+    /// The maximum number of executions of the binary search.
+    ///
+    /// If the search didn't converge by then, the lowest gas limit the transaction is known to
+    /// succeed with is returned.
+    pub max_iterations: u64,
+    /// Whether the binary search for requests without an access list starts from the gas used
+    /// with the access list that `eth_createAccessList` creates for them.
+    ///
+    /// The access list is not returned to the caller, so the request itself is still estimated
+    /// without it.
+    pub with_access_list: bool,
+}
+
+impl Default for EstimateGasConfig {
+    fn default() -> Self {
+        Self {
+            padding_percent: 0,
+            max_iterations: DEFAULT_ESTIMATE_GAS_MAX_ITERATIONS,
+            with_access_list: false,
+        }
+    }
+}
 
 impl<Provider, Pool, Network> EthApi<Provider, Pool, Network>
 where
@@ -68,7 +107,11 @@ where
 
     /// Estimates the gas usage of the `request` with the state.
     ///
-    /// This will execute the [CallRequest] and find the best gas limit via binary search
+    /// This will execute the [CallRequest] and find the best gas limit via binary search.
+    ///
+    /// If [EstimateGasConfig::with_access_list] is set and the request doesn't contain an access
+    /// list, the gas used with the access list created for the request bounds the search from
+    /// below.
     fn estimate_gas_with<S>(
         &self,
         mut cfg: CfgEnv,
@@ -88,6 +131,11 @@ where
         // <https://github.com/ethereum/go-ethereum/blob/ee8e83fa5f6cb261dad2ed0a7bbcde4930c41e6c/internal/ethapi/api.go#L985>
         cfg.disable_base_fee = true;
 
+        // keep a copy of the inputs for the access list execution
+        let access_list_inputs = (self.inner.estimate_gas.with_access_list &&
+            request.access_list.is_none())
+        .then(|| (cfg.clone(), block.clone(), request.clone()));
+
         // keep a copy of gas related request values
         let request_gas = request.gas;
         let request_gas_price = request.gas_price;
@@ -180,16 +228,46 @@ where
         let mut lowest_gas_limit =
             if env.tx.transact_to.is_create() { MIN_CREATE_GAS } else { MIN_TRANSACTION_GAS };
         let mut highest_gas_limit: u64 = highest_gas_limit.try_into().unwrap_or(u64::MAX);
+        let gas_cap = highest_gas_limit;
+
+        if let Some((cfg, block, request)) = access_list_inputs {
+            let (access_list, result) = stable_access_list(cfg, block, &request, &mut db)?;
+            if let ExecutionResult::Success { gas_used, .. } = result {
+                // without the access list, the execution costs at least as much as with it minus
+                // the upfront cost of the list, and a transaction can't succeed with less gas than
+                // it uses
+                let access_list_gas = access_list
+                    .0
+                    .iter()
+                    .map(|item| {
+                        ACCESS_LIST_ADDRESS_GAS +
+                            ACCESS_LIST_STORAGE_KEY_GAS * item.storage_keys.len() as u64
+                    })
+                    .sum::<u64>();
+                let bound = gas_used.saturating_sub(access_list_gas).saturating_sub(1);
+                lowest_gas_limit = lowest_gas_limit.max(bound).min(highest_gas_limit);
+            }
+        }
+
         // pick a point that's close to the estimated gas
-        let mut mid_gas_limit = std::cmp::min(
-            gas_used * 3,
-            ((highest_gas_limit as u128 + lowest_gas_limit as u128) / 2) as u64,
-        );
+        let mut mid_gas_limit = ((highest_gas_limit as u128 + lowest_gas_limit as u128) / 2) as u64;
+        if gas_used * 3 > lowest_gas_limit {
+            mid_gas_limit = mid_gas_limit.min(gas_used * 3);
+        }
 
         trace!(target: "rpc::eth::estimate", ?env, ?highest_gas_limit, ?lowest_gas_limit, ?mid_gas_limit, "Starting binary search for gas");
 
+        let EstimateGasConfig { padding_percent, max_iterations, .. } = self.inner.estimate_gas;
+
         // binary search
+        let mut iterations = 0;
         while (highest_gas_limit - lowest_gas_limit) > 1 {
+            if iterations >= max_iterations {
+                trace!(target: "rpc::eth::estimate", ?highest_gas_limit, ?lowest_gas_limit, "Reached max binary search iterations");
+                break
+            }
+            iterations += 1;
+
             let mut env = env.clone();
             env.tx.gas_limit = mid_gas_limit;
             let ethres = transact(&mut db, env);
@@ -235,61 +313,127 @@ where
             mid_gas_limit = ((highest_gas_limit as u128 + lowest_gas_limit as u128) / 2) as u64;
         }
 
-        Ok(U256::from(highest_gas_limit))
+        // pad the estimate for transactions with state dependent gas costs
+        let padded = highest_gas_limit as u128 * (100 + padding_percent as u128) / 100;
+        Ok(U256::from(padded.min(gas_cap as u128)))
     }
 
+    /// Creates an access list for the `request` at the [BlockId].
+    ///
+    /// The transaction is executed with the access list of the previous execution until the
+    /// access list doesn't change anymore, since a different access list changes the gas costs
+    /// and can therefore change the execution path.
+    ///
+    /// If the transaction reverts or halts, the access list collected up to that point is
+    /// returned together with the error message and the gas used by the failed execution.
     pub(crate) async fn create_access_list_at(
         &self,
         request: CallRequest,
         at: Option<BlockId>,
-    ) -> EthResult<AccessList> {
+    ) -> EthResult<AccessListWithGasUsed> {
         let block_id = at.unwrap_or(BlockId::Number(BlockNumberOrTag::Latest));
         let (cfg, block, at) = self.evm_env_at(block_id).await?;
         let state = self.state_at(at)?;
+        self.create_access_list_with(cfg, block, request, state)
+    }
 
-        let mut env = build_call_evm_env(cfg, block, request.clone())?;
+    /// Creates an access list for the `request` with the state.
+    ///
+    /// See [Self::create_access_list_at].
+    fn create_access_list_with<S>(
+        &self,
+        cfg: CfgEnv,
+        block: BlockEnv,
+        mut request: CallRequest,
+        state: S,
+    ) -> EthResult<AccessListWithGasUsed>
+    where
+        S: StateProvider,
+    {
+        let mut db = SubState::new(State::new(state));
+        let (access_list, result) =
+            stable_access_list(cfg.clone(), block.clone(), &request, &mut db)?;
+
+        let execution_gas_used = result.gas_used();
+        let error = match result {
+            ExecutionResult::Success { .. } => None,
+            ExecutionResult::Revert { output, .. } => Some(RevertError::new(output).to_string()),
+            ExecutionResult::Halt { reason, .. } => Some(
+                match reason {
+                    Halt::NonceOverflow => RpcInvalidTransactionError::NonceMaxValue,
+                    halt => RpcInvalidTransactionError::EvmHalt(halt),
+                }
+                .to_string(),
+            ),
+        };
 
-        // we want to disable this in eth_createAccessList, since this is common practice used by
-        // other node impls and providers <https://github.com/foundry-rs/foundry/issues/4388>
-        env.cfg.disable_block_gas_limit = true;
+        let gas_used = if error.is_none() {
+            request.access_list = Some(access_list.clone());
+            // the executions above were not committed, so the state is unchanged
+            self.estimate_gas_with(cfg, block, request, db.db.into_inner())?
+        } else {
+            U256::from(execution_gas_used)
+        };
 
-        // The basefee should be ignored for eth_createAccessList
-        // See:
-        // <https://github.com/ethereum/go-ethereum/blob/8990c92aea01ca07801597b00c0d83d4e2d9b811/internal/ethapi/api.go#L1476-L1476>
-        env.cfg.disable_base_fee = true;
+        Ok(AccessListWithGasUsed { access_list, gas_used, error })
+    }
+}
 
-        let mut db = SubState::new(State::new(state));
+/// Executes the `request` with the access list of the previous execution until the access list
+/// doesn't change anymore.
+///
+/// Returns the stable access list and the result of the last execution. The executions are not
+/// committed to the `db`.
+fn stable_access_list<S>(
+    cfg: CfgEnv,
+    block: BlockEnv,
+    request: &CallRequest,
+    db: &mut SubState<S>,
+) -> EthResult<(AccessList, ExecutionResult)>
+where
+    S: StateProvider,
+{
+    let mut env = build_call_evm_env(cfg, block, request.clone())?;
 
-        if request.gas.is_none() && env.tx.gas_price > U256::ZERO {
-            // no gas limit was provided in the request, so we need to cap the request's gas limit
-            cap_tx_gas_limit_with_caller_allowance(&mut db, &mut env.tx)?;
-        }
+    // we want to disable this in eth_createAccessList, since this is common practice used by
+    // other node impls and providers <https://github.com/foundry-rs/foundry/issues/4388>
+    env.cfg.disable_block_gas_limit = true;
 
-        let from = request.from.unwrap_or_default();
-        let to = if let Some(to) = request.to {
-            to
-        } else {
-            let nonce = db.basic(from)?.unwrap_or_default().nonce;
-            get_contract_address(from, nonce).into()
-        };
+    // The basefee should be ignored for eth_createAccessList
+    // See:
+    // <https://github.com/ethereum/go-ethereum/blob/8990c92aea01ca07801597b00c0d83d4e2d9b811/internal/ethapi/api.go#L1476-L1476>
+    env.cfg.disable_base_fee = true;
 
-        let initial = request.access_list.clone().unwrap_or_default();
+    if request.gas.is_none() && env.tx.gas_price > U256::ZERO {
+        // no gas limit was provided in the request, so we need to cap the request's gas limit
+        cap_tx_gas_limit_with_caller_allowance(&mut *db, &mut env.tx)?;
+    }
 
-        let precompiles = get_precompiles(&env.cfg.spec_id);
-        let mut inspector = AccessListInspector::new(initial, from, to, precompiles);
-        let (result, _env) = inspect(&mut db, env, &mut inspector)?;
+    let from = request.from.unwrap_or_default();
+    let to = if let Some(to) = request.to {
+        to
+    } else {
+        let nonce = db.basic(from)?.unwrap_or_default().nonce;
+        get_contract_address(from, nonce).into()
+    };
 
-        match result.result {
-            ExecutionResult::Halt { reason, .. } => Err(match reason {
-                Halt::NonceOverflow => RpcInvalidTransactionError::NonceMaxValue,
-                halt => RpcInvalidTransactionError::EvmHalt(halt),
-            }),
-            ExecutionResult::Revert { output, .. } => {
-                Err(RpcInvalidTransactionError::Revert(RevertError::new(output)))
-            }
-            ExecutionResult::Success { .. } => Ok(()),
-        }?;
-        Ok(inspector.into_access_list())
+    let precompiles = get_precompiles(&env.cfg.spec_id);
+    let mut access_list = request.access_list.clone().unwrap_or_default();
+    loop {
+        env.tx.access_list = access_list.clone().flattened();
+        let mut inspector =
+            AccessListInspector::new(access_list.clone(), from, to, precompiles.clone());
+        // the state changes are not committed, so every iteration starts from the same state
+        let (result, _) = inspect(&mut *db, env.clone(), &mut inspector)?;
+
+        let mut next = inspector.into_access_list();
+        next.0.sort_unstable_by_key(|item| item.address);
+        // the inspector starts from the previous access list, so the list only grows and the
+        // loop terminates once no new accounts or slots are touched
+        if next == access_list {
+            return Ok((access_list, result.result))
+        }
+        access_list = next;
     }
 }
 
@@ -323,3 +467,174 @@ where
         ExecutionResult::Halt { reason, .. } => RpcInvalidTransactionError::EvmHalt(reason).into(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eth::{cache::EthStateCache, gas_oracle::GasPriceOracle};
+    use reth_network_api::test_utils::NoopNetwork;
+    use reth_primitives::Address;
+    use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
+    use reth_tasks::TokioTaskExecutor;
+    use reth_transaction_pool::test_utils::{testing_pool, TestPool};
+    use revm::interpreter::opcode;
+
+    fn build_test_eth_api(
+        provider: MockEthProvider,
+        estimate_gas: EstimateGasConfig,
+    ) -> EthApi<MockEthProvider, TestPool, NoopNetwork> {
+        let cache = EthStateCache::spawn(provider.clone(), Default::default());
+        EthApi::with_spawner(
+            provider.clone(),
+            testing_pool(),
+            NoopNetwork,
+            cache.clone(),
+            GasPriceOracle::new(provider, Default::default(), cache),
+            estimate_gas,
+            Box::<TokioTaskExecutor>::default(),
+        )
+    }
+
+    /// Returns code that checks the balance of `rich` if more than `threshold` gas is left, and
+    /// the balance of `poor` otherwise.
+    fn gas_dependent_code(threshold: u32, rich: Address, poor: Address) -> Bytes {
+        let mut code = vec![opcode::GAS, opcode::PUSH4];
+        code.extend(threshold.to_be_bytes());
+        // jump to the `rich` branch at offset 33 if `threshold < gas`
+        code.extend([opcode::LT, opcode::PUSH1, 33, opcode::JUMPI, opcode::PUSH20]);
+        code.extend(poor.as_bytes());
+        code.extend([opcode::BALANCE, opcode::STOP, opcode::JUMPDEST, opcode::PUSH20]);
+        code.extend(rich.as_bytes());
+        code.extend([opcode::BALANCE, opcode::STOP]);
+        code.into()
+    }
+
+    /// Returns a provider with a contract that loads a cold storage slot.
+    ///
+    /// Calling the contract costs 21_000 + 3 (PUSH1) + 2_100 (SLOAD) + 2 (POP) = 23_105 gas.
+    fn sload_contract() -> (MockEthProvider, Address) {
+        let contract = Address::from_low_u64_be(0x1000);
+        let code = vec![opcode::PUSH1, 0, opcode::SLOAD, opcode::POP, opcode::STOP];
+        let provider = MockEthProvider::default();
+        provider
+            .add_account(contract, ExtendedAccount::new(0, U256::ZERO).with_bytecode(code.into()));
+        (provider, contract)
+    }
+
+    fn estimate_gas(
+        provider: &MockEthProvider,
+        contract: Address,
+        estimate_gas: EstimateGasConfig,
+    ) -> U256 {
+        let request = CallRequest {
+            from: Some(Address::from_low_u64_be(0x4000)),
+            to: Some(contract),
+            gas: Some(U256::from(100_000)),
+            ..Default::default()
+        };
+        build_test_eth_api(provider.clone(), estimate_gas)
+            .estimate_gas_with(CfgEnv::default(), BlockEnv::default(), request, provider.clone())
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn estimate_gas_padding() {
+        let (provider, contract) = sload_contract();
+        let config = |padding_percent| EstimateGasConfig { padding_percent, ..Default::default() };
+
+        assert_eq!(estimate_gas(&provider, contract, config(0)), U256::from(23_105));
+        assert_eq!(estimate_gas(&provider, contract, config(10)), U256::from(25_415));
+        // the padded estimate is capped by the gas limit of the request
+        assert_eq!(estimate_gas(&provider, contract, config(1_000)), U256::from(100_000));
+    }
+
+    #[tokio::test]
+    async fn estimate_gas_max_iterations() {
+        let (provider, contract) = sload_contract();
+        let config = |max_iterations| EstimateGasConfig { max_iterations, ..Default::default() };
+
+        // without any iteration, the gas limit of the request is the lowest known to succeed
+        assert_eq!(estimate_gas(&provider, contract, config(0)), U256::from(100_000));
+        // the first iteration succeeds with the midpoint of 21_000 and 100_000
+        assert_eq!(estimate_gas(&provider, contract, config(1)), U256::from(60_500));
+        assert_eq!(
+            estimate_gas(&provider, contract, config(DEFAULT_ESTIMATE_GAS_MAX_ITERATIONS)),
+            U256::from(23_105)
+        );
+    }
+
+    #[tokio::test]
+    async fn estimate_gas_with_access_list() {
+        let (provider, contract) = sload_contract();
+        let config = EstimateGasConfig { with_access_list: true, ..Default::default() };
+
+        // the access list of the contract and its slot would cost 25_405 gas, but the request is
+        // estimated without it
+        assert_eq!(estimate_gas(&provider, contract, config), U256::from(23_105));
+    }
+
+    #[tokio::test]
+    async fn create_access_list_until_stable() {
+        let contract = Address::from_low_u64_be(0x1000);
+        let rich = Address::from_low_u64_be(0x2000);
+        let poor = Address::from_low_u64_be(0x3000);
+        let provider = MockEthProvider::default();
+        provider.add_account(
+            contract,
+            ExtendedAccount::new(0, U256::ZERO)
+                .with_bytecode(gas_dependent_code(77_000, rich, poor)),
+        );
+        let eth_api = build_test_eth_api(provider.clone(), EstimateGasConfig::default());
+
+        let request = CallRequest {
+            from: Some(Address::from_low_u64_be(0x4000)),
+            to: Some(contract),
+            gas: Some(U256::from(100_000)),
+            ..Default::default()
+        };
+        let result = eth_api
+            .create_access_list_with(CfgEnv::default(), BlockEnv::default(), request, provider)
+            .unwrap();
+
+        // without an access list 78_998 gas are left at the GAS opcode, so `rich` is touched.
+        // adding `rich` to the access list costs 2_400 gas, which leaves 76_598 gas, so `poor` is
+        // touched on the next execution.
+        assert_eq!(result.error, None);
+        assert_eq!(
+            result.access_list.0.iter().map(|item| item.address).collect::<Vec<_>>(),
+            vec![rich, poor]
+        );
+    }
+
+    #[tokio::test]
+    async fn create_access_list_revert() {
+        let contract = Address::from_low_u64_be(0x1000);
+        let touched = Address::from_low_u64_be(0x2000);
+        let mut code = vec![opcode::PUSH20];
+        code.extend(touched.as_bytes());
+        code.extend([opcode::BALANCE, opcode::POP, opcode::PUSH1, 0, opcode::PUSH1, 0]);
+        code.push(opcode::REVERT);
+        let provider = MockEthProvider::default();
+        provider
+            .add_account(contract, ExtendedAccount::new(0, U256::ZERO).with_bytecode(code.into()));
+        let eth_api = build_test_eth_api(provider.clone(), EstimateGasConfig::default());
+
+        let request = CallRequest {
+            from: Some(Address::from_low_u64_be(0x4000)),
+            to: Some(contract),
+            gas: Some(U256::from(100_000)),
+            ..Default::default()
+        };
+        let result = eth_api
+            .create_access_list_with(CfgEnv::default(), BlockEnv::default(), request, provider)
+            .unwrap();
+
+        // the list collected up to the revert is returned with the gas used by the execution
+        assert!(result.error.is_some());
+        assert_eq!(
+            result.access_list.0.iter().map(|item| item.address).collect::<Vec<_>>(),
+            vec![touched]
+        );
+        assert!(result.gas_used > U256::from(MIN_TRANSACTION_GAS));
+    }
+}
@@ -37,10 +37,14 @@ where
 
     /// Reports the fee history, for the given amount of blocks, up until the newest block
     /// provided.
+    ///
+    /// The block count is clamped to the configured maximum and to the number of blocks since
+    /// genesis. A request for the pending block is served from the latest block, since the
+    /// pending block is not available as a header.
     pub(crate) async fn fee_history(
         &self,
         mut block_count: u64,
-        newest_block: BlockNumberOrTag,
+        mut newest_block: BlockNumberOrTag,
         reward_percentiles: Option<Vec<f64>>,
     ) -> EthResult<FeeHistory> {
         if block_count == 0 {
@@ -63,21 +67,25 @@ where
             block_count = max_fee_history
         }
 
+        if newest_block.is_pending() {
+            newest_block = BlockNumberOrTag::Latest;
+        }
+
         let Some(end_block) = self.provider().block_number_for_id(newest_block.into())? else {
             return Err(EthApiError::UnknownBlockNumber)
         };
 
         // Check that we would not be querying outside of genesis
-        if end_block < block_count {
-            return Err(EthApiError::InvalidBlockRange)
+        if block_count > end_block + 1 {
+            block_count = end_block + 1;
         }
 
         // If reward percentiles were specified, we need to validate that they are monotonically
         // increasing and 0 <= p <= 100
-        //
-        // Note: The types used ensure that the percentiles are never < 0
         if let Some(percentiles) = &reward_percentiles {
-            if percentiles.windows(2).any(|w| w[0] > w[1] || w[0] > 100.) {
+            if percentiles.iter().any(|p| !(0. ..=100.).contains(p)) ||
+                percentiles.windows(2).any(|w| w[0] > w[1])
+            {
                 return Err(EthApiError::InvalidRewardPercentiles)
             }
         }
@@ -126,7 +134,12 @@ where
         })
     }
 
-    // todo: docs
+    /// Calculates the effective priority fee of the block's transactions at the given
+    /// percentiles.
+    ///
+    /// The transactions are sorted by their priority fee and weighted by the gas they used, so the
+    /// reward at a percentile is the fee of the transaction at which the cumulative gas used
+    /// reaches that percentile of the block's gas used. This matches the computation of geth.
     async fn calculate_reward_percentiles(
         &self,
         percentiles: &[f64],
@@ -28,6 +28,7 @@ mod sign;
 mod state;
 mod transactions;
 
+pub use call::{EstimateGasConfig, DEFAULT_ESTIMATE_GAS_MAX_ITERATIONS};
 pub use transactions::{EthTransactions, TransactionSource};
 
 /// `Eth` API trait.
@@ -85,6 +86,7 @@ where
             network,
             eth_cache,
             gas_oracle,
+            Default::default(),
             Box::<TokioTaskExecutor>::default(),
         )
     }
@@ -96,6 +98,7 @@ where
         network: Network,
         eth_cache: EthStateCache,
         gas_oracle: GasPriceOracle<Provider>,
+        estimate_gas: EstimateGasConfig,
         task_spawner: Box<dyn TaskSpawner>,
     ) -> Self {
         // get the block number of the latest block
@@ -113,6 +116,7 @@ where
             signers: Default::default(),
             eth_cache,
             gas_oracle,
+            estimate_gas,
             starting_block: U256::from(latest_block),
             task_spawner,
         };
@@ -280,6 +284,8 @@ struct EthApiInner<Provider, Pool, Network> {
     eth_cache: EthStateCache,
     /// The async gas oracle frontend for gas price suggestions
     gas_oracle: GasPriceOracle<Provider>,
+    /// Settings for `eth_estimateGas`
+    estimate_gas: EstimateGasConfig,
     /// The block number at which the node started
     starting_block: U256,
     /// The type that can spawn tasks which would otherwise block.
@@ -248,17 +248,13 @@ where
     /// Handler for: `eth_createAccessList`
     async fn create_access_list(
         &self,
-        mut request: CallRequest,
+        request: CallRequest,
         block_number: Option<BlockId>,
     ) -> Result<AccessListWithGasUsed> {
         trace!(target: "rpc::eth", ?request, ?block_number, "Serving eth_createAccessList");
         Ok(self
             .on_blocking_task(|this| async move {
-                let block_id = block_number.unwrap_or(BlockId::Number(BlockNumberOrTag::Latest));
-                let access_list = this.create_access_list_at(request.clone(), block_number).await?;
-                request.access_list = Some(access_list.clone());
-                let gas_used = this.estimate_gas_at(request, block_id).await?;
-                Ok(AccessListWithGasUsed { access_list, gas_used })
+                this.create_access_list_at(request, block_number).await
             })
             .await?)
     }
@@ -370,28 +366,19 @@ where
     /// Handler for: `eth_getProof`
     async fn get_proof(
         &self,
-        _address: Address,
-        _keys: Vec<JsonStorageKey>,
-        _block_number: Option<BlockId>,
+        address: Address,
+        keys: Vec<JsonStorageKey>,
+        block_number: Option<BlockId>,
     ) -> Result<EIP1186AccountProofResponse> {
-        // TODO: uncomment when implemented
-        // trace!(target: "rpc::eth", ?address, ?keys, ?block_number, "Serving eth_getProof");
-        // let res = EthApi::get_proof(self, address, keys, block_number);
-
-        // Ok(res.map_err(|e| match e {
-        //     EthApiError::InvalidBlockRange => {
-        //         internal_rpc_err("eth_getProof is unimplemented for historical blocks")
-        //     }
-        //     _ => e.into(),
-        // })?)
-        Err(internal_rpc_err("unimplemented"))
+        trace!(target: "rpc::eth", ?address, ?keys, ?block_number, "Serving eth_getProof");
+        Ok(EthApi::get_proof(self, address, keys, block_number)?)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::{
-        eth::{cache::EthStateCache, gas_oracle::GasPriceOracle},
+        eth::{cache::EthStateCache, error::EthApiError, gas_oracle::GasPriceOracle},
         EthApi,
     };
     use jsonrpsee::types::error::INVALID_PARAMS_CODE;
@@ -528,7 +515,8 @@ mod tests {
 
         let eth_api = build_test_eth_api(mock_provider);
 
-        // Invalid block range (request is before genesis)
+        // Invalid block range (the count is clamped to genesis, but the provider only has the
+        // last `block_count` headers)
         let response = <EthApi<_, _, _> as EthApiServer>::fee_history(
             &eth_api,
             (newest_block + 1).into(),
@@ -621,4 +609,30 @@ mod tests {
             "all: no percentiles were requested, so there should be no rewards result"
         );
     }
+
+    #[tokio::test]
+    async fn test_fee_history_clamps_to_genesis() {
+        let mock_provider = MockEthProvider::default();
+        for number in 0..3 {
+            let hash = H256::random();
+            let header = Header { number, gas_limit: 100, gas_used: 50, ..Default::default() };
+            mock_provider.add_block(hash, Block { header: header.clone(), ..Default::default() });
+            mock_provider.add_header(hash, header);
+        }
+        let eth_api = build_test_eth_api(mock_provider);
+
+        let fee_history = eth_api.fee_history(10, BlockNumberOrTag::Number(2), None).await.unwrap();
+        assert_eq!(fee_history.oldest_block, U256::ZERO);
+        assert_eq!(fee_history.gas_used_ratio, vec![0.5; 3]);
+        assert_eq!(fee_history.base_fee_per_gas.len(), 4);
+
+        // the pending block is served from the latest block
+        let pending = eth_api.fee_history(1, BlockNumberOrTag::Pending, None).await.unwrap();
+        assert_eq!(pending.oldest_block, U256::from(2));
+
+        // percentiles above 100 are rejected, even if they are the last ones
+        let response =
+            eth_api.fee_history(1, BlockNumberOrTag::Number(2), Some(vec![10.0, 101.0])).await;
+        assert!(matches!(response, Err(EthApiError::InvalidRewardPercentiles)));
+    }
 }
@@ -1,7 +1,7 @@
 //! Contains RPC handler implementations specific to state.
 
 use crate::{
-    eth::error::{EthApiError, EthResult, RpcInvalidTransactionError},
+    eth::error::{EthResult, RpcInvalidTransactionError},
     EthApi,
 };
 use reth_primitives::{
@@ -82,31 +82,13 @@ where
         Ok(H256(value.to_be_bytes()))
     }
 
-    #[allow(unused)]
     pub(crate) fn get_proof(
         &self,
         address: Address,
         keys: Vec<JsonStorageKey>,
         block_id: Option<BlockId>,
     ) -> EthResult<EIP1186AccountProofResponse> {
-        let chain_info = self.provider().chain_info()?;
         let block_id = block_id.unwrap_or(BlockId::Number(BlockNumberOrTag::Latest));
-
-        // if we are trying to create a proof for the latest block, but have a BlockId as input
-        // that is not BlockNumberOrTag::Latest, then we need to figure out whether or not the
-        // BlockId corresponds to the latest block
-        let is_blockid_latest = match block_id {
-            BlockId::Number(BlockNumberOrTag::Number(num)) => num == chain_info.best_number,
-            BlockId::Hash(hash) => hash == chain_info.best_hash.into(),
-            BlockId::Number(BlockNumberOrTag::Latest) => true,
-            _ => false,
-        };
-
-        // TODO: remove when HistoricalStateProviderRef::proof is implemented
-        if !is_blockid_latest {
-            return Err(EthApiError::InvalidBlockRange)
-        }
-
         let state = self.state_at_block_id(block_id)?;
 
         let hash_keys = keys.iter().map(|key| key.0).collect::<Vec<_>>();
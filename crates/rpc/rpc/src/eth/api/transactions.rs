@@ -818,9 +818,6 @@ pub(crate) fn build_transaction_receipt_with_block_receipts(
     receipt: Receipt,
     all_receipts: &[Receipt],
 ) -> EthResult<TransactionReceipt> {
-    let transaction =
-        tx.clone().into_ecrecovered().ok_or(EthApiError::InvalidTransactionSignature)?;
-
     // get the previous transaction cumulative gas used
     let gas_used = if meta.index == 0 {
         receipt.cumulative_gas_used
@@ -832,6 +829,27 @@ pub(crate) fn build_transaction_receipt_with_block_receipts(
             .unwrap_or_default()
     };
 
+    // get number of logs in the block
+    let mut num_logs = 0;
+    for prev_receipt in all_receipts.iter().take(meta.index as usize) {
+        num_logs += prev_receipt.logs.len();
+    }
+
+    build_transaction_receipt(tx, meta, receipt, gas_used, num_logs)
+}
+
+/// Helper function to construct a transaction receipt, given the gas used by the transaction and
+/// the block-wide index of its first log.
+pub(crate) fn build_transaction_receipt(
+    tx: TransactionSigned,
+    meta: TransactionMeta,
+    receipt: Receipt,
+    gas_used: u64,
+    first_log_index: usize,
+) -> EthResult<TransactionReceipt> {
+    let transaction =
+        tx.clone().into_ecrecovered().ok_or(EthApiError::InvalidTransactionSignature)?;
+
     let mut res_receipt = TransactionReceipt {
         transaction_hash: Some(meta.tx_hash),
         transaction_index: Some(U256::from(meta.index)),
@@ -861,12 +879,6 @@ pub(crate) fn build_transaction_receipt_with_block_receipts(
         }
     }
 
-    // get number of logs in the block
-    let mut num_logs = 0;
-    for prev_receipt in all_receipts.iter().take(meta.index as usize) {
-        num_logs += prev_receipt.logs.len();
-    }
-
     for (tx_log_idx, log) in receipt.logs.into_iter().enumerate() {
         let rpclog = Log {
             address: log.address,
@@ -876,7 +888,7 @@ pub(crate) fn build_transaction_receipt_with_block_receipts(
             block_number: Some(U256::from(meta.block_number)),
             transaction_hash: Some(meta.tx_hash),
             transaction_index: Some(U256::from(meta.index)),
-            log_index: Some(U256::from(num_logs + tx_log_idx)),
+            log_index: Some(U256::from(first_log_index + tx_log_idx)),
             removed: false,
         };
         res_receipt.logs.push(rpclog);
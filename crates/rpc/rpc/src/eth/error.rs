@@ -116,6 +116,12 @@ impl From<EthApiError> for RpcError {
         RpcError::Call(error.into())
     }
 }
+impl From<std::convert::Infallible> for EthApiError {
+    fn from(err: std::convert::Infallible) -> Self {
+        match err {}
+    }
+}
+
 impl From<JsInspectorError> for EthApiError {
     fn from(error: JsInspectorError) -> Self {
         match error {
@@ -1,15 +1,12 @@
 use super::cache::EthStateCache;
 use crate::{
-    eth::{
-        error::{EthApiError, EthResult},
-        logs_utils,
-    },
+    eth::{error::EthApiError, logs_utils},
     result::{rpc_error_with_code, ToRpcResult},
     EthSubscriptionIdProvider,
 };
 use async_trait::async_trait;
 use jsonrpsee::{core::RpcResult, server::IdProvider};
-use reth_primitives::{BlockHashOrNumber, Receipt, SealedBlock};
+use reth_primitives::{BlockHashOrNumber, ReceiptWithMeta};
 use reth_provider::{BlockIdReader, BlockReader, EvmEnvProvider};
 use reth_rpc_api::EthFilterApiServer;
 use reth_rpc_types::{Filter, FilterBlockOption, FilterChanges, FilterId, FilteredParams, Log};
@@ -35,14 +32,16 @@ impl<Provider, Pool> EthFilter<Provider, Pool> {
     /// Creates a new, shareable instance.
     ///
     /// This uses the given pool to get notified about new transactions, the provider to interact
-    /// with the blockchain, the cache to fetch cacheable data, like the logs and the
+    /// with the blockchain, the cache to fetch cacheable data, like the logs, the
     /// max_logs_per_response to limit the amount of logs returned in a single response
-    /// `eth_getLogs`
+    /// `eth_getLogs` and the max_blocks_per_filter to limit the block range of a single
+    /// `eth_getLogs` query. A limit of zero disables the respective limit.
     pub fn new(
         provider: Provider,
         pool: Pool,
         eth_cache: EthStateCache,
         max_logs_per_response: usize,
+        max_blocks_per_filter: u64,
         task_spawner: Box<dyn TaskSpawner>,
     ) -> Self {
         let inner = EthFilterInner {
@@ -51,6 +50,7 @@ impl<Provider, Pool> EthFilter<Provider, Pool> {
             pool,
             id_provider: Arc::new(EthSubscriptionIdProvider::default()),
             max_logs_per_response,
+            max_blocks_per_filter,
             eth_cache,
             max_headers_range: MAX_HEADERS_RANGE,
             task_spawner,
@@ -69,10 +69,11 @@ where
     Provider: BlockReader + BlockIdReader + EvmEnvProvider + 'static,
     Pool: TransactionPool + 'static,
 {
-    /// Executes the given filter on a new task.
+    /// Executes the given filter on a new blocking task.
     ///
-    /// All the filter handles are implemented asynchronously. However, filtering is still a bit CPU
-    /// intensive.
+    /// All the filter handles are implemented asynchronously. However, filtering reads headers and
+    /// receipts from the database and is still a bit CPU intensive, so it runs on the blocking
+    /// pool.
     async fn spawn_filter_task<C, F, R>(&self, c: C) -> Result<R, FilterError>
     where
         C: FnOnce(Self) -> F,
@@ -82,7 +83,7 @@ where
         let (tx, rx) = oneshot::channel();
         let this = self.clone();
         let f = c(this);
-        self.inner.task_spawner.spawn(Box::pin(async move {
+        self.inner.task_spawner.spawn_blocking(Box::pin(async move {
             let res = f.await;
             let _ = tx.send(res);
         }));
@@ -262,8 +263,10 @@ struct EthFilterInner<Provider, Pool> {
     active_filters: ActiveFilters,
     /// Provides ids to identify filters
     id_provider: Arc<dyn IdProvider>,
-    /// Maximum number of logs that can be returned in a response
+    /// Maximum number of logs that can be returned in a response, zero for unlimited
     max_logs_per_response: usize,
+    /// Maximum block range of a log query, zero for unlimited
+    max_blocks_per_filter: u64,
     /// The async cache frontend for eth related data
     eth_cache: EthStateCache,
     /// maximum number of headers to read at once for range filter
@@ -286,14 +289,13 @@ where
                 if let Some((block, receipts)) =
                     self.eth_cache.get_block_and_receipts(block_hash).await?
                 {
-                    let filter = FilteredParams::new(Some(filter));
-                    logs_utils::append_matching_block_logs(
-                        &mut all_logs,
-                        &filter,
-                        (block_hash, block.number).into(),
+                    let receipts = ReceiptWithMeta::from_block_receipts(
+                        block.num_hash(),
+                        block.base_fee_per_gas,
                         block.body.into_iter().map(|tx| tx.hash()).zip(receipts),
-                        false,
                     );
+                    let filter = FilteredParams::new(Some(filter));
+                    logs_utils::append_matching_block_logs(&mut all_logs, &filter, receipts, false);
                 }
                 Ok(all_logs)
             }
@@ -313,6 +315,14 @@ where
                     .flatten();
                 let (from_block_number, to_block_number) =
                     logs_utils::get_filter_block_range(from, to, start_block, info);
+
+                if self.max_blocks_per_filter > 0 &&
+                    to_block_number.saturating_sub(from_block_number) >=
+                        self.max_blocks_per_filter
+                {
+                    return Err(FilterError::QueryExceedsMaxBlocks(self.max_blocks_per_filter))
+                }
+
                 self.get_logs_in_block_range(&filter, from_block_number, to_block_number).await
             }
         }
@@ -334,24 +344,18 @@ where
         Ok(id)
     }
 
-    /// Fetches both receipts and block for the given block number.
-    async fn block_and_receipts_by_number(
-        &self,
-        hash_or_number: BlockHashOrNumber,
-    ) -> EthResult<Option<(SealedBlock, Vec<Receipt>)>> {
-        let block_hash = match self.provider.convert_block_hash(hash_or_number)? {
-            Some(hash) => hash,
-            None => return Ok(None),
-        };
-
-        Ok(self.eth_cache.get_block_and_receipts(block_hash).await?)
-    }
-
     /// Returns all logs in the given _inclusive_ range that match the filter
     ///
     /// Returns an error if:
     ///  - underlying database error
     ///  - amount of matches exceeds configured limit
+    ///
+    /// The bloom filter of each header is checked first, so receipts are only loaded for blocks
+    /// that may contain matching logs. The receipts are read together with their position in the
+    /// block, without computing their individual blooms.
+    ///
+    /// This reads from the database and should be called from a blocking task, see
+    /// [Self::spawn_filter_task].
     async fn get_logs_in_block_range(
         &self,
         filter: &Filter,
@@ -390,25 +394,25 @@ where
                 if FilteredParams::matches_address(header.logs_bloom, &address_filter) &&
                     FilteredParams::matches_topics(header.logs_bloom, &topics_filter)
                 {
-                    if let Some((block, receipts)) =
-                        self.block_and_receipts_by_number(num_hash).await?
-                    {
-                        let block_hash = block.hash;
-
+                    if let Some(receipts) = self.provider.receipts_with_meta(num_hash)? {
                         logs_utils::append_matching_block_logs(
                             &mut all_logs,
                             &filter_params,
-                            (block.number, block_hash).into(),
-                            block.body.into_iter().map(|tx| tx.hash()).zip(receipts),
+                            receipts,
                             false,
                         );
 
                         // size check but only if range is multiple blocks, so we always return all
                         // logs of a single block
-                        if is_multi_block_range && all_logs.len() > self.max_logs_per_response {
-                            return Err(FilterError::QueryExceedsMaxResults(
-                                self.max_logs_per_response,
-                            ))
+                        if is_multi_block_range &&
+                            self.max_logs_per_response > 0 &&
+                            all_logs.len() > self.max_logs_per_response
+                        {
+                            return Err(FilterError::QueryExceedsMaxResults {
+                                max_logs: self.max_logs_per_response,
+                                from_block,
+                                to_block: header.number.saturating_sub(1).max(from_block),
+                            })
                         }
                     }
                 }
@@ -448,8 +452,10 @@ enum FilterKind {
 pub enum FilterError {
     #[error("filter not found")]
     FilterNotFound(FilterId),
-    #[error("Query exceeds max results {0}")]
-    QueryExceedsMaxResults(usize),
+    #[error("query exceeds max block range {0}")]
+    QueryExceedsMaxBlocks(u64),
+    #[error("query exceeds max results {max_logs}, retry with the range {from_block}-{to_block}")]
+    QueryExceedsMaxResults { max_logs: usize, from_block: u64, to_block: u64 },
     #[error(transparent)]
     EthAPIError(#[from] EthApiError),
     /// Error thrown when a spawned task failed to deliver a response.
@@ -469,7 +475,8 @@ impl From<FilterError> for jsonrpsee::types::error::ErrorObject<'static> {
                 rpc_error_with_code(jsonrpsee::types::error::INTERNAL_ERROR_CODE, err.to_string())
             }
             FilterError::EthAPIError(err) => err.into(),
-            err @ FilterError::QueryExceedsMaxResults(_) => {
+            err @ (FilterError::QueryExceedsMaxBlocks(_) |
+            FilterError::QueryExceedsMaxResults { .. }) => {
                 rpc_error_with_code(jsonrpsee::types::error::INVALID_PARAMS_CODE, err.to_string())
             }
         }
@@ -512,7 +519,44 @@ impl Iterator for BlockRangeInclusiveIter {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use assert_matches::assert_matches;
     use rand::{thread_rng, Rng};
+    use reth_primitives::{Header, H256};
+    use reth_provider::test_utils::MockEthProvider;
+    use reth_tasks::TokioTaskExecutor;
+    use reth_transaction_pool::test_utils::testing_pool;
+
+    #[tokio::test]
+    async fn test_logs_max_blocks_per_filter() {
+        let provider = MockEthProvider::default();
+        provider.extend_headers(
+            (0..=20).map(|number| (H256::random(), Header { number, ..Default::default() })),
+        );
+        let cache = EthStateCache::spawn(provider.clone(), Default::default());
+        let eth_filter = EthFilter::new(
+            provider,
+            testing_pool(),
+            cache,
+            0,
+            10,
+            Box::new(TokioTaskExecutor::default()),
+        );
+
+        // a range of 10 blocks is within the limit
+        let filter = Filter::new().from_block(0u64).to_block(9u64);
+        assert_matches!(eth_filter.inner.logs_for_filter(filter).await, Ok(logs) if logs.is_empty());
+
+        // a range of 11 blocks exceeds the limit
+        let filter = Filter::new().from_block(0u64).to_block(10u64);
+        assert_matches!(
+            eth_filter.inner.logs_for_filter(filter).await,
+            Err(FilterError::QueryExceedsMaxBlocks(10))
+        );
+
+        // the range is checked after capping it at the best block
+        let filter = Filter::new().from_block(15u64).to_block(100u64);
+        assert_matches!(eth_filter.inner.logs_for_filter(filter).await, Ok(_));
+    }
 
     #[test]
     fn test_block_range_iter() {
@@ -1,52 +1,48 @@
-use reth_primitives::{BlockNumHash, ChainInfo, Receipt, TxHash, U256};
+use reth_primitives::{BlockNumHash, ChainInfo, ReceiptWithMeta, U256};
 use reth_rpc_types::{FilteredParams, Log};
 
-/// Returns all matching logs of a block's receipts grouped with the hash of their transaction.
+/// Returns all matching logs of a block's receipts.
 pub(crate) fn matching_block_logs<I>(
     filter: &FilteredParams,
-    block: BlockNumHash,
-    tx_and_receipts: I,
+    receipts: I,
     removed: bool,
 ) -> Vec<Log>
 where
-    I: IntoIterator<Item = (TxHash, Receipt)>,
+    I: IntoIterator<Item = ReceiptWithMeta>,
 {
     let mut all_logs = Vec::new();
-    append_matching_block_logs(&mut all_logs, filter, block, tx_and_receipts, removed);
+    append_matching_block_logs(&mut all_logs, filter, receipts, removed);
     all_logs
 }
 
-/// Appends all matching logs of a block's receipts grouped with the hash of their transaction
+/// Appends all matching logs of a block's receipts.
+///
+/// The position of each log is taken from the meta of its receipt.
 pub(crate) fn append_matching_block_logs<I>(
     all_logs: &mut Vec<Log>,
     filter: &FilteredParams,
-    block: BlockNumHash,
-    tx_and_receipts: I,
+    receipts: I,
     removed: bool,
 ) where
-    I: IntoIterator<Item = (TxHash, Receipt)>,
+    I: IntoIterator<Item = ReceiptWithMeta>,
 {
-    let block_number_u256 = U256::from(block.number);
-    // tracks the index of a log in the entire block
-    let mut log_index: u32 = 0;
-    for (transaction_idx, (transaction_hash, receipt)) in tx_and_receipts.into_iter().enumerate() {
-        let logs = receipt.logs;
-        for log in logs.into_iter() {
+    for ReceiptWithMeta { receipt, meta, first_log_index } in receipts {
+        let block = BlockNumHash::new(meta.block_number, meta.block_hash);
+        for (tx_log_idx, log) in receipt.logs.into_iter().enumerate() {
             if log_matches_filter(block, &log, filter) {
                 let log = Log {
                     address: log.address,
                     topics: log.topics,
                     data: log.data,
-                    block_hash: Some(block.hash),
-                    block_number: Some(block_number_u256),
-                    transaction_hash: Some(transaction_hash),
-                    transaction_index: Some(U256::from(transaction_idx)),
-                    log_index: Some(U256::from(log_index)),
+                    block_hash: Some(meta.block_hash),
+                    block_number: Some(U256::from(meta.block_number)),
+                    transaction_hash: Some(meta.tx_hash),
+                    transaction_index: Some(U256::from(meta.index)),
+                    log_index: Some(U256::from(first_log_index + tx_log_idx as u64)),
                     removed,
                 };
                 all_logs.push(log);
             }
-            log_index += 1;
         }
     }
 }
@@ -97,9 +93,36 @@ pub(crate) fn get_filter_block_range(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use reth_primitives::BlockNumberOrTag;
+    use reth_primitives::{BlockNumberOrTag, Receipt, H256};
     use reth_rpc_types::Filter;
 
+    #[test]
+    fn test_matching_block_logs_positions() {
+        let log = reth_primitives::Log::default();
+        let receipt = |logs: usize| Receipt { logs: vec![log.clone(); logs], ..Default::default() };
+        let block = BlockNumHash::new(1, H256::random());
+        let tx_hashes = [H256::random(), H256::random(), H256::random()];
+        let receipts = ReceiptWithMeta::from_block_receipts(
+            block,
+            None,
+            tx_hashes.into_iter().zip([receipt(2), receipt(0), receipt(1)]),
+        );
+
+        let logs = matching_block_logs(&FilteredParams::new(None), receipts, false);
+        assert_eq!(
+            logs.iter()
+                .map(|log| (log.transaction_hash.unwrap(), log.log_index.unwrap()))
+                .collect::<Vec<_>>(),
+            vec![
+                (tx_hashes[0], U256::from(0)),
+                (tx_hashes[0], U256::from(1)),
+                (tx_hashes[2], U256::from(2)),
+            ]
+        );
+        assert_eq!(logs[2].transaction_index, Some(U256::from(2)));
+        assert!(logs.iter().all(|log| log.block_hash == Some(block.hash)));
+    }
+
     #[test]
     fn test_log_range_from_and_to() {
         let from = 14000000u64;
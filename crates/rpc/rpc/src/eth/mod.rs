@@ -12,7 +12,10 @@ pub(crate) mod revm_utils;
 mod signer;
 pub(crate) mod utils;
 
-pub use api::{EthApi, EthApiSpec, EthTransactions, TransactionSource};
+pub use api::{
+    EstimateGasConfig, EthApi, EthApiSpec, EthTransactions, TransactionSource,
+    DEFAULT_ESTIMATE_GAS_MAX_ITERATIONS,
+};
 pub use filter::EthFilter;
 pub use id_provider::EthSubscriptionIdProvider;
 pub use pubsub::EthPubSub;
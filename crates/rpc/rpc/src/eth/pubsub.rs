@@ -2,28 +2,42 @@
 use crate::eth::logs_utils;
 use futures::StreamExt;
 use jsonrpsee::{server::SubscriptionMessage, PendingSubscriptionSink, SubscriptionSink};
+use reth_metrics::{
+    metrics::{self, Counter},
+    Metrics,
+};
 use reth_network_api::NetworkInfo;
-use reth_primitives::TxHash;
-use reth_provider::{BlockReader, CanonStateSubscriptions, EvmEnvProvider};
+use reth_primitives::{IntoRecoveredTransaction, ReceiptWithMeta, SealedHeader, TxHash};
+use reth_provider::{
+    BlockReader, CanonStateSubscriptions, EvmEnvProvider, ForkchoiceSubscriptions,
+};
 use reth_rpc_api::EthPubSubApiServer;
 use reth_rpc_types::FilteredParams;
-use std::sync::Arc;
+use std::{future::Future, sync::Arc};
 
 use reth_rpc_types::{
     pubsub::{
         Params, PubSubSyncStatus, SubscriptionKind, SubscriptionResult as EthSubscriptionResult,
         SyncStatusMetadata,
     },
-    Header, Log,
+    Header, Log, Transaction,
 };
 use reth_tasks::{TaskSpawner, TokioTaskExecutor};
-use reth_transaction_pool::TransactionPool;
+use reth_transaction_pool::{NewTransactionEvent, TransactionPool};
 use serde::Serialize;
+use tokio::sync::{
+    broadcast::{self, error::RecvError},
+    watch,
+};
 use tokio_stream::{
-    wrappers::{BroadcastStream, ReceiverStream},
+    wrappers::{BroadcastStream, ReceiverStream, WatchStream},
     Stream,
 };
 
+/// The maximum number of full pending transactions that are buffered per subscription if the
+/// subscriber can't keep up.
+const FULL_PENDING_TRANSACTIONS_BUFFER: usize = 1024;
+
 /// `Eth` pubsub RPC implementation.
 ///
 /// This handles `eth_subscribe` RPC calls.
@@ -68,7 +82,7 @@ impl<Provider, Pool, Events, Network> EthPubSub<Provider, Pool, Events, Network>
 impl<Provider, Pool, Events, Network> EthPubSubApiServer
     for EthPubSub<Provider, Pool, Events, Network>
 where
-    Provider: BlockReader + EvmEnvProvider + Clone + 'static,
+    Provider: BlockReader + EvmEnvProvider + ForkchoiceSubscriptions + Clone + 'static,
     Pool: TransactionPool + 'static,
     Events: CanonStateSubscriptions + Clone + 'static,
     Network: NetworkInfo + Clone + 'static,
@@ -98,7 +112,7 @@ async fn handle_accepted<Provider, Pool, Events, Network>(
     params: Option<Params>,
 ) -> Result<(), jsonrpsee::core::Error>
 where
-    Provider: BlockReader + EvmEnvProvider + Clone + 'static,
+    Provider: BlockReader + EvmEnvProvider + ForkchoiceSubscriptions + Clone + 'static,
     Pool: TransactionPool + 'static,
     Events: CanonStateSubscriptions + Clone + 'static,
     Network: NetworkInfo + Clone + 'static,
@@ -121,10 +135,35 @@ where
             pipe_from_stream(accepted_sink, stream).await
         }
         SubscriptionKind::NewPendingTransactions => {
+            if let Some(Params::Bool(true)) = params {
+                let stream = pubsub.full_pending_transaction_stream().map(|event| {
+                    let tx = event.transaction.to_recovered_transaction();
+                    EthSubscriptionResult::FullTransaction(Box::new(Transaction::from_recovered(
+                        tx,
+                    )))
+                });
+                let dropped = PubSubMetrics::default().dropped_full_pending_transactions;
+                return pipe_from_stream_drop_oldest(
+                    accepted_sink,
+                    stream,
+                    FULL_PENDING_TRANSACTIONS_BUFFER,
+                    dropped,
+                )
+                .await
+            }
+
             let stream =
                 pubsub.pending_transaction_stream().map(EthSubscriptionResult::TransactionHash);
             pipe_from_stream(accepted_sink, stream).await
         }
+        SubscriptionKind::FinalizedHeaders => {
+            let stream = headers_stream(pubsub.provider.subscribe_to_finalized_header());
+            pipe_from_stream(accepted_sink, stream).await
+        }
+        SubscriptionKind::SafeHeaders => {
+            let stream = headers_stream(pubsub.provider.subscribe_to_safe_header());
+            pipe_from_stream(accepted_sink, stream).await
+        }
         SubscriptionKind::Syncing => {
             // get new block subscription
             let mut canon_state =
@@ -160,6 +199,18 @@ where
     }
 }
 
+/// Returns a stream that yields the current header of the receiver, if any, and all headers it
+/// changes to.
+fn headers_stream(
+    headers: watch::Receiver<Option<SealedHeader>>,
+) -> impl Stream<Item = EthSubscriptionResult> + Unpin {
+    WatchStream::new(headers).filter_map(|header| {
+        futures::future::ready(header.map(|header| {
+            EthSubscriptionResult::Header(Box::new(Header::from_primitive_with_hash(header).into()))
+        }))
+    })
+}
+
 /// Pipes all stream items to the subscription sink.
 async fn pipe_from_stream<T, St>(
     sink: SubscriptionSink,
@@ -192,6 +243,83 @@ where
     }
 }
 
+/// Pipes all stream items to the subscription sink, buffering at most `capacity` items if the sink
+/// is slower than the stream.
+///
+/// If the buffer is full, the oldest items are dropped and counted in `dropped`.
+async fn pipe_from_stream_drop_oldest<T, St>(
+    sink: SubscriptionSink,
+    stream: St,
+    capacity: usize,
+    dropped: Counter,
+) -> Result<(), jsonrpsee::core::Error>
+where
+    St: Stream<Item = T> + Unpin,
+    T: Serialize + Clone,
+{
+    let sink = &sink;
+    let send = forward_drop_oldest(stream, capacity, dropped, |item| async move {
+        let msg = SubscriptionMessage::from_json(&item)?;
+        Ok::<_, jsonrpsee::core::Error>(sink.send(msg).await.is_ok())
+    });
+
+    tokio::select! {
+        _ = sink.closed() => Ok(()),
+        res = send => res,
+    }
+}
+
+/// Forwards all stream items to `send`, buffering at most `capacity` items if `send` is slower
+/// than the stream.
+///
+/// If the buffer is full, the oldest items are dropped and counted in `dropped`. Forwarding stops
+/// once `send` returns `false` or all items of the ended stream were sent.
+async fn forward_drop_oldest<T, St, F, Fut, E>(
+    mut stream: St,
+    capacity: usize,
+    dropped: Counter,
+    mut send: F,
+) -> Result<(), E>
+where
+    St: Stream<Item = T> + Unpin,
+    T: Clone,
+    F: FnMut(T) -> Fut,
+    Fut: Future<Output = Result<bool, E>>,
+{
+    // the broadcast channel overwrites the oldest items if the receiver lags behind
+    let (tx, mut rx) = broadcast::channel(capacity);
+    let forward = async move {
+        while let Some(item) = stream.next().await {
+            let _ = tx.send(item);
+        }
+    };
+    let deliver = async {
+        loop {
+            let item = match rx.recv().await {
+                Ok(item) => item,
+                Err(RecvError::Lagged(skipped)) => {
+                    dropped.increment(skipped);
+                    continue
+                }
+                // stream ended and all buffered items were sent
+                Err(RecvError::Closed) => return Ok(()),
+            };
+            if !send(item).await? {
+                return Ok(())
+            }
+        }
+    };
+    tokio::pin!(deliver);
+
+    tokio::select! {
+        res = &mut deliver => return res,
+        _ = forward => {}
+    }
+
+    // the stream ended, send the remaining buffered items
+    deliver.await
+}
+
 impl<Provider, Pool, Events, Network> std::fmt::Debug
     for EthPubSub<Provider, Pool, Events, Network>
 {
@@ -244,6 +372,15 @@ where
     fn pending_transaction_stream(&self) -> impl Stream<Item = TxHash> {
         ReceiverStream::new(self.pool.pending_transactions_listener())
     }
+
+    /// Returns a stream that yields all transactions that are added to the pending sub-pool of
+    /// the txpool.
+    fn full_pending_transaction_stream(
+        &self,
+    ) -> impl Stream<Item = NewTransactionEvent<Pool::Transaction>> {
+        ReceiverStream::new(self.pool.new_transactions_listener())
+            .filter(|event| futures::future::ready(event.subpool.is_pending()))
+    }
 }
 
 impl<Provider, Pool, Events, Network> EthPubSubInner<Provider, Pool, Events, Network>
@@ -281,13 +418,51 @@ where
             })
             .flat_map(futures::stream::iter)
             .flat_map(move |(block_receipts, removed)| {
-                let all_logs = logs_utils::matching_block_logs(
-                    &filter,
+                // the base fee is not part of a log
+                let receipts = ReceiptWithMeta::from_block_receipts(
                     block_receipts.block,
+                    None,
                     block_receipts.tx_receipts,
-                    removed,
                 );
+                let all_logs = logs_utils::matching_block_logs(&filter, receipts, removed);
                 futures::stream::iter(all_logs)
             })
     }
 }
+
+/// Metrics for the `eth_` pubsub subscriptions.
+#[derive(Metrics)]
+#[metrics(scope = "rpc.eth_pubsub")]
+struct PubSubMetrics {
+    /// The number of full pending transactions that were dropped because a subscriber couldn't
+    /// keep up.
+    dropped_full_pending_transactions: Counter,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    #[tokio::test]
+    async fn forward_drops_oldest_items() {
+        let dropped = Arc::new(AtomicU64::new(0));
+        let mut sent = Vec::new();
+
+        // the stream is always ready, so all items are buffered before the first one is sent
+        let res: Result<(), ()> = forward_drop_oldest(
+            futures::stream::iter(0..10u64),
+            4,
+            Counter::from_arc(dropped.clone()),
+            |item| {
+                sent.push(item);
+                async { Ok(true) }
+            },
+        )
+        .await;
+
+        assert!(res.is_ok());
+        assert_eq!(sent, vec![6, 7, 8, 9]);
+        assert_eq!(dropped.load(Ordering::Relaxed), 6);
+    }
+}
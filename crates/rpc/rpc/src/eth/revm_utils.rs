@@ -546,4 +546,49 @@ mod tests {
             CallFees::ensure_fees(None, None, None, U256::from(99)).unwrap();
         assert_eq!(gas_price, U256::ZERO);
     }
+
+    #[test]
+    fn test_state_overrides_storage() {
+        let account = Address::random();
+        let (slot_1, slot_2) = (H256::from_low_u64_be(1), H256::from_low_u64_be(2));
+        let mut db = CacheDB::new(EmptyDB::default());
+        db.insert_account_info(account, Default::default());
+        db.insert_account_storage(account, U256::from(1), U256::from(10)).unwrap();
+        db.insert_account_storage(account, U256::from(2), U256::from(20)).unwrap();
+
+        // `stateDiff` only overrides the given slots
+        let mut diff_db = db.clone();
+        let state_diff = AccountOverride {
+            balance: Some(U256::from(100)),
+            state_diff: Some([(slot_1, H256::from_low_u64_be(11))].into()),
+            ..Default::default()
+        };
+        apply_state_overrides([(account, state_diff)].into(), &mut diff_db).unwrap();
+        assert_eq!(
+            DatabaseRef::basic(&diff_db, account).unwrap().unwrap().balance,
+            U256::from(100)
+        );
+        assert_eq!(DatabaseRef::storage(&diff_db, account, U256::from(1)).unwrap(), U256::from(11));
+        assert_eq!(DatabaseRef::storage(&diff_db, account, U256::from(2)).unwrap(), U256::from(20));
+
+        // `state` replaces the entire storage
+        let state = AccountOverride {
+            state: Some([(slot_2, H256::from_low_u64_be(22))].into()),
+            ..Default::default()
+        };
+        apply_state_overrides([(account, state)].into(), &mut db).unwrap();
+        assert_eq!(DatabaseRef::storage(&db, account, U256::from(1)).unwrap(), U256::ZERO);
+        assert_eq!(DatabaseRef::storage(&db, account, U256::from(2)).unwrap(), U256::from(22));
+
+        // both at once are rejected
+        let both = AccountOverride {
+            state: Some(Default::default()),
+            state_diff: Some(Default::default()),
+            ..Default::default()
+        };
+        assert!(matches!(
+            apply_state_overrides([(account, both)].into(), &mut db),
+            Err(EthApiError::BothStateAndStateDiffInOverride(_))
+        ));
+    }
 }
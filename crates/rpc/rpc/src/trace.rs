@@ -6,7 +6,6 @@ use crate::{
         utils::recover_raw_transaction,
         EthTransactions,
     },
-    result::internal_rpc_err,
     TracingCallGuard,
 };
 use async_trait::async_trait;
@@ -51,12 +50,16 @@ impl<Provider, Eth> TraceApi<Provider, Eth> {
     }
 
     /// Create a new instance of the [TraceApi]
+    ///
+    /// `max_trace_filter_blocks` limits the block range of a single `trace_filter` call, 0 means
+    /// unlimited.
     pub fn new(
         provider: Provider,
         eth_api: Eth,
         eth_cache: EthStateCache,
         task_spawner: Box<dyn TaskSpawner>,
         tracing_call_guard: TracingCallGuard,
+        max_trace_filter_blocks: u64,
     ) -> Self {
         let inner = Arc::new(TraceApiInner {
             provider,
@@ -64,6 +67,7 @@ impl<Provider, Eth> TraceApi<Provider, Eth> {
             eth_cache,
             task_spawner,
             tracing_call_guard,
+            max_trace_filter_blocks,
         });
         Self { inner }
     }
@@ -450,6 +454,51 @@ where
         Ok(maybe_traces)
     }
 
+    /// Returns all traces in the block range of the filter that match its addresses.
+    ///
+    /// The blocks are replayed one after another, and the matching traces, including the block
+    /// reward traces, are paginated with the `after` and `count` fields of the filter. Replaying
+    /// stops as soon as `count` traces were collected.
+    pub async fn trace_filter(
+        &self,
+        filter: TraceFilter,
+    ) -> EthResult<Vec<LocalizedTransactionTrace>> {
+        let best_block = self.provider().best_block_number()?;
+        let to_block = filter.to_block.unwrap_or(best_block);
+        let from_block = filter.from_block.unwrap_or(to_block);
+        if from_block > to_block {
+            return Err(EthApiError::InvalidBlockRange)
+        }
+        if to_block > best_block {
+            return Err(EthApiError::UnknownBlockNumber)
+        }
+        let max_blocks = self.inner.max_trace_filter_blocks;
+        if max_blocks > 0 && to_block - from_block >= max_blocks {
+            return Err(EthApiError::InvalidParams(format!(
+                "block range exceeds the maximum of {max_blocks} blocks"
+            )))
+        }
+
+        let matcher = filter.matcher();
+        let mut skip = filter.after.unwrap_or_default();
+        let count = filter.count.unwrap_or(usize::MAX);
+        let mut traces = Vec::new();
+        for block_number in from_block..=to_block {
+            if traces.len() >= count {
+                break
+            }
+            let Some(block_traces) = self.trace_block(block_number.into()).await? else { continue };
+            paginate(
+                block_traces.into_iter().filter(|trace| matcher.matches(&trace.trace)),
+                &mut skip,
+                count,
+                &mut traces,
+            );
+        }
+
+        Ok(traces)
+    }
+
     /// Replays all transactions in a block
     pub async fn replay_block_transactions(
         &self,
@@ -564,8 +613,9 @@ where
     }
 
     /// Handler for `trace_filter`
-    async fn trace_filter(&self, _filter: TraceFilter) -> Result<Vec<LocalizedTransactionTrace>> {
-        Err(internal_rpc_err("unimplemented"))
+    async fn trace_filter(&self, filter: TraceFilter) -> Result<Vec<LocalizedTransactionTrace>> {
+        let _permit = self.acquire_trace_permit().await;
+        Ok(TraceApi::trace_filter(self, filter).await?)
     }
 
     /// Returns transaction trace at given index.
@@ -606,12 +656,14 @@ struct TraceApiInner<Provider, Eth> {
     /// Access to commonly used code of the `eth` namespace
     eth_api: Eth,
     /// The async cache frontend for eth-related data
-    #[allow(unused)] // we need this for trace_filter eventually
+    #[allow(unused)]
     eth_cache: EthStateCache,
     /// The type that can spawn tasks which would otherwise be blocking.
     task_spawner: Box<dyn TaskSpawner>,
     // restrict the number of concurrent calls to `trace_*`
     tracing_call_guard: TracingCallGuard,
+    /// The maximum number of blocks of a single `trace_filter` call, 0 means unlimited.
+    max_trace_filter_blocks: u64,
 }
 
 /// Returns the [TracingInspectorConfig] depending on the enabled [TraceType]s
@@ -640,10 +692,49 @@ fn reward_trace(header: &SealedHeader, reward: RewardAction) -> LocalizedTransac
     }
 }
 
+/// Appends the `items` to `out` until it holds `count` items, after skipping the first `skip`
+/// items.
+///
+/// `skip` is decremented by the number of skipped items, so the pagination can continue with the
+/// items of the next block.
+fn paginate<T>(
+    items: impl IntoIterator<Item = T>,
+    skip: &mut usize,
+    count: usize,
+    out: &mut Vec<T>,
+) {
+    for item in items {
+        if *skip > 0 {
+            *skip -= 1;
+            continue
+        }
+        if out.len() >= count {
+            break
+        }
+        out.push(item);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_paginate_across_blocks() {
+        let mut skip = 3;
+        let mut out = Vec::new();
+        paginate(0..2, &mut skip, 4, &mut out);
+        assert!(out.is_empty());
+        assert_eq!(skip, 1);
+
+        paginate(2..5, &mut skip, 4, &mut out);
+        assert_eq!(out, vec![3, 4]);
+        assert_eq!(skip, 0);
+
+        paginate(5..10, &mut skip, 4, &mut out);
+        assert_eq!(out, vec![3, 4, 5, 6]);
+    }
+
     #[test]
     fn test_parity_config() {
         let mut s = HashSet::new();
@@ -3,11 +3,14 @@ use jsonrpsee::core::RpcResult as Result;
 use reth_primitives::{Address, U256, U64};
 use reth_rpc_api::TxPoolApiServer;
 use reth_rpc_types::{
-    txpool::{TxpoolContent, TxpoolContentFrom, TxpoolInspect, TxpoolInspectSummary, TxpoolStatus},
+    txpool::{
+        TxpoolContent, TxpoolContentFrom, TxpoolContentPage, TxpoolInspect, TxpoolInspectSummary,
+        TxpoolStatus,
+    },
     Transaction,
 };
 use reth_transaction_pool::{AllPoolTransactions, PoolTransaction, TransactionPool};
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use tracing::trace;
 
 /// `txpool` API implementation.
@@ -55,6 +58,34 @@ where
 
         content
     }
+
+    /// Returns the transactions of at most `limit` senders, starting at sender `from`.
+    ///
+    /// The page is built from a single snapshot of the pool and contains all transactions of its
+    /// senders. Since pages are keyed by sender, consecutive pages never overlap.
+    fn content_page(&self, limit: usize, from: Option<Address>) -> TxpoolContentPage {
+        let AllPoolTransactions { pending, queued } = self.pool.all_transactions();
+
+        let mut senders = pending
+            .iter()
+            .chain(queued.iter())
+            .map(|tx| tx.sender())
+            .filter(|sender| from.map_or(true, |from| *sender >= from))
+            .collect::<BTreeSet<_>>()
+            .into_iter();
+        let page = senders.by_ref().take(limit.max(1)).collect::<BTreeSet<_>>();
+
+        let mut content = TxpoolContentPage { next: senders.next(), ..Default::default() };
+        for (txs, target) in [(pending, &mut content.pending), (queued, &mut content.queued)] {
+            for tx in txs.into_iter().filter(|tx| page.contains(&tx.sender())) {
+                let tx = &tx.transaction;
+                let tx_content = Transaction::from_recovered(tx.to_recovered_transaction());
+                target.entry(tx.sender()).or_default().insert(tx.nonce().to_string(), tx_content);
+            }
+        }
+
+        content
+    }
 }
 
 #[async_trait]
@@ -138,6 +169,16 @@ where
         trace!(target: "rpc::eth", "Serving txpool_inspect");
         Ok(self.content())
     }
+
+    /// Handler for `txpool_contentPaged`
+    async fn txpool_content_paged(
+        &self,
+        limit: usize,
+        from: Option<Address>,
+    ) -> Result<TxpoolContentPage> {
+        trace!(target: "rpc::eth", limit, ?from, "Serving txpool_contentPaged");
+        Ok(self.content_page(limit, from))
+    }
 }
 
 impl<Pool> std::fmt::Debug for TxPoolApi<Pool> {
@@ -145,3 +186,35 @@ impl<Pool> std::fmt::Debug for TxPoolApi<Pool> {
         f.debug_struct("TxpoolApi").finish_non_exhaustive()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_transaction_pool::test_utils::{testing_pool, MockTransaction};
+
+    #[tokio::test]
+    async fn content_paged() {
+        let pool = testing_pool();
+        for sender in 1..=3 {
+            let tx = MockTransaction::eip1559().with_sender(Address::from_low_u64_be(sender));
+            pool.add_external_transaction(tx.clone()).await.unwrap();
+            pool.add_external_transaction(tx.next()).await.unwrap();
+        }
+        let api = TxPoolApi::new(pool);
+
+        let senders = |page: &TxpoolContentPage| {
+            page.pending.keys().chain(page.queued.keys()).copied().collect::<BTreeSet<_>>()
+        };
+
+        let page = api.content_page(2, None);
+        assert_eq!(
+            senders(&page),
+            BTreeSet::from([Address::from_low_u64_be(1), Address::from_low_u64_be(2)])
+        );
+        assert_eq!(page.next, Some(Address::from_low_u64_be(3)));
+
+        let page = api.content_page(2, page.next);
+        assert_eq!(senders(&page), BTreeSet::from([Address::from_low_u64_be(3)]));
+        assert_eq!(page.next, None);
+    }
+}
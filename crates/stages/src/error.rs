@@ -3,7 +3,7 @@ use reth_interfaces::{
     consensus, db::DatabaseError as DbError, executor, p2p::error::DownloadError,
     provider::ProviderError,
 };
-use reth_primitives::SealedHeader;
+use reth_primitives::{stage::StageId, SealedHeader};
 use thiserror::Error;
 use tokio::sync::mpsc::error::SendError;
 
@@ -11,7 +11,11 @@ use tokio::sync::mpsc::error::SendError;
 #[derive(Error, Debug)]
 pub enum StageError {
     /// The stage encountered a state validation error.
-    #[error("Stage encountered a validation error in block {number}: {error}.", number = block.number)]
+    #[error(
+        "Stage encountered a validation error in block #{number} ({hash:?}): {error}.",
+        number = block.number,
+        hash = block.hash,
+    )]
     Validation {
         /// The block that failed validation.
         block: SealedHeader,
@@ -39,7 +43,11 @@ pub enum StageError {
     /// The stage encountered a database error.
     #[error("An internal database error occurred: {0}")]
     Database(#[from] DbError),
-    #[error("Stage encountered a execution error in block {number}: {error}.", number = block.number)]
+    #[error(
+        "Stage encountered an execution error in block #{number} ({hash:?}): {error}.",
+        number = block.number,
+        hash = block.hash,
+    )]
     /// The stage encountered a execution error
     // TODO: Probably redundant, should be rolled into `Validation`
     ExecutionError {
@@ -108,6 +116,29 @@ pub enum PipelineError {
     /// The pipeline encountered an error while trying to send an event.
     #[error("The pipeline encountered an error while trying to send an event.")]
     Channel(#[from] SendError<PipelineEvent>),
+    /// A stage of the pipeline depends on a stage that is skipped.
+    #[error("Stage {stage} depends on the skipped stage {dependency}.")]
+    SkippedStageDependency {
+        /// The stage that is not skipped.
+        stage: StageId,
+        /// The skipped stage it depends on.
+        dependency: StageId,
+    },
+    /// A stage of the pipeline is ordered before a stage it depends on.
+    #[error("Stage {stage} depends on {dependency}, which is ordered after it.")]
+    InvalidStageOrder {
+        /// The stage that is ordered too early.
+        stage: StageId,
+        /// The stage it depends on.
+        dependency: StageId,
+    },
+    /// The pipeline was stopped by a graceful shutdown, see
+    /// [PipelineBuilder::with_graceful_shutdown][crate::PipelineBuilder::with_graceful_shutdown].
+    #[error("The pipeline was shut down.")]
+    Shutdown,
+    /// The stage the pipeline should stop after is not part of the pipeline.
+    #[error("Stage {0} to stop after is not part of the pipeline.")]
+    MissingStopStage(StageId),
     /// The stage encountered an internal error.
     #[error(transparent)]
     Internal(Box<dyn std::error::Error + Send + Sync>),
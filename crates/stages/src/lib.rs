@@ -16,7 +16,9 @@
 //! This crate contains the syncing primitives [`Pipeline`] and [`Stage`], as well as all stages
 //! that reth uses to sync.
 //!
-//! A pipeline can be configured using [`Pipeline::builder()`].
+//! A pipeline can be configured using [`Pipeline::builder()`]. Any subset of stages can be added
+//! in any order, as long as each stage comes after the stages it depends on
+//! ([`Stage::dependencies`]).
 //!
 //! For ease of use, this crate also exposes a set of [`StageSet`]s, which are collections of stages
 //! that perform specific functions during sync. Stage sets can be customized; it is possible to
@@ -57,7 +59,8 @@
 //!     .add_stages(
 //!         DefaultStages::new(HeaderSyncMode::Tip(tip_rx), consensus, headers_downloader, bodies_downloader, factory)
 //!     )
-//!     .build(db, MAINNET.clone());
+//!     .try_build(db, MAINNET.clone())?;
+//! # Ok::<(), reth_stages::PipelineError>(())
 //! ```
 //!
 //! ## Feature Flags
@@ -7,6 +7,7 @@ use std::{
     future::Future,
     pin::Pin,
     task::{ready, Context, Poll},
+    time::Duration,
 };
 use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
 
@@ -31,6 +32,20 @@ pub enum MetricEvent {
         /// If specified, `entities_total` metric is updated.
         max_block_number: Option<BlockNumber>,
     },
+    /// Stage executed a single batch.
+    StageExecution {
+        /// Stage ID.
+        stage_id: StageId,
+        /// Time spent executing the batch.
+        elapsed: Duration,
+    },
+    /// Stage committed its progress to the database.
+    StageCommit {
+        /// Stage ID.
+        stage_id: StageId,
+        /// Time spent committing the database transaction.
+        elapsed: Duration,
+    },
 }
 
 /// Metrics routine that listens to new metric events on the `events_rx` receiver.
@@ -70,6 +85,18 @@ impl MetricsListener {
                 if let Some(total) = total {
                     stage_metrics.entities_total.set(total as f64);
                 }
+
+                self.sync_metrics.update_entities_per_second(stage_id, processed);
+            }
+            MetricEvent::StageExecution { stage_id, elapsed } => {
+                let stage_metrics = self.sync_metrics.get_stage_metrics(stage_id);
+
+                stage_metrics.execution_duration_seconds.record(elapsed.as_secs_f64());
+            }
+            MetricEvent::StageCommit { stage_id, elapsed } => {
+                let stage_metrics = self.sync_metrics.get_stage_metrics(stage_id);
+
+                stage_metrics.commit_duration_seconds.record(elapsed.as_secs_f64());
             }
         }
     }
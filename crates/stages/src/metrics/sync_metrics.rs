@@ -1,21 +1,58 @@
 use reth_metrics::{
-    metrics::{self, Gauge},
+    metrics::{self, Gauge, Histogram},
     Metrics,
 };
 use reth_primitives::stage::StageId;
-use std::collections::HashMap;
+use std::{collections::HashMap, time::Instant};
 
-#[derive(Debug, Default)]
+#[derive(Debug)]
 pub(crate) struct SyncMetrics {
     pub(crate) stages: HashMap<StageId, StageMetrics>,
+    /// The number of processed entities and the time of the last commit for each stage, used to
+    /// calculate the throughput.
+    last_commits: HashMap<StageId, (u64, Instant)>,
+}
+
+impl Default for SyncMetrics {
+    /// Eagerly registers [StageMetrics] for all known stages, so every series is present from
+    /// startup, even for stages that have not run yet.
+    fn default() -> Self {
+        Self {
+            stages: StageId::ALL
+                .into_iter()
+                .map(|stage_id| (stage_id, StageMetrics::new_with_stage_id(stage_id)))
+                .collect(),
+            last_commits: HashMap::default(),
+        }
+    }
 }
 
 impl SyncMetrics {
     /// Returns existing or initializes a new instance of [StageMetrics] for the provided [StageId].
+    ///
+    /// Metrics for all known stages are registered on construction, so only custom stages
+    /// ([StageId::Other]) are initialized here.
     pub(crate) fn get_stage_metrics(&mut self, stage_id: StageId) -> &mut StageMetrics {
-        self.stages
-            .entry(stage_id)
-            .or_insert_with(|| StageMetrics::new_with_labels(&[("stage", stage_id.to_string())]))
+        self.stages.entry(stage_id).or_insert_with(|| StageMetrics::new_with_stage_id(stage_id))
+    }
+
+    /// Updates the `entities_per_second` metric of the stage with the number of entities processed
+    /// since the previous commit.
+    ///
+    /// The first commit of a stage has nothing to compare against, so it only records the sample
+    /// and leaves the gauge untouched. The same goes for commits that decrease the number of
+    /// processed entities, e.g. after an unwind.
+    pub(crate) fn update_entities_per_second(&mut self, stage_id: StageId, processed: u64) {
+        let now = Instant::now();
+        let previous = self.last_commits.insert(stage_id, (processed, now));
+
+        if let Some((previous_processed, previous_commit)) = previous {
+            let elapsed = now.duration_since(previous_commit).as_secs_f64();
+            if processed >= previous_processed && elapsed > 0.0 {
+                let entities_per_second = (processed - previous_processed) as f64 / elapsed;
+                self.get_stage_metrics(stage_id).entities_per_second.set(entities_per_second);
+            }
+        }
     }
 }
 
@@ -28,4 +65,17 @@ pub(crate) struct StageMetrics {
     pub(crate) entities_processed: Gauge,
     /// The number of total entities of the last commit for a stage, if applicable.
     pub(crate) entities_total: Gauge,
+    /// The number of entities processed per second between the last two commits for a stage.
+    pub(crate) entities_per_second: Gauge,
+    /// The time it took for a stage to execute a single batch.
+    pub(crate) execution_duration_seconds: Histogram,
+    /// The time it took for a stage to commit its database transaction.
+    pub(crate) commit_duration_seconds: Histogram,
+}
+
+impl StageMetrics {
+    /// Creates a new instance of [StageMetrics] labeled with the provided [StageId].
+    pub(crate) fn new_with_stage_id(stage_id: StageId) -> Self {
+        Self::new_with_labels(&[("stage", stage_id.to_string())])
+    }
 }
@@ -1,12 +1,19 @@
-use std::sync::Arc;
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
 
-use crate::{pipeline::BoxedStage, MetricEventsSender, Pipeline, Stage, StageSet};
+use crate::{
+    pipeline::{BoxedStage, STAGE_COMMITS_CHANNEL_CAPACITY},
+    MetricEventsSender, Pipeline, PipelineError, Stage, StageSet,
+};
 use reth_db::database::Database;
 use reth_primitives::{stage::StageId, BlockNumber, ChainSpec, H256};
-use tokio::sync::watch;
+use reth_tasks::shutdown::GracefulShutdown;
+use tokio::sync::{broadcast, watch};
 
 /// Builds a [`Pipeline`].
-#[must_use = "call `build` to construct the pipeline"]
+#[must_use = "call `try_build` to construct the pipeline"]
 pub struct PipelineBuilder<DB>
 where
     DB: Database,
@@ -18,6 +25,12 @@ where
     /// A receiver for the current chain tip to sync to.
     tip_tx: Option<watch::Sender<H256>>,
     metrics_tx: Option<MetricEventsSender>,
+    /// The stages that are skipped.
+    skipped_stages: HashSet<StageId>,
+    /// The stage after which the pipeline stops.
+    stop_after: Option<StageId>,
+    /// The signal to stop the pipeline after the current batch.
+    graceful_shutdown: Option<GracefulShutdown>,
 }
 
 impl<DB> PipelineBuilder<DB>
@@ -67,27 +80,153 @@ where
         self
     }
 
+    /// Skip the stage with the given id.
+    ///
+    /// A skipped stage keeps its place in the pipeline, but is never executed or unwound. Instead,
+    /// its checkpoint is moved to the target of the execution or unwind right away, as if the
+    /// stage completed without doing any work.
+    ///
+    /// A stage can only be skipped if none of the remaining stages depend on it, see
+    /// [`PipelineBuilder::try_build`]. Since [`StageId::Finish`] depends on the last stages of the
+    /// pipeline, skipping one of them requires skipping the finish stage as well.
+    pub fn skip_stage(mut self, stage_id: StageId) -> Self {
+        self.skipped_stages.insert(stage_id);
+        self
+    }
+
+    /// Stop the pipeline after the stage with the given id.
+    ///
+    /// The stages up to and including the given stage are executed and committed as usual, the
+    /// later stages are never executed, and [`Pipeline::run`] returns after a single pass. The
+    /// checkpoints of the later stages are left behind those of the earlier stages, so a pipeline
+    /// without this setting resumes from them.
+    pub fn with_stop_after(mut self, stage_id: StageId) -> Self {
+        self.stop_after = Some(stage_id);
+        self
+    }
+
+    /// Set the graceful shutdown signal.
+    ///
+    /// Once the signal is fired, the pipeline finishes and commits the batch the current stage is
+    /// executing, and then returns [`PipelineError::Shutdown`]. The shutdown is delayed until the
+    /// batch is committed, so a restarted pipeline resumes from the checkpoint of the last batch.
+    ///
+    /// This requires that the pipeline future isn't dropped on shutdown, so a pipeline that runs as
+    /// a task must be spawned with
+    /// [TaskExecutor::spawn_critical_blocking_graceful](reth_tasks::TaskExecutor::spawn_critical_blocking_graceful).
+    pub fn with_graceful_shutdown(mut self, graceful_shutdown: GracefulShutdown) -> Self {
+        self.graceful_shutdown = Some(graceful_shutdown);
+        self
+    }
+
+    /// Builds the final [`Pipeline`] using the given database, panicking on an invalid
+    /// configuration.
+    ///
+    /// This is only meant for pipelines whose stages are known to be valid, e.g. in tests. Prefer
+    /// [`PipelineBuilder::try_build`] when the stages depend on user configuration.
+    ///
+    /// # Panics
+    ///
+    /// If the stages are not ordered by their dependencies, a skipped stage is required by another
+    /// stage of the pipeline, or the stage to stop after is not part of the pipeline.
+    pub fn build(self, db: DB, chain_spec: Arc<ChainSpec>) -> Pipeline<DB> {
+        match self.try_build(db, chain_spec) {
+            Ok(pipeline) => pipeline,
+            Err(err) => panic!("Invalid pipeline: {err}"),
+        }
+    }
+
     /// Builds the final [`Pipeline`] using the given database.
     ///
     /// Note: it's expected that this is either an [Arc](std::sync::Arc) or an Arc wrapper type.
-    pub fn build(self, db: DB, chain_spec: Arc<ChainSpec>) -> Pipeline<DB> {
-        let Self { stages, max_block, tip_tx, metrics_tx } = self;
-        Pipeline {
+    ///
+    /// The stages can be any subset of the built-in and custom stages in any order, as long as
+    /// every stage runs after the stages it depends on, see [`Stage::dependencies`]. Dependencies
+    /// that are not part of the pipeline are assumed to be synced already.
+    ///
+    /// Returns an error if a stage is ordered before one of its dependencies, if a skipped stage
+    /// is a dependency of another stage of the pipeline which is not skipped, or if the stage to
+    /// stop after is not part of the pipeline.
+    pub fn try_build(
+        self,
+        db: DB,
+        chain_spec: Arc<ChainSpec>,
+    ) -> Result<Pipeline<DB>, PipelineError> {
+        let Self {
+            stages,
+            max_block,
+            tip_tx,
+            metrics_tx,
+            skipped_stages,
+            stop_after,
+            graceful_shutdown,
+        } = self;
+
+        let positions = stages
+            .iter()
+            .enumerate()
+            .map(|(position, stage)| (stage.id(), position))
+            .collect::<HashMap<_, _>>();
+        if let Some(stage_id) = stop_after.filter(|stage_id| !positions.contains_key(stage_id)) {
+            return Err(PipelineError::MissingStopStage(stage_id))
+        }
+
+        for (position, stage) in stages.iter().enumerate() {
+            if let Some(dependency) = stage
+                .dependencies()
+                .iter()
+                .find(|id| positions.get(*id).map_or(false, |dependency| *dependency > position))
+            {
+                return Err(PipelineError::InvalidStageOrder {
+                    stage: stage.id(),
+                    dependency: *dependency,
+                })
+            }
+        }
+
+        for stage in &stages {
+            let stage_id = stage.id();
+            if skipped_stages.contains(&stage_id) {
+                continue
+            }
+            if let Some(dependency) =
+                stage.dependencies().iter().find(|id| skipped_stages.contains(id))
+            {
+                return Err(PipelineError::SkippedStageDependency {
+                    stage: stage_id,
+                    dependency: *dependency,
+                })
+            }
+        }
+
+        Ok(Pipeline {
             db,
             chain_spec,
             stages,
             max_block,
             tip_tx,
             listeners: Default::default(),
+            commits_tx: broadcast::channel(STAGE_COMMITS_CHANNEL_CAPACITY).0,
             progress: Default::default(),
             metrics_tx,
-        }
+            skipped_stages,
+            stop_after,
+            graceful_shutdown,
+        })
     }
 }
 
 impl<DB: Database> Default for PipelineBuilder<DB> {
     fn default() -> Self {
-        Self { stages: Vec::new(), max_block: None, tip_tx: None, metrics_tx: None }
+        Self {
+            stages: Vec::new(),
+            max_block: None,
+            tip_tx: None,
+            metrics_tx: None,
+            skipped_stages: HashSet::new(),
+            stop_after: None,
+            graceful_shutdown: None,
+        }
     }
 }
 
@@ -96,6 +235,8 @@ impl<DB: Database> std::fmt::Debug for PipelineBuilder<DB> {
         f.debug_struct("PipelineBuilder")
             .field("stages", &self.stages.iter().map(|stage| stage.id()).collect::<Vec<StageId>>())
             .field("max_block", &self.max_block)
+            .field("skipped_stages", &self.skipped_stages)
+            .field("stop_after", &self.stop_after)
             .finish()
     }
 }
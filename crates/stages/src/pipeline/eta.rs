@@ -0,0 +1,84 @@
+use reth_primitives::stage::{EntitiesCheckpoint, StageCheckpoint};
+use std::time::{Duration, Instant};
+
+/// The weight of the most recent throughput sample in the moving average.
+///
+/// Higher values make the estimate adapt faster to changes in throughput, e.g. when blocks get
+/// heavier, at the cost of a noisier estimate.
+const THROUGHPUT_SMOOTHING_FACTOR: f64 = 0.3;
+
+/// Estimates the remaining time of a single stage.
+///
+/// The estimate is based on an exponentially weighted moving average of the throughput between
+/// consecutive checkpoints of the stage.
+#[derive(Debug, Default)]
+pub(crate) struct StageEta {
+    /// The last entities checkpoint and the time it was reported.
+    last: Option<(EntitiesCheckpoint, Instant)>,
+    /// The moving average of the processed entities per second.
+    throughput: Option<f64>,
+}
+
+impl StageEta {
+    /// Updates the throughput with the given checkpoint and returns the estimated remaining time.
+    ///
+    /// Returns `None` if the stage doesn't report the total number of entities, or if the
+    /// throughput is not known yet.
+    pub(crate) fn update(&mut self, checkpoint: StageCheckpoint, now: Instant) -> Option<Duration> {
+        let Some(current) = checkpoint.entities() else {
+            *self = Self::default();
+            return None
+        };
+
+        if let Some((last, last_time)) = self.last {
+            let elapsed = now.saturating_duration_since(last_time).as_secs_f64();
+            if elapsed > 0.0 {
+                let sample = current.processed.saturating_sub(last.processed) as f64 / elapsed;
+                self.throughput = Some(self.throughput.map_or(sample, |average| {
+                    THROUGHPUT_SMOOTHING_FACTOR * sample +
+                        (1.0 - THROUGHPUT_SMOOTHING_FACTOR) * average
+                }));
+            }
+        }
+        self.last = Some((current, now));
+
+        let remaining = current.total.saturating_sub(current.processed);
+        self.throughput
+            .filter(|throughput| *throughput > 0.0)
+            .map(|throughput| Duration::from_secs_f64(remaining as f64 / throughput))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn checkpoint(processed: u64, total: u64) -> StageCheckpoint {
+        StageCheckpoint::new(0)
+            .with_entities_stage_checkpoint(EntitiesCheckpoint { processed, total })
+    }
+
+    #[test]
+    fn eta_unknown_without_entities() {
+        let mut eta = StageEta::default();
+        let now = Instant::now();
+        assert_eq!(eta.update(StageCheckpoint::new(0), now), None);
+        assert_eq!(eta.update(StageCheckpoint::new(10), now + Duration::from_secs(1)), None);
+    }
+
+    #[test]
+    fn eta_moving_average() {
+        let mut eta = StageEta::default();
+        let now = Instant::now();
+
+        assert_eq!(eta.update(checkpoint(0, 1000), now), None);
+        // 100 entities per second, 900 remaining
+        assert_eq!(
+            eta.update(checkpoint(100, 1000), now + Duration::from_secs(1)),
+            Some(Duration::from_secs(9))
+        );
+        // 10 entities per second sample, averaged to 0.3 * 10 + 0.7 * 100 = 73 per second
+        let remaining = eta.update(checkpoint(110, 1000), now + Duration::from_secs(2)).unwrap();
+        assert_eq!(remaining.as_secs(), (890.0 / 73.0) as u64);
+    }
+}
@@ -1,5 +1,9 @@
 use crate::stage::{ExecOutput, UnwindInput, UnwindOutput};
-use reth_primitives::stage::{StageCheckpoint, StageId};
+use reth_primitives::{
+    stage::{EntitiesCheckpoint, StageCheckpoint, StageId},
+    BlockNumber,
+};
+use std::time::Duration;
 
 /// An event emitted by a [Pipeline][crate::Pipeline].
 ///
@@ -31,6 +35,18 @@ pub enum PipelineEvent {
         stage_id: StageId,
         /// The result of executing the stage.
         result: ExecOutput,
+        /// The estimated time until the stage is done, if the stage reports its total entities.
+        eta: Option<Duration>,
+    },
+    /// Emitted when the total number of entities a stage processes becomes known or changes.
+    ///
+    /// This may be emitted late, or not at all, for stages that can't determine the total
+    /// upfront.
+    EntitiesTotal {
+        /// The stage that reported the total.
+        stage_id: StageId,
+        /// The total number of entities.
+        total: u64,
     },
     /// Emitted when a stage is about to be unwound.
     Unwinding {
@@ -61,3 +77,20 @@ pub enum PipelineEvent {
         stage_id: StageId,
     },
 }
+
+/// A range of blocks that a stage has executed and committed to the database.
+///
+/// These are broadcast by the [Pipeline][crate::Pipeline] after every commit of an execution step
+/// that made progress, see [Pipeline::commits][crate::Pipeline::commits]. Unwinds are not
+/// reported as commits, see [PipelineEvent::Unwound].
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct StageCommit {
+    /// The stage that committed.
+    pub stage_id: StageId,
+    /// The first block of the committed range (inclusive).
+    pub from: BlockNumber,
+    /// The last block of the committed range (inclusive).
+    pub to: BlockNumber,
+    /// The entities processed by the stage as of this commit, if the stage tracks them.
+    pub entities: Option<EntitiesCheckpoint>,
+}
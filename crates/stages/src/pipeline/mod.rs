@@ -3,26 +3,37 @@ use crate::{
     UnwindInput,
 };
 use futures_util::Future;
-use reth_db::database::Database;
+use reth_db::{
+    database::Database,
+    tables,
+    transaction::{DbTx, DbTxMut},
+};
 use reth_interfaces::executor::BlockExecutionError;
 use reth_primitives::{
-    constants::BEACON_CONSENSUS_REORG_UNWIND_DEPTH, listener::EventListeners, stage::StageId,
+    constants::BEACON_CONSENSUS_REORG_UNWIND_DEPTH,
+    listener::EventListeners,
+    stage::{StageCheckpoint, StageId},
     BlockNumber, ChainSpec, H256,
 };
-use reth_provider::{ProviderFactory, StageCheckpointReader, StageCheckpointWriter};
-use std::{pin::Pin, sync::Arc};
-use tokio::sync::watch;
+use reth_provider::{
+    ProviderFactory, PruneCheckpointWriter, StageCheckpointReader, StageCheckpointWriter,
+};
+use reth_tasks::shutdown::GracefulShutdown;
+use std::{collections::HashSet, pin::Pin, sync::Arc, time::Instant};
+use tokio::sync::{broadcast, watch};
 use tokio_stream::wrappers::UnboundedReceiverStream;
 use tracing::*;
 
 mod builder;
 mod ctrl;
+mod eta;
 mod event;
 mod progress;
 mod set;
 
 pub use crate::pipeline::ctrl::ControlFlow;
 pub use builder::*;
+use eta::*;
 pub use event::*;
 use progress::*;
 pub use set::*;
@@ -30,6 +41,18 @@ pub use set::*;
 /// A container for a queued stage.
 pub(crate) type BoxedStage<DB> = Box<dyn Stage<DB>>;
 
+/// The [Metadata](tables::Metadata) key of the target of an unwind in progress, see
+/// [Pipeline::unwind].
+pub(crate) const UNWIND_TARGET_KEY: &str = "pipeline_unwind_target";
+
+/// The [Metadata](tables::Metadata) key of the bad block of an unwind in progress, if any.
+pub(crate) const UNWIND_BAD_BLOCK_KEY: &str = "pipeline_unwind_bad_block";
+
+/// The capacity of the [StageCommit] broadcast channel, see [Pipeline::commits].
+///
+/// Subscribers that fall behind by more than this many commits miss the oldest ones.
+pub(crate) const STAGE_COMMITS_CHANNEL_CAPACITY: usize = 1024;
+
 /// The future that returns the owned pipeline and the result of the pipeline run. See
 /// [Pipeline::run_as_fut].
 pub type PipelineFut<DB> = Pin<Box<dyn Future<Output = PipelineWithResult<DB>> + Send>>;
@@ -102,11 +125,20 @@ pub struct Pipeline<DB: Database> {
     max_block: Option<BlockNumber>,
     /// All listeners for events the pipeline emits.
     listeners: EventListeners<PipelineEvent>,
+    /// Broadcasts the block ranges committed by the stages.
+    commits_tx: broadcast::Sender<StageCommit>,
     /// Keeps track of the progress of the pipeline.
     progress: PipelineProgress,
     /// A receiver for the current chain tip to sync to.
     tip_tx: Option<watch::Sender<H256>>,
     metrics_tx: Option<MetricEventsSender>,
+    /// The stages that are skipped, see [PipelineBuilder::skip_stage].
+    skipped_stages: HashSet<StageId>,
+    /// The stage after which the pipeline stops, see [PipelineBuilder::with_stop_after].
+    stop_after: Option<StageId>,
+    /// The signal to stop the pipeline after the current batch, see
+    /// [PipelineBuilder::with_graceful_shutdown].
+    graceful_shutdown: Option<GracefulShutdown>,
 }
 
 impl<DB> Pipeline<DB>
@@ -137,6 +169,15 @@ where
         self.listeners.new_listener()
     }
 
+    /// Subscribe to the block ranges committed by each stage during execution.
+    ///
+    /// The channel is bounded and lossy: a subscriber that doesn't keep up will observe
+    /// [broadcast::error::RecvError::Lagged] and miss the oldest commits instead of stalling the
+    /// pipeline.
+    pub fn commits(&self) -> broadcast::Receiver<StageCommit> {
+        self.commits_tx.subscribe()
+    }
+
     /// Registers progress metrics for each registered stage
     pub fn register_metrics(&mut self) -> Result<(), PipelineError> {
         let Some(metrics_tx) = &mut self.metrics_tx else { return Ok(()) };
@@ -173,13 +214,24 @@ where
     }
 
     /// Run the pipeline in an infinite loop. Will terminate early if the user has specified
-    /// a `max_block` in the pipeline.
+    /// a `max_block` in the pipeline, or after a single pass if the user has specified a stage to
+    /// stop after.
     pub async fn run(&mut self) -> Result<(), PipelineError> {
         let _ = self.register_metrics(); // ignore error
 
         loop {
             let next_action = self.run_loop().await?;
 
+            if next_action.should_continue() && self.stop_after.is_some() {
+                trace!(
+                    target: "sync::pipeline",
+                    ?next_action,
+                    stop_after = ?self.stop_after,
+                    "Terminating pipeline."
+                );
+                return Ok(())
+            }
+
             // Terminate the loop early if it's reached the maximum user
             // configured block.
             if next_action.should_continue() &&
@@ -206,7 +258,15 @@ where
     /// If any stage is unsuccessful at execution, we proceed to
     /// unwind. This will undo the progress across the entire pipeline
     /// up to the block that caused the error.
+    ///
+    /// If a previous unwind was interrupted, it is finished before any stage is executed. If a
+    /// stage to stop after is set, the stages after it are not executed.
     pub async fn run_loop(&mut self) -> Result<ControlFlow, PipelineError> {
+        if let Some((target, bad_block)) = self.unfinished_unwind()? {
+            warn!(target: "sync::pipeline", unwind_to = target, ?bad_block, "Resuming interrupted unwind");
+            self.unwind(target, bad_block).await?;
+        }
+
         let mut previous_stage = None;
         for stage_index in 0..self.stages.len() {
             let stage = &self.stages[stage_index];
@@ -242,14 +302,38 @@ where
                     .unwrap_or_default()
                     .block_number,
             );
+
+            if self.stop_after == Some(stage_id) {
+                info!(target: "sync::pipeline", stage = %stage_id, "Stopping after stage");
+                break
+            }
         }
 
         Ok(self.progress.next_ctrl())
     }
 
+    /// Returns the target and the bad block of an unwind that was started, but not finished, e.g.
+    /// because the node was shut down in the middle of it.
+    pub fn unfinished_unwind(
+        &self,
+    ) -> Result<Option<(BlockNumber, Option<BlockNumber>)>, PipelineError> {
+        let factory = ProviderFactory::new(&self.db, self.chain_spec.clone());
+        let provider = factory.provider()?;
+        let tx = provider.tx_ref();
+        let Some(target) = tx.get::<tables::Metadata>(UNWIND_TARGET_KEY.to_string())? else {
+            return Ok(None)
+        };
+        let bad_block = tx.get::<tables::Metadata>(UNWIND_BAD_BLOCK_KEY.to_string())?;
+        Ok(Some((target, bad_block)))
+    }
+
     /// Unwind the stages to the target block.
     ///
     /// If the unwind is due to a bad block the number of that block should be specified.
+    ///
+    /// The unwind target is persisted until all stages are unwound, so an interrupted unwind is
+    /// resumed by the next [Pipeline::run_loop], see [Pipeline::unfinished_unwind]. Each
+    /// stage commits its own checkpoint after every unwind step.
     pub async fn unwind(
         &mut self,
         to: BlockNumber,
@@ -261,6 +345,19 @@ where
         let factory = ProviderFactory::new(&self.db, self.chain_spec.clone());
         let mut provider_rw = factory.provider_rw().map_err(PipelineError::Interface)?;
 
+        let tx = provider_rw.tx_ref();
+        tx.put::<tables::Metadata>(UNWIND_TARGET_KEY.to_string(), to)?;
+        match bad_block {
+            Some(bad_block) => {
+                tx.put::<tables::Metadata>(UNWIND_BAD_BLOCK_KEY.to_string(), bad_block)?
+            }
+            None => {
+                tx.delete::<tables::Metadata>(UNWIND_BAD_BLOCK_KEY.to_string(), None)?;
+            }
+        }
+        provider_rw.commit()?;
+        provider_rw = factory.provider_rw().map_err(PipelineError::Interface)?;
+
         for stage in unwind_pipeline {
             let stage_id = stage.id();
             let span = info_span!("Unwinding", stage = %stage_id);
@@ -273,6 +370,13 @@ where
                 continue
             }
 
+            if self.skipped_stages.contains(&stage_id) {
+                debug!(target: "sync::pipeline", from = %checkpoint, %to, "Stage is skipped");
+                provider_rw.save_stage_checkpoint(stage_id, StageCheckpoint::new(to))?;
+                self.listeners.notify(PipelineEvent::Skipped { stage_id });
+                continue
+            }
+
             debug!(target: "sync::pipeline", from = %checkpoint, %to, ?bad_block, "Starting unwind");
             while checkpoint.block_number > to {
                 let input = UnwindInput { checkpoint, unwind_to: to, bad_block };
@@ -304,7 +408,14 @@ where
                         self.listeners
                             .notify(PipelineEvent::Unwound { stage_id, result: unwind_output });
 
+                        let commit_started_at = Instant::now();
                         provider_rw.commit()?;
+                        if let Some(metrics_tx) = &mut self.metrics_tx {
+                            let _ = metrics_tx.send(MetricEvent::StageCommit {
+                                stage_id,
+                                elapsed: commit_started_at.elapsed(),
+                            });
+                        }
                         provider_rw = factory.provider_rw().map_err(PipelineError::Interface)?;
                     }
                     Err(err) => {
@@ -315,6 +426,15 @@ where
             }
         }
 
+        // Blocks above the unwind target will be synced again, so they need to be pruned again.
+        provider_rw.unwind_prune_checkpoints(to)?;
+
+        // All stages are unwound
+        let tx = provider_rw.tx_ref();
+        tx.delete::<tables::Metadata>(UNWIND_TARGET_KEY.to_string(), None)?;
+        tx.delete::<tables::Metadata>(UNWIND_BAD_BLOCK_KEY.to_string(), None)?;
+        provider_rw.commit()?;
+
         Ok(())
     }
 
@@ -328,12 +448,60 @@ where
         let stage = &mut self.stages[stage_index];
         let stage_id = stage.id();
         let mut made_progress = false;
-        let target = self.max_block.or(previous_stage);
+        // A stage never runs past the previous stage, nor past the configured max block.
+        let target = match (previous_stage, self.max_block) {
+            (Some(previous_stage), Some(max_block)) => Some(previous_stage.min(max_block)),
+            (previous_stage, max_block) => previous_stage.or(max_block),
+        };
 
         let factory = ProviderFactory::new(&self.db, self.chain_spec.clone());
         let mut provider_rw = factory.provider_rw().map_err(PipelineError::Interface)?;
 
+        if self.skipped_stages.contains(&stage_id) {
+            let prev_checkpoint = provider_rw.get_stage_checkpoint(stage_id)?;
+            let prev_block_number = prev_checkpoint.map(|checkpoint| checkpoint.block_number);
+            self.listeners.notify(PipelineEvent::Skipped { stage_id });
+
+            // The stage is treated as complete once it's run, so its checkpoint is moved to the
+            // target without doing any work.
+            return Ok(match target {
+                Some(block_number)
+                    if prev_block_number.map_or(true, |prev| prev < block_number) =>
+                {
+                    debug!(target: "sync::pipeline", stage = %stage_id, ?prev_block_number, target = block_number, "Stage is skipped, moving checkpoint to target");
+                    provider_rw
+                        .save_stage_checkpoint(stage_id, StageCheckpoint::new(block_number))?;
+                    provider_rw.commit()?;
+                    ControlFlow::Continue { block_number }
+                }
+                _ => ControlFlow::NoProgress { block_number: prev_block_number },
+            })
+        }
+
+        let mut entities_total = None;
+        let mut stage_eta = StageEta::default();
+        let prev_checkpoint = provider_rw.get_stage_checkpoint(stage_id)?;
+        stage_eta.update(prev_checkpoint.unwrap_or_default(), Instant::now());
+        if let Some(total) =
+            stage.entities_total(&provider_rw, ExecInput { target, checkpoint: prev_checkpoint })?
+        {
+            entities_total = Some(total);
+            self.listeners.notify(PipelineEvent::EntitiesTotal { stage_id, total });
+        }
+
         loop {
+            // Delays a graceful shutdown until the batch is committed.
+            let _shutdown_guard = match &self.graceful_shutdown {
+                Some(graceful_shutdown) => match graceful_shutdown.try_guard() {
+                    Some(guard) => Some(guard),
+                    None => {
+                        info!(target: "sync::pipeline", stage = %stage_id, "Shutting down pipeline");
+                        return Err(PipelineError::Shutdown)
+                    }
+                },
+                None => None,
+            };
+
             let prev_checkpoint = provider_rw.get_stage_checkpoint(stage_id)?;
 
             let stage_reached_max_block = prev_checkpoint
@@ -362,11 +530,13 @@ where
                 checkpoint: prev_checkpoint,
             });
 
+            let execution_started_at = Instant::now();
             match stage
                 .execute(&provider_rw, ExecInput { target, checkpoint: prev_checkpoint })
                 .await
             {
                 Ok(out @ ExecOutput { checkpoint, done }) => {
+                    let elapsed = execution_started_at.elapsed();
                     made_progress |=
                         checkpoint.block_number != prev_checkpoint.unwrap_or_default().block_number;
                     debug!(
@@ -378,6 +548,7 @@ where
                         "Stage committed progress"
                     );
                     if let Some(metrics_tx) = &mut self.metrics_tx {
+                        let _ = metrics_tx.send(MetricEvent::StageExecution { stage_id, elapsed });
                         let _ = metrics_tx.send(MetricEvent::StageCheckpoint {
                             stage_id,
                             checkpoint,
@@ -386,17 +557,45 @@ where
                     }
                     provider_rw.save_stage_checkpoint(stage_id, checkpoint)?;
 
+                    if let Some(total) = checkpoint
+                        .entities()
+                        .map(|entities| entities.total)
+                        .filter(|total| entities_total != Some(*total))
+                    {
+                        entities_total = Some(total);
+                        self.listeners.notify(PipelineEvent::EntitiesTotal { stage_id, total });
+                    }
+
                     self.listeners.notify(PipelineEvent::Ran {
                         pipeline_position: stage_index + 1,
                         pipeline_total: total_stages,
                         stage_id,
                         result: out.clone(),
+                        eta: stage_eta.update(checkpoint, Instant::now()),
                     });
 
                     // TODO: Make the commit interval configurable
+                    let commit_started_at = Instant::now();
                     provider_rw.commit()?;
+                    if let Some(metrics_tx) = &mut self.metrics_tx {
+                        let _ = metrics_tx.send(MetricEvent::StageCommit {
+                            stage_id,
+                            elapsed: commit_started_at.elapsed(),
+                        });
+                    }
                     provider_rw = factory.provider_rw().map_err(PipelineError::Interface)?;
 
+                    let from = prev_checkpoint.unwrap_or_default().block_number + 1;
+                    if checkpoint.block_number >= from {
+                        // Nobody might be subscribed, in which case the commit is dropped.
+                        let _ = self.commits_tx.send(StageCommit {
+                            stage_id,
+                            from,
+                            to: checkpoint.block_number,
+                            entities: checkpoint.entities(),
+                        });
+                    }
+
                     if done {
                         let block_number = checkpoint.block_number;
                         return Ok(if made_progress {
@@ -423,6 +622,7 @@ where
                             target: "sync::pipeline",
                             stage = %stage_id,
                             bad_block = %block.number,
+                            bad_block_hash = ?block.hash,
                             "Stage encountered a validation error: {error}"
                         );
 
@@ -442,6 +642,7 @@ where
                             target: "sync::pipeline",
                             stage = %stage_id,
                             bad_block = %block.number,
+                            bad_block_hash = ?block.hash,
                             "Stage encountered an execution error: {error}"
                         );
 
@@ -481,6 +682,7 @@ impl<DB: Database> std::fmt::Debug for Pipeline<DB> {
         f.debug_struct("Pipeline")
             .field("stages", &self.stages.iter().map(|stage| stage.id()).collect::<Vec<StageId>>())
             .field("max_block", &self.max_block)
+            .field("skipped_stages", &self.skipped_stages)
             .field("listeners", &self.listeners)
             .finish()
     }
@@ -497,7 +699,10 @@ mod tests {
         provider::ProviderError,
         test_utils::{generators, generators::random_header},
     };
-    use reth_primitives::{stage::StageCheckpoint, MAINNET};
+    use reth_primitives::{
+        stage::{EntitiesCheckpoint, StageCheckpoint},
+        MAINNET,
+    };
     use tokio_stream::StreamExt;
 
     #[test]
@@ -565,7 +770,113 @@ mod tests {
                     pipeline_total: 2,
                     stage_id: StageId::Other("A"),
                     result: ExecOutput { checkpoint: StageCheckpoint::new(20), done: true },
+                    eta: None,
+                },
+                PipelineEvent::Running {
+                    pipeline_position: 2,
+                    pipeline_total: 2,
+                    stage_id: StageId::Other("B"),
+                    checkpoint: None
+                },
+                PipelineEvent::Ran {
+                    pipeline_position: 2,
+                    pipeline_total: 2,
+                    stage_id: StageId::Other("B"),
+                    result: ExecOutput { checkpoint: StageCheckpoint::new(10), done: true },
+                    eta: None,
                 },
+            ]
+        );
+    }
+
+    /// Checks that the pipeline forwards the entities total reported in stage checkpoints once.
+    #[tokio::test]
+    async fn run_pipeline_forwards_entities_total() {
+        let db = create_test_rw_db();
+
+        let checkpoint = |block_number, processed| {
+            StageCheckpoint::new(block_number)
+                .with_entities_stage_checkpoint(EntitiesCheckpoint { processed, total: 10 })
+        };
+        let mut pipeline = Pipeline::builder()
+            .add_stage(
+                TestStage::new(StageId::Other("A"))
+                    .add_exec(Ok(ExecOutput { checkpoint: checkpoint(5, 5), done: false }))
+                    .add_exec(Ok(ExecOutput { checkpoint: checkpoint(10, 10), done: true })),
+            )
+            .with_max_block(10)
+            .build(db, MAINNET.clone());
+        let events = pipeline.events();
+
+        tokio::spawn(async move {
+            pipeline.run().await.unwrap();
+        });
+
+        let totals = events
+            .filter(|event| matches!(event, PipelineEvent::EntitiesTotal { .. }))
+            .collect::<Vec<PipelineEvent>>()
+            .await;
+        assert_eq!(
+            totals,
+            vec![PipelineEvent::EntitiesTotal { stage_id: StageId::Other("A"), total: 10 }]
+        );
+    }
+
+    /// Checks that the pipeline broadcasts every committed block range.
+    #[tokio::test]
+    async fn run_pipeline_broadcasts_commits() {
+        let db = create_test_rw_db();
+
+        let mut pipeline = Pipeline::builder()
+            .add_stage(
+                TestStage::new(StageId::Other("A"))
+                    .add_exec(Ok(ExecOutput { checkpoint: StageCheckpoint::new(5), done: false }))
+                    .add_exec(Ok(ExecOutput { checkpoint: StageCheckpoint::new(10), done: true })),
+            )
+            .with_max_block(10)
+            .build(db, MAINNET.clone());
+        let mut commits = pipeline.commits();
+
+        pipeline.run().await.unwrap();
+
+        let stage_id = StageId::Other("A");
+        assert_eq!(
+            commits.try_recv(),
+            Ok(StageCommit { stage_id, from: 1, to: 5, entities: None })
+        );
+        assert_eq!(
+            commits.try_recv(),
+            Ok(StageCommit { stage_id, from: 6, to: 10, entities: None })
+        );
+        assert_eq!(commits.try_recv(), Err(broadcast::error::TryRecvError::Empty));
+    }
+
+    /// Runs a pipeline with a skipped stage.
+    #[tokio::test]
+    async fn run_pipeline_with_skipped_stage() {
+        let db = create_test_rw_db();
+
+        let mut pipeline = Pipeline::builder()
+            .add_stage(TestStage::new(StageId::Other("A")))
+            .add_stage(
+                TestStage::new(StageId::Other("B"))
+                    .add_exec(Ok(ExecOutput { checkpoint: StageCheckpoint::new(10), done: true })),
+            )
+            .with_max_block(10)
+            .skip_stage(StageId::Other("A"))
+            .build(db, MAINNET.clone());
+        let events = pipeline.events();
+
+        // Run pipeline
+        tokio::spawn(async move {
+            pipeline.run().await.unwrap();
+        });
+
+        // Check that the skipped stage was advanced to the target without running
+        assert_eq!(
+            events.collect::<Vec<PipelineEvent>>().await,
+            vec![
+                PipelineEvent::Skipped { stage_id: StageId::Other("A") },
                 PipelineEvent::Running {
                     pipeline_position: 2,
                     pipeline_total: 2,
@@ -577,11 +888,241 @@ mod tests {
                     pipeline_total: 2,
                     stage_id: StageId::Other("B"),
                     result: ExecOutput { checkpoint: StageCheckpoint::new(10), done: true },
+                    eta: None,
                 },
             ]
         );
     }
 
+    /// Checks that a stage that others depend on cannot be skipped.
+    #[test]
+    fn skip_stage_with_dependents() {
+        let result = Pipeline::builder()
+            .add_stage(TestStage::new(StageId::Headers))
+            .add_stage(TestStage::new(StageId::Bodies))
+            .skip_stage(StageId::Headers)
+            .try_build(create_test_rw_db(), MAINNET.clone());
+        assert_matches!(
+            result,
+            Err(PipelineError::SkippedStageDependency {
+                stage: StageId::Bodies,
+                dependency: StageId::Headers
+            })
+        );
+
+        // Skipping the dependent stage as well is fine
+        let result = Pipeline::builder()
+            .add_stage(TestStage::new(StageId::Headers))
+            .add_stage(TestStage::new(StageId::Bodies))
+            .skip_stage(StageId::Headers)
+            .skip_stage(StageId::Bodies)
+            .try_build(create_test_rw_db(), MAINNET.clone());
+        assert!(result.is_ok());
+
+        // The finish stage depends on the last stages of the pipeline
+        let result = Pipeline::builder()
+            .add_stage(TestStage::new(StageId::TransactionLookup))
+            .add_stage(TestStage::new(StageId::Finish))
+            .skip_stage(StageId::TransactionLookup)
+            .try_build(create_test_rw_db(), MAINNET.clone());
+        assert_matches!(
+            result,
+            Err(PipelineError::SkippedStageDependency {
+                stage: StageId::Finish,
+                dependency: StageId::TransactionLookup
+            })
+        );
+    }
+
+    /// Checks that the pipeline stops before the next batch once the graceful shutdown signal is
+    /// fired.
+    #[tokio::test]
+    async fn run_pipeline_graceful_shutdown() {
+        let db = create_test_rw_db();
+        let manager = reth_tasks::TaskManager::new(tokio::runtime::Handle::current());
+
+        let mut pipeline = Pipeline::builder()
+            .add_stage(TestStage::new(StageId::Other("A")))
+            .with_max_block(10)
+            .with_graceful_shutdown(manager.executor().on_graceful_shutdown_signal())
+            .build(db.clone(), MAINNET.clone());
+        let events = pipeline.events();
+
+        drop(manager);
+        assert_matches!(pipeline.run().await, Err(PipelineError::Shutdown));
+        drop(pipeline);
+
+        // The stage was not executed
+        assert_eq!(events.collect::<Vec<PipelineEvent>>().await, vec![]);
+        let provider = ProviderFactory::new(&db, MAINNET.clone()).provider().unwrap();
+        assert_eq!(provider.get_stage_checkpoint(StageId::Other("A")).unwrap(), None);
+    }
+
+    /// Runs the pipeline up to and including the stage to stop after.
+    #[tokio::test]
+    async fn run_pipeline_stop_after() {
+        let db = create_test_rw_db();
+
+        let mut pipeline = Pipeline::builder()
+            .add_stage(
+                TestStage::new(StageId::Other("A"))
+                    .add_exec(Ok(ExecOutput { checkpoint: StageCheckpoint::new(20), done: true })),
+            )
+            .add_stage(
+                TestStage::new(StageId::Other("B"))
+                    .add_exec(Ok(ExecOutput { checkpoint: StageCheckpoint::new(10), done: true })),
+            )
+            .add_stage(TestStage::new(StageId::Other("C")))
+            .with_stop_after(StageId::Other("B"))
+            .build(db.clone(), MAINNET.clone());
+        let events = pipeline.events();
+
+        // Run pipeline
+        tokio::spawn(async move {
+            pipeline.run().await.unwrap();
+        });
+
+        // Check that stage C was not run
+        assert_eq!(
+            events
+                .filter_map(|event| match event {
+                    PipelineEvent::Ran { stage_id, .. } => Some(stage_id),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+                .await,
+            vec![StageId::Other("A"), StageId::Other("B")]
+        );
+        let provider = ProviderFactory::new(&db, MAINNET.clone()).provider().unwrap();
+        assert_eq!(
+            provider.get_stage_checkpoint(StageId::Other("B")).unwrap(),
+            Some(StageCheckpoint::new(10))
+        );
+        assert_eq!(provider.get_stage_checkpoint(StageId::Other("C")).unwrap(), None);
+
+        let result = Pipeline::builder()
+            .add_stage(TestStage::new(StageId::Other("A")))
+            .with_stop_after(StageId::Other("B"))
+            .try_build(create_test_rw_db(), MAINNET.clone());
+        assert_matches!(result, Err(PipelineError::MissingStopStage(StageId::Other("B"))));
+    }
+
+    /// Checks that stages can't be ordered before the stages they depend on.
+    #[test]
+    fn stage_order() {
+        let result = Pipeline::builder()
+            .add_stage(TestStage::new(StageId::Bodies))
+            .add_stage(TestStage::new(StageId::Headers))
+            .try_build(create_test_rw_db(), MAINNET.clone());
+        assert_matches!(
+            result,
+            Err(PipelineError::InvalidStageOrder {
+                stage: StageId::Bodies,
+                dependency: StageId::Headers
+            })
+        );
+
+        let result = Pipeline::builder()
+            .add_stage(
+                TestStage::new(StageId::Other("B")).with_dependencies(vec![StageId::Other("A")]),
+            )
+            .add_stage(TestStage::new(StageId::Other("A")))
+            .try_build(create_test_rw_db(), MAINNET.clone());
+        assert_matches!(
+            result,
+            Err(PipelineError::InvalidStageOrder {
+                stage: StageId::Other("B"),
+                dependency: StageId::Other("A")
+            })
+        );
+
+        let result = Pipeline::builder()
+            .add_stage(TestStage::new(StageId::MerkleUnwind))
+            .add_stage(TestStage::new(StageId::Execution))
+            .try_build(create_test_rw_db(), MAINNET.clone());
+        assert_matches!(
+            result,
+            Err(PipelineError::InvalidStageOrder {
+                stage: StageId::MerkleUnwind,
+                dependency: StageId::Execution
+            })
+        );
+
+        // Reordering independent stages and leaving out dependencies is fine
+        let result = Pipeline::builder()
+            .add_stage(TestStage::new(StageId::Headers))
+            .add_stage(TestStage::new(StageId::Bodies))
+            .add_stage(TestStage::new(StageId::TransactionLookup))
+            .add_stage(TestStage::new(StageId::TotalDifficulty))
+            .add_stage(TestStage::new(StageId::MerkleExecute))
+            .try_build(create_test_rw_db(), MAINNET.clone());
+        assert!(result.is_ok());
+    }
+
+    /// Resumes an interrupted unwind before executing any stage.
+    #[tokio::test]
+    async fn resume_interrupted_unwind() {
+        let db = create_test_rw_db();
+
+        // Stage A was unwound to 50 already, stage B wasn't unwound yet
+        let factory = ProviderFactory::new(&db, MAINNET.clone());
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw.save_stage_checkpoint(StageId::Other("A"), StageCheckpoint::new(50)).unwrap();
+        provider_rw.save_stage_checkpoint(StageId::Other("B"), StageCheckpoint::new(100)).unwrap();
+        let tx = provider_rw.tx_ref();
+        tx.put::<tables::Metadata>(UNWIND_TARGET_KEY.to_string(), 50).unwrap();
+        tx.put::<tables::Metadata>(UNWIND_BAD_BLOCK_KEY.to_string(), 51).unwrap();
+        provider_rw.commit().unwrap();
+
+        let mut pipeline = Pipeline::builder()
+            .add_stage(
+                TestStage::new(StageId::Other("A"))
+                    .add_exec(Ok(ExecOutput { checkpoint: StageCheckpoint::new(100), done: true })),
+            )
+            .add_stage(
+                TestStage::new(StageId::Other("B"))
+                    .add_unwind(Ok(UnwindOutput { checkpoint: StageCheckpoint::new(50) }))
+                    .add_exec(Ok(ExecOutput { checkpoint: StageCheckpoint::new(100), done: true })),
+            )
+            .build(db.clone(), MAINNET.clone());
+        assert_eq!(pipeline.unfinished_unwind().unwrap(), Some((50, Some(51))));
+        let mut events = pipeline.events();
+
+        pipeline.run_loop().await.expect("Could not run pipeline");
+        assert_eq!(pipeline.unfinished_unwind().unwrap(), None);
+        let provider = factory.provider().unwrap();
+        assert_eq!(provider.tx_ref().entries::<tables::Metadata>().unwrap(), 0);
+
+        // Check that stage B was unwound before any stage was executed
+        assert_eq!(
+            events.next().await,
+            Some(PipelineEvent::Unwinding {
+                stage_id: StageId::Other("B"),
+                input: UnwindInput {
+                    checkpoint: StageCheckpoint::new(100),
+                    unwind_to: 50,
+                    bad_block: Some(51),
+                },
+            })
+        );
+        assert_eq!(
+            events.next().await,
+            Some(PipelineEvent::Unwound {
+                stage_id: StageId::Other("B"),
+                result: UnwindOutput { checkpoint: StageCheckpoint::new(50) },
+            })
+        );
+        assert_eq!(
+            events.next().await,
+            Some(PipelineEvent::Running {
+                pipeline_position: 1,
+                pipeline_total: 2,
+                stage_id: StageId::Other("A"),
+                checkpoint: Some(StageCheckpoint::new(50))
+            })
+        );
+    }
+
     /// Unwinds a simple pipeline.
     #[tokio::test]
     async fn unwind_pipeline() {
@@ -632,6 +1173,7 @@ mod tests {
                     pipeline_total: 3,
                     stage_id: StageId::Other("A"),
                     result: ExecOutput { checkpoint: StageCheckpoint::new(100), done: true },
+                    eta: None,
                 },
                 PipelineEvent::Running {
                     pipeline_position: 2,
@@ -644,6 +1186,7 @@ mod tests {
                     pipeline_total: 3,
                     stage_id: StageId::Other("B"),
                     result: ExecOutput { checkpoint: StageCheckpoint::new(10), done: true },
+                    eta: None,
                 },
                 PipelineEvent::Running {
                     pipeline_position: 3,
@@ -656,6 +1199,7 @@ mod tests {
                     pipeline_total: 3,
                     stage_id: StageId::Other("C"),
                     result: ExecOutput { checkpoint: StageCheckpoint::new(20), done: true },
+                    eta: None,
                 },
                 // Unwinding
                 PipelineEvent::Unwinding {
@@ -742,6 +1286,7 @@ mod tests {
                     pipeline_total: 2,
                     stage_id: StageId::Other("A"),
                     result: ExecOutput { checkpoint: StageCheckpoint::new(100), done: true },
+                    eta: None,
                 },
                 PipelineEvent::Running {
                     pipeline_position: 2,
@@ -754,6 +1299,7 @@ mod tests {
                     pipeline_total: 2,
                     stage_id: StageId::Other("B"),
                     result: ExecOutput { checkpoint: StageCheckpoint::new(10), done: true },
+                    eta: None,
                 },
                 // Unwinding
                 // Nothing to unwind in stage "B"
@@ -830,6 +1376,7 @@ mod tests {
                     pipeline_total: 2,
                     stage_id: StageId::Other("A"),
                     result: ExecOutput { checkpoint: StageCheckpoint::new(10), done: true },
+                    eta: None,
                 },
                 PipelineEvent::Running {
                     pipeline_position: 2,
@@ -861,6 +1408,7 @@ mod tests {
                     pipeline_total: 2,
                     stage_id: StageId::Other("A"),
                     result: ExecOutput { checkpoint: StageCheckpoint::new(10), done: true },
+                    eta: None,
                 },
                 PipelineEvent::Running {
                     pipeline_position: 2,
@@ -873,6 +1421,7 @@ mod tests {
                     pipeline_total: 2,
                     stage_id: StageId::Other("B"),
                     result: ExecOutput { checkpoint: StageCheckpoint::new(10), done: true },
+                    eta: None,
                 },
             ]
         );
@@ -180,6 +180,15 @@ pub trait Stage<DB: Database>: Send + Sync {
     /// Stage IDs must be unique.
     fn id(&self) -> StageId;
 
+    /// Returns the stages this stage depends on.
+    ///
+    /// The pipeline builder rejects pipelines where a dependency is ordered after this stage,
+    /// see [PipelineBuilder::try_build][crate::PipelineBuilder::try_build]. Defaults to
+    /// [StageId::dependencies] of the stage's [ID][StageId], custom stages have none.
+    fn dependencies(&self) -> &[StageId] {
+        self.id().dependencies()
+    }
+
     /// Execute the stage.
     async fn execute(
         &mut self,
@@ -193,6 +202,21 @@ pub trait Stage<DB: Database>: Send + Sync {
         provider: &DatabaseProviderRW<'_, &DB>,
         input: UnwindInput,
     ) -> Result<UnwindOutput, StageError>;
+
+    /// Returns the total number of entities the stage processes, if it's known before the stage
+    /// is executed.
+    ///
+    /// The pipeline calls this before executing the stage and forwards the result as
+    /// [PipelineEvent::EntitiesTotal][crate::PipelineEvent::EntitiesTotal]. Stages that only
+    /// discover the total during execution report it in their checkpoint instead, which the
+    /// pipeline forwards as well once it changes.
+    fn entities_total(
+        &self,
+        _provider: &DatabaseProviderRW<'_, &DB>,
+        _input: ExecInput,
+    ) -> Result<Option<u64>, StageError> {
+        Ok(None)
+    }
 }
 
 /// Prune target.
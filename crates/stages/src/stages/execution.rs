@@ -6,7 +6,7 @@ use reth_db::{
     tables,
     transaction::{DbTx, DbTxMut},
 };
-use reth_interfaces::db::DatabaseError;
+use reth_interfaces::{consensus, db::DatabaseError};
 use reth_metrics::{
     metrics::{self, Gauge},
     Metrics,
@@ -16,11 +16,12 @@ use reth_primitives::{
     stage::{
         CheckpointBlockRange, EntitiesCheckpoint, ExecutionCheckpoint, StageCheckpoint, StageId,
     },
-    BlockNumber, Header, U256,
+    BlockNumber, Header, H256, U256,
 };
 use reth_provider::{
-    post_state::PostState, BlockExecutor, BlockReader, DatabaseProviderRW, ExecutorFactory,
-    HeaderProvider, LatestStateProviderRef, ProviderError,
+    post_state::PostState, BlockExecutor, BlockReader, DatabaseProvider, DatabaseProviderRW,
+    ExecutorFactory, HeaderProvider, HistoricalStateProviderRef, LatestStateProviderRef,
+    ProviderError, StageCheckpointReader, StateProvider,
 };
 use std::{ops::RangeInclusive, time::Instant};
 use tracing::*;
@@ -31,6 +32,8 @@ use tracing::*;
 pub struct ExecutionStageMetrics {
     /// The total amount of gas processed (in millions)
     mgas_processed_total: Gauge,
+    /// The amount of gas processed per second (in millions) during the last batch
+    mgas_per_second: Gauge,
 }
 
 /// The execution stage executes all transactions and
@@ -69,12 +72,163 @@ pub struct ExecutionStage<EF: ExecutorFactory> {
     executor_factory: EF,
     /// The commit thresholds of the execution stage.
     thresholds: ExecutionStageThresholds,
+    /// Whether to compute the state root of each batch from the executed state changes.
+    incremental_state_root: bool,
+    /// The ramp of the commit thresholds over the first batches, if any.
+    batch_size_ramp: Option<BatchSizeRamp>,
+    /// The number of batches committed since the stage was created.
+    batches: u64,
 }
 
 impl<EF: ExecutorFactory> ExecutionStage<EF> {
     /// Create new execution stage with specified config.
     pub fn new(executor_factory: EF, thresholds: ExecutionStageThresholds) -> Self {
-        Self { metrics: ExecutionStageMetrics::default(), executor_factory, thresholds }
+        Self {
+            metrics: ExecutionStageMetrics::default(),
+            executor_factory,
+            thresholds,
+            incremental_state_root: false,
+            batch_size_ramp: None,
+            batches: 0,
+        }
+    }
+
+    /// Start with smaller batches and grow them to the configured thresholds over the first
+    /// batches, see [BatchSizeRamp].
+    ///
+    /// This keeps the first commits after startup short, e.g. when the node restarts close to
+    /// the tip.
+    pub fn with_batch_size_ramp(mut self, ramp: BatchSizeRamp) -> Self {
+        self.batch_size_ramp = Some(ramp);
+        self
+    }
+
+    /// Returns the commit thresholds of the next batch.
+    fn batch_thresholds(&self) -> ExecutionStageThresholds {
+        match &self.batch_size_ramp {
+            Some(ramp) => self.thresholds.scaled(ramp.factor(self.batches)),
+            None => self.thresholds,
+        }
+    }
+
+    /// Compute the state root of each batch from the executed state changes on top of the hashed
+    /// state and the trie, and validate it against the last executed block before committing.
+    ///
+    /// The root can only be computed this way if the hashing and merkle stages are caught up
+    /// with the execution stage at the start of the batch, which is usually the case during live
+    /// sync. Otherwise, the batch is committed without validating the root, and the merkle stage
+    /// validates it as usual. Disabled by default, since walking the trie once in the merkle
+    /// stage is faster for large ranges, e.g. during the initial sync.
+    pub fn with_incremental_state_root(mut self, incremental_state_root: bool) -> Self {
+        self.incremental_state_root = incremental_state_root;
+        self
+    }
+
+    /// Returns `true` if the hashed state and the trie reflect the state right before the given
+    /// block, i.e. the state root of the executed state changes can be computed on top of them.
+    fn can_compute_state_root<DB: Database>(
+        &self,
+        provider: &DatabaseProviderRW<'_, &DB>,
+        start_block: BlockNumber,
+    ) -> Result<bool, StageError> {
+        if !self.incremental_state_root {
+            return Ok(false)
+        }
+
+        trie_is_before(&**provider, start_block)
+    }
+
+    /// Executes the given block range without writing any state changes and returns the gas used
+    /// and the computed roots of each block, for comparison against the block headers.
+    ///
+    /// Blocks are executed with the same executor as the stage, which also validates the gas used
+    /// of each block against its header. Blocks that follow the execution checkpoint are
+    /// executed on top of the latest state, ranges that start at or below the checkpoint on top of
+    /// the historical state, which requires the history indices to be built up to the execution
+    /// checkpoint. Ranges that start after the block following the checkpoint are rejected with
+    /// [StageError::StageCheckpoint], since the state before them is not known.
+    ///
+    /// The state root can only be computed if the hashed state and the trie reflect the state
+    /// right before the range, otherwise it's `None`. See also
+    /// [ExecutionStage::with_incremental_state_root].
+    ///
+    /// **Note:** the executor can use more stack space than what's allocated by default for
+    /// threads, see [Stage::execute] of this stage.
+    pub fn validate_only<'a, TX: DbTx<'a>>(
+        &self,
+        provider: &DatabaseProvider<'a, TX>,
+        range: RangeInclusive<BlockNumber>,
+    ) -> Result<Vec<ValidatedBlock>, StageError> {
+        let start_block = *range.start();
+        let execution = provider.get_stage_checkpoint(StageId::Execution)?.unwrap_or_default();
+
+        if execution.block_number + 1 == start_block {
+            let can_compute_state_root = trie_is_before(provider, start_block)?;
+            self.validate_with_state(
+                provider,
+                LatestStateProviderRef::new(provider.tx_ref()),
+                range,
+                can_compute_state_root,
+            )
+        } else if start_block > execution.block_number + 1 {
+            Err(StageError::StageCheckpoint(execution.block_number))
+        } else {
+            self.validate_with_state(
+                provider,
+                HistoricalStateProviderRef::new(provider.tx_ref(), start_block),
+                range,
+                false,
+            )
+        }
+    }
+
+    fn validate_with_state<'a, TX: DbTx<'a>, SP: StateProvider>(
+        &self,
+        provider: &DatabaseProvider<'a, TX>,
+        state_provider: SP,
+        range: RangeInclusive<BlockNumber>,
+        can_compute_state_root: bool,
+    ) -> Result<Vec<ValidatedBlock>, StageError> {
+        let mut executor = self.executor_factory.with_sp(state_provider);
+
+        let mut state = PostState::default();
+        let mut validated = Vec::with_capacity(range.clone().count());
+        for block_number in range {
+            let td = provider
+                .header_td_by_number(block_number)?
+                .ok_or_else(|| ProviderError::HeaderNotFound(block_number.into()))?;
+            let block = provider
+                .block_with_senders(block_number)?
+                .ok_or_else(|| ProviderError::BlockNotFound(block_number.into()))?;
+
+            trace!(target: "sync::stages::execution", number = block_number, txs = block.body.len(), "Validating block");
+
+            let (block, senders) = block.into_components();
+            let block_state = executor.execute(&block, td, Some(senders)).map_err(|error| {
+                StageError::ExecutionError { block: block.header.clone().seal_slow(), error }
+            })?;
+            let receipts_root = block_state.receipts_root(block_number);
+            state.extend(block_state);
+
+            let state_root = if can_compute_state_root {
+                Some(
+                    state
+                        .state_root_slow(provider.tx_ref())
+                        .map_err(|error| StageError::Fatal(Box::new(error)))?,
+                )
+            } else {
+                None
+            };
+
+            validated.push(ValidatedBlock {
+                number: block_number,
+                gas_used: block.gas_used,
+                receipts_root,
+                state_root,
+            });
+        }
+
+        Ok(validated)
     }
 
     /// Create an execution stage with the provided  executor factory.
@@ -91,6 +245,8 @@ impl<EF: ExecutorFactory> ExecutionStage<EF> {
         input: ExecInput,
     ) -> Result<ExecOutput, StageError> {
         if input.target_reached() {
+            // Nothing is executed, so don't keep reporting the throughput of the previous batch.
+            self.metrics.mgas_per_second.set(0.0);
             return Ok(ExecOutput::done(input.checkpoint()))
         }
 
@@ -106,8 +262,13 @@ impl<EF: ExecutorFactory> ExecutionStage<EF> {
         let mut stage_checkpoint =
             execution_checkpoint(provider, start_block, max_block, input.checkpoint())?;
 
+        let can_compute_state_root = self.can_compute_state_root(provider, start_block)?;
+
         // Execute block range
         let mut state = PostState::default();
+        let mut batch_gas_used = 0;
+        let execution_started_at = Instant::now();
+        let thresholds = self.batch_thresholds();
         for block_number in start_block..=max_block {
             let td = provider
                 .header_td_by_number(block_number)?
@@ -137,14 +298,44 @@ impl<EF: ExecutorFactory> ExecutionStage<EF> {
             state.extend(block_state);
             stage_progress = block_number;
             stage_checkpoint.progress.processed += block.gas_used;
+            batch_gas_used += block.gas_used;
 
             // Check if we should commit now
-            if self.thresholds.is_end_of_batch(block_number - start_block, state.size_hint() as u64)
-            {
+            if thresholds.is_end_of_batch(
+                block_number - start_block,
+                state.size_hint() as u64,
+                batch_gas_used,
+            ) {
                 break
             }
         }
 
+        let execution_elapsed = execution_started_at.elapsed().as_secs_f64();
+        if execution_elapsed > 0.0 {
+            self.metrics
+                .mgas_per_second
+                .set(batch_gas_used as f64 / MGAS_TO_GAS as f64 / execution_elapsed);
+        }
+
+        if can_compute_state_root {
+            let header = provider
+                .sealed_header(stage_progress)?
+                .ok_or_else(|| ProviderError::HeaderNotFound(stage_progress.into()))?;
+            let state_root = state
+                .state_root_slow(provider.tx_ref())
+                .map_err(|error| StageError::Fatal(Box::new(error)))?;
+            if state_root != header.state_root {
+                warn!(target: "sync::stages::execution", block = stage_progress, got = ?state_root, expected = ?header.state_root, "Failed to verify block state root");
+                return Err(StageError::Validation {
+                    error: consensus::ConsensusError::BodyStateRootDiff {
+                        got: state_root,
+                        expected: header.state_root,
+                    },
+                    block: header,
+                })
+            }
+        }
+
         // Write remaining changes
         trace!(target: "sync::stages::execution", accounts = state.accounts().len(), "Writing updated state to database");
         let start = Instant::now();
@@ -160,6 +351,34 @@ impl<EF: ExecutorFactory> ExecutionStage<EF> {
     }
 }
 
+/// Returns `true` if the hashing and merkle stages are at the block right before the given one.
+fn trie_is_before<'a, TX: DbTx<'a>>(
+    provider: &DatabaseProvider<'a, TX>,
+    block_number: BlockNumber,
+) -> Result<bool, StageError> {
+    for stage_id in [StageId::AccountHashing, StageId::StorageHashing, StageId::MerkleExecute] {
+        let checkpoint = provider.get_stage_checkpoint(stage_id)?.unwrap_or_default();
+        if checkpoint.block_number + 1 != block_number {
+            return Ok(false)
+        }
+    }
+
+    Ok(true)
+}
+
+/// The gas used and the computed roots of a block executed by [ExecutionStage::validate_only].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ValidatedBlock {
+    /// The number of the block.
+    pub number: BlockNumber,
+    /// The gas used by the block.
+    pub gas_used: u64,
+    /// The root of the receipts of the block.
+    pub receipts_root: H256,
+    /// The state root after the block, if it could be computed.
+    pub state_root: Option<H256>,
+}
+
 fn execution_checkpoint<DB: Database>(
     provider: &DatabaseProviderRW<'_, &DB>,
     start_block: BlockNumber,
@@ -274,7 +493,7 @@ impl<EF: ExecutorFactory, DB: Database> Stage<DB> for ExecutionStage<EF> {
         // to optimize revm or move data to the heap.
         //
         // See https://github.com/bluealloy/revm/issues/305
-        std::thread::scope(|scope| {
+        let output = std::thread::scope(|scope| {
             let handle = std::thread::Builder::new()
                 .stack_size(BIG_STACK_SIZE)
                 .spawn_scoped(scope, || {
@@ -283,7 +502,13 @@ impl<EF: ExecutorFactory, DB: Database> Stage<DB> for ExecutionStage<EF> {
                 })
                 .expect("Expects that thread name is not null");
             handle.join().expect("Expects for thread to not panic")
-        })
+        })?;
+
+        if output.checkpoint.block_number > input.checkpoint().block_number {
+            self.batches += 1;
+        }
+
+        Ok(output)
     }
 
     /// Unwind the stage.
@@ -387,42 +612,96 @@ impl<EF: ExecutorFactory, DB: Database> Stage<DB> for ExecutionStage<EF> {
 
 /// The thresholds at which the execution stage writes state changes to the database.
 ///
-/// If either of the thresholds (`max_blocks` and `max_changes`) are hit, then the execution stage
-/// commits all pending changes to the database.
-#[derive(Debug)]
+/// If any of the thresholds (`max_blocks`, `max_changes` or `max_cumulative_gas`) are hit, then
+/// the execution stage commits all pending changes to the database.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct ExecutionStageThresholds {
     /// The maximum number of blocks to process before the execution stage commits.
     pub max_blocks: Option<u64>,
     /// The maximum amount of state changes to keep in memory before the execution stage commits.
     pub max_changes: Option<u64>,
+    /// The maximum amount of gas to process before the execution stage commits.
+    pub max_cumulative_gas: Option<u64>,
 }
 
 impl Default for ExecutionStageThresholds {
     fn default() -> Self {
-        Self { max_blocks: Some(500_000), max_changes: Some(5_000_000) }
+        Self { max_blocks: Some(500_000), max_changes: Some(5_000_000), max_cumulative_gas: None }
     }
 }
 
 impl ExecutionStageThresholds {
     /// Check if the batch thresholds have been hit.
     #[inline]
-    pub fn is_end_of_batch(&self, blocks_processed: u64, changes_processed: u64) -> bool {
+    pub fn is_end_of_batch(
+        &self,
+        blocks_processed: u64,
+        changes_processed: u64,
+        cumulative_gas_used: u64,
+    ) -> bool {
         blocks_processed >= self.max_blocks.unwrap_or(u64::MAX) ||
-            changes_processed >= self.max_changes.unwrap_or(u64::MAX)
+            changes_processed >= self.max_changes.unwrap_or(u64::MAX) ||
+            cumulative_gas_used >= self.max_cumulative_gas.unwrap_or(u64::MAX)
+    }
+
+    /// Scales all thresholds by the given factor, keeping them at least 1.
+    fn scaled(&self, factor: f64) -> Self {
+        let scale = |threshold: Option<u64>| {
+            threshold.map(|threshold| ((threshold as f64 * factor) as u64).max(1))
+        };
+        Self {
+            max_blocks: scale(self.max_blocks),
+            max_changes: scale(self.max_changes),
+            max_cumulative_gas: scale(self.max_cumulative_gas),
+        }
+    }
+}
+
+/// The ramp of the execution stage commit thresholds after startup.
+///
+/// The first batch is committed at `initial_percent` of the [ExecutionStageThresholds], and the
+/// thresholds grow geometrically until they reach 100% with batch number `batches`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BatchSizeRamp {
+    /// The thresholds of the first batch, in percent of the configured thresholds.
+    pub initial_percent: u64,
+    /// The number of batches until the configured thresholds are reached. `0` disables the ramp.
+    pub batches: u64,
+}
+
+impl Default for BatchSizeRamp {
+    fn default() -> Self {
+        Self { initial_percent: 1, batches: 5 }
+    }
+}
+
+impl BatchSizeRamp {
+    /// Returns the factor the thresholds of the given zero-based batch are scaled by.
+    pub fn factor(&self, batch: u64) -> f64 {
+        let initial = self.initial_percent.clamp(1, 100) as f64 / 100.0;
+        if self.batches == 0 || initial >= 1.0 || batch >= self.batches {
+            return 1.0
+        }
+        initial.powf(1.0 - batch as f64 / self.batches as f64)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::test_utils::TestTransaction;
+    use crate::{
+        stages::{IndexAccountHistoryStage, IndexStorageHistoryStage},
+        test_utils::TestTransaction,
+    };
     use assert_matches::assert_matches;
     use reth_db::{models::AccountBeforeTx, test_utils::create_test_rw_db};
     use reth_primitives::{
         hex_literal::hex, keccak256, stage::StageUnitCheckpoint, Account, Bytecode,
         ChainSpecBuilder, SealedBlock, StorageEntry, H160, H256, MAINNET, U256,
     };
-    use reth_provider::{AccountReader, BlockWriter, ProviderFactory, ReceiptProvider};
+    use reth_provider::{
+        AccountReader, BlockWriter, ProviderFactory, ReceiptProvider, StageCheckpointWriter,
+    };
     use reth_revm::Factory;
     use reth_rlp::Decodable;
     use std::sync::Arc;
@@ -432,10 +711,62 @@ mod tests {
             Factory::new(Arc::new(ChainSpecBuilder::mainnet().berlin_activated().build()));
         ExecutionStage::new(
             factory,
-            ExecutionStageThresholds { max_blocks: Some(100), max_changes: None },
+            ExecutionStageThresholds {
+                max_blocks: Some(100),
+                max_changes: None,
+                max_cumulative_gas: None,
+            },
         )
     }
 
+    #[test]
+    fn batch_size_ramp() {
+        let ramp = BatchSizeRamp { initial_percent: 1, batches: 2 };
+        assert_eq!(ramp.factor(0), 0.01);
+        assert!((ramp.factor(1) - 0.1).abs() < 1e-9);
+        assert_eq!(ramp.factor(2), 1.0);
+        assert_eq!(ramp.factor(100), 1.0);
+
+        // disabled
+        assert_eq!(BatchSizeRamp { initial_percent: 1, batches: 0 }.factor(0), 1.0);
+        assert_eq!(BatchSizeRamp { initial_percent: 100, batches: 5 }.factor(0), 1.0);
+        assert_eq!(BatchSizeRamp { initial_percent: 200, batches: 5 }.factor(0), 1.0);
+    }
+
+    #[test]
+    fn batch_thresholds() {
+        let mut stage =
+            stage().with_batch_size_ramp(BatchSizeRamp { initial_percent: 25, batches: 2 });
+        assert_eq!(
+            stage.batch_thresholds(),
+            ExecutionStageThresholds {
+                max_blocks: Some(25),
+                max_changes: None,
+                max_cumulative_gas: None
+            }
+        );
+
+        stage.batches = 1;
+        assert_eq!(stage.batch_thresholds().max_blocks, Some(50));
+
+        stage.batches = 2;
+        assert_eq!(stage.batch_thresholds(), stage.thresholds);
+
+        let thresholds = ExecutionStageThresholds {
+            max_blocks: Some(500_000),
+            max_changes: Some(5_000_000),
+            max_cumulative_gas: Some(10),
+        };
+        assert_eq!(
+            thresholds.scaled(0.01),
+            ExecutionStageThresholds {
+                max_blocks: Some(5_000),
+                max_changes: Some(50_000),
+                max_cumulative_gas: Some(1),
+            }
+        );
+    }
+
     #[test]
     fn execution_checkpoint_matches() {
         let state_db = create_test_rw_db();
@@ -670,6 +1001,245 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn validate_only_does_not_write_state() {
+        let state_db = create_test_rw_db();
+        let factory = ProviderFactory::new(state_db.as_ref(), MAINNET.clone());
+        let provider = factory.provider_rw().unwrap();
+        let mut genesis_rlp = hex!("f901faf901f5a00000000000000000000000000000000000000000000000000000000000000000a01dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347942adc25665018aa1fe0e6bc666dac8fc2697ff9baa045571b40ae66ca7480791bbb2887286e4e4c4b1b298b191c889d6959023a32eda056e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421a056e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421b901000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000083020000808502540be400808000a00000000000000000000000000000000000000000000000000000000000000000880000000000000000c0c0").as_slice();
+        let genesis = SealedBlock::decode(&mut genesis_rlp).unwrap();
+        let mut block_rlp = hex!("f90262f901f9a075c371ba45999d87f4542326910a11af515897aebce5265d3f6acd1f1161f82fa01dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347942adc25665018aa1fe0e6bc666dac8fc2697ff9baa098f2dcd87c8ae4083e7017a05456c14eea4b1db2032126e27b3b1563d57d7cc0a08151d548273f6683169524b66ca9fe338b9ce42bc3540046c828fd939ae23bcba03f4e5c2ec5b2170b711d97ee755c160457bb58d8daa338e835ec02ae6860bbabb901000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000083020000018502540be40082a8798203e800a00000000000000000000000000000000000000000000000000000000000000000880000000000000000f863f861800a8405f5e10094100000000000000000000000000000000000000080801ba07e09e26678ed4fac08a249ebe8ed680bf9051a5e14ad223e4b2b9d26e0208f37a05f6e3f188e3e6eab7d7d3b6568f5eac7d687b08d307d3154ccd8c87b4630509bc0").as_slice();
+        let block = SealedBlock::decode(&mut block_rlp).unwrap();
+        provider.insert_block(genesis, None).unwrap();
+        provider.insert_block(block.clone(), None).unwrap();
+        provider.commit().unwrap();
+
+        // insert pre state
+        let provider = factory.provider_rw().unwrap();
+        let db_tx = provider.tx_ref();
+        let acc1 = H160(hex!("1000000000000000000000000000000000000000"));
+        let acc2 = H160(hex!("a94f5374fce5edbc8e2a8697c15331677e6ebf0b"));
+        let code = hex!("5a465a905090036002900360015500");
+        let balance = U256::from(0x3635c9adc5dea00000u128);
+        let code_hash = keccak256(code);
+        db_tx
+            .put::<tables::PlainAccountState>(
+                acc1,
+                Account { nonce: 0, balance: U256::ZERO, bytecode_hash: Some(code_hash) },
+            )
+            .unwrap();
+        db_tx
+            .put::<tables::PlainAccountState>(
+                acc2,
+                Account { nonce: 0, balance, bytecode_hash: None },
+            )
+            .unwrap();
+        db_tx.put::<tables::Bytecodes>(code_hash, Bytecode::new_raw(code.to_vec().into())).unwrap();
+        provider.commit().unwrap();
+
+        let provider = factory.provider().unwrap();
+        let validated = stage().validate_only(&provider, 1..=1).unwrap();
+        assert_eq!(validated.len(), 1);
+        assert_eq!(validated[0].number, 1);
+        assert_eq!(validated[0].gas_used, block.gas_used);
+        assert_eq!(validated[0].receipts_root, block.receipts_root);
+
+        // the state is untouched
+        assert_eq!(
+            provider.basic_account(acc2),
+            Ok(Some(Account { nonce: 0, balance, bytecode_hash: None }))
+        );
+        assert_eq!(provider.tx_ref().entries::<tables::AccountChangeSet>(), Ok(0));
+    }
+
+    #[tokio::test]
+    async fn validate_only_historical_range() {
+        let state_db = create_test_rw_db();
+        let factory = ProviderFactory::new(state_db.as_ref(), MAINNET.clone());
+        let provider = factory.provider_rw().unwrap();
+        let mut genesis_rlp = hex!("f901faf901f5a00000000000000000000000000000000000000000000000000000000000000000a01dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347942adc25665018aa1fe0e6bc666dac8fc2697ff9baa045571b40ae66ca7480791bbb2887286e4e4c4b1b298b191c889d6959023a32eda056e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421a056e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421b901000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000083020000808502540be400808000a00000000000000000000000000000000000000000000000000000000000000000880000000000000000c0c0").as_slice();
+        let genesis = SealedBlock::decode(&mut genesis_rlp).unwrap();
+        let mut block_rlp = hex!("f90262f901f9a075c371ba45999d87f4542326910a11af515897aebce5265d3f6acd1f1161f82fa01dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347942adc25665018aa1fe0e6bc666dac8fc2697ff9baa098f2dcd87c8ae4083e7017a05456c14eea4b1db2032126e27b3b1563d57d7cc0a08151d548273f6683169524b66ca9fe338b9ce42bc3540046c828fd939ae23bcba03f4e5c2ec5b2170b711d97ee755c160457bb58d8daa338e835ec02ae6860bbabb901000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000083020000018502540be40082a8798203e800a00000000000000000000000000000000000000000000000000000000000000000880000000000000000f863f861800a8405f5e10094100000000000000000000000000000000000000080801ba07e09e26678ed4fac08a249ebe8ed680bf9051a5e14ad223e4b2b9d26e0208f37a05f6e3f188e3e6eab7d7d3b6568f5eac7d687b08d307d3154ccd8c87b4630509bc0").as_slice();
+        let block = SealedBlock::decode(&mut block_rlp).unwrap();
+        provider.insert_block(genesis, None).unwrap();
+        provider.insert_block(block.clone(), None).unwrap();
+        provider.commit().unwrap();
+
+        // insert pre state
+        let provider = factory.provider_rw().unwrap();
+        let db_tx = provider.tx_ref();
+        let acc1 = H160(hex!("1000000000000000000000000000000000000000"));
+        let acc2 = H160(hex!("a94f5374fce5edbc8e2a8697c15331677e6ebf0b"));
+        let code = hex!("5a465a905090036002900360015500");
+        let balance = U256::from(0x3635c9adc5dea00000u128);
+        let code_hash = keccak256(code);
+        db_tx
+            .put::<tables::PlainAccountState>(
+                acc1,
+                Account { nonce: 0, balance: U256::ZERO, bytecode_hash: Some(code_hash) },
+            )
+            .unwrap();
+        db_tx
+            .put::<tables::PlainAccountState>(
+                acc2,
+                Account { nonce: 0, balance, bytecode_hash: None },
+            )
+            .unwrap();
+        db_tx.put::<tables::Bytecodes>(code_hash, Bytecode::new_raw(code.to_vec().into())).unwrap();
+        provider.commit().unwrap();
+
+        // execute and index the block
+        let input = ExecInput { target: Some(1), checkpoint: None };
+        let provider = factory.provider_rw().unwrap();
+        let output = stage().execute(&provider, input).await.unwrap();
+        provider.save_stage_checkpoint(StageId::Execution, output.checkpoint).unwrap();
+        IndexAccountHistoryStage::default().execute(&provider, input).await.unwrap();
+        IndexStorageHistoryStage::default().execute(&provider, input).await.unwrap();
+        provider.commit().unwrap();
+
+        // the already executed block is validated on top of the historical state
+        let provider = factory.provider().unwrap();
+        let validated = stage().validate_only(&provider, 1..=1).unwrap();
+        assert_eq!(validated.len(), 1);
+        assert_eq!(validated[0].gas_used, block.gas_used);
+        assert_eq!(validated[0].receipts_root, block.receipts_root);
+        assert_eq!(validated[0].state_root, None);
+
+        // the state before a range that doesn't follow the checkpoint is unknown
+        assert_matches!(
+            stage().validate_only(&provider, 3..=3),
+            Err(StageError::StageCheckpoint(1))
+        );
+    }
+
+    #[tokio::test]
+    async fn incremental_state_root() {
+        let state_db = create_test_rw_db();
+        let factory = ProviderFactory::new(state_db.as_ref(), MAINNET.clone());
+        let provider = factory.provider_rw().unwrap();
+        let mut genesis_rlp = hex!("f901faf901f5a00000000000000000000000000000000000000000000000000000000000000000a01dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347942adc25665018aa1fe0e6bc666dac8fc2697ff9baa045571b40ae66ca7480791bbb2887286e4e4c4b1b298b191c889d6959023a32eda056e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421a056e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421b901000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000083020000808502540be400808000a00000000000000000000000000000000000000000000000000000000000000000880000000000000000c0c0").as_slice();
+        let genesis = SealedBlock::decode(&mut genesis_rlp).unwrap();
+        let mut block_rlp = hex!("f90262f901f9a075c371ba45999d87f4542326910a11af515897aebce5265d3f6acd1f1161f82fa01dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347942adc25665018aa1fe0e6bc666dac8fc2697ff9baa098f2dcd87c8ae4083e7017a05456c14eea4b1db2032126e27b3b1563d57d7cc0a08151d548273f6683169524b66ca9fe338b9ce42bc3540046c828fd939ae23bcba03f4e5c2ec5b2170b711d97ee755c160457bb58d8daa338e835ec02ae6860bbabb901000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000083020000018502540be40082a8798203e800a00000000000000000000000000000000000000000000000000000000000000000880000000000000000f863f861800a8405f5e10094100000000000000000000000000000000000000080801ba07e09e26678ed4fac08a249ebe8ed680bf9051a5e14ad223e4b2b9d26e0208f37a05f6e3f188e3e6eab7d7d3b6568f5eac7d687b08d307d3154ccd8c87b4630509bc0").as_slice();
+        let block = SealedBlock::decode(&mut block_rlp).unwrap();
+        provider.insert_block(genesis, None).unwrap();
+        provider.insert_block(block.clone(), None).unwrap();
+        provider.commit().unwrap();
+
+        // insert pre state
+        let provider = factory.provider_rw().unwrap();
+        let db_tx = provider.tx_ref();
+        let acc1 = H160(hex!("1000000000000000000000000000000000000000"));
+        let acc2 = H160(hex!("a94f5374fce5edbc8e2a8697c15331677e6ebf0b"));
+        let code = hex!("5a465a905090036002900360015500");
+        let balance = U256::from(0x3635c9adc5dea00000u128);
+        let code_hash = keccak256(code);
+        db_tx
+            .put::<tables::PlainAccountState>(
+                acc1,
+                Account { nonce: 0, balance: U256::ZERO, bytecode_hash: Some(code_hash) },
+            )
+            .unwrap();
+        db_tx
+            .put::<tables::PlainAccountState>(
+                acc2,
+                Account { nonce: 0, balance, bytecode_hash: None },
+            )
+            .unwrap();
+        db_tx.put::<tables::Bytecodes>(code_hash, Bytecode::new_raw(code.to_vec().into())).unwrap();
+        // a hashed account that is not part of the state, so the state root can't match
+        db_tx
+            .put::<tables::HashedAccount>(
+                keccak256(H160::from_low_u64_be(1)),
+                Account { nonce: 1, balance: U256::ZERO, bytecode_hash: None },
+            )
+            .unwrap();
+        provider.commit().unwrap();
+
+        let input = ExecInput { target: Some(1), checkpoint: None };
+
+        // the trie is not right before the batch, so the root is left to the merkle stage
+        let provider = factory.provider_rw().unwrap();
+        provider.save_stage_checkpoint(StageId::MerkleExecute, StageCheckpoint::new(1)).unwrap();
+        let output = stage().with_incremental_state_root(true).execute(&provider, input).await;
+        assert_matches!(output, Ok(ExecOutput { done: true, .. }));
+        drop(provider);
+
+        // the root of the batch is computed on top of the trie and validated
+        let provider = factory.provider_rw().unwrap();
+        let output = stage().with_incremental_state_root(true).execute(&provider, input).await;
+        assert_matches!(
+            output,
+            Err(StageError::Validation {
+                error: consensus::ConsensusError::BodyStateRootDiff { .. },
+                ..
+            })
+        );
+        assert_eq!(provider.tx_ref().entries::<tables::AccountChangeSet>(), Ok(0));
+    }
+
+    #[tokio::test]
+    async fn incremental_state_root_matches() {
+        let state_db = create_test_rw_db();
+        let factory = ProviderFactory::new(state_db.as_ref(), MAINNET.clone());
+        let provider = factory.provider_rw().unwrap();
+        let mut genesis_rlp = hex!("f901faf901f5a00000000000000000000000000000000000000000000000000000000000000000a01dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347942adc25665018aa1fe0e6bc666dac8fc2697ff9baa045571b40ae66ca7480791bbb2887286e4e4c4b1b298b191c889d6959023a32eda056e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421a056e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421b901000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000083020000808502540be400808000a00000000000000000000000000000000000000000000000000000000000000000880000000000000000c0c0").as_slice();
+        let genesis = SealedBlock::decode(&mut genesis_rlp).unwrap();
+        let mut block_rlp = hex!("f90262f901f9a075c371ba45999d87f4542326910a11af515897aebce5265d3f6acd1f1161f82fa01dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347942adc25665018aa1fe0e6bc666dac8fc2697ff9baa098f2dcd87c8ae4083e7017a05456c14eea4b1db2032126e27b3b1563d57d7cc0a08151d548273f6683169524b66ca9fe338b9ce42bc3540046c828fd939ae23bcba03f4e5c2ec5b2170b711d97ee755c160457bb58d8daa338e835ec02ae6860bbabb901000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000083020000018502540be40082a8798203e800a00000000000000000000000000000000000000000000000000000000000000000880000000000000000f863f861800a8405f5e10094100000000000000000000000000000000000000080801ba07e09e26678ed4fac08a249ebe8ed680bf9051a5e14ad223e4b2b9d26e0208f37a05f6e3f188e3e6eab7d7d3b6568f5eac7d687b08d307d3154ccd8c87b4630509bc0").as_slice();
+        let block = SealedBlock::decode(&mut block_rlp).unwrap();
+        provider.insert_block(genesis, None).unwrap();
+        provider.insert_block(block.clone(), None).unwrap();
+        provider.commit().unwrap();
+
+        // insert pre state
+        let provider = factory.provider_rw().unwrap();
+        let db_tx = provider.tx_ref();
+        let acc1 = H160(hex!("1000000000000000000000000000000000000000"));
+        let acc2 = H160(hex!("a94f5374fce5edbc8e2a8697c15331677e6ebf0b"));
+        let code = hex!("5a465a905090036002900360015500");
+        let balance = U256::from(0x3635c9adc5dea00000u128);
+        let code_hash = keccak256(code);
+        db_tx
+            .put::<tables::PlainAccountState>(
+                acc1,
+                Account { nonce: 0, balance: U256::ZERO, bytecode_hash: Some(code_hash) },
+            )
+            .unwrap();
+        db_tx
+            .put::<tables::PlainAccountState>(
+                acc2,
+                Account { nonce: 0, balance, bytecode_hash: None },
+            )
+            .unwrap();
+        db_tx.put::<tables::Bytecodes>(code_hash, Bytecode::new_raw(code.to_vec().into())).unwrap();
+        // the hashed state matches the plain state, and the trie is empty
+        db_tx
+            .put::<tables::HashedAccount>(
+                keccak256(acc1),
+                Account { nonce: 0, balance: U256::ZERO, bytecode_hash: Some(code_hash) },
+            )
+            .unwrap();
+        db_tx
+            .put::<tables::HashedAccount>(
+                keccak256(acc2),
+                Account { nonce: 0, balance, bytecode_hash: None },
+            )
+            .unwrap();
+        provider.commit().unwrap();
+
+        let input = ExecInput { target: Some(1), checkpoint: None };
+
+        // the root of the batch is computed on top of the trie and matches the header
+        let provider = factory.provider_rw().unwrap();
+        let output = stage().with_incremental_state_root(true).execute(&provider, input).await;
+        assert_matches!(output, Ok(ExecOutput { checkpoint, done: true }) if checkpoint.block_number == 1);
+        provider.commit().unwrap();
+
+        // the batch is committed
+        let provider = factory.provider().unwrap();
+        assert!(provider.tx_ref().entries::<tables::AccountChangeSet>().unwrap() > 0);
+        assert_eq!(provider.tx_ref().entries::<tables::Receipts>(), Ok(1));
+    }
+
     #[tokio::test]
     async fn sanity_execute_unwind() {
         // TODO cleanup the setup after https://github.com/paradigmxyz/reth/issues/332
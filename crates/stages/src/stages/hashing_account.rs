@@ -16,7 +16,7 @@ use reth_primitives::{
         StageId,
     },
 };
-use reth_provider::{AccountExtReader, DatabaseProviderRW, HashingWriter};
+use reth_provider::{AccountExtReader, AccountReader, DatabaseProviderRW, HashingWriter};
 use std::{
     cmp::max,
     fmt::Debug,
@@ -248,7 +248,7 @@ impl<DB: Database> Stage<DB> for AccountHashingStage {
             // Iterate over plain state and get newest value.
             // Assumption we are okay to make is that plainstate represent
             // `previous_stage_progress` state.
-            let accounts = provider.basic_accounts(lists)?;
+            let accounts = provider.basic_accounts(&lists.into_iter().collect::<Vec<_>>())?;
             // Insert and hash accounts to hashing table
             provider.insert_account_for_hashing(accounts)?;
         }
@@ -1,5 +1,6 @@
+use super::utils::{changesets_range_end, changesets_stage_checkpoint, processed_changesets};
 use crate::{ExecInput, ExecOutput, Stage, StageError, UnwindInput, UnwindOutput};
-use reth_db::database::Database;
+use reth_db::{database::Database, tables};
 use reth_primitives::stage::{StageCheckpoint, StageId};
 use reth_provider::{AccountExtReader, DatabaseProviderRW, HistoryWriter};
 use std::fmt::Debug;
@@ -12,18 +13,31 @@ pub struct IndexAccountHistoryStage {
     /// Number of blocks after which the control
     /// flow will be returned to the pipeline for commit.
     pub commit_threshold: u64,
+    /// Maximum number of account changesets to index before the control flow is returned to the
+    /// pipeline for commit. Only whole blocks are indexed, so the first block of a range is
+    /// always indexed even if it exceeds the threshold.
+    pub max_changesets_per_commit: Option<u64>,
 }
 
 impl IndexAccountHistoryStage {
     /// Create new instance of [IndexAccountHistoryStage].
     pub fn new(commit_threshold: u64) -> Self {
-        Self { commit_threshold }
+        Self { commit_threshold, max_changesets_per_commit: None }
+    }
+
+    /// Set the maximum number of changesets to index before committing.
+    pub fn with_max_changesets_per_commit(
+        mut self,
+        max_changesets_per_commit: Option<u64>,
+    ) -> Self {
+        self.max_changesets_per_commit = max_changesets_per_commit;
+        self
     }
 }
 
 impl Default for IndexAccountHistoryStage {
     fn default() -> Self {
-        Self { commit_threshold: 100_000 }
+        Self { commit_threshold: 100_000, max_changesets_per_commit: None }
     }
 }
 
@@ -44,13 +58,35 @@ impl<DB: Database> Stage<DB> for IndexAccountHistoryStage {
             return Ok(ExecOutput::done(input.checkpoint()))
         }
 
-        let (range, is_final_range) = input.next_block_range_with_threshold(self.commit_threshold);
+        let (mut range, mut is_final_range) =
+            input.next_block_range_with_threshold(self.commit_threshold);
+
+        let end_block = changesets_range_end::<_, tables::AccountChangeSet>(
+            provider,
+            range.clone(),
+            self.max_changesets_per_commit,
+        )?;
+        if end_block < *range.end() {
+            range = *range.start()..=end_block;
+            is_final_range = false;
+        }
 
         let indices = provider.changed_accounts_and_blocks_with_range(range.clone())?;
+        let changesets = indices.values().map(|blocks| blocks.len() as u64).sum::<u64>();
         // Insert changeset to history index
         provider.insert_account_history_index(indices)?;
 
-        Ok(ExecOutput { checkpoint: StageCheckpoint::new(*range.end()), done: is_final_range })
+        let processed =
+            processed_changesets::<_, tables::AccountChangeSet>(provider, input.checkpoint())?;
+        Ok(ExecOutput {
+            checkpoint: StageCheckpoint::new(*range.end()).with_entities_stage_checkpoint(
+                changesets_stage_checkpoint::<_, tables::AccountChangeSet>(
+                    provider,
+                    processed + changesets,
+                )?,
+            ),
+            done: is_final_range,
+        })
     }
 
     /// Unwind the stage.
@@ -62,10 +98,19 @@ impl<DB: Database> Stage<DB> for IndexAccountHistoryStage {
         let (range, unwind_progress, _) =
             input.unwind_block_range_with_threshold(self.commit_threshold);
 
-        provider.unwind_account_history_indices(range)?;
+        let changesets = provider.unwind_account_history_indices(range)? as u64;
 
+        let processed =
+            processed_changesets::<_, tables::AccountChangeSet>(provider, input.checkpoint)?;
         // from HistoryIndex higher than that number.
-        Ok(UnwindOutput { checkpoint: StageCheckpoint::new(unwind_progress) })
+        Ok(UnwindOutput {
+            checkpoint: StageCheckpoint::new(unwind_progress).with_entities_stage_checkpoint(
+                changesets_stage_checkpoint::<_, tables::AccountChangeSet>(
+                    provider,
+                    processed.saturating_sub(changesets),
+                )?,
+            ),
+        })
     }
 }
 
@@ -85,7 +130,7 @@ mod tests {
         transaction::DbTxMut,
         BlockNumberList,
     };
-    use reth_primitives::{hex_literal::hex, H160, MAINNET};
+    use reth_primitives::{hex_literal::hex, stage::EntitiesCheckpoint, H160, MAINNET};
 
     const ADDRESS: H160 = H160(hex!("0000000000000000000000000000000000000001"));
 
@@ -144,7 +189,14 @@ mod tests {
         let factory = ProviderFactory::new(tx.tx.as_ref(), MAINNET.clone());
         let provider = factory.provider_rw().unwrap();
         let out = stage.execute(&provider, input).await.unwrap();
-        assert_eq!(out, ExecOutput { checkpoint: StageCheckpoint::new(5), done: true });
+        assert_eq!(
+            out,
+            ExecOutput {
+                checkpoint: StageCheckpoint::new(5)
+                    .with_entities_stage_checkpoint(EntitiesCheckpoint { processed: 2, total: 2 }),
+                done: true
+            }
+        );
         provider.commit().unwrap();
     }
 
@@ -158,7 +210,13 @@ mod tests {
         let factory = ProviderFactory::new(tx.tx.as_ref(), MAINNET.clone());
         let provider = factory.provider_rw().unwrap();
         let out = stage.unwind(&provider, input).await.unwrap();
-        assert_eq!(out, UnwindOutput { checkpoint: StageCheckpoint::new(unwind_to) });
+        assert_eq!(
+            out,
+            UnwindOutput {
+                checkpoint: StageCheckpoint::new(unwind_to)
+                    .with_entities_stage_checkpoint(EntitiesCheckpoint { processed: 0, total: 2 })
+            }
+        );
         provider.commit().unwrap();
     }
 
@@ -185,6 +243,73 @@ mod tests {
         assert!(table.is_empty());
     }
 
+    #[tokio::test]
+    async fn insert_index_with_max_changesets_per_commit() {
+        // init
+        let tx = TestTransaction::default();
+
+        // setup
+        partial_setup(&tx);
+
+        // run, one changeset per commit
+        let mut stage = IndexAccountHistoryStage::default().with_max_changesets_per_commit(Some(1));
+        let factory = ProviderFactory::new(tx.tx.as_ref(), MAINNET.clone());
+
+        let provider = factory.provider_rw().unwrap();
+        let input = ExecInput { target: Some(5), ..Default::default() };
+        let out = stage.execute(&provider, input).await.unwrap();
+        assert_eq!(
+            out,
+            ExecOutput {
+                checkpoint: StageCheckpoint::new(4)
+                    .with_entities_stage_checkpoint(EntitiesCheckpoint { processed: 1, total: 2 }),
+                done: false
+            }
+        );
+        provider.commit().unwrap();
+
+        let provider = factory.provider_rw().unwrap();
+        let input = ExecInput { target: Some(5), checkpoint: Some(out.checkpoint) };
+        let out = stage.execute(&provider, input).await.unwrap();
+        assert_eq!(
+            out,
+            ExecOutput {
+                checkpoint: StageCheckpoint::new(5)
+                    .with_entities_stage_checkpoint(EntitiesCheckpoint { processed: 2, total: 2 }),
+                done: true
+            }
+        );
+        provider.commit().unwrap();
+
+        // verify
+        let table = cast(tx.table::<tables::AccountHistory>().unwrap());
+        assert_eq!(table, BTreeMap::from([(shard(u64::MAX), vec![4, 5])]));
+    }
+
+    #[tokio::test]
+    async fn seed_processed_changesets() {
+        // init
+        let tx = TestTransaction::default();
+
+        // setup
+        partial_setup(&tx);
+
+        // a checkpoint from before the stage tracked its entities, with block 4 already indexed
+        let mut stage = IndexAccountHistoryStage::default();
+        let factory = ProviderFactory::new(tx.tx.as_ref(), MAINNET.clone());
+        let provider = factory.provider_rw().unwrap();
+        let input = ExecInput { target: Some(5), checkpoint: Some(StageCheckpoint::new(4)) };
+        let out = stage.execute(&provider, input).await.unwrap();
+        assert_eq!(
+            out,
+            ExecOutput {
+                checkpoint: StageCheckpoint::new(5)
+                    .with_entities_stage_checkpoint(EntitiesCheckpoint { processed: 2, total: 2 }),
+                done: true
+            }
+        );
+    }
+
     #[tokio::test]
     async fn insert_index_to_not_empty_shard() {
         // init
@@ -1,5 +1,6 @@
+use super::utils::{changesets_range_end, changesets_stage_checkpoint, processed_changesets};
 use crate::{ExecInput, ExecOutput, Stage, StageError, UnwindInput, UnwindOutput};
-use reth_db::{database::Database, models::BlockNumberAddress};
+use reth_db::{database::Database, models::BlockNumberAddress, tables};
 use reth_primitives::stage::{StageCheckpoint, StageId};
 use reth_provider::{DatabaseProviderRW, HistoryWriter, StorageReader};
 use std::fmt::Debug;
@@ -12,18 +13,31 @@ pub struct IndexStorageHistoryStage {
     /// Number of blocks after which the control
     /// flow will be returned to the pipeline for commit.
     pub commit_threshold: u64,
+    /// Maximum number of storage changesets to index before the control flow is returned to the
+    /// pipeline for commit. Only whole blocks are indexed, so the first block of a range is
+    /// always indexed even if it exceeds the threshold.
+    pub max_changesets_per_commit: Option<u64>,
 }
 
 impl IndexStorageHistoryStage {
     /// Create new instance of [IndexStorageHistoryStage].
     pub fn new(commit_threshold: u64) -> Self {
-        Self { commit_threshold }
+        Self { commit_threshold, max_changesets_per_commit: None }
+    }
+
+    /// Set the maximum number of changesets to index before committing.
+    pub fn with_max_changesets_per_commit(
+        mut self,
+        max_changesets_per_commit: Option<u64>,
+    ) -> Self {
+        self.max_changesets_per_commit = max_changesets_per_commit;
+        self
     }
 }
 
 impl Default for IndexStorageHistoryStage {
     fn default() -> Self {
-        Self { commit_threshold: 100_000 }
+        Self { commit_threshold: 100_000, max_changesets_per_commit: None }
     }
 }
 
@@ -44,12 +58,34 @@ impl<DB: Database> Stage<DB> for IndexStorageHistoryStage {
             return Ok(ExecOutput::done(input.checkpoint()))
         }
 
-        let (range, is_final_range) = input.next_block_range_with_threshold(self.commit_threshold);
+        let (mut range, mut is_final_range) =
+            input.next_block_range_with_threshold(self.commit_threshold);
+
+        let end_block = changesets_range_end::<_, tables::StorageChangeSet>(
+            provider,
+            range.clone(),
+            self.max_changesets_per_commit,
+        )?;
+        if end_block < *range.end() {
+            range = *range.start()..=end_block;
+            is_final_range = false;
+        }
 
         let indices = provider.changed_storages_and_blocks_with_range(range.clone())?;
+        let changesets = indices.values().map(|blocks| blocks.len() as u64).sum::<u64>();
         provider.insert_storage_history_index(indices)?;
 
-        Ok(ExecOutput { checkpoint: StageCheckpoint::new(*range.end()), done: is_final_range })
+        let processed =
+            processed_changesets::<_, tables::StorageChangeSet>(provider, input.checkpoint())?;
+        Ok(ExecOutput {
+            checkpoint: StageCheckpoint::new(*range.end()).with_entities_stage_checkpoint(
+                changesets_stage_checkpoint::<_, tables::StorageChangeSet>(
+                    provider,
+                    processed + changesets,
+                )?,
+            ),
+            done: is_final_range,
+        })
     }
 
     /// Unwind the stage.
@@ -61,9 +97,19 @@ impl<DB: Database> Stage<DB> for IndexStorageHistoryStage {
         let (range, unwind_progress, _) =
             input.unwind_block_range_with_threshold(self.commit_threshold);
 
-        provider.unwind_storage_history_indices(BlockNumberAddress::range(range))?;
-
-        Ok(UnwindOutput { checkpoint: StageCheckpoint::new(unwind_progress) })
+        let changesets =
+            provider.unwind_storage_history_indices(BlockNumberAddress::range(range))? as u64;
+
+        let processed =
+            processed_changesets::<_, tables::StorageChangeSet>(provider, input.checkpoint)?;
+        Ok(UnwindOutput {
+            checkpoint: StageCheckpoint::new(unwind_progress).with_entities_stage_checkpoint(
+                changesets_stage_checkpoint::<_, tables::StorageChangeSet>(
+                    provider,
+                    processed.saturating_sub(changesets),
+                )?,
+            ),
+        })
     }
 }
 
@@ -83,7 +129,9 @@ mod tests {
         transaction::DbTxMut,
         BlockNumberList,
     };
-    use reth_primitives::{hex_literal::hex, StorageEntry, H160, H256, MAINNET, U256};
+    use reth_primitives::{
+        hex_literal::hex, stage::EntitiesCheckpoint, StorageEntry, H160, H256, MAINNET, U256,
+    };
 
     const ADDRESS: H160 = H160(hex!("0000000000000000000000000000000000000001"));
     const STORAGE_KEY: H256 =
@@ -152,7 +200,14 @@ mod tests {
         let factory = ProviderFactory::new(tx.tx.as_ref(), MAINNET.clone());
         let provider = factory.provider_rw().unwrap();
         let out = stage.execute(&provider, input).await.unwrap();
-        assert_eq!(out, ExecOutput { checkpoint: StageCheckpoint::new(5), done: true });
+        assert_eq!(
+            out,
+            ExecOutput {
+                checkpoint: StageCheckpoint::new(5)
+                    .with_entities_stage_checkpoint(EntitiesCheckpoint { processed: 2, total: 2 }),
+                done: true
+            }
+        );
         provider.commit().unwrap();
     }
 
@@ -166,7 +221,13 @@ mod tests {
         let factory = ProviderFactory::new(tx.tx.as_ref(), MAINNET.clone());
         let provider = factory.provider_rw().unwrap();
         let out = stage.unwind(&provider, input).await.unwrap();
-        assert_eq!(out, UnwindOutput { checkpoint: StageCheckpoint::new(unwind_to) });
+        assert_eq!(
+            out,
+            UnwindOutput {
+                checkpoint: StageCheckpoint::new(unwind_to)
+                    .with_entities_stage_checkpoint(EntitiesCheckpoint { processed: 0, total: 2 })
+            }
+        );
         provider.commit().unwrap();
     }
 
@@ -287,6 +287,13 @@ impl<DB: Database> Stage<DB> for MerkleStage {
         let tx = provider.tx_ref();
         let range = input.unwind_block_range();
         if matches!(self, MerkleStage::Execution { .. }) {
+            // The intermediate trie progress was computed for a chain that is being unwound, so it
+            // must not be resumed once the stage targets the same block again.
+            if self.get_execution_checkpoint(provider)?.is_some() {
+                debug!(target: "sync::stages::merkle::unwind", "Discarding inner merkle checkpoint");
+                self.save_execution_checkpoint(provider, None)?;
+            }
+
             info!(target: "sync::stages::merkle::unwind", "Stage is always skipped");
             return Ok(UnwindOutput { checkpoint: StageCheckpoint::new(input.unwind_to) })
         }
@@ -22,6 +22,8 @@ mod sender_recovery;
 mod total_difficulty;
 /// The transaction lookup stage
 mod tx_lookup;
+/// Utils shared by the history index stages.
+mod utils;
 
 pub use bodies::*;
 pub use execution::*;
@@ -11,10 +11,10 @@ use reth_interfaces::consensus;
 use reth_primitives::{
     keccak256,
     stage::{EntitiesCheckpoint, StageCheckpoint, StageId},
-    TransactionSignedNoHash, TxNumber, H160,
+    TransactionSignedNoHash, TxNumber, H160, H256,
 };
 use reth_provider::{BlockReader, DatabaseProviderRW, HeaderProvider, ProviderError};
-use std::fmt::Debug;
+use std::{fmt::Debug, sync::Arc};
 use thiserror::Error;
 use tokio::sync::mpsc;
 use tracing::*;
@@ -27,18 +27,51 @@ pub struct SenderRecoveryStage {
     /// The size of inserted items after which the control
     /// flow will be returned to the pipeline for commit
     pub commit_threshold: u64,
+    /// The number of threads used to recover senders, one per physical core if not set.
+    threads: Option<usize>,
+    /// The thread pool used to recover senders, built on first use.
+    thread_pool: Option<Arc<rayon::ThreadPool>>,
 }
 
 impl SenderRecoveryStage {
     /// Create new instance of [SenderRecoveryStage].
+    ///
+    /// Senders are recovered on as many threads as there are physical cores, see
+    /// [SenderRecoveryStage::with_threads].
     pub fn new(commit_threshold: u64) -> Self {
-        Self { commit_threshold }
+        Self { commit_threshold, threads: None, thread_pool: None }
+    }
+
+    /// Set the number of threads used to recover senders.
+    ///
+    /// If `None`, one thread per physical core is used.
+    pub fn with_threads(mut self, threads: Option<usize>) -> Self {
+        self.threads = threads;
+        self.thread_pool = None;
+        self
+    }
+
+    /// Returns the thread pool used to recover senders, building it if necessary.
+    fn thread_pool(&mut self) -> Result<Arc<rayon::ThreadPool>, StageError> {
+        if let Some(thread_pool) = &self.thread_pool {
+            return Ok(thread_pool.clone())
+        }
+
+        let thread_pool = Arc::new(
+            rayon::ThreadPoolBuilder::new()
+                .num_threads(self.threads.unwrap_or_else(num_cpus::get_physical).max(1))
+                .thread_name(|idx| format!("sender-recovery-{idx}"))
+                .build()
+                .map_err(|err| StageError::Fatal(Box::new(err)))?,
+        );
+        self.thread_pool = Some(thread_pool.clone());
+        Ok(thread_pool)
     }
 }
 
 impl Default for SenderRecoveryStage {
     fn default() -> Self {
-        Self { commit_threshold: 5_000_000 }
+        Self::new(5_000_000)
     }
 }
 
@@ -94,13 +127,14 @@ impl<DB: Database> Stage<DB> for SenderRecoveryStage {
         // channels used to return result of sender recovery.
         let mut channels = Vec::new();
 
-        // Spawn recovery jobs onto the default rayon threadpool and send the result through the
+        // Spawn recovery jobs onto the stage's rayon threadpool and send the result through the
         // channel.
         //
         // We try to evenly divide the transactions to recover across all threads in the threadpool.
         // Chunks are submitted instead of individual transactions to reduce the overhead of work
         // stealing in the threadpool workers.
-        let chunk_size = self.commit_threshold as usize / rayon::current_num_threads();
+        let thread_pool = self.thread_pool()?;
+        let chunk_size = self.commit_threshold as usize / thread_pool.current_num_threads();
         // prevents an edge case
         // where the chunk size is either 0 or too small
         // to gain anything from using more than 1 thread
@@ -113,9 +147,9 @@ impl<DB: Database> Stage<DB> for SenderRecoveryStage {
             // Note: Unfortunate side-effect of how chunk is designed in itertools (it is not Send)
             let chunk: Vec<_> = chunk.collect();
 
-            // Spawn the sender recovery task onto the global rayon pool
+            // Spawn the sender recovery task onto the rayon pool
             // This task will send the results through the channel after it recovered the senders.
-            rayon::spawn(move || {
+            thread_pool.spawn(move || {
                 let mut rlp_buf = Vec::with_capacity(128);
                 for entry in chunk {
                     rlp_buf.clear();
@@ -133,6 +167,8 @@ impl<DB: Database> Stage<DB> for SenderRecoveryStage {
                     Err(error) => {
                         match *error {
                             SenderRecoveryStageError::FailedRecovery(err) => {
+                                warn!(target: "sync::stages::sender_recovery", tx = err.tx, tx_hash = ?err.tx_hash, "Failed to recover transaction sender");
+
                                 // get the block number for the bad transaction
                                 let block_number = tx
                                     .get::<tables::TransactionBlock>(err.tx)?
@@ -146,7 +182,9 @@ impl<DB: Database> Stage<DB> for SenderRecoveryStage {
                                 return Err(StageError::Validation {
                                     block: sealed_header,
                                     error:
-                                        consensus::ConsensusError::TransactionSignerRecoveryError,
+                                        consensus::ConsensusError::TransactionSignerRecoveryError {
+                                            hash: err.tx_hash,
+                                        },
                                 })
                             }
                             SenderRecoveryStageError::StageError(err) => return Err(err),
@@ -184,6 +222,14 @@ impl<DB: Database> Stage<DB> for SenderRecoveryStage {
                 .with_entities_stage_checkpoint(stage_checkpoint(provider)?),
         })
     }
+
+    fn entities_total(
+        &self,
+        provider: &DatabaseProviderRW<'_, &DB>,
+        _input: ExecInput,
+    ) -> Result<Option<u64>, StageError> {
+        Ok(Some(stage_checkpoint(provider)?.total))
+    }
 }
 
 fn recover_sender(
@@ -192,15 +238,20 @@ fn recover_sender(
 ) -> Result<(u64, H160), Box<SenderRecoveryStageError>> {
     let (tx_id, transaction) =
         entry.map_err(|e| Box::new(SenderRecoveryStageError::StageError(e.into())))?;
-    let tx_id = tx_id.key().expect("key to be formated");
+    let tx_id =
+        tx_id.key().map_err(|e| Box::new(SenderRecoveryStageError::StageError(e.into())))?;
 
-    let tx = transaction.value().expect("value to be formated");
+    let tx = transaction
+        .value()
+        .map_err(|e| Box::new(SenderRecoveryStageError::StageError(e.into())))?;
     tx.transaction.encode_without_signature(rlp_buf);
 
-    let sender = tx
-        .signature
-        .recover_signer(keccak256(rlp_buf))
-        .ok_or(SenderRecoveryStageError::FailedRecovery(FailedSenderRecoveryError { tx: tx_id }))?;
+    let sender = tx.signature.recover_signer(keccak256(rlp_buf)).ok_or_else(|| {
+        SenderRecoveryStageError::FailedRecovery(FailedSenderRecoveryError {
+            tx: tx_id,
+            tx_hash: tx.hash(),
+        })
+    })?;
 
     Ok((tx_id, sender))
 }
@@ -225,10 +276,12 @@ enum SenderRecoveryStageError {
 }
 
 #[derive(Error, Debug)]
-#[error("Sender recovery failed for transaction {tx}.")]
+#[error("Sender recovery failed for transaction {tx} ({tx_hash:?}).")]
 struct FailedSenderRecoveryError {
     /// The transaction that failed sender recovery
     tx: TxNumber,
+    /// The hash of the transaction that failed sender recovery
+    tx_hash: H256,
 }
 
 #[cfg(test)]
@@ -415,7 +468,7 @@ mod tests {
         }
 
         fn stage(&self) -> Self::S {
-            SenderRecoveryStage { commit_threshold: self.threshold }
+            SenderRecoveryStage::new(self.threshold)
         }
     }
 
@@ -110,6 +110,14 @@ impl<DB: Database> Stage<DB> for TotalDifficultyStage {
                 .with_entities_stage_checkpoint(stage_checkpoint(provider)?),
         })
     }
+
+    fn entities_total(
+        &self,
+        provider: &DatabaseProviderRW<'_, &DB>,
+        _input: ExecInput,
+    ) -> Result<Option<u64>, StageError> {
+        Ok(Some(stage_checkpoint(provider)?.total))
+    }
 }
 
 fn stage_checkpoint<DB: Database>(
@@ -120,7 +120,9 @@ impl<DB: Database> Stage<DB> for TransactionLookupStage {
 
         for (tx_hash, id) in tx_list {
             if insert {
-                txhash_cursor.insert(tx_hash, id)?;
+                // Entries may already exist if the range was indexed before, e.g. by a manual
+                // `reth stage run`, so they are overwritten.
+                txhash_cursor.upsert(tx_hash, id)?;
             } else {
                 txhash_cursor.append(tx_hash, id)?;
             }
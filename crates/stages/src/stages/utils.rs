@@ -0,0 +1,111 @@
+//! Utils shared by the history index stages.
+use reth_db::{
+    cursor::DbCursorRO,
+    database::Database,
+    models::BlockNumberAddress,
+    table::{DupSort, Table},
+    tables,
+    transaction::DbTx,
+    DatabaseError,
+};
+use reth_primitives::{
+    stage::{EntitiesCheckpoint, StageCheckpoint},
+    BlockNumber,
+};
+use reth_provider::DatabaseProviderRW;
+use std::ops::{Range, RangeBounds, RangeInclusive};
+
+/// A changeset table whose keys are ordered by the block number of the changeset.
+pub(crate) trait ChangeSetTable: DupSort {
+    /// The range of keys covering a range of blocks.
+    type KeyRange: RangeBounds<Self::Key>;
+
+    /// Returns the range of keys covering the changesets of the given blocks.
+    fn key_range(range: RangeInclusive<BlockNumber>) -> Self::KeyRange;
+
+    /// Returns the block number of the changeset with the given key.
+    fn block_number(key: &Self::Key) -> BlockNumber;
+}
+
+impl ChangeSetTable for tables::AccountChangeSet {
+    type KeyRange = RangeInclusive<BlockNumber>;
+
+    fn key_range(range: RangeInclusive<BlockNumber>) -> Self::KeyRange {
+        range
+    }
+
+    fn block_number(key: &Self::Key) -> BlockNumber {
+        *key
+    }
+}
+
+impl ChangeSetTable for tables::StorageChangeSet {
+    type KeyRange = Range<BlockNumberAddress>;
+
+    fn key_range(range: RangeInclusive<BlockNumber>) -> Self::KeyRange {
+        BlockNumberAddress::range(range)
+    }
+
+    fn block_number(key: &Self::Key) -> BlockNumber {
+        key.block_number()
+    }
+}
+
+/// Returns the last block of the given range to index.
+///
+/// If `max_changesets` is set, the changesets in the range are walked and the range ends at the
+/// first block boundary after the number of changesets reaches it. Blocks are never split, so the
+/// threshold can be exceeded by the changesets of the last block.
+pub(crate) fn changesets_range_end<DB: Database, T: ChangeSetTable>(
+    provider: &DatabaseProviderRW<'_, &DB>,
+    range: RangeInclusive<BlockNumber>,
+    max_changesets: Option<u64>,
+) -> Result<BlockNumber, DatabaseError> {
+    let max_changesets = match max_changesets {
+        Some(max_changesets) => max_changesets,
+        None => return Ok(*range.end()),
+    };
+
+    let mut cursor = provider.tx_ref().cursor_read::<T>()?;
+    let mut changesets = 0;
+    let mut last_block = None;
+    for entry in cursor.walk_range(T::key_range(range.clone()))? {
+        let block_number = T::block_number(&entry?.0);
+        if last_block.map_or(false, |last| last != block_number) && changesets >= max_changesets {
+            return Ok(block_number - 1)
+        }
+        last_block = Some(block_number);
+        changesets += 1;
+    }
+    Ok(*range.end())
+}
+
+/// Returns the number of changesets that were indexed up to the given checkpoint.
+///
+/// Checkpoints written before the stages tracked their progress have no entities checkpoint, in
+/// which case the changesets up to the checkpoint block are counted.
+pub(crate) fn processed_changesets<DB: Database, T: ChangeSetTable>(
+    provider: &DatabaseProviderRW<'_, &DB>,
+    checkpoint: StageCheckpoint,
+) -> Result<u64, DatabaseError> {
+    if let Some(entities) = checkpoint.entities() {
+        return Ok(entities.processed)
+    }
+
+    let mut cursor = provider.tx_ref().cursor_read::<T>()?;
+    let mut processed = 0;
+    for entry in cursor.walk_range(T::key_range(0..=checkpoint.block_number))? {
+        entry?;
+        processed += 1;
+    }
+    Ok(processed)
+}
+
+/// Returns the entities checkpoint of a history index stage with the given number of processed
+/// changesets.
+pub(crate) fn changesets_stage_checkpoint<DB: Database, T: ChangeSetTable>(
+    provider: &DatabaseProviderRW<'_, &DB>,
+    processed: u64,
+) -> Result<EntitiesCheckpoint, DatabaseError> {
+    Ok(EntitiesCheckpoint { processed, total: provider.tx_ref().entries::<T>()? as u64 })
+}
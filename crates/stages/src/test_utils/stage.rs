@@ -7,13 +7,24 @@ use std::collections::VecDeque;
 #[derive(Debug)]
 pub struct TestStage {
     id: StageId,
+    dependencies: Option<Vec<StageId>>,
     exec_outputs: VecDeque<Result<ExecOutput, StageError>>,
     unwind_outputs: VecDeque<Result<UnwindOutput, StageError>>,
 }
 
 impl TestStage {
     pub fn new(id: StageId) -> Self {
-        Self { id, exec_outputs: VecDeque::new(), unwind_outputs: VecDeque::new() }
+        Self {
+            id,
+            dependencies: None,
+            exec_outputs: VecDeque::new(),
+            unwind_outputs: VecDeque::new(),
+        }
+    }
+
+    pub fn with_dependencies(mut self, dependencies: Vec<StageId>) -> Self {
+        self.dependencies = Some(dependencies);
+        self
     }
 
     pub fn with_exec(mut self, exec_outputs: VecDeque<Result<ExecOutput, StageError>>) -> Self {
@@ -46,6 +57,13 @@ impl<DB: Database> Stage<DB> for TestStage {
         self.id
     }
 
+    fn dependencies(&self) -> &[StageId] {
+        match &self.dependencies {
+            Some(dependencies) => dependencies,
+            None => self.id.dependencies(),
+        }
+    }
+
     async fn execute(
         &mut self,
         _: &DatabaseProviderRW<'_, &DB>,
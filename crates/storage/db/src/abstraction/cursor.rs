@@ -4,7 +4,7 @@ use std::{
 };
 
 use crate::{
-    common::{IterPairResult, PairResult, ValueOnlyResult},
+    common::{IterPairResult, KeyValue, PairResult, ValueOnlyResult},
     table::{DupSort, Table},
     DatabaseError,
 };
@@ -343,3 +343,144 @@ impl<'cursor, 'tx, T: DupSort, CURSOR: DbDupCursorRO<'tx, T>> std::iter::Iterato
         self.cursor.next_dup().transpose()
     }
 }
+
+/// Provides a range iterator over table `T` that owns its cursor.
+///
+/// Created by [DbTx::walk_range](crate::transaction::DbTx::walk_range). Unlike [RangeWalker], it
+/// supports exclusive start bounds.
+pub struct OwnedRangeWalker<'tx, T: Table, CURSOR: DbCursorRO<'tx, T>> {
+    /// Cursor to be used to walk through the table.
+    cursor: CURSOR,
+    /// `(key, value)` where to start the walk.
+    start: Option<KeyValue<T>>,
+    /// `key` where to stop the walk.
+    end_key: Bound<T::Key>,
+    /// flag whether is ended
+    is_done: bool,
+    /// Phantom data for 'tx. As it is only used for `DbCursorRO`.
+    _tx_phantom: PhantomData<&'tx T>,
+}
+
+impl<'tx, T: Table, CURSOR: DbCursorRO<'tx, T>> OwnedRangeWalker<'tx, T, CURSOR> {
+    /// Positions the cursor at the start of the range and constructs the walker.
+    pub fn new(mut cursor: CURSOR, range: impl RangeBounds<T::Key>) -> Result<Self, DatabaseError> {
+        let start = match range.start_bound().cloned() {
+            Bound::Included(key) => cursor.seek(key)?,
+            Bound::Excluded(key) => {
+                let mut start = cursor.seek(key.clone())?;
+                // skip all duplicates of the excluded key in dupsort tables
+                while matches!(&start, Some((start_key, _)) if *start_key == key) {
+                    start = cursor.next()?;
+                }
+                start
+            }
+            Bound::Unbounded => cursor.first()?,
+        };
+
+        Ok(Self {
+            cursor,
+            is_done: start.is_none(),
+            start,
+            end_key: range.end_bound().cloned(),
+            _tx_phantom: std::marker::PhantomData,
+        })
+    }
+}
+
+impl<'tx, T: Table, CURSOR: DbCursorRO<'tx, T>> std::iter::Iterator
+    for OwnedRangeWalker<'tx, T, CURSOR>
+{
+    type Item = Result<KeyValue<T>, DatabaseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.is_done {
+            return None
+        }
+
+        let next_item = match self.start.take() {
+            Some(start) => Ok(Some(start)),
+            None => self.cursor.next(),
+        };
+
+        match next_item {
+            Ok(Some((key, value))) => {
+                let in_range = match &self.end_key {
+                    Bound::Included(end_key) => &key <= end_key,
+                    Bound::Excluded(end_key) => &key < end_key,
+                    Bound::Unbounded => true,
+                };
+                if in_range {
+                    return Some(Ok((key, value)))
+                }
+                self.is_done = true;
+                None
+            }
+            Ok(None) => {
+                self.is_done = true;
+                None
+            }
+            Err(err) => {
+                self.is_done = true;
+                Some(Err(err))
+            }
+        }
+    }
+}
+
+/// Provides an iterator over the duplicates of a single key of the dup table `T` that owns its
+/// cursor.
+///
+/// Created by [DbTx::walk_dup](crate::transaction::DbTx::walk_dup).
+pub struct OwnedDupWalker<'tx, T: DupSort, CURSOR: DbDupCursorRO<'tx, T>> {
+    /// Cursor to be used to walk through the table.
+    cursor: CURSOR,
+    /// `(key, value)` where to start the walk.
+    start: Option<KeyValue<T>>,
+    /// flag whether is ended
+    is_done: bool,
+    /// Phantom data for 'tx. As it is only used for `DbDupCursorRO`.
+    _tx_phantom: PhantomData<&'tx T>,
+}
+
+impl<'tx, T: DupSort, CURSOR: DbDupCursorRO<'tx, T> + DbCursorRO<'tx, T>>
+    OwnedDupWalker<'tx, T, CURSOR>
+{
+    /// Positions the cursor at the first duplicate of `key` whose subkey is greater than or equal
+    /// to `subkey`, or the first duplicate of `key` if `subkey` is `None`, and constructs the
+    /// walker.
+    pub fn new(
+        mut cursor: CURSOR,
+        key: T::Key,
+        subkey: Option<T::SubKey>,
+    ) -> Result<Self, DatabaseError> {
+        let start = match subkey {
+            Some(subkey) => {
+                cursor.seek_by_key_subkey(key.clone(), subkey)?.map(|value| (key, value))
+            }
+            None => cursor.seek_exact(key)?,
+        };
+
+        Ok(Self { cursor, is_done: start.is_none(), start, _tx_phantom: std::marker::PhantomData })
+    }
+}
+
+impl<'tx, T: DupSort, CURSOR: DbDupCursorRO<'tx, T>> std::iter::Iterator
+    for OwnedDupWalker<'tx, T, CURSOR>
+{
+    type Item = Result<KeyValue<T>, DatabaseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.is_done {
+            return None
+        }
+
+        let next_item = match self.start.take() {
+            Some(start) => Ok(Some(start)),
+            None => self.cursor.next_dup(),
+        };
+        if !matches!(next_item, Ok(Some(_))) {
+            self.is_done = true;
+        }
+        next_item.transpose()
+    }
+}
@@ -1,9 +1,12 @@
 use crate::{
     common::{Bounds, Sealed},
-    cursor::{DbCursorRO, DbCursorRW, DbDupCursorRO, DbDupCursorRW},
+    cursor::{
+        DbCursorRO, DbCursorRW, DbDupCursorRO, DbDupCursorRW, OwnedDupWalker, OwnedRangeWalker,
+    },
     table::{DupSort, Table},
     DatabaseError,
 };
+use std::ops::RangeBounds;
 
 /// Implements the GAT method from:
 /// <https://sabrinajewson.org/blog/the-better-alternative-to-lifetime-gats#the-better-gats>.
@@ -49,6 +52,29 @@ pub trait DbTx<'tx>: for<'a> DbTxGAT<'a> {
     ) -> Result<<Self as DbTxGAT<'_>>::DupCursor<T>, DatabaseError>;
     /// Returns number of entries in the table.
     fn entries<T: Table>(&self) -> Result<usize, DatabaseError>;
+
+    /// Returns an iterator over the entries of the table whose keys are within `range`.
+    ///
+    /// Both inclusive and exclusive bounds are supported. An empty range, or a range whose start
+    /// is greater than its end, yields no entries.
+    fn walk_range<T: Table>(
+        &self,
+        range: impl RangeBounds<T::Key>,
+    ) -> Result<OwnedRangeWalker<'_, T, <Self as DbTxGAT<'_>>::Cursor<T>>, DatabaseError> {
+        OwnedRangeWalker::new(self.cursor_read::<T>()?, range)
+    }
+
+    /// Returns an iterator over the duplicates of `key` in the dup sorted table.
+    ///
+    /// If `subkey` is set, the iterator starts at the first duplicate whose subkey is greater than
+    /// or equal to it.
+    fn walk_dup<T: DupSort>(
+        &self,
+        key: T::Key,
+        subkey: Option<T::SubKey>,
+    ) -> Result<OwnedDupWalker<'_, T, <Self as DbTxGAT<'_>>::DupCursor<T>>, DatabaseError> {
+        OwnedDupWalker::new(self.cursor_dup_read::<T>()?, key, subkey)
+    }
 }
 
 /// Read write transaction that allows writing to database
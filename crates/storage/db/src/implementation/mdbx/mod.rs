@@ -6,7 +6,7 @@ use crate::{
     utils::default_page_size,
     DatabaseError,
 };
-use reth_interfaces::db::LogLevel;
+use reth_interfaces::db::{DatabaseGeometryError, LogLevel};
 use reth_libmdbx::{
     DatabaseFlags, Environment, EnvironmentFlags, EnvironmentKind, Geometry, Mode, PageSize,
     SyncMode, RO, RW,
@@ -23,8 +23,74 @@ const TERABYTE: usize = GIGABYTE * 1024;
 /// MDBX allows up to 32767 readers (`MDBX_READERS_LIMIT`), but we limit it to slightly below that
 const DEFAULT_MAX_READERS: u64 = 32_000;
 
+/// The maximum number of pages of a MDBX database (`MDBX_MAX_PAGENO`).
+const MAX_PAGES: usize = 0x7FFF_FFFF;
+
+/// The name of the MDBX data file in the database directory.
+pub const DATA_FILE_NAME: &str = "mdbx.dat";
+
+/// The size bounds of a MDBX environment.
+///
+/// MDBX reserves address space for the whole [`max_size`](Self::max_size) when the environment is
+/// opened, and grows the data file in increments of [`growth_step`](Self::growth_step) until the
+/// maximum is reached, at which point writes fail with `MDBX_MAP_FULL`.
+///
+/// The reservation only consumes virtual memory, so sizes in the terabytes require a 64-bit
+/// platform. The data file is extended without writing the new pages, which relies on the
+/// filesystem supporting sparse files. On filesystems without sparse files, every growth step
+/// allocates its full size on disk.
+///
+/// The geometry is applied every time the environment is opened read-write, so it can be changed
+/// between restarts, as long as the maximum size doesn't drop below the size of the existing data
+/// file. The page size is fixed when the database is created and can't be changed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DatabaseGeometry {
+    /// The maximum size of the database in bytes.
+    pub max_size: usize,
+    /// The size in bytes the data file grows by once it is full.
+    pub growth_step: usize,
+}
+
+impl DatabaseGeometry {
+    /// Validates the geometry for the page size that is used for new databases.
+    pub fn validate(&self) -> Result<(), DatabaseGeometryError> {
+        if self.growth_step == 0 {
+            return Err(DatabaseGeometryError::GrowthStepZero)
+        }
+        if self.growth_step > self.max_size {
+            return Err(DatabaseGeometryError::GrowthStepExceedsMaxSize {
+                growth_step: self.growth_step,
+                max_size: self.max_size,
+            })
+        }
+
+        let page_size = default_page_size();
+        let limit = page_size.saturating_mul(MAX_PAGES);
+        if self.max_size > limit {
+            return Err(DatabaseGeometryError::MaxSizeTooLarge {
+                max_size: self.max_size,
+                limit,
+                page_size,
+            })
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for DatabaseGeometry {
+    fn default() -> Self {
+        Self {
+            // Maximum database size of 4 terabytes
+            max_size: 4 * TERABYTE,
+            // We grow the database in increments of 4 gigabytes
+            growth_step: 4 * GIGABYTE,
+        }
+    }
+}
+
 /// Environment used when opening a MDBX environment. RO/RW.
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum EnvKind {
     /// Read-only MDBX environment.
     RO,
@@ -37,6 +103,8 @@ pub enum EnvKind {
 pub struct Env<E: EnvironmentKind> {
     /// Libmdbx-sys environment.
     pub inner: Environment<E>,
+    /// Whether the environment was opened read-only or read-write.
+    kind: EnvKind,
 }
 
 impl<'a, E: EnvironmentKind> DatabaseGAT<'a> for Env<E> {
@@ -52,6 +120,10 @@ impl<E: EnvironmentKind> Database for Env<E> {
     }
 
     fn tx_mut(&self) -> Result<<Self as DatabaseGAT<'_>>::TXMut, DatabaseError> {
+        if self.is_read_only() {
+            return Err(DatabaseError::ReadOnly)
+        }
+
         Ok(Tx::new(
             self.inner.begin_rw_txn().map_err(|e| DatabaseError::InitTransaction(e.into()))?,
         ))
@@ -66,19 +138,44 @@ impl<E: EnvironmentKind> Env<E> {
         path: &Path,
         kind: EnvKind,
         log_level: Option<LogLevel>,
+    ) -> Result<Env<E>, DatabaseError> {
+        Self::open_with_geometry(path, kind, log_level, DatabaseGeometry::default())
+    }
+
+    /// Opens the database at the specified path with the given `EnvKind` and [DatabaseGeometry].
+    ///
+    /// The geometry is validated before a read-write environment is opened, see
+    /// [DatabaseGeometry] for the constraints.
+    ///
+    /// It does not create the tables, for that call [`Env::create_tables`].
+    pub fn open_with_geometry(
+        path: &Path,
+        kind: EnvKind,
+        log_level: Option<LogLevel>,
+        geometry: DatabaseGeometry,
     ) -> Result<Env<E>, DatabaseError> {
         let mode = match kind {
             EnvKind::RO => Mode::ReadOnly,
-            EnvKind::RW => Mode::ReadWrite { sync_mode: SyncMode::Durable },
+            EnvKind::RW => {
+                geometry.validate()?;
+                if let Ok(metadata) = std::fs::metadata(path.join(DATA_FILE_NAME)) {
+                    if metadata.len() > geometry.max_size as u64 {
+                        return Err(DatabaseGeometryError::MaxSizeBelowFileSize {
+                            max_size: geometry.max_size,
+                            file_size: metadata.len(),
+                        }
+                        .into())
+                    }
+                }
+                Mode::ReadWrite { sync_mode: SyncMode::Durable }
+            }
         };
 
         let mut inner_env = Environment::new();
         inner_env.set_max_dbs(Tables::ALL.len());
         inner_env.set_geometry(Geometry {
-            // Maximum database size of 4 terabytes
-            size: Some(0..(4 * TERABYTE)),
-            // We grow the database in increments of 4 gigabytes
-            growth_step: Some(4 * GIGABYTE as isize),
+            size: Some(0..geometry.max_size),
+            growth_step: Some(geometry.growth_step as isize),
             // The database never shrinks
             shrink_threshold: None,
             page_size: Some(PageSize::Set(default_page_size())),
@@ -120,14 +217,27 @@ impl<E: EnvironmentKind> Env<E> {
             }
         }
 
-        let env =
-            Env { inner: inner_env.open(path).map_err(|e| DatabaseError::FailedToOpen(e.into()))? };
+        let env = Env {
+            inner: inner_env.open(path).map_err(|e| DatabaseError::FailedToOpen(e.into()))?,
+            kind,
+        };
 
         Ok(env)
     }
 
+    /// Returns `true` if the environment was opened with [EnvKind::RO].
+    ///
+    /// Write transactions of a read-only environment fail with [DatabaseError::ReadOnly].
+    pub fn is_read_only(&self) -> bool {
+        self.kind == EnvKind::RO
+    }
+
     /// Creates all the defined tables, if necessary.
     pub fn create_tables(&self) -> Result<(), DatabaseError> {
+        if self.is_read_only() {
+            return Err(DatabaseError::ReadOnly)
+        }
+
         let tx = self.inner.begin_rw_txn().map_err(|e| DatabaseError::InitTransaction(e.into()))?;
 
         for table in Tables::ALL {
@@ -168,7 +278,7 @@ mod tests {
     };
     use reth_libmdbx::{NoWriteMap, WriteMap};
     use reth_primitives::{Account, Address, Header, IntegerList, StorageEntry, H160, H256, U256};
-    use std::{path::Path, str::FromStr, sync::Arc};
+    use std::{ops::Bound, path::Path, str::FromStr, sync::Arc};
     use tempfile::TempDir;
 
     /// Create database for testing
@@ -196,6 +306,66 @@ mod tests {
     const ERROR_INIT_TX: &str = "Failed to create a MDBX transaction.";
     const ERROR_ETH_ADDRESS: &str = "Invalid address.";
 
+    #[test]
+    fn db_geometry() {
+        let path = TempDir::new().expect(ERROR_TEMPDIR).into_path();
+        let open =
+            |geometry| Env::<WriteMap>::open_with_geometry(&path, EnvKind::RW, None, geometry);
+
+        let geometry = DatabaseGeometry { max_size: GIGABYTE, growth_step: 0 };
+        assert_eq!(
+            open(geometry).unwrap_err(),
+            DatabaseError::InvalidGeometry(DatabaseGeometryError::GrowthStepZero)
+        );
+        let geometry = DatabaseGeometry { max_size: GIGABYTE, growth_step: 2 * GIGABYTE };
+        assert!(matches!(
+            open(geometry).unwrap_err(),
+            DatabaseError::InvalidGeometry(DatabaseGeometryError::GrowthStepExceedsMaxSize { .. })
+        ));
+        let geometry = DatabaseGeometry { max_size: usize::MAX, growth_step: GIGABYTE };
+        assert!(matches!(
+            open(geometry).unwrap_err(),
+            DatabaseError::InvalidGeometry(DatabaseGeometryError::MaxSizeTooLarge { .. })
+        ));
+
+        let geometry = DatabaseGeometry { max_size: GIGABYTE, growth_step: 1024 * 1024 };
+        open(geometry).expect(ERROR_DB_CREATION).create_tables().expect(ERROR_TABLE_CREATION);
+
+        // the geometry can be changed across restarts
+        let geometry = DatabaseGeometry { max_size: 2 * GIGABYTE, growth_step: 4 * 1024 * 1024 };
+        open(geometry).expect(ERROR_DB_CREATION);
+
+        // but not below the size of the existing data file
+        let file_size = std::fs::metadata(path.join(DATA_FILE_NAME)).unwrap().len();
+        let geometry = DatabaseGeometry { max_size: 4096, growth_step: 4096 };
+        assert_eq!(
+            open(geometry).unwrap_err(),
+            DatabaseError::InvalidGeometry(DatabaseGeometryError::MaxSizeBelowFileSize {
+                max_size: 4096,
+                file_size,
+            })
+        );
+    }
+
+    #[test]
+    fn db_read_only() {
+        let path = TempDir::new().expect(ERROR_TEMPDIR).into_path();
+        {
+            let env = create_test_db_with_path::<WriteMap>(EnvKind::RW, &path);
+            let tx = env.tx_mut().expect(ERROR_INIT_TX);
+            tx.put::<CanonicalHeaders>(1, H256::zero()).expect(ERROR_PUT);
+            tx.commit().expect(ERROR_COMMIT);
+        }
+
+        let env = Env::<NoWriteMap>::open(&path, EnvKind::RO, None).expect(ERROR_DB_CREATION);
+        assert!(env.is_read_only());
+        assert_eq!(env.tx_mut().unwrap_err(), DatabaseError::ReadOnly);
+        assert_eq!(env.create_tables().unwrap_err(), DatabaseError::ReadOnly);
+
+        let tx = env.tx().expect(ERROR_INIT_TX);
+        assert_eq!(tx.get::<CanonicalHeaders>(1).expect(ERROR_GET), Some(H256::zero()));
+    }
+
     #[test]
     fn db_creation() {
         create_test_db::<NoWriteMap>(EnvKind::RW);
@@ -379,6 +549,114 @@ mod tests {
         assert_eq!(walker.next(), None);
     }
 
+    #[allow(clippy::reversed_empty_ranges)]
+    #[test]
+    fn db_tx_walk_range() {
+        let db: Arc<Env<WriteMap>> = create_test_db(EnvKind::RW);
+
+        // PUT (0, 0), (1, 0), (3, 0)
+        let tx = db.tx_mut().expect(ERROR_INIT_TX);
+        vec![0, 1, 3]
+            .into_iter()
+            .try_for_each(|key| tx.put::<CanonicalHeaders>(key, H256::zero()))
+            .expect(ERROR_PUT);
+        tx.commit().expect(ERROR_COMMIT);
+
+        let tx = db.tx().expect(ERROR_INIT_TX);
+        let keys = |range: (Bound<u64>, Bound<u64>)| {
+            tx.walk_range::<CanonicalHeaders>(range)
+                .unwrap()
+                .map(|entry| entry.map(|(key, _)| key))
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap()
+        };
+
+        assert_eq!(keys((Bound::Included(1), Bound::Included(3))), vec![1, 3]);
+        assert_eq!(keys((Bound::Included(1), Bound::Excluded(3))), vec![1]);
+        assert_eq!(keys((Bound::Excluded(0), Bound::Unbounded)), vec![1, 3]);
+        // the exclusive start key doesn't exist
+        assert_eq!(keys((Bound::Excluded(2), Bound::Unbounded)), vec![3]);
+        assert_eq!(keys((Bound::Unbounded, Bound::Excluded(1))), vec![0]);
+        assert_eq!(keys((Bound::Unbounded, Bound::Unbounded)), vec![0, 1, 3]);
+
+        // empty ranges
+        assert_eq!(keys((Bound::Included(1), Bound::Excluded(1))), Vec::<u64>::new());
+        assert_eq!(keys((Bound::Excluded(1), Bound::Included(1))), Vec::<u64>::new());
+        assert_eq!(keys((Bound::Included(3), Bound::Included(1))), Vec::<u64>::new());
+        assert_eq!(keys((Bound::Excluded(3), Bound::Unbounded)), Vec::<u64>::new());
+        assert_eq!(keys((Bound::Included(4), Bound::Unbounded)), Vec::<u64>::new());
+
+        // the iterator can outlive the statement that created it
+        let mut walker = tx.walk_range::<CanonicalHeaders>(2..).unwrap();
+        assert_eq!(walker.next(), Some(Ok((3, H256::zero()))));
+        assert_eq!(walker.next(), None);
+        assert_eq!(walker.next(), None);
+        drop(walker);
+        drop(tx);
+
+        // PUT (5, address0), (5, address1), (6, address0) into a dupsort table
+        let address0 = Address::zero();
+        let address1 = Address::from_low_u64_be(1);
+        let tx = db.tx_mut().expect(ERROR_INIT_TX);
+        for (block, address) in [(5, address0), (5, address1), (6, address0)] {
+            tx.put::<AccountChangeSet>(block, AccountBeforeTx { address, info: None })
+                .expect(ERROR_PUT);
+        }
+        tx.commit().expect(ERROR_COMMIT);
+
+        let tx = db.tx().expect(ERROR_INIT_TX);
+        let changesets = |range: (Bound<u64>, Bound<u64>)| {
+            tx.walk_range::<AccountChangeSet>(range)
+                .unwrap()
+                .map(|entry| entry.map(|(block, change)| (block, change.address)))
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap()
+        };
+
+        // an exclusive start bound skips all duplicates of the key
+        assert_eq!(changesets((Bound::Excluded(5), Bound::Unbounded)), vec![(6, address0)]);
+        assert_eq!(
+            changesets((Bound::Included(5), Bound::Excluded(6))),
+            vec![(5, address0), (5, address1)]
+        );
+        assert_eq!(changesets((Bound::Excluded(4), Bound::Included(5))).len(), 2);
+    }
+
+    #[test]
+    fn db_tx_walk_dup() {
+        let db: Arc<Env<WriteMap>> = create_test_db(EnvKind::RW);
+        let address0 = Address::zero();
+        let address1 = Address::from_low_u64_be(1);
+        let entry = |key| StorageEntry { key: H256::from_low_u64_be(key), value: U256::from(key) };
+
+        let tx = db.tx_mut().expect(ERROR_INIT_TX);
+        for key in 0..3 {
+            tx.put::<PlainStorageState>(address0, entry(key)).expect(ERROR_PUT);
+        }
+        tx.put::<PlainStorageState>(address1, entry(0)).expect(ERROR_PUT);
+        tx.commit().expect(ERROR_COMMIT);
+
+        let tx = db.tx().expect(ERROR_INIT_TX);
+        let dups = |address, subkey| {
+            tx.walk_dup::<PlainStorageState>(address, subkey)
+                .unwrap()
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap()
+        };
+
+        assert_eq!(
+            dups(address0, None),
+            vec![(address0, entry(0)), (address0, entry(1)), (address0, entry(2))]
+        );
+        assert_eq!(
+            dups(address0, Some(H256::from_low_u64_be(1))),
+            vec![(address0, entry(1)), (address0, entry(2))]
+        );
+        assert_eq!(dups(address0, Some(H256::from_low_u64_be(3))), vec![]);
+        assert_eq!(dups(address1, None), vec![(address1, entry(0))]);
+        assert_eq!(dups(Address::from_low_u64_be(2), None), vec![]);
+    }
+
     #[test]
     fn db_walker() {
         let db: Arc<Env<WriteMap>> = create_test_db(EnvKind::RW);
@@ -9,7 +9,7 @@ use crate::{
 };
 use parking_lot::RwLock;
 use reth_libmdbx::{ffi::DBI, EnvironmentKind, Transaction, TransactionKind, WriteFlags, RW};
-use reth_metrics::metrics::{self, histogram};
+use reth_metrics::metrics::{self, decrement_gauge, histogram, increment_gauge};
 use std::{marker::PhantomData, str::FromStr, sync::Arc, time::Instant};
 
 /// Wrapper for the libmdbx transaction.
@@ -19,6 +19,8 @@ pub struct Tx<'a, K: TransactionKind, E: EnvironmentKind> {
     pub inner: Transaction<'a, K, E>,
     /// Database table handle cache
     pub db_handles: Arc<RwLock<[Option<DBI>; NUM_TABLES]>>,
+    /// Records the lifetime of the transaction.
+    metrics: TxMetrics,
 }
 
 impl<'env, K: TransactionKind, E: EnvironmentKind> Tx<'env, K, E> {
@@ -27,7 +29,7 @@ impl<'env, K: TransactionKind, E: EnvironmentKind> Tx<'env, K, E> {
     where
         'a: 'env,
     {
-        Self { inner, db_handles: Default::default() }
+        Self { inner, db_handles: Default::default(), metrics: TxMetrics::new(K::IS_READ_ONLY) }
     }
 
     /// Gets this transaction ID.
@@ -68,6 +70,46 @@ impl<'env, K: TransactionKind, E: EnvironmentKind> Tx<'env, K, E> {
     }
 }
 
+/// Tracks the number of open transactions and records the lifetime of a transaction once it's
+/// committed or aborted.
+///
+/// Long-lived read transactions prevent MDBX from reusing the pages freed after they were opened,
+/// which makes the database grow.
+#[derive(Debug)]
+struct TxMetrics {
+    /// The time the transaction was opened.
+    start: Instant,
+    /// Whether the transaction is read-only.
+    read_only: bool,
+    /// Whether the transaction was committed successfully.
+    committed: bool,
+}
+
+impl TxMetrics {
+    fn new(read_only: bool) -> Self {
+        let mode = Self::mode(read_only);
+        increment_gauge!("tx.open", 1.0, "mode" => mode);
+        Self { start: Instant::now(), read_only, committed: false }
+    }
+
+    fn mode(read_only: bool) -> &'static str {
+        if read_only {
+            "read-only"
+        } else {
+            "read-write"
+        }
+    }
+}
+
+impl Drop for TxMetrics {
+    fn drop(&mut self) {
+        let mode = Self::mode(self.read_only);
+        let outcome = if self.committed { "commit" } else { "abort" };
+        decrement_gauge!("tx.open", 1.0, "mode" => mode);
+        histogram!("tx.duration", self.start.elapsed(), "mode" => mode, "outcome" => outcome);
+    }
+}
+
 impl<'a, K: TransactionKind, E: EnvironmentKind> DbTxGAT<'a> for Tx<'_, K, E> {
     type Cursor<T: Table> = Cursor<'a, K, T>;
     type DupCursor<T: DupSort> = Cursor<'a, K, T>;
@@ -89,10 +131,11 @@ impl<'tx, K: TransactionKind, E: EnvironmentKind> DbTx<'tx> for Tx<'tx, K, E> {
             .transpose()
     }
 
-    fn commit(self) -> Result<bool, DatabaseError> {
+    fn commit(mut self) -> Result<bool, DatabaseError> {
         let start = Instant::now();
         let result = self.inner.commit().map_err(|e| DatabaseError::Commit(e.into()));
         histogram!("tx.commit", start.elapsed());
+        self.metrics.committed = result.is_ok();
         result
     }
 
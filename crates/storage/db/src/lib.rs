@@ -72,6 +72,7 @@
 pub mod abstraction;
 
 mod implementation;
+pub mod migration;
 pub mod tables;
 mod utils;
 pub mod version;
@@ -89,7 +90,7 @@ pub use tables::*;
 pub use utils::is_database_empty;
 
 #[cfg(feature = "mdbx")]
-use mdbx::{Env, EnvKind, NoWriteMap, WriteMap};
+use mdbx::{DatabaseGeometry, Env, EnvKind, NoWriteMap, WriteMap};
 
 #[cfg(feature = "mdbx")]
 /// Alias type for the database environment in use. Read/Write mode.
@@ -106,6 +107,17 @@ use std::path::Path;
 /// Opens up an existing database or creates a new one at the specified path. Creates tables if
 /// necessary. Read/Write mode.
 pub fn init_db<P: AsRef<Path>>(path: P, log_level: Option<LogLevel>) -> eyre::Result<DatabaseEnv> {
+    init_db_with_geometry(path, log_level, Default::default())
+}
+
+/// Opens up an existing database or creates a new one at the specified path with the given
+/// [DatabaseGeometry]. Creates tables if necessary and runs pending schema
+/// [migrations](crate::migration). Read/Write mode.
+pub fn init_db_with_geometry<P: AsRef<Path>>(
+    path: P,
+    log_level: Option<LogLevel>,
+    geometry: DatabaseGeometry,
+) -> eyre::Result<DatabaseEnv> {
     use crate::version::{check_db_version_file, create_db_version_file, DatabaseVersionError};
 
     let rpath = path.as_ref();
@@ -122,8 +134,9 @@ pub fn init_db<P: AsRef<Path>>(path: P, log_level: Option<LogLevel>) -> eyre::Re
     }
     #[cfg(feature = "mdbx")]
     {
-        let db = DatabaseEnv::open(rpath, EnvKind::RW, log_level)?;
+        let db = DatabaseEnv::open_with_geometry(rpath, EnvKind::RW, log_level, geometry)?;
         db.create_tables()?;
+        migration::Migrations::default().run(&db)?;
         Ok(db)
     }
     #[cfg(not(feature = "mdbx"))]
@@ -133,6 +146,15 @@ pub fn init_db<P: AsRef<Path>>(path: P, log_level: Option<LogLevel>) -> eyre::Re
 }
 
 /// Opens up an existing database. Read only mode. It doesn't create it or create tables if missing.
+///
+/// The database can be opened read-only by other processes while a node has it opened read-write,
+/// e.g. to run analytics or indexers against a live node. Readers always see the last committed
+/// state. Write transactions, and any provider method that requires one, fail with
+/// [DatabaseError::ReadOnly].
+///
+/// The process still needs write access to the `mdbx.lck` lock file next to the data file to
+/// register its readers. Keep read transactions short: pages referenced by an open read
+/// transaction can't be reused by the writer, so long running transactions make the database grow.
 pub fn open_db_read_only(path: &Path, log_level: Option<LogLevel>) -> eyre::Result<DatabaseEnvRO> {
     #[cfg(feature = "mdbx")]
     {
@@ -148,9 +170,19 @@ pub fn open_db_read_only(path: &Path, log_level: Option<LogLevel>) -> eyre::Resu
 /// Opens up an existing database. Read/Write mode. It doesn't create it or create tables if
 /// missing.
 pub fn open_db(path: &Path, log_level: Option<LogLevel>) -> eyre::Result<DatabaseEnv> {
+    open_db_with_geometry(path, log_level, Default::default())
+}
+
+/// Opens up an existing database with the given [DatabaseGeometry]. Read/Write mode. It doesn't
+/// create it or create tables if missing.
+pub fn open_db_with_geometry(
+    path: &Path,
+    log_level: Option<LogLevel>,
+    geometry: DatabaseGeometry,
+) -> eyre::Result<DatabaseEnv> {
     #[cfg(feature = "mdbx")]
     {
-        Env::<WriteMap>::open(path, EnvKind::RW, log_level)
+        Env::<WriteMap>::open_with_geometry(path, EnvKind::RW, log_level, geometry)
             .with_context(|| format!("Could not open database at path: {}", path.display()))
     }
     #[cfg(not(feature = "mdbx"))]
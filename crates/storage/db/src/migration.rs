@@ -0,0 +1,342 @@
+//! Versioned schema migrations.
+//!
+//! The schema version of the database is stored in the [Metadata] table. On startup,
+//! [Migrations::run] upgrades the database from the stored version to [SCHEMA_VERSION] by running
+//! the registered [Migration]s in order, or refuses to continue if the database can't be upgraded.
+//!
+//! This is complementary to the [version file](crate::version): a change of
+//! [DB_VERSION](crate::version::DB_VERSION) denotes a breaking change that requires a resync,
+//! while a schema version bump comes with a migration that upgrades the existing data in place.
+
+use crate::{
+    database::{Database, DatabaseGAT},
+    tables::{CanonicalHeaders, Metadata, PruneCheckpoints},
+    transaction::{DbTx, DbTxMut},
+    DatabaseError,
+};
+use std::fmt;
+
+/// The key of the schema version in the [Metadata] table.
+pub const SCHEMA_VERSION_KEY: &str = "schema_version";
+
+/// The schema version of databases that were created before the schema version was tracked.
+pub const INITIAL_SCHEMA_VERSION: u64 = 1;
+
+/// The current schema version of the database.
+pub const SCHEMA_VERSION: u64 = 2;
+
+/// Error while running the [Migrations].
+#[derive(thiserror::Error, Debug)]
+pub enum MigrationError {
+    /// Database error.
+    #[error(transparent)]
+    Database(#[from] DatabaseError),
+    /// The database was written by a newer version of reth.
+    #[error(
+        "Database schema version v{stored} is newer than the latest supported schema version \
+         v{supported}. Please upgrade reth."
+    )]
+    NewerSchema {
+        /// The schema version stored in the database.
+        stored: u64,
+        /// The schema version supported by this version of reth.
+        supported: u64,
+    },
+    /// No migration upgrades the database from the given version.
+    #[error(
+        "No migration found from database schema version v{version}. \
+         The database has to be resynced."
+    )]
+    MissingMigration {
+        /// The schema version without a migration.
+        version: u64,
+    },
+    /// The migration doesn't increase the schema version.
+    #[error("Migration {name} does not upgrade the database schema version.")]
+    InvalidMigration {
+        /// The name of the migration.
+        name: &'static str,
+    },
+}
+
+/// An upgrade of the database schema from one version to the next.
+///
+/// A migration runs in a single write transaction that also updates the stored schema version, so
+/// if it fails, neither its changes nor the version change are committed and it is retried from
+/// scratch on the next startup.
+pub trait Migration<DB: Database>: Send + Sync {
+    /// The name of the migration, used for logging and errors.
+    fn name(&self) -> &'static str;
+
+    /// The schema version the migration upgrades from.
+    fn from_version(&self) -> u64;
+
+    /// The schema version the migration upgrades to. Must be greater than
+    /// [Migration::from_version].
+    fn to_version(&self) -> u64;
+
+    /// Upgrades the data of the database.
+    ///
+    /// The transaction is committed by the caller.
+    fn migrate<'a>(&self, tx: &<DB as DatabaseGAT<'a>>::TXMut) -> Result<(), DatabaseError>;
+}
+
+/// The registry of all [Migration]s.
+pub struct Migrations<DB: Database> {
+    /// The schema version to upgrade to.
+    target: u64,
+    /// The registered migrations.
+    migrations: Vec<Box<dyn Migration<DB>>>,
+}
+
+impl<DB: Database> Migrations<DB> {
+    /// Creates an empty registry that upgrades databases to the given schema version.
+    pub fn new(target: u64) -> Self {
+        Self { target, migrations: Vec::new() }
+    }
+
+    /// Registers a migration.
+    pub fn with_migration(mut self, migration: impl Migration<DB> + 'static) -> Self {
+        self.migrations.push(Box::new(migration));
+        self
+    }
+
+    /// Returns the schema version stored in the database.
+    ///
+    /// Databases without a stored version are at the target version if they are empty, and at
+    /// [INITIAL_SCHEMA_VERSION] otherwise.
+    pub fn stored_version(&self, db: &DB) -> Result<u64, DatabaseError> {
+        let tx = db.tx()?;
+        if let Some(version) = tx.get::<Metadata>(SCHEMA_VERSION_KEY.to_string())? {
+            return Ok(version)
+        }
+        if tx.entries::<CanonicalHeaders>()? == 0 {
+            Ok(self.target)
+        } else {
+            Ok(INITIAL_SCHEMA_VERSION)
+        }
+    }
+
+    /// Runs all pending migrations and returns the schema version the database was at before.
+    ///
+    /// Each migration is committed together with the schema version it upgrades to, so an
+    /// interrupted upgrade resumes from the last completed migration.
+    pub fn run(&self, db: &DB) -> Result<u64, MigrationError> {
+        let stored = self.stored_version(db)?;
+        if stored > self.target {
+            return Err(MigrationError::NewerSchema { stored, supported: self.target })
+        }
+
+        let mut version = stored;
+        while version < self.target {
+            let migration = self
+                .migrations
+                .iter()
+                .find(|migration| migration.from_version() == version)
+                .ok_or(MigrationError::MissingMigration { version })?;
+            let to_version = migration.to_version();
+            if to_version <= version || to_version > self.target {
+                return Err(MigrationError::InvalidMigration { name: migration.name() })
+            }
+
+            let tx = db.tx_mut()?;
+            migration.migrate(&tx)?;
+            tx.put::<Metadata>(SCHEMA_VERSION_KEY.to_string(), to_version)?;
+            tx.commit()?;
+            version = to_version;
+        }
+
+        // Also persists the version of fresh databases and databases that predate the table.
+        let tx = db.tx_mut()?;
+        tx.put::<Metadata>(SCHEMA_VERSION_KEY.to_string(), version)?;
+        tx.commit()?;
+
+        Ok(stored)
+    }
+}
+
+impl<DB: Database> Default for Migrations<DB> {
+    fn default() -> Self {
+        Self::new(SCHEMA_VERSION).with_migration(ClearPruneCheckpoints)
+    }
+}
+
+/// Clears the [PruneCheckpoints] table, whose values gained the highest pruned transaction number
+/// in schema version 2.
+///
+/// The checkpoints of version 1 can't be decoded anymore. Nothing prunes the database yet, so no
+/// pruning progress is lost.
+#[derive(Debug)]
+pub struct ClearPruneCheckpoints;
+
+impl<DB: Database> Migration<DB> for ClearPruneCheckpoints {
+    fn name(&self) -> &'static str {
+        "clear_prune_checkpoints"
+    }
+
+    fn from_version(&self) -> u64 {
+        1
+    }
+
+    fn to_version(&self) -> u64 {
+        2
+    }
+
+    fn migrate<'a>(&self, tx: &<DB as DatabaseGAT<'a>>::TXMut) -> Result<(), DatabaseError> {
+        tx.clear::<PruneCheckpoints>()
+    }
+}
+
+impl<DB: Database> fmt::Debug for Migrations<DB> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Migrations")
+            .field("target", &self.target)
+            .field(
+                "migrations",
+                &self.migrations.iter().map(|migration| migration.name()).collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_utils::create_test_rw_db, DatabaseEnv};
+    use assert_matches::assert_matches;
+    use reth_primitives::{PruneCheckpoint, PruneMode, PruneSegment, H256};
+
+    /// Inserts a canonical header at the given number.
+    struct InsertHeader {
+        from: u64,
+        number: u64,
+        fail: bool,
+    }
+
+    impl Migration<DatabaseEnv> for InsertHeader {
+        fn name(&self) -> &'static str {
+            "insert_header"
+        }
+
+        fn from_version(&self) -> u64 {
+            self.from
+        }
+
+        fn to_version(&self) -> u64 {
+            self.from + 1
+        }
+
+        fn migrate<'a>(
+            &self,
+            tx: &<DatabaseEnv as DatabaseGAT<'a>>::TXMut,
+        ) -> Result<(), DatabaseError> {
+            tx.put::<CanonicalHeaders>(self.number, H256::zero())?;
+            if self.fail {
+                return Err(DatabaseError::ReadOnly)
+            }
+            Ok(())
+        }
+    }
+
+    fn set_version(db: &DatabaseEnv, version: Option<u64>) {
+        let tx = db.tx_mut().unwrap();
+        match version {
+            Some(version) => tx.put::<Metadata>(SCHEMA_VERSION_KEY.to_string(), version).unwrap(),
+            None => {
+                tx.delete::<Metadata>(SCHEMA_VERSION_KEY.to_string(), None).unwrap();
+            }
+        }
+        tx.commit().unwrap();
+    }
+
+    fn version(db: &DatabaseEnv) -> Option<u64> {
+        db.tx().unwrap().get::<Metadata>(SCHEMA_VERSION_KEY.to_string()).unwrap()
+    }
+
+    #[test]
+    fn fresh_database_at_latest_version() {
+        let db = create_test_rw_db();
+        assert_eq!(version(&db), Some(SCHEMA_VERSION));
+
+        set_version(&db, None);
+        let migrations = Migrations::<DatabaseEnv>::new(3);
+        assert_eq!(migrations.run(&db).unwrap(), 3);
+        assert_eq!(version(&db), Some(3));
+    }
+
+    #[test]
+    fn run_migrations_in_order() {
+        let db = create_test_rw_db();
+        // a database that predates the schema version
+        set_version(&db, None);
+        db.update(|tx| tx.put::<CanonicalHeaders>(0, H256::zero())).unwrap().unwrap();
+
+        let migrations = Migrations::<DatabaseEnv>::new(3)
+            .with_migration(InsertHeader { from: 2, number: 2, fail: false })
+            .with_migration(InsertHeader { from: 1, number: 1, fail: false });
+        assert_eq!(migrations.run(&db).unwrap(), INITIAL_SCHEMA_VERSION);
+        assert_eq!(version(&db), Some(3));
+        assert_eq!(db.tx().unwrap().entries::<CanonicalHeaders>().unwrap(), 3);
+
+        // nothing to do on the next startup
+        assert_eq!(migrations.run(&db).unwrap(), 3);
+        assert_eq!(db.tx().unwrap().entries::<CanonicalHeaders>().unwrap(), 3);
+    }
+
+    #[test]
+    fn failed_migration_is_rolled_back() {
+        let db = create_test_rw_db();
+        set_version(&db, Some(1));
+
+        let migrations = Migrations::<DatabaseEnv>::new(3)
+            .with_migration(InsertHeader { from: 1, number: 1, fail: false })
+            .with_migration(InsertHeader { from: 2, number: 2, fail: true });
+        assert_matches!(migrations.run(&db), Err(MigrationError::Database(_)));
+        // the first migration is committed, the second one is rolled back
+        assert_eq!(version(&db), Some(2));
+        assert_eq!(db.tx().unwrap().get::<CanonicalHeaders>(1).unwrap(), Some(H256::zero()));
+        assert_eq!(db.tx().unwrap().get::<CanonicalHeaders>(2).unwrap(), None);
+
+        // resumes from the last completed migration
+        let migrations = Migrations::<DatabaseEnv>::new(3).with_migration(InsertHeader {
+            from: 2,
+            number: 2,
+            fail: false,
+        });
+        assert_eq!(migrations.run(&db).unwrap(), 2);
+        assert_eq!(version(&db), Some(3));
+    }
+
+    #[test]
+    fn refuse_unsupported_versions() {
+        let db = create_test_rw_db();
+
+        set_version(&db, Some(SCHEMA_VERSION + 1));
+        assert_matches!(
+            Migrations::<DatabaseEnv>::default().run(&db),
+            Err(MigrationError::NewerSchema { .. })
+        );
+
+        set_version(&db, Some(1));
+        assert_matches!(
+            Migrations::<DatabaseEnv>::new(2).run(&db),
+            Err(MigrationError::MissingMigration { version: 1 })
+        );
+        assert_eq!(version(&db), Some(1));
+    }
+
+    #[test]
+    fn clear_prune_checkpoints() {
+        let db = create_test_rw_db();
+        set_version(&db, Some(1));
+        let checkpoint =
+            PruneCheckpoint { block_number: 10, tx_number: None, prune_mode: PruneMode::Full };
+        db.update(|tx| tx.put::<PruneCheckpoints>(PruneSegment::Receipts, checkpoint))
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(Migrations::<DatabaseEnv>::default().run(&db).unwrap(), 1);
+        assert_eq!(version(&db), Some(SCHEMA_VERSION));
+        assert_eq!(db.tx().unwrap().entries::<PruneCheckpoints>().unwrap(), 0);
+    }
+}
@@ -37,8 +37,8 @@ use crate::{
 use reth_primitives::{
     stage::StageCheckpoint,
     trie::{BranchNodeCompact, StorageTrieEntry, StoredNibbles, StoredNibblesSubKey},
-    Account, Address, BlockHash, BlockNumber, Bytecode, Header, IntegerList, Receipt, StorageEntry,
-    TransactionSignedNoHash, TxHash, TxNumber, H256,
+    Account, Address, BlockHash, BlockNumber, Bytecode, Header, IntegerList, PruneCheckpoint,
+    PruneSegment, Receipt, StorageEntry, TransactionSignedNoHash, TxHash, TxNumber, H256,
 };
 
 /// Enum for the types of tables present in libmdbx.
@@ -51,7 +51,7 @@ pub enum TableType {
 }
 
 /// Number of tables that should be present inside database.
-pub const NUM_TABLES: usize = 25;
+pub const NUM_TABLES: usize = 27;
 
 /// The general purpose of this is to use with a combination of Tables enum,
 /// by implementing a `TableViewer` trait you can operate on db tables in an abstract way.
@@ -183,7 +183,9 @@ tables!([
     (StoragesTrie, TableType::DupSort),
     (TxSenders, TableType::Table),
     (SyncStage, TableType::Table),
-    (SyncStageProgress, TableType::Table)
+    (SyncStageProgress, TableType::Table),
+    (PruneCheckpoints, TableType::Table),
+    (Metadata, TableType::Table)
 ]);
 
 #[macro_export]
@@ -415,6 +417,17 @@ table!(
     ( SyncStageProgress ) StageId | Vec<u8>
 );
 
+table!(
+    /// Stores the highest pruned block number and prune mode of each prune segment.
+    ( PruneCheckpoints ) PruneSegment | PruneCheckpoint
+);
+
+table!(
+    /// Stores database wide metadata, e.g. the schema version used by
+    /// [migrations](crate::migration).
+    ( Metadata ) String | u64
+);
+
 /// Alias Types
 
 /// List with transaction numbers.
@@ -454,6 +467,8 @@ mod tests {
         (TableType::Table, TxSenders::const_name()),
         (TableType::Table, SyncStage::const_name()),
         (TableType::Table, SyncStageProgress::const_name()),
+        (TableType::Table, PruneCheckpoints::const_name()),
+        (TableType::Table, Metadata::const_name()),
     ];
 
     #[test]
@@ -6,7 +6,7 @@ use crate::{
 use reth_codecs::Compact;
 use reth_primitives::{
     trie::{StoredNibbles, StoredNibblesSubKey},
-    Address, H256,
+    Address, PruneSegment, H256,
 };
 
 pub mod accounts;
@@ -135,3 +135,24 @@ impl Decode for StoredNibblesSubKey {
         Ok(Self::from_compact(buf, buf.len()).0)
     }
 }
+
+impl Encode for PruneSegment {
+    type Encoded = [u8; 1];
+
+    // Delegate to the Compact implementation, which returns the variant as the identifier
+    fn encode(self) -> Self::Encoded {
+        let mut buf = Vec::new();
+        [self.to_compact(&mut buf) as u8]
+    }
+}
+
+impl Decode for PruneSegment {
+    fn decode<B: AsRef<[u8]>>(value: B) -> Result<Self, DatabaseError> {
+        match value.as_ref() {
+            [identifier] if (*identifier as usize) < PruneSegment::ALL.len() => {
+                Ok(Self::from_compact(&[], *identifier as usize).0)
+            }
+            _ => Err(DatabaseError::DecodeError),
+        }
+    }
+}
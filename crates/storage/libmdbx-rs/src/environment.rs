@@ -21,6 +21,25 @@ use std::{
     time::Duration,
 };
 
+#[cfg(unix)]
+fn path_to_bytes<P: AsRef<Path>>(path: P) -> Vec<u8> {
+    use std::os::unix::ffi::OsStrExt;
+    path.as_ref().as_os_str().as_bytes().to_vec()
+}
+
+#[cfg(windows)]
+fn path_to_bytes<P: AsRef<Path>>(path: P) -> Vec<u8> {
+    // On Windows, could use std::os::windows::ffi::OsStrExt to encode_wide(),
+    // but we end up with a Vec<u16> instead of a Vec<u8>, so that doesn't
+    // really help.
+    path.as_ref().to_string_lossy().to_string().into_bytes()
+}
+
+/// Converts the path to a C string, failing if it contains the null character.
+fn path_to_cstring(path: &Path) -> Result<CString> {
+    CString::new(path_to_bytes(path)).map_err(|_| Error::Invalid)
+}
+
 mod private {
     use super::*;
 
@@ -132,6 +151,24 @@ where
         Ok(Transaction::new_from_ptr(self, txn.0))
     }
 
+    /// Copies the environment to a new data file at `dest`, which must not exist yet.
+    ///
+    /// With `compact`, free pages are omitted from the copy and all pages are renumbered
+    /// sequentially, so the copy is only as large as the data it contains. The copy keeps a
+    /// dynamic size, and the lock file is recreated once the copy is opened.
+    ///
+    /// The copy is made from a read transaction, so copying an environment that is written to
+    /// concurrently can make its data file grow significantly.
+    pub fn copy(&self, dest: &Path, compact: bool) -> Result<()> {
+        let dest = path_to_cstring(dest)?;
+        let mut flags = ffi::MDBX_CP_FORCE_DYNAMIC_SIZE;
+        if compact {
+            flags |= ffi::MDBX_CP_COMPACT;
+        }
+        mdbx_result(unsafe { ffi::mdbx_env_copy(self.env(), dest.as_ptr(), flags) })?;
+        Ok(())
+    }
+
     /// Flush the environment data buffers to disk.
     pub fn sync(&self, force: bool) -> Result<bool> {
         mdbx_result(unsafe { ffi::mdbx_env_sync_ex(self.env(), force, false) })
@@ -470,24 +507,7 @@ where
                     ))?;
                 }
 
-                #[cfg(unix)]
-                fn path_to_bytes<P: AsRef<Path>>(path: P) -> Vec<u8> {
-                    use std::os::unix::ffi::OsStrExt;
-                    path.as_ref().as_os_str().as_bytes().to_vec()
-                }
-
-                #[cfg(windows)]
-                fn path_to_bytes<P: AsRef<Path>>(path: P) -> Vec<u8> {
-                    // On Windows, could use std::os::windows::ffi::OsStrExt to encode_wide(),
-                    // but we end up with a Vec<u16> instead of a Vec<u8>, so that doesn't
-                    // really help.
-                    path.as_ref().to_string_lossy().to_string().into_bytes()
-                }
-
-                let path = match CString::new(path_to_bytes(path)) {
-                    Ok(path) => path,
-                    Err(_) => return Err(Error::Invalid),
-                };
+                let path = path_to_cstring(path)?;
                 mdbx_result(ffi::mdbx_env_open(
                     env,
                     path.as_ptr(),
@@ -29,6 +29,9 @@ pub trait TransactionKind: private::Sealed + Debug + 'static {
 
     #[doc(hidden)]
     const OPEN_FLAGS: MDBX_txn_flags_t;
+
+    /// Convenience flag for distinguishing between read-only and read-write transactions.
+    const IS_READ_ONLY: bool;
 }
 
 #[derive(Debug)]
@@ -39,10 +42,12 @@ pub struct RW;
 impl TransactionKind for RO {
     const ONLY_CLEAN: bool = true;
     const OPEN_FLAGS: MDBX_txn_flags_t = MDBX_TXN_RDONLY;
+    const IS_READ_ONLY: bool = true;
 }
 impl TransactionKind for RW {
     const ONLY_CLEAN: bool = false;
     const OPEN_FLAGS: MDBX_txn_flags_t = MDBX_TXN_READWRITE;
+    const IS_READ_ONLY: bool = false;
 }
 
 /// An MDBX transaction.
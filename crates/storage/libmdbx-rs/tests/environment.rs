@@ -156,3 +156,36 @@ fn test_freelist() {
     freelist = env.freelist().unwrap();
     assert!(freelist > 0);
 }
+
+#[test]
+fn test_copy_compact() {
+    let dir = tempdir().unwrap();
+    let env = Environment::new().open(dir.path()).unwrap();
+
+    // Write values and delete every other one to populate the freelist.
+    let tx = env.begin_rw_txn().expect("begin_rw_txn");
+    let dbi = tx.open_db(None).unwrap().dbi();
+    for i in 0..1024u64 {
+        let mut value = [0u8; 8];
+        LittleEndian::write_u64(&mut value, i);
+        tx.put(dbi, value, [0u8; 512], WriteFlags::default()).expect("tx.put");
+    }
+    tx.commit().expect("tx.commit");
+    let tx = env.begin_rw_txn().expect("begin_rw_txn");
+    let dbi = tx.open_db(None).unwrap().dbi();
+    for i in (0..1024u64).step_by(2) {
+        let mut value = [0u8; 8];
+        LittleEndian::write_u64(&mut value, i);
+        tx.del(dbi, value, None).expect("tx.del");
+    }
+    tx.commit().expect("tx.commit");
+
+    let copy_dir = tempdir().unwrap();
+    env.copy(&copy_dir.path().join("mdbx.dat"), true).unwrap();
+    // The destination must not exist.
+    assert!(env.copy(&copy_dir.path().join("mdbx.dat"), true).is_err());
+
+    let copy = Environment::new().open(copy_dir.path()).unwrap();
+    assert_eq!(copy.stat().unwrap().entries(), 512);
+    assert!(copy.info().unwrap().last_pgno() < env.info().unwrap().last_pgno());
+}
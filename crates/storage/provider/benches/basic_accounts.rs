@@ -0,0 +1,59 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use reth_db::{
+    database::Database, tables, test_utils::create_test_rw_db, transaction::DbTxMut, DatabaseEnv,
+};
+use reth_primitives::{Account, Address, MAINNET};
+use reth_provider::{AccountReader, ProviderFactory};
+use std::sync::Arc;
+
+criterion_group!(benches, basic_accounts);
+criterion_main!(benches);
+
+const NUM_ACCOUNTS: usize = 100_000;
+const BATCH_SIZES: [usize; 3] = [10, 100, 1_000];
+
+/// Compares a batched [AccountReader::basic_accounts] lookup against individual
+/// [AccountReader::basic_account] calls for the same addresses.
+fn basic_accounts(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Basic Accounts");
+    let (factory, addresses) = setup();
+
+    for batch_size in BATCH_SIZES {
+        // every other address doesn't exist in the database
+        let batch = addresses
+            .iter()
+            .step_by(NUM_ACCOUNTS / batch_size)
+            .enumerate()
+            .map(|(idx, address)| if idx % 2 == 0 { *address } else { Address::random() })
+            .collect::<Vec<_>>();
+
+        group.bench_function(format!("individual | batch: {batch_size}"), |b| {
+            b.iter(|| {
+                let provider = factory.provider().unwrap();
+                for address in &batch {
+                    black_box(provider.basic_account(*address).unwrap());
+                }
+            })
+        });
+        group.bench_function(format!("batched | batch: {batch_size}"), |b| {
+            b.iter(|| {
+                let provider = factory.provider().unwrap();
+                black_box(provider.basic_accounts(&batch).unwrap());
+            })
+        });
+    }
+}
+
+fn setup() -> (ProviderFactory<Arc<DatabaseEnv>>, Vec<Address>) {
+    let db = create_test_rw_db();
+    let addresses = (0..NUM_ACCOUNTS).map(|_| Address::random()).collect::<Vec<_>>();
+
+    let tx = db.tx_mut().unwrap();
+    for (nonce, address) in addresses.iter().enumerate() {
+        let account = Account { nonce: nonce as u64, ..Default::default() };
+        tx.put::<tables::PlainAccountState>(*address, account).unwrap();
+    }
+    tx.commit().unwrap();
+
+    (ProviderFactory::new(db, MAINNET.clone()), addresses)
+}
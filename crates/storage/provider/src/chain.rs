@@ -3,10 +3,14 @@
 use crate::PostState;
 use reth_interfaces::{executor::BlockExecutionError, Error};
 use reth_primitives::{
-    BlockHash, BlockNumHash, BlockNumber, ForkBlock, Receipt, SealedBlock, SealedBlockWithSenders,
-    TransactionSigned, TxHash,
+    bloom::Input, Address, BlockHash, BlockNumHash, BlockNumber, Bloom, ForkBlock, Log, Receipt,
+    SealedBlock, SealedBlockWithSenders, TransactionSigned, TxHash, H256,
+};
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, HashSet},
+    fmt,
 };
-use std::{borrow::Cow, collections::BTreeMap, fmt};
 
 /// A chain of blocks and their final state.
 ///
@@ -67,6 +71,12 @@ impl Chain {
         None
     }
 
+    /// Returns the same chain with a state that only contains the changes of the given addresses,
+    /// see [PostState::filter_accounts].
+    pub fn filter_accounts(&self, addresses: &HashSet<Address>) -> Self {
+        Self { state: self.state.filter_accounts(addresses), blocks: self.blocks.clone() }
+    }
+
     /// Destructure the chain into its inner components, the blocks and the state at the tip of the
     /// chain.
     pub fn into_inner(self) -> (ChainBlocks<'static>, PostState) {
@@ -151,6 +161,52 @@ impl Chain {
         receipt_attch
     }
 
+    /// Returns the logs of the chain that match the given addresses and topics, together with the
+    /// hash of the transaction that emitted them, in the order of the chain.
+    ///
+    /// An empty set of `addresses` matches any address. The `topics` are matched by position,
+    /// `None` matches any topic at that position.
+    ///
+    /// Blocks whose logs bloom can't contain a matching log are skipped without scanning their
+    /// receipts.
+    pub fn logs_matching(
+        &self,
+        addresses: &HashSet<Address>,
+        topics: &[Option<H256>],
+    ) -> Vec<(TxHash, Log)> {
+        let address_blooms = addresses
+            .iter()
+            .map(|address| Bloom::from(Input::Raw(address.as_bytes())))
+            .collect::<Vec<_>>();
+        let topic_blooms = topics
+            .iter()
+            .flatten()
+            .map(|topic| Bloom::from(Input::Raw(topic.as_bytes())))
+            .collect::<Vec<_>>();
+
+        let mut logs = Vec::new();
+        for (block_number, block) in self.blocks.iter() {
+            let bloom = &block.header.logs_bloom;
+            if !topic_blooms.iter().all(|topic| bloom.contains_bloom(topic)) ||
+                (!address_blooms.is_empty() &&
+                    !address_blooms.iter().any(|address| bloom.contains_bloom(address)))
+            {
+                continue
+            }
+
+            for (tx, receipt) in block.body.iter().zip(self.state.receipts(*block_number)) {
+                let matching = receipt.logs.iter().filter(|log| {
+                    (addresses.is_empty() || addresses.contains(&log.address)) &&
+                        topics.iter().enumerate().all(|(idx, topic)| {
+                            topic.map_or(true, |topic| log.topics.get(idx) == Some(&topic))
+                        })
+                });
+                logs.extend(matching.map(|log| (tx.hash(), log.clone())));
+            }
+        }
+        logs
+    }
+
     /// Merge two chains by appending the given chain into the current one.
     ///
     /// The state of accounts for this chain is set to the state of the newest chain.
@@ -384,6 +440,38 @@ mod tests {
         assert!(chain1.append_chain(chain2).is_err());
     }
 
+    #[test]
+    fn logs_matching() {
+        let address = H160([1; 20]);
+        let topic = H256([2; 32]);
+        let log = Log { address, topics: vec![topic], data: Default::default() };
+        let receipt = Receipt { logs: vec![log.clone()], ..Default::default() };
+        let tx = TransactionSigned::default();
+
+        let mut state = PostState::default();
+        let mut block1 = SealedBlockWithSenders::default();
+        block1.number = 1;
+        block1.body.push(tx.clone());
+        block1.header.header.logs_bloom = reth_primitives::bloom::logs_bloom([&log]);
+        state.add_receipt(1, receipt.clone());
+
+        // The bloom of the second block doesn't contain the log, so it's skipped.
+        let mut block2 = SealedBlockWithSenders::default();
+        block2.number = 2;
+        block2.body.push(tx.clone());
+        state.add_receipt(2, receipt);
+
+        let chain = Chain { state, blocks: BTreeMap::from([(1, block1), (2, block2)]) };
+
+        assert_eq!(
+            chain.logs_matching(&HashSet::from([address]), &[Some(topic)]),
+            vec![(tx.hash(), log.clone())]
+        );
+        assert_eq!(chain.logs_matching(&HashSet::new(), &[None]), vec![(tx.hash(), log)]);
+        assert!(chain.logs_matching(&HashSet::from([H160([3; 20])]), &[]).is_empty());
+        assert!(chain.logs_matching(&HashSet::new(), &[None, Some(topic)]).is_empty());
+    }
+
     #[test]
     fn test_number_split() {
         let mut base_state = PostState::default();
@@ -22,21 +22,23 @@
 mod traits;
 pub use traits::{
     AccountExtReader, AccountReader, BlockExecutionWriter, BlockExecutor, BlockHashReader,
-    BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt, BlockSource, BlockWriter,
-    BlockchainTreePendingStateProvider, CanonChainTracker, CanonStateNotification,
-    CanonStateNotificationSender, CanonStateNotifications, CanonStateSubscriptions,
-    ChainSpecProvider, EvmEnvProvider, ExecutorFactory, HashingWriter, HeaderProvider,
-    HistoryWriter, PostStateDataProvider, ReceiptProvider, ReceiptProviderIdExt,
-    StageCheckpointReader, StageCheckpointWriter, StateProvider, StateProviderBox,
-    StateProviderFactory, StateRootProvider, StorageReader, TransactionsProvider,
-    WithdrawalsProvider,
+    BlockIdReader, BlockNumReader, BlockRangeIter, BlockReader, BlockReaderIdExt, BlockSource,
+    BlockWriter, BlockchainTreePendingStateProvider, CanonChainTracker, CanonStateNotification,
+    CanonStateNotificationSender, CanonStateNotificationStream, CanonStateNotifications,
+    CanonStateSubscriptions, ChainSpecProvider, EvmEnvProvider, ExecutorFactory,
+    FilteredCanonStateNotificationStream, ForkchoiceSubscriptions, HashingWriter, HeaderProvider,
+    HistoryWriter, PostStateDataProvider, PruneCheckpointReader, PruneCheckpointWriter,
+    ReceiptProvider, ReceiptProviderIdExt, ReorgDiff, StageCheckpointReader, StageCheckpointWriter,
+    StateProvider, StateProviderBox, StateProviderFactory, StateRootProvider, StorageMode,
+    StorageReader, TransactionsProvider, WithdrawalsProvider,
 };
 
 /// Provider trait implementations.
 pub mod providers;
 pub use providers::{
-    DatabaseProvider, DatabaseProviderRO, DatabaseProviderRW, HistoricalStateProvider,
-    HistoricalStateProviderRef, LatestStateProvider, LatestStateProviderRef, ProviderFactory,
+    ConsistencyReport, ConsistencyViolation, DatabaseProvider, DatabaseProviderRO,
+    DatabaseProviderRW, HistoricalStateProvider, HistoricalStateProviderRef, LatestStateProvider,
+    LatestStateProviderRef, ProviderFactory,
 };
 
 /// Execution result
@@ -14,7 +14,7 @@ use reth_trie::{
     hashed_cursor::{HashedPostState, HashedPostStateCursorFactory, HashedStorage},
     StateRoot, StateRootError,
 };
-use std::collections::{BTreeMap, BTreeSet};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
 
 mod account;
 pub use account::AccountChanges;
@@ -419,6 +419,79 @@ impl PostState {
         non_reverted_state
     }
 
+    /// Returns a [PostState] that only contains the account and storage changes of the given
+    /// addresses.
+    ///
+    /// Bytecodes are only kept if they are used by one of the retained accounts, receipts are kept
+    /// as is.
+    pub fn filter_accounts(&self, addresses: &HashSet<Address>) -> Self {
+        let accounts: BTreeMap<_, _> = self
+            .accounts
+            .iter()
+            .filter(|(address, _)| addresses.contains(*address))
+            .map(|(address, account)| (*address, *account))
+            .collect();
+
+        let storage = self
+            .storage
+            .iter()
+            .filter(|(address, _)| addresses.contains(*address))
+            .map(|(address, storage)| (*address, storage.clone()))
+            .collect();
+
+        let mut account_changes = AccountChanges::default();
+        for (block_number, changes) in self.account_changes.iter() {
+            let changes: BTreeMap<_, _> = changes
+                .iter()
+                .filter(|(address, _)| addresses.contains(*address))
+                .map(|(address, account)| (*address, *account))
+                .collect();
+            if !changes.is_empty() {
+                account_changes.insert_for_block(*block_number, changes);
+            }
+        }
+
+        let mut storage_changes = StorageChanges::default();
+        for (block_number, changes) in self.storage_changes.iter() {
+            for (address, transition) in
+                changes.iter().filter(|(address, _)| addresses.contains(*address))
+            {
+                storage_changes.insert_for_block_and_address(
+                    *block_number,
+                    *address,
+                    transition.wipe.clone(),
+                    transition.storage.iter().map(|(slot, value)| (*slot, *value)),
+                );
+            }
+        }
+
+        let bytecode = self
+            .bytecode
+            .iter()
+            .filter(|(hash, _)| {
+                accounts.values().flatten().any(|account| account.bytecode_hash == Some(**hash))
+            })
+            .map(|(hash, bytecode)| (*hash, bytecode.clone()))
+            .collect();
+
+        Self {
+            accounts,
+            storage,
+            account_changes,
+            storage_changes,
+            bytecode,
+            receipts: self.receipts.clone(),
+        }
+    }
+
+    /// Returns `true` if the post-state doesn't contain any account or storage changes.
+    pub fn has_no_state_changes(&self) -> bool {
+        self.accounts.is_empty() &&
+            self.storage.is_empty() &&
+            self.account_changes.is_empty() &&
+            self.storage_changes.is_empty()
+    }
+
     /// Add a newly created account to the post-state.
     pub fn create_account(
         &mut self,
@@ -658,6 +731,34 @@ mod tests {
     use reth_trie::test_utils::state_root;
     use std::sync::Arc;
 
+    #[test]
+    fn filter_accounts() {
+        let address1 = Address::random();
+        let address2 = Address::random();
+        let account = Account { nonce: 1, balance: U256::from(1), bytecode_hash: None };
+
+        let mut state = PostState::new();
+        state.create_account(1, address1, account);
+        state.create_account(1, address2, account);
+        state.change_storage(
+            1,
+            address2,
+            BTreeMap::from([(U256::from(1), (U256::ZERO, U256::from(2)))]),
+        );
+        state.add_receipt(1, Receipt::default());
+
+        let filtered = state.filter_accounts(&HashSet::from([address1]));
+        assert_eq!(filtered.accounts(), &BTreeMap::from([(address1, Some(account))]));
+        assert!(filtered.storage().is_empty());
+        assert_eq!(filtered.account_changes().size, 1);
+        assert!(filtered.storage_changes().is_empty());
+        assert_eq!(filtered.receipts(1).len(), 1);
+        assert!(!filtered.has_no_state_changes());
+
+        let filtered = state.filter_accounts(&HashSet::from([Address::random()]));
+        assert!(filtered.has_no_state_changes());
+    }
+
     // Ensure that the transition id is not incremented if postate is extended by another empty
     // poststate.
     #[test]
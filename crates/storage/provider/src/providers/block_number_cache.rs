@@ -0,0 +1,180 @@
+use crate::CanonStateNotifications;
+use parking_lot::Mutex;
+use reth_interfaces::Result;
+use reth_primitives::{BlockHash, BlockNumber};
+use schnellru::{ByLength, LruMap};
+use std::{fmt, sync::Arc};
+use tokio::sync::broadcast::error::TryRecvError;
+
+/// Default number of entries of the block hash to number cache.
+pub const DEFAULT_BLOCK_NUMBER_CACHE_LEN: u32 = 1024;
+
+/// A bounded LRU cache of block hash to number lookups.
+///
+/// Hashes that are not in the database are cached as absent, so repeated lookups of unknown hashes
+/// don't open a database transaction.
+///
+/// The entries are only valid for the canonical tip they were fetched at, so the cache is cleared
+/// whenever the tip changes. Additionally, the entries of committed and reverted blocks are evicted
+/// once the corresponding canonical state notification has been received.
+#[derive(Clone)]
+pub(crate) struct BlockNumberCache {
+    inner: Arc<Mutex<BlockNumberCacheInner>>,
+}
+
+impl BlockNumberCache {
+    /// Creates a new cache with the given maximum number of entries.
+    pub(crate) fn new(max_len: u32, notifications: CanonStateNotifications) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(BlockNumberCacheInner {
+                entries: LruMap::new(ByLength::new(max_len)),
+                tip: None,
+                generation: 0,
+                notifications,
+            })),
+        }
+    }
+
+    /// Returns the cached block number of the hash, or fetches and caches it.
+    ///
+    /// `tip` is the number of the current canonical tip.
+    pub(crate) fn get_or_fetch(
+        &self,
+        hash: BlockHash,
+        tip: BlockNumber,
+        fetch: impl FnOnce() -> Result<Option<BlockNumber>>,
+    ) -> Result<Option<BlockNumber>> {
+        let generation = {
+            let mut inner = self.inner.lock();
+            inner.sync(tip);
+            if let Some(number) = inner.entries.get(&hash) {
+                return Ok(*number)
+            }
+            inner.generation
+        };
+
+        // The lock isn't held while fetching, so the fetched value may already be outdated by a
+        // canonical state change that happened in the meantime. It's only cached if no entries
+        // were evicted since the fetch started.
+        let number = fetch()?;
+        let mut inner = self.inner.lock();
+        inner.sync(tip);
+        if inner.generation == generation {
+            inner.entries.insert(hash, number);
+        }
+        Ok(number)
+    }
+}
+
+impl fmt::Debug for BlockNumberCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let inner = self.inner.lock();
+        f.debug_struct("BlockNumberCache")
+            .field("len", &inner.entries.len())
+            .field("tip", &inner.tip)
+            .finish()
+    }
+}
+
+struct BlockNumberCacheInner {
+    /// Cached block numbers, `None` if the hash is not in the database.
+    entries: LruMap<BlockHash, Option<BlockNumber>, ByLength>,
+    /// The canonical tip the entries are valid for.
+    tip: Option<BlockNumber>,
+    /// Incremented whenever entries are evicted because of a canonical state change.
+    generation: u64,
+    /// Receiver for canonical state changes.
+    notifications: CanonStateNotifications,
+}
+
+impl BlockNumberCacheInner {
+    /// Evicts all entries that are outdated because of a canonical state change.
+    fn sync(&mut self, tip: BlockNumber) {
+        if self.tip != Some(tip) {
+            self.entries.clear();
+            self.tip = Some(tip);
+            self.generation += 1;
+        }
+
+        loop {
+            match self.notifications.try_recv() {
+                Ok(notification) => {
+                    self.generation += 1;
+                    let chains =
+                        notification.reverted().into_iter().chain(notification.committed());
+                    for chain in chains {
+                        for block in chain.blocks().values() {
+                            self.entries.remove(&block.hash());
+                        }
+                    }
+                }
+                Err(TryRecvError::Lagged(_)) => {
+                    self.entries.clear();
+                    self.generation += 1;
+                }
+                Err(TryRecvError::Empty | TryRecvError::Closed) => break,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_utils::blocks::BlockChainTestData, CanonStateNotification, Chain};
+    use reth_interfaces::provider::ProviderError;
+    use tokio::sync::broadcast;
+
+    #[test]
+    fn caches_absent_hashes() {
+        let (tx, rx) = broadcast::channel(1);
+        let cache = BlockNumberCache::new(10, rx);
+        let data = BlockChainTestData::default();
+        let (block, post_state) = data.blocks[0].clone();
+        let hash = block.hash();
+
+        assert_eq!(cache.get_or_fetch(hash, 0, || Ok(None)).unwrap(), None);
+        // served from the cache
+        let fail = || -> Result<Option<BlockNumber>> {
+            Err(ProviderError::BlockHashNotFound(hash).into())
+        };
+        assert_eq!(cache.get_or_fetch(hash, 0, fail).unwrap(), None);
+
+        // a commit of the block evicts the entry
+        let chain = Arc::new(Chain::new(vec![(block.clone(), post_state)]));
+        tx.send(CanonStateNotification::Commit { new: chain }).unwrap();
+        assert_eq!(cache.get_or_fetch(hash, 0, || Ok(Some(block.number))).unwrap(), Some(1));
+        assert_eq!(cache.get_or_fetch(hash, 0, fail).unwrap(), Some(1));
+
+        // a new tip clears the cache
+        assert_eq!(cache.get_or_fetch(hash, 1, || Ok(None)).unwrap(), None);
+    }
+
+    #[test]
+    fn skips_outdated_fetches() {
+        let (tx, rx) = broadcast::channel(1);
+        let cache = BlockNumberCache::new(10, rx);
+        let data = BlockChainTestData::default();
+        let (block, post_state) = data.blocks[0].clone();
+        let hash = block.hash();
+
+        // the block is committed while the absent hash is fetched
+        let chain = Arc::new(Chain::new(vec![(block.clone(), post_state)]));
+        let fetch = || -> Result<Option<BlockNumber>> {
+            tx.send(CanonStateNotification::Commit { new: chain }).unwrap();
+            Ok(None)
+        };
+        assert_eq!(cache.get_or_fetch(hash, 0, fetch).unwrap(), None);
+        // the outdated value wasn't cached
+        assert_eq!(cache.get_or_fetch(hash, 0, || Ok(Some(block.number))).unwrap(), Some(1));
+
+        // the tip changes while fetching
+        let other = BlockHash::repeat_byte(1);
+        let fetch = || -> Result<Option<BlockNumber>> {
+            cache.get_or_fetch(hash, 1, || Ok(Some(block.number))).unwrap();
+            Ok(None)
+        };
+        assert_eq!(cache.get_or_fetch(other, 0, fetch).unwrap(), None);
+        assert_eq!(cache.get_or_fetch(other, 0, || Ok(Some(2))).unwrap(), Some(2));
+    }
+}
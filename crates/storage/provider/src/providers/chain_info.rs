@@ -7,6 +7,7 @@ use std::{
     },
     time::Instant,
 };
+use tokio::sync::watch;
 
 /// Tracks the chain info: canonical head, safe block, finalized block.
 #[derive(Debug, Clone)]
@@ -22,16 +23,16 @@ impl ChainInfoTracker {
                 last_forkchoice_update: RwLock::new(None),
                 last_transition_configuration_exchange: RwLock::new(None),
                 canonical_head_number: AtomicU64::new(head.number),
-                canonical_head: RwLock::new(head),
-                safe_block: RwLock::new(None),
-                finalized_block: RwLock::new(None),
+                canonical_head: watch::channel(head).0,
+                safe_block: watch::channel(None).0,
+                finalized_block: watch::channel(None).0,
             }),
         }
     }
 
     /// Returns the [ChainInfo] for the canonical head.
     pub(crate) fn chain_info(&self) -> ChainInfo {
-        let inner = self.inner.canonical_head.read();
+        let inner = self.inner.canonical_head.borrow();
         ChainInfo { best_hash: inner.hash(), best_number: inner.number }
     }
 
@@ -58,25 +59,25 @@ impl ChainInfoTracker {
     /// Returns the canonical head of the chain.
     #[allow(unused)]
     pub(crate) fn get_canonical_head(&self) -> SealedHeader {
-        self.inner.canonical_head.read().clone()
+        self.inner.canonical_head.borrow().clone()
     }
 
     /// Returns the safe header of the chain.
     #[allow(unused)]
     pub(crate) fn get_safe_header(&self) -> Option<SealedHeader> {
-        self.inner.safe_block.read().clone()
+        self.inner.safe_block.borrow().clone()
     }
 
     /// Returns the finalized header of the chain.
     #[allow(unused)]
     pub(crate) fn get_finalized_header(&self) -> Option<SealedHeader> {
-        self.inner.finalized_block.read().clone()
+        self.inner.finalized_block.borrow().clone()
     }
 
     /// Returns the canonical head of the chain.
     #[allow(unused)]
     pub(crate) fn get_canonical_num_hash(&self) -> BlockNumHash {
-        self.inner.canonical_head.read().num_hash()
+        self.inner.canonical_head.borrow().num_hash()
     }
 
     /// Returns the canonical head of the chain.
@@ -87,37 +88,63 @@ impl ChainInfoTracker {
     /// Returns the safe header of the chain.
     #[allow(unused)]
     pub(crate) fn get_safe_num_hash(&self) -> Option<BlockNumHash> {
-        let h = self.inner.safe_block.read();
-        h.as_ref().map(|h| h.num_hash())
+        self.inner.safe_block.borrow().as_ref().map(|h| h.num_hash())
     }
 
     /// Returns the finalized header of the chain.
     #[allow(unused)]
     pub(crate) fn get_finalized_num_hash(&self) -> Option<BlockNumHash> {
-        let h = self.inner.finalized_block.read();
-        h.as_ref().map(|h| h.num_hash())
+        self.inner.finalized_block.borrow().as_ref().map(|h| h.num_hash())
+    }
+
+    /// Returns a receiver that is notified whenever the canonical head changes.
+    pub(crate) fn subscribe_to_canonical_head(&self) -> watch::Receiver<SealedHeader> {
+        self.inner.canonical_head.subscribe()
     }
 
     /// Sets the canonical head of the chain.
     pub(crate) fn set_canonical_head(&self, header: SealedHeader) {
         let number = header.number;
-        *self.inner.canonical_head.write() = header;
-
-        // also update the atomic number.
+        // update the atomic number first, so subscribers that are woken up observe it
         self.inner.canonical_head_number.store(number, Ordering::Relaxed);
+
+        self.inner.canonical_head.send_replace(header);
+    }
+
+    /// Returns a receiver that is notified whenever the safe header changes.
+    pub(crate) fn subscribe_to_safe_header(&self) -> watch::Receiver<Option<SealedHeader>> {
+        self.inner.safe_block.subscribe()
+    }
+
+    /// Returns a receiver that is notified whenever the finalized header changes.
+    pub(crate) fn subscribe_to_finalized_header(&self) -> watch::Receiver<Option<SealedHeader>> {
+        self.inner.finalized_block.subscribe()
     }
 
     /// Sets the safe header of the chain.
     pub(crate) fn set_safe(&self, header: SealedHeader) {
-        self.inner.safe_block.write().replace(header);
+        replace_if_changed(&self.inner.safe_block, header);
     }
 
     /// Sets the finalized header of the chain.
     pub(crate) fn set_finalized(&self, header: SealedHeader) {
-        self.inner.finalized_block.write().replace(header);
+        replace_if_changed(&self.inner.finalized_block, header);
     }
 }
 
+/// Replaces the header of the channel, subscribers are only notified if the hash changed.
+///
+/// Forkchoice updates usually repeat the same safe and finalized blocks.
+fn replace_if_changed(sender: &watch::Sender<Option<SealedHeader>>, header: SealedHeader) {
+    sender.send_if_modified(|current| {
+        if current.as_ref().map(|current| current.hash()) == Some(header.hash()) {
+            return false
+        }
+        *current = Some(header);
+        true
+    });
+}
+
 /// Container type for all chain info fields
 #[derive(Debug)]
 struct ChainInfoInner {
@@ -131,10 +158,50 @@ struct ChainInfoInner {
     last_transition_configuration_exchange: RwLock<Option<Instant>>,
     /// Tracks the number of the `canonical_head`.
     canonical_head_number: AtomicU64,
-    /// The canonical head of the chain.
-    canonical_head: RwLock<SealedHeader>,
-    /// The block that the beacon node considers safe.
-    safe_block: RwLock<Option<SealedHeader>>,
-    /// The block that the beacon node considers finalized.
-    finalized_block: RwLock<Option<SealedHeader>>,
+    /// The canonical head of the chain, which also notifies subscribers on changes.
+    canonical_head: watch::Sender<SealedHeader>,
+    /// The block that the beacon node considers safe, which also notifies subscribers on changes.
+    safe_block: watch::Sender<Option<SealedHeader>>,
+    /// The block that the beacon node considers finalized, which also notifies subscribers on
+    /// changes.
+    finalized_block: watch::Sender<Option<SealedHeader>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::Header;
+
+    #[test]
+    fn canonical_head_subscription() {
+        let genesis = Header::default().seal_slow();
+        let tracker = ChainInfoTracker::new(genesis.clone());
+        let mut head = tracker.subscribe_to_canonical_head();
+        assert_eq!(*head.borrow_and_update(), genesis);
+
+        let next = Header { number: 1, parent_hash: genesis.hash(), ..Default::default() };
+        let next = next.seal_slow();
+        tracker.set_canonical_head(next.clone());
+        assert!(head.has_changed().unwrap());
+        assert_eq!(*head.borrow_and_update(), next);
+        assert_eq!(tracker.get_canonical_block_number(), 1);
+    }
+
+    #[test]
+    fn finalized_header_subscription() {
+        let genesis = Header::default().seal_slow();
+        let tracker = ChainInfoTracker::new(genesis.clone());
+        let mut finalized = tracker.subscribe_to_finalized_header();
+        assert_eq!(*finalized.borrow_and_update(), None);
+
+        tracker.set_finalized(genesis.clone());
+        assert!(finalized.has_changed().unwrap());
+        assert_eq!(*finalized.borrow_and_update(), Some(genesis.clone()));
+
+        // the same header again doesn't notify
+        tracker.set_finalized(genesis.clone());
+        assert!(!finalized.has_changed().unwrap());
+        assert_eq!(tracker.get_finalized_num_hash(), Some(genesis.num_hash()));
+        assert_eq!(tracker.get_safe_header(), None);
+    }
 }
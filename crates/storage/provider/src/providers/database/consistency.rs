@@ -0,0 +1,162 @@
+use crate::{DatabaseProvider, StageCheckpointReader};
+use reth_db::{cursor::DbCursorRO, table::Table, tables, transaction::DbTx};
+use reth_interfaces::Result;
+use reth_primitives::{stage::StageId, BlockNumber};
+use std::{collections::HashMap, fmt};
+
+/// The result of [DatabaseProvider::check_consistency].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConsistencyReport {
+    /// All violations found, in the order of [StageId::ALL].
+    pub violations: Vec<ConsistencyViolation>,
+}
+
+impl ConsistencyReport {
+    /// Returns `true` if no violations were found.
+    pub fn is_consistent(&self) -> bool {
+        self.violations.is_empty()
+    }
+
+    /// Returns the highest block all stages can be unwound to in order to restore consistency,
+    /// or `None` if the checkpoints are already consistent.
+    pub fn unwind_target(&self) -> Option<BlockNumber> {
+        self.violations.iter().map(ConsistencyViolation::consistent_block).min()
+    }
+}
+
+/// A single inconsistency between a stage checkpoint and the database.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConsistencyViolation {
+    /// The stage checkpoint is ahead of the checkpoint of a stage it depends on.
+    StageAhead {
+        /// The stage that is ahead.
+        stage: StageId,
+        /// The block number of the stage checkpoint.
+        checkpoint: BlockNumber,
+        /// The stage that is behind.
+        dependency: StageId,
+        /// The block number of the dependency checkpoint.
+        dependency_checkpoint: BlockNumber,
+    },
+    /// The data the stage checkpoint refers to is missing from the database.
+    MissingData {
+        /// The stage whose data is missing.
+        stage: StageId,
+        /// The block number of the stage checkpoint.
+        checkpoint: BlockNumber,
+        /// The table the data is missing from.
+        table: &'static str,
+        /// The highest block below the checkpoint that is present in the table.
+        last_available: Option<BlockNumber>,
+    },
+}
+
+impl ConsistencyViolation {
+    /// Returns the highest block the stage is consistent up to.
+    pub fn consistent_block(&self) -> BlockNumber {
+        match self {
+            ConsistencyViolation::StageAhead { dependency_checkpoint, .. } => {
+                *dependency_checkpoint
+            }
+            ConsistencyViolation::MissingData { last_available, .. } => {
+                last_available.unwrap_or_default()
+            }
+        }
+    }
+}
+
+impl fmt::Display for ConsistencyViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConsistencyViolation::StageAhead {
+                stage,
+                checkpoint,
+                dependency,
+                dependency_checkpoint,
+            } => write!(
+                f,
+                "{stage} checkpoint #{checkpoint} is ahead of {dependency} checkpoint #{dependency_checkpoint}"
+            ),
+            ConsistencyViolation::MissingData { stage, checkpoint, table, last_available } => {
+                write!(f, "{stage} checkpoint #{checkpoint} is missing from {table}")?;
+                match last_available {
+                    Some(block) => write!(f, ", last available block is #{block}"),
+                    None => write!(f, ", table has no entries"),
+                }
+            }
+        }
+    }
+}
+
+impl<'this, TX: DbTx<'this>> DatabaseProvider<'this, TX> {
+    /// Verifies that the checkpoints of the stages in [StageId::ALL] are not ahead of the stages
+    /// they depend on, and that the headers and bodies they refer to are present.
+    ///
+    /// Stages without a checkpoint are treated as if they were at genesis.
+    pub fn check_consistency(&self) -> Result<ConsistencyReport> {
+        let mut checkpoints = HashMap::with_capacity(StageId::ALL.len());
+        for stage in StageId::ALL {
+            checkpoints.insert(stage, self.get_stage_checkpoint(stage)?);
+        }
+        let block_number = |stage: &StageId| {
+            checkpoints[stage].map(|checkpoint| checkpoint.block_number).unwrap_or_default()
+        };
+
+        let mut violations = Vec::new();
+        for stage in StageId::ALL {
+            let checkpoint = block_number(&stage);
+            for dependency in stage.dependencies() {
+                let dependency_checkpoint = block_number(dependency);
+                if checkpoint > dependency_checkpoint {
+                    violations.push(ConsistencyViolation::StageAhead {
+                        stage,
+                        checkpoint,
+                        dependency: *dependency,
+                        dependency_checkpoint,
+                    });
+                }
+            }
+
+            if checkpoints[&stage].is_none() {
+                continue
+            }
+            let missing = match stage {
+                StageId::Headers => self
+                    .missing_block_data::<tables::CanonicalHeaders>(stage, checkpoint)?
+                    .or(self.missing_block_data::<tables::Headers>(stage, checkpoint)?),
+                StageId::TotalDifficulty => {
+                    self.missing_block_data::<tables::HeaderTD>(stage, checkpoint)?
+                }
+                StageId::Bodies => {
+                    self.missing_block_data::<tables::BlockBodyIndices>(stage, checkpoint)?
+                }
+                _ => None,
+            };
+            violations.extend(missing);
+        }
+
+        Ok(ConsistencyReport { violations })
+    }
+
+    /// Returns a violation if the table has no entry for the given block number.
+    fn missing_block_data<T: Table<Key = BlockNumber>>(
+        &self,
+        stage: StageId,
+        checkpoint: BlockNumber,
+    ) -> Result<Option<ConsistencyViolation>> {
+        let mut cursor = self.tx_ref().cursor_read::<T>()?;
+        let last_available = match cursor.seek(checkpoint)? {
+            Some((number, _)) if number == checkpoint => return Ok(None),
+            Some(_) => cursor.prev()?,
+            None => cursor.last()?,
+        }
+        .map(|(number, _)| number);
+
+        Ok(Some(ConsistencyViolation::MissingData {
+            stage,
+            checkpoint,
+            table: T::NAME,
+            last_available,
+        }))
+    }
+}
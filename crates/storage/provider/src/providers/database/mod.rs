@@ -1,24 +1,31 @@
 use crate::{
     providers::state::{historical::HistoricalStateProvider, latest::LatestStateProvider},
-    traits::{BlockSource, ReceiptProvider},
+    traits::{block::sealed_block_with_senders, BlockRangeIter, BlockSource, ReceiptProvider},
     BlockHashReader, BlockNumReader, BlockReader, ChainSpecProvider, EvmEnvProvider,
-    HeaderProvider, ProviderError, StageCheckpointReader, StateProviderBox, TransactionsProvider,
-    WithdrawalsProvider,
+    HeaderProvider, ProviderError, PruneCheckpointReader, StageCheckpointReader, StateProviderBox,
+    TransactionsProvider, WithdrawalsProvider,
 };
 use reth_db::{database::Database, init_db, models::StoredBlockBodyIndices, DatabaseEnv};
 use reth_interfaces::Result;
 use reth_primitives::{
     stage::{StageCheckpoint, StageId},
-    Address, Block, BlockHash, BlockHashOrNumber, BlockNumber, BlockWithSenders, ChainInfo,
-    ChainSpec, Header, Receipt, SealedBlock, SealedHeader, TransactionMeta, TransactionSigned,
-    TransactionSignedNoHash, TxHash, TxNumber, Withdrawal, H256, U256,
+    Address, Block, BlockBody, BlockHash, BlockHashOrNumber, BlockNumber, BlockWithSenders,
+    ChainInfo, ChainSpec, Header, PruneCheckpoint, PruneSegment, Receipt, ReceiptWithMeta,
+    SealedBlock, SealedHeader, TransactionMeta, TransactionSigned, TransactionSignedNoHash, TxHash,
+    TxNumber, Withdrawal, H256, U256,
 };
 use reth_revm_primitives::primitives::{BlockEnv, CfgEnv};
-use std::{ops::RangeBounds, sync::Arc};
+use std::{
+    ops::{RangeBounds, RangeInclusive},
+    sync::Arc,
+};
 use tracing::trace;
 
 mod provider;
 pub use provider::{DatabaseProvider, DatabaseProviderRO, DatabaseProviderRW};
+
+mod consistency;
+pub use consistency::{ConsistencyReport, ConsistencyViolation};
 use reth_interfaces::db::LogLevel;
 
 /// A common provider that fetches data from a database.
@@ -98,6 +105,8 @@ impl<DB: Database> ProviderFactory<DB> {
             return Ok(Box::new(LatestStateProvider::new(provider.into_tx())))
         }
 
+        Self::ensure_history_available(&provider, block_number)?;
+
         // +1 as the changeset that we want is the one that was applied after this block.
         block_number += 1;
 
@@ -113,12 +122,23 @@ impl<DB: Database> ProviderFactory<DB> {
             .block_number(block_hash)?
             .ok_or(ProviderError::BlockHashNotFound(block_hash))?;
 
+        // the hash is known, but a different block is canonical at this height
+        if provider.block_hash(block_number)? != Some(block_hash) {
+            return Err(ProviderError::BlockHashNotCanonical {
+                hash: block_hash,
+                number: Some(block_number),
+            }
+            .into())
+        }
+
         if block_number == provider.best_block_number().unwrap_or_default() &&
             block_number == provider.last_block_number().unwrap_or_default()
         {
             return Ok(Box::new(LatestStateProvider::new(provider.into_tx())))
         }
 
+        Self::ensure_history_available(&provider, block_number)?;
+
         // +1 as the changeset that we want is the one that was applied after this block.
         // as the  changeset contains old values.
         block_number += 1;
@@ -126,6 +146,27 @@ impl<DB: Database> ProviderFactory<DB> {
         trace!(target: "providers::db", ?block_hash, "Returning historical state provider for block hash");
         Ok(Box::new(HistoricalStateProvider::new(provider.into_tx(), block_number)))
     }
+
+    /// Returns an error if the account or storage history required to serve the state at the given
+    /// block has already been pruned.
+    fn ensure_history_available(
+        provider: &DatabaseProviderRO<'_, DB>,
+        block_number: BlockNumber,
+    ) -> Result<()> {
+        for segment in [PruneSegment::AccountHistory, PruneSegment::StorageHistory] {
+            // The state at a block is reverted with the changesets of the blocks above it, so it's
+            // available as long as the changesets of the next block are.
+            let earliest_available = provider.earliest_available_block(segment)?;
+            if block_number + 1 < earliest_available {
+                return Err(ProviderError::StateHistoryPruned {
+                    block_number,
+                    lowest_available: earliest_available - 1,
+                }
+                .into())
+            }
+        }
+        Ok(())
+    }
 }
 
 impl<DB: Database> HeaderProvider for ProviderFactory<DB> {
@@ -217,6 +258,19 @@ impl<DB: Database> BlockReader for ProviderFactory<DB> {
     fn block_with_senders(&self, number: BlockNumber) -> Result<Option<BlockWithSenders>> {
         self.provider()?.block_with_senders(number)
     }
+
+    fn block_range_iter(&self, range: RangeInclusive<BlockNumber>) -> Result<BlockRangeIter<'_>> {
+        // the iterator owns the provider, so all blocks are read from the same transaction
+        let provider = self.provider()?;
+        Ok(Box::new(range.map(move |number| sealed_block_with_senders(&provider, number))))
+    }
+
+    fn block_body_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> Result<Vec<Option<BlockBody>>> {
+        self.provider()?.block_body_range(range)
+    }
 }
 
 impl<DB: Database> TransactionsProvider for ProviderFactory<DB> {
@@ -285,6 +339,10 @@ impl<DB: Database> ReceiptProvider for ProviderFactory<DB> {
     fn receipts_by_block(&self, block: BlockHashOrNumber) -> Result<Option<Vec<Receipt>>> {
         self.provider()?.receipts_by_block(block)
     }
+
+    fn receipts_with_meta(&self, block: BlockHashOrNumber) -> Result<Option<Vec<ReceiptWithMeta>>> {
+        self.provider()?.receipts_with_meta(block)
+    }
 }
 
 impl<DB: Database> WithdrawalsProvider for ProviderFactory<DB> {
@@ -311,6 +369,12 @@ impl<DB: Database> StageCheckpointReader for ProviderFactory<DB> {
     }
 }
 
+impl<DB: Database> PruneCheckpointReader for ProviderFactory<DB> {
+    fn get_prune_checkpoint(&self, segment: PruneSegment) -> Result<Option<PruneCheckpoint>> {
+        self.provider()?.get_prune_checkpoint(segment)
+    }
+}
+
 impl<DB: Database> EvmEnvProvider for ProviderFactory<DB> {
     fn fill_env_at(
         &self,
@@ -358,13 +422,24 @@ where
 
 #[cfg(test)]
 mod tests {
-    use super::ProviderFactory;
-    use crate::{BlockHashReader, BlockNumReader};
+    use super::{ConsistencyViolation, ProviderFactory};
+    use crate::{
+        test_utils::blocks::BlockChainTestData, BlockHashReader, BlockNumReader, BlockReader,
+        BlockWriter, HeaderProvider, ProviderError, PruneCheckpointReader, PruneCheckpointWriter,
+        ReceiptProvider, StageCheckpointWriter, StorageMode, TransactionsProvider,
+    };
     use reth_db::{
+        table::Table,
+        tables,
         test_utils::{create_test_rw_db, ERROR_TEMPDIR},
+        transaction::{DbTx, DbTxMut},
         DatabaseEnv,
     };
-    use reth_primitives::{ChainSpecBuilder, H256};
+    use reth_primitives::{
+        stage::{StageCheckpoint, StageId},
+        BlockBody, ChainSpecBuilder, Header, PruneCheckpoint, PruneMode, PruneSegment, SealedBlock,
+        H256, U256,
+    };
     use std::sync::Arc;
 
     #[test]
@@ -415,4 +490,365 @@ mod tests {
         provider_rw.block_hash(0).unwrap();
         provider.block_hash(0).unwrap();
     }
+
+    #[test]
+    fn unwind_prune_checkpoints() {
+        let chain_spec = ChainSpecBuilder::mainnet().build();
+        let db = create_test_rw_db();
+        let factory = ProviderFactory::new(db, Arc::new(chain_spec));
+        let provider_rw = factory.provider_rw().unwrap();
+
+        let receipts =
+            PruneCheckpoint { block_number: 10, tx_number: None, prune_mode: PruneMode::Full };
+        let account_history =
+            PruneCheckpoint { block_number: 3, tx_number: None, prune_mode: PruneMode::Before(4) };
+        provider_rw.save_prune_checkpoint(PruneSegment::Receipts, receipts).unwrap();
+        provider_rw.save_prune_checkpoint(PruneSegment::AccountHistory, account_history).unwrap();
+
+        provider_rw.unwind_prune_checkpoints(5).unwrap();
+
+        assert_eq!(
+            provider_rw.get_prune_checkpoint(PruneSegment::Receipts).unwrap(),
+            Some(PruneCheckpoint { block_number: 5, ..receipts })
+        );
+        assert_eq!(
+            provider_rw.get_prune_checkpoint(PruneSegment::AccountHistory).unwrap(),
+            Some(account_history)
+        );
+        assert_eq!(provider_rw.get_prune_checkpoint(PruneSegment::StorageHistory).unwrap(), None);
+    }
+
+    #[test]
+    fn block_range_iter() {
+        let chain_spec = ChainSpecBuilder::mainnet().shanghai_activated().build();
+        let db = create_test_rw_db();
+        let factory = ProviderFactory::new(db, Arc::new(chain_spec));
+
+        let data = BlockChainTestData::default();
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw.insert_block(data.genesis.clone(), None).unwrap();
+        for (block, post_state) in data.blocks.clone() {
+            provider_rw.append_blocks_with_post_state(vec![block], post_state).unwrap();
+        }
+        provider_rw.commit().unwrap();
+
+        let blocks = factory.block_range_iter(1..=2).unwrap().collect::<Result<Vec<_>, _>>();
+        assert_eq!(
+            blocks.unwrap(),
+            data.blocks.into_iter().map(|(block, _)| block).collect::<Vec<_>>()
+        );
+
+        // blocks beyond the tip are reported as missing
+        let mut iter = factory.block_range_iter(2..=3).unwrap();
+        assert!(iter.next().unwrap().is_ok());
+        assert!(iter.next().unwrap().is_err());
+        assert!(iter.next().is_none());
+    }
+
+    #[test]
+    fn block_body_range() {
+        let chain_spec = ChainSpecBuilder::mainnet().shanghai_activated().build();
+        let db = create_test_rw_db();
+        let factory = ProviderFactory::new(db, Arc::new(chain_spec));
+
+        let data = BlockChainTestData::default();
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw.insert_block(data.genesis.clone(), None).unwrap();
+        for (block, post_state) in data.blocks.clone() {
+            provider_rw.append_blocks_with_post_state(vec![block], post_state).unwrap();
+        }
+        provider_rw.commit().unwrap();
+
+        let expected = (1..=3)
+            .map(|number| factory.block(number.into()).unwrap())
+            .map(|block| {
+                block.map(|block| BlockBody {
+                    transactions: block.body,
+                    ommers: block.ommers,
+                    withdrawals: block.withdrawals,
+                })
+            })
+            .collect::<Vec<_>>();
+        assert!(expected[0].is_some());
+        assert!(expected[2].is_none());
+        assert_eq!(factory.block_body_range(1..=3).unwrap(), expected);
+        assert_eq!(factory.block_body_range(4..=5).unwrap(), vec![None, None]);
+    }
+
+    #[test]
+    fn receipts_with_meta() {
+        let chain_spec = ChainSpecBuilder::mainnet().shanghai_activated().build();
+        let db = create_test_rw_db();
+        let factory = ProviderFactory::new(db, Arc::new(chain_spec));
+
+        let data = BlockChainTestData::default();
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw.insert_block(data.genesis.clone(), None).unwrap();
+        for (block, post_state) in data.blocks.clone() {
+            provider_rw.append_blocks_with_post_state(vec![block], post_state).unwrap();
+        }
+        provider_rw.commit().unwrap();
+
+        assert_eq!(factory.receipts_with_meta(0.into()).unwrap(), Some(vec![]));
+        assert_eq!(factory.receipts_with_meta(3.into()).unwrap(), None);
+
+        let (block, _) = &data.blocks[1];
+        let receipts = factory.receipts_with_meta(block.hash().into()).unwrap().unwrap();
+        assert_eq!(receipts.len(), 1);
+        assert_eq!(receipts[0].meta.tx_hash, block.body[0].hash());
+        assert_eq!(receipts[0].meta.index, 0);
+        assert_eq!(receipts[0].meta.block_hash, block.hash());
+        assert_eq!(receipts[0].meta.block_number, 2);
+        assert_eq!(receipts[0].first_log_index, 0);
+        assert_eq!(
+            Some(receipts[0].receipt.clone().into_receipt()),
+            factory.receipt_by_hash(block.body[0].hash()).unwrap()
+        );
+    }
+
+    #[test]
+    fn insert_blocks() {
+        let chain_spec = ChainSpecBuilder::mainnet().shanghai_activated().build();
+        let db = create_test_rw_db();
+        let factory = ProviderFactory::new(db, Arc::new(chain_spec));
+
+        let data = BlockChainTestData::default();
+        let blocks = data.blocks.into_iter().map(|(block, _)| block).collect::<Vec<_>>();
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw.insert_block(data.genesis, None).unwrap();
+
+        assert_eq!(
+            provider_rw.insert_blocks(&[], StorageMode::Append).unwrap(),
+            StageCheckpoint::new(0)
+        );
+        assert_eq!(
+            provider_rw.insert_blocks(&blocks, StorageMode::Append).unwrap(),
+            StageCheckpoint::new(2)
+        );
+
+        // appending requires the blocks to follow the tip
+        assert!(matches!(
+            provider_rw.insert_blocks(&blocks[1..], StorageMode::Append),
+            Err(reth_interfaces::Error::Provider(ProviderError::NonSequentialBlock {
+                expected_parent: 2,
+                got: 2
+            }))
+        ));
+        // upserting allows overwriting existing blocks
+        assert_eq!(
+            provider_rw.insert_blocks(&blocks[1..], StorageMode::Upsert).unwrap(),
+            StageCheckpoint::new(2)
+        );
+        // gaps are rejected in any mode
+        let gapped = [blocks[1].clone(), blocks[1].clone()];
+        assert!(matches!(
+            provider_rw.insert_blocks(&gapped, StorageMode::Upsert),
+            Err(reth_interfaces::Error::Provider(ProviderError::NonSequentialBlock { .. }))
+        ));
+        // replacing a block below the tip would overwrite the transactions of the blocks above it
+        let mut below_tip = blocks[0].clone();
+        below_tip.block.body.clear();
+        below_tip.senders.clear();
+        assert!(matches!(
+            provider_rw.insert_blocks(&[below_tip], StorageMode::Upsert),
+            Err(reth_interfaces::Error::Provider(ProviderError::UpsertBelowTip {
+                tip: 2,
+                last: 1
+            }))
+        ));
+        provider_rw.commit().unwrap();
+
+        let provider = factory.provider().unwrap();
+        for block in &blocks {
+            assert_eq!(provider.block_number(block.hash()).unwrap(), Some(block.number));
+            for (transaction, sender) in block.body.iter().zip(block.senders.iter()) {
+                let tx_id = provider.transaction_id(transaction.hash()).unwrap().unwrap();
+                assert_eq!(provider.transaction_sender(tx_id).unwrap(), Some(*sender));
+            }
+        }
+        let stored = factory.block_range_iter(1..=2).unwrap().collect::<Result<Vec<_>, _>>();
+        assert_eq!(stored.unwrap(), blocks);
+
+        // replacing a block removes the entries of the old one
+        let old = blocks[1].clone();
+        let mut new = old.clone();
+        let mut header = new.block.header.clone().unseal();
+        header.extra_data = vec![1].into();
+        new.block.header = header.seal_slow();
+        new.block.body.clear();
+        new.block.withdrawals = None;
+        new.senders.clear();
+
+        let provider_rw = factory.provider_rw().unwrap();
+        assert_eq!(
+            provider_rw.insert_blocks(&[new.clone()], StorageMode::Upsert).unwrap(),
+            StageCheckpoint::new(2)
+        );
+        provider_rw.commit().unwrap();
+
+        let provider = factory.provider().unwrap();
+        assert_eq!(provider.block_number(old.hash()).unwrap(), None);
+        assert_eq!(provider.block_number(new.hash()).unwrap(), Some(2));
+        for transaction in &old.body {
+            assert_eq!(provider.transaction_id(transaction.hash()).unwrap(), None);
+        }
+        assert_eq!(provider.tx_ref().entries::<tables::Transactions>().unwrap(), 1);
+        assert_eq!(provider.tx_ref().entries::<tables::BlockWithdrawals>().unwrap(), 1);
+
+        // the senders must match the transactions
+        let mut invalid = old.clone();
+        invalid.senders.clear();
+        let provider_rw = factory.provider_rw().unwrap();
+        assert!(matches!(
+            provider_rw.insert_blocks(&[invalid], StorageMode::Upsert),
+            Err(reth_interfaces::Error::Provider(ProviderError::BlockSendersMismatch {
+                block_number: 2,
+                transactions: 1,
+                senders: 0,
+            }))
+        ));
+    }
+
+    #[test]
+    fn check_consistency() {
+        let chain_spec = ChainSpecBuilder::mainnet().build();
+        let db = create_test_rw_db();
+        let factory = ProviderFactory::new(db, Arc::new(chain_spec));
+        let provider_rw = factory.provider_rw().unwrap();
+        assert!(provider_rw.check_consistency().unwrap().is_consistent());
+
+        provider_rw.insert_block(BlockChainTestData::default().genesis, None).unwrap();
+        for stage in StageId::ALL {
+            provider_rw.save_stage_checkpoint(stage, StageCheckpoint::new(0)).unwrap();
+        }
+        assert!(provider_rw.check_consistency().unwrap().is_consistent());
+
+        provider_rw.save_stage_checkpoint(StageId::Execution, StageCheckpoint::new(5)).unwrap();
+        let report = provider_rw.check_consistency().unwrap();
+        assert_eq!(
+            report.violations,
+            vec![
+                ConsistencyViolation::StageAhead {
+                    stage: StageId::Execution,
+                    checkpoint: 5,
+                    dependency: StageId::TotalDifficulty,
+                    dependency_checkpoint: 0,
+                },
+                ConsistencyViolation::StageAhead {
+                    stage: StageId::Execution,
+                    checkpoint: 5,
+                    dependency: StageId::SenderRecovery,
+                    dependency_checkpoint: 0,
+                },
+            ]
+        );
+        assert_eq!(report.unwind_target(), Some(0));
+
+        provider_rw.save_stage_checkpoint(StageId::Execution, StageCheckpoint::new(0)).unwrap();
+        provider_rw.save_stage_checkpoint(StageId::Headers, StageCheckpoint::new(3)).unwrap();
+        let report = provider_rw.check_consistency().unwrap();
+        assert_eq!(
+            report.violations,
+            vec![ConsistencyViolation::MissingData {
+                stage: StageId::Headers,
+                checkpoint: 3,
+                table: tables::CanonicalHeaders::NAME,
+                last_available: Some(0),
+            }]
+        );
+        assert_eq!(report.unwind_target(), Some(0));
+    }
+
+    #[test]
+    fn total_difficulty() {
+        let chain_spec = ChainSpecBuilder::mainnet().build();
+        let db = create_test_rw_db();
+        let factory = ProviderFactory::new(db, Arc::new(chain_spec));
+
+        let pow =
+            Header { number: 0, difficulty: U256::from(10), ..Default::default() }.seal_slow();
+        let pos = Header { number: 1, parent_hash: pow.hash(), ..Default::default() }.seal_slow();
+        let provider_rw = factory.provider_rw().unwrap();
+        for header in [pow.clone(), pos.clone()] {
+            provider_rw.insert_block(SealedBlock { header, ..Default::default() }, None).unwrap();
+        }
+        provider_rw.commit().unwrap();
+
+        let provider = factory.provider().unwrap();
+        assert_eq!(provider.total_difficulty_by_number(0).unwrap(), Some(U256::from(10)));
+        assert_eq!(provider.total_difficulty_by_hash(&pow.hash()).unwrap(), Some(U256::from(10)));
+        // the total difficulty is still stored for post-merge blocks, but not exposed
+        assert_eq!(provider.header_td_by_number(1).unwrap(), Some(U256::from(10)));
+        assert_eq!(provider.total_difficulty_by_number(1).unwrap(), None);
+        assert_eq!(provider.total_difficulty_by_hash(&pos.hash()).unwrap(), None);
+        assert_eq!(provider.total_difficulty_by_number(2).unwrap(), None);
+    }
+
+    #[test]
+    fn earliest_available_block() {
+        let chain_spec = ChainSpecBuilder::mainnet().build();
+        let db = create_test_rw_db();
+        let factory = ProviderFactory::new(db, Arc::new(chain_spec));
+        let provider_rw = factory.provider_rw().unwrap();
+
+        assert_eq!(provider_rw.earliest_available_block(PruneSegment::Receipts).unwrap(), 0);
+
+        provider_rw
+            .save_prune_checkpoint(
+                PruneSegment::Receipts,
+                PruneCheckpoint { block_number: 10, tx_number: None, prune_mode: PruneMode::Full },
+            )
+            .unwrap();
+        provider_rw
+            .save_prune_checkpoint(
+                PruneSegment::AccountHistory,
+                PruneCheckpoint {
+                    block_number: 3,
+                    tx_number: None,
+                    prune_mode: PruneMode::Before(4),
+                },
+            )
+            .unwrap();
+
+        assert_eq!(provider_rw.earliest_available_block(PruneSegment::Receipts).unwrap(), 11);
+        assert_eq!(provider_rw.earliest_available_block(PruneSegment::AccountHistory).unwrap(), 4);
+        assert_eq!(provider_rw.earliest_available_block(PruneSegment::StorageHistory).unwrap(), 0);
+    }
+
+    #[test]
+    fn history_by_block_unavailable() {
+        let chain_spec = ChainSpecBuilder::mainnet().build();
+        let db = create_test_rw_db();
+        let factory = ProviderFactory::new(db, Arc::new(chain_spec));
+
+        let canonical = H256::random();
+        let fork = H256::random();
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw.tx_ref().put::<tables::CanonicalHeaders>(1, canonical).unwrap();
+        provider_rw.tx_ref().put::<tables::HeaderNumbers>(canonical, 1).unwrap();
+        provider_rw.tx_ref().put::<tables::HeaderNumbers>(fork, 1).unwrap();
+        provider_rw
+            .save_prune_checkpoint(
+                PruneSegment::AccountHistory,
+                PruneCheckpoint { block_number: 5, tx_number: None, prune_mode: PruneMode::Full },
+            )
+            .unwrap();
+        provider_rw.commit().unwrap();
+
+        assert_eq!(
+            factory.history_by_block_hash(fork).err(),
+            Some(ProviderError::BlockHashNotCanonical { hash: fork, number: Some(1) }.into())
+        );
+        assert_eq!(
+            factory.history_by_block_hash(canonical).err(),
+            Some(ProviderError::StateHistoryPruned { block_number: 1, lowest_available: 5 }.into())
+        );
+        // the changesets of the checkpoint block are pruned, but the state at the checkpoint block
+        // only requires the changesets above it
+        assert!(factory.history_by_block_number(5).is_ok());
+        assert_eq!(
+            factory.history_by_block_number(4).err(),
+            Some(ProviderError::StateHistoryPruned { block_number: 4, lowest_available: 5 }.into())
+        );
+    }
 }
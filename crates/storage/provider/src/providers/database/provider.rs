@@ -1,9 +1,11 @@
 use crate::{
     post_state::StorageChangeset,
-    traits::{AccountExtReader, BlockSource, ReceiptProvider, StageCheckpointWriter},
+    providers::state::latest::plain_accounts,
+    traits::{AccountExtReader, BlockSource, ReceiptProvider, StageCheckpointWriter, StorageMode},
     AccountReader, BlockExecutionWriter, BlockHashReader, BlockNumReader, BlockReader, BlockWriter,
     EvmEnvProvider, HashingWriter, HeaderProvider, HistoryWriter, PostState, ProviderError,
-    StageCheckpointReader, StorageReader, TransactionsProvider, WithdrawalsProvider,
+    PruneCheckpointReader, PruneCheckpointWriter, StageCheckpointReader, StorageReader,
+    TransactionsProvider, WithdrawalsProvider,
 };
 use itertools::{izip, Itertools};
 use reth_db::{
@@ -23,10 +25,11 @@ use reth_interfaces::Result;
 use reth_primitives::{
     keccak256,
     stage::{StageCheckpoint, StageId},
-    Account, Address, Block, BlockHash, BlockHashOrNumber, BlockNumber, BlockWithSenders,
-    ChainInfo, ChainSpec, Hardfork, Head, Header, Receipt, SealedBlock, SealedBlockWithSenders,
-    SealedHeader, StorageEntry, TransactionMeta, TransactionSigned, TransactionSignedEcRecovered,
-    TransactionSignedNoHash, TxHash, TxNumber, Withdrawal, H256, U256,
+    Account, Address, Block, BlockBody, BlockHash, BlockHashOrNumber, BlockNumber,
+    BlockWithSenders, ChainInfo, ChainSpec, Hardfork, Head, Header, PruneCheckpoint, PruneSegment,
+    Receipt, ReceiptWithMeta, SealedBlock, SealedBlockWithSenders, SealedHeader, StorageEntry,
+    TransactionMeta, TransactionSigned, TransactionSignedEcRecovered, TransactionSignedNoHash,
+    TxHash, TxNumber, Withdrawal, H256, U256,
 };
 use reth_revm_primitives::{
     config::revm_spec,
@@ -388,10 +391,7 @@ impl<'this, TX: DbTxMut<'this> + DbTx<'this>> DatabaseProvider<'this, TX> {
             }
             Ok(items)
         } else {
-            self.tx
-                .cursor_read::<T>()?
-                .walk_range(range)?
-                .collect::<std::result::Result<Vec<_>, _>>()
+            self.tx.walk_range::<T>(range)?.collect::<std::result::Result<Vec<_>, _>>()
         }
     }
 
@@ -680,6 +680,10 @@ impl<'this, TX: DbTx<'this>> AccountReader for DatabaseProvider<'this, TX> {
     fn basic_account(&self, address: Address) -> Result<Option<Account>> {
         Ok(self.tx.get::<tables::PlainAccountState>(address)?)
     }
+
+    fn basic_accounts(&self, addresses: &[Address]) -> Result<Vec<(Address, Option<Account>)>> {
+        plain_accounts(&self.tx, addresses)
+    }
 }
 
 impl<'this, TX: DbTx<'this>> AccountExtReader for DatabaseProvider<'this, TX> {
@@ -688,25 +692,13 @@ impl<'this, TX: DbTx<'this>> AccountExtReader for DatabaseProvider<'this, TX> {
         range: impl RangeBounds<BlockNumber>,
     ) -> Result<BTreeSet<Address>> {
         self.tx
-            .cursor_read::<tables::AccountChangeSet>()?
-            .walk_range(range)?
+            .walk_range::<tables::AccountChangeSet>(range)?
             .map(|entry| {
                 entry.map(|(_, account_before)| account_before.address).map_err(Into::into)
             })
             .collect()
     }
 
-    fn basic_accounts(
-        &self,
-        iter: impl IntoIterator<Item = Address>,
-    ) -> Result<Vec<(Address, Option<Account>)>> {
-        let mut plain_accounts = self.tx.cursor_read::<tables::PlainAccountState>()?;
-        Ok(iter
-            .into_iter()
-            .map(|address| plain_accounts.seek_exact(address).map(|a| (address, a.map(|(_, v)| v))))
-            .collect::<std::result::Result<Vec<_>, _>>()?)
-    }
-
     fn changed_accounts_and_blocks_with_range(
         &self,
         range: RangeInclusive<BlockNumber>,
@@ -882,6 +874,55 @@ impl<'this, TX: DbTx<'this>> BlockReader for DatabaseProvider<'this, TX> {
         Ok(self.tx.get::<tables::BlockBodyIndices>(num)?)
     }
 
+    /// Walks the body indices of the range with a single cursor, so that headers are only read for
+    /// blocks without stored withdrawals.
+    fn block_body_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> Result<Vec<Option<BlockBody>>> {
+        if range.is_empty() {
+            return Ok(Vec::new())
+        }
+        let start = *range.start();
+        let len = (range.end() - start).saturating_add(1) as usize;
+        let mut bodies = Vec::with_capacity(len);
+
+        let mut body_cursor = self.tx.cursor_read::<tables::BlockBodyIndices>()?;
+        let mut tx_cursor = self.tx.cursor_read::<tables::Transactions>()?;
+        let mut withdrawals_cursor = self.tx.cursor_read::<tables::BlockWithdrawals>()?;
+        for entry in body_cursor.walk_range(range)? {
+            let (number, indices) = entry?;
+            // blocks without body indices are missing
+            bodies.resize_with((number - start) as usize, || None);
+
+            let tx_range = indices.tx_num_range();
+            let transactions = if tx_range.is_empty() {
+                Vec::new()
+            } else {
+                tx_cursor
+                    .walk_range(tx_range)?
+                    .map(|result| result.map(|(_, tx)| tx.into()))
+                    .collect::<std::result::Result<Vec<_>, _>>()?
+            };
+            let ommers = self.ommers(number.into())?.unwrap_or_default();
+            let withdrawals = match withdrawals_cursor.seek_exact(number)? {
+                Some((_, stored)) => Some(stored.withdrawals),
+                // past shanghai, all blocks have a withdrawal list, even if empty
+                None => self
+                    .header_by_number(number)?
+                    .filter(|header| {
+                        self.chain_spec.is_shanghai_activated_at_timestamp(header.timestamp)
+                    })
+                    .map(|_| Vec::new()),
+            };
+
+            bodies.push(Some(BlockBody { transactions, ommers, withdrawals }));
+        }
+        bodies.resize_with(len, || None);
+
+        Ok(bodies)
+    }
+
     /// Returns the block with senders with matching number from database.
     ///
     /// **NOTE: The transactions have invalid hashes, since they would need to be calculated on the
@@ -1037,8 +1078,7 @@ impl<'this, TX: DbTx<'this>> TransactionsProvider for DatabaseProvider<'this, TX
     ) -> Result<Vec<TransactionSignedNoHash>> {
         Ok(self
             .tx
-            .cursor_read::<tables::Transactions>()?
-            .walk_range(range)?
+            .walk_range::<tables::Transactions>(range)?
             .map(|entry| entry.map(|tx| tx.1))
             .collect::<std::result::Result<Vec<_>, _>>()?)
     }
@@ -1046,8 +1086,7 @@ impl<'this, TX: DbTx<'this>> TransactionsProvider for DatabaseProvider<'this, TX
     fn senders_by_tx_range(&self, range: impl RangeBounds<TxNumber>) -> Result<Vec<Address>> {
         Ok(self
             .tx
-            .cursor_read::<tables::TxSenders>()?
-            .walk_range(range)?
+            .walk_range::<tables::TxSenders>(range)?
             .map(|entry| entry.map(|sender| sender.1))
             .collect::<std::result::Result<Vec<_>, _>>()?)
     }
@@ -1088,6 +1127,37 @@ impl<'this, TX: DbTx<'this>> ReceiptProvider for DatabaseProvider<'this, TX> {
         }
         Ok(None)
     }
+
+    fn receipts_with_meta(&self, block: BlockHashOrNumber) -> Result<Option<Vec<ReceiptWithMeta>>> {
+        let Some(number) = self.convert_hash_or_number(block)? else { return Ok(None) };
+        let (Some(header), Some(body)) =
+            (self.sealed_header(number)?, self.block_body_indices(number)?)
+        else {
+            return Ok(None)
+        };
+
+        let receipts = self
+            .tx
+            .cursor_read::<tables::Receipts>()?
+            .walk_range(body.tx_num_range())?
+            .map(|result| result.map(|(_, receipt)| receipt))
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        if receipts.len() as u64 != body.tx_count {
+            return Ok(None)
+        }
+        let tx_hashes = self
+            .tx
+            .cursor_read::<tables::Transactions>()?
+            .walk_range(body.tx_num_range())?
+            .map(|result| result.map(|(_, tx)| tx.hash()))
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        Ok(Some(ReceiptWithMeta::from_block_receipts(
+            header.num_hash(),
+            header.base_fee_per_gas,
+            tx_hashes.into_iter().zip(receipts),
+        )))
+    }
 }
 
 impl<'this, TX: DbTx<'this>> WithdrawalsProvider for DatabaseProvider<'this, TX> {
@@ -1228,6 +1298,48 @@ impl<'this, TX: DbTxMut<'this>> StageCheckpointWriter for DatabaseProvider<'this
     }
 }
 
+impl<'this, TX: DbTx<'this>> PruneCheckpointReader for DatabaseProvider<'this, TX> {
+    fn get_prune_checkpoint(&self, segment: PruneSegment) -> Result<Option<PruneCheckpoint>> {
+        Ok(self.tx.get::<tables::PruneCheckpoints>(segment)?)
+    }
+}
+
+impl<'this, TX: DbTxMut<'this> + DbTx<'this>> PruneCheckpointWriter
+    for DatabaseProvider<'this, TX>
+{
+    fn save_prune_checkpoint(
+        &self,
+        segment: PruneSegment,
+        checkpoint: PruneCheckpoint,
+    ) -> Result<()> {
+        Ok(self.tx.put::<tables::PruneCheckpoints>(segment, checkpoint)?)
+    }
+
+    fn unwind_prune_checkpoints(&self, block_number: BlockNumber) -> Result<()> {
+        let mut cursor = self.tx.cursor_write::<tables::PruneCheckpoints>()?;
+        while let Some((segment, checkpoint)) = cursor.next()? {
+            if checkpoint.block_number <= block_number {
+                continue
+            }
+
+            // Transaction-based segments need to be lowered to the last transaction of the block.
+            let tx_number = if checkpoint.tx_number.is_some() {
+                Some(
+                    self.block_body_indices(block_number)?
+                        .ok_or(ProviderError::BlockBodyIndicesNotFound(block_number))?
+                        .last_tx_num(),
+                )
+            } else {
+                None
+            };
+
+            cursor.upsert(segment, PruneCheckpoint { block_number, tx_number, ..checkpoint })?
+        }
+
+        Ok(())
+    }
+}
+
 impl<'this, TX: DbTx<'this>> StorageReader for DatabaseProvider<'this, TX> {
     fn plainstate_storages(
         &self,
@@ -1308,7 +1420,7 @@ impl<'this, TX: DbTxMut<'this> + DbTx<'this>> HashingWriter for DatabaseProvider
         // account hashing stage
         {
             let lists = self.changed_accounts_with_range(range.clone())?;
-            let accounts = self.basic_accounts(lists)?;
+            let accounts = self.basic_accounts(&lists.into_iter().collect::<Vec<_>>())?;
             self.insert_account_for_hashing(accounts)?;
         }
 
@@ -1748,6 +1860,143 @@ impl<'this, TX: DbTxMut<'this> + DbTx<'this>> BlockWriter for DatabaseProvider<'
         Ok(block_indices)
     }
 
+    fn insert_blocks(
+        &self,
+        blocks: &[SealedBlockWithSenders],
+        mode: StorageMode,
+    ) -> Result<StageCheckpoint> {
+        let tip = self.tx.cursor_read::<tables::CanonicalHeaders>()?.last()?.map(|(n, _)| n);
+        let Some(first) = blocks.first() else {
+            return Ok(StageCheckpoint::new(tip.unwrap_or_default()))
+        };
+
+        // Appending is only possible on top of the current tip, while upserting requires the range
+        // to reach the tip. Transaction numbers continue from the parent of the first block, so
+        // upserting below the tip would overwrite the transactions of the blocks above the range.
+        if let Some(tip) = tip {
+            match mode {
+                StorageMode::Append if first.number != tip + 1 => {
+                    return Err(ProviderError::NonSequentialBlock {
+                        expected_parent: tip,
+                        got: first.number,
+                    }
+                    .into())
+                }
+                StorageMode::Upsert => {
+                    let last = blocks.last().expect("not empty").number;
+                    if last < tip {
+                        return Err(ProviderError::UpsertBelowTip { tip, last }.into())
+                    }
+                }
+                _ => {}
+            }
+        }
+        for (parent, block) in blocks.iter().tuple_windows() {
+            if block.number != parent.number + 1 {
+                return Err(ProviderError::NonSequentialBlock {
+                    expected_parent: parent.number,
+                    got: block.number,
+                }
+                .into())
+            }
+        }
+        for block in blocks {
+            if block.body.len() != block.senders.len() {
+                return Err(ProviderError::BlockSendersMismatch {
+                    block_number: block.number,
+                    transactions: block.body.len(),
+                    senders: block.senders.len(),
+                }
+                .into())
+            }
+        }
+
+        let (mut td, mut next_tx_num) = if first.number == 0 {
+            (U256::ZERO, 0)
+        } else {
+            let parent = first.number - 1;
+            let td = self
+                .header_td_by_number(parent)?
+                .ok_or(ProviderError::TotalDifficultyNotFound { number: parent })?;
+            let next_tx_num = self
+                .block_body_indices(parent)?
+                .ok_or(ProviderError::BlockBodyIndicesNotFound(parent))?
+                .next_tx_num();
+            (td, next_tx_num)
+        };
+
+        // Replaced blocks leave entries behind that are not overwritten by the new blocks.
+        if mode == StorageMode::Upsert {
+            for block in blocks {
+                remove_block_entries(&self.tx, block.number)?;
+            }
+        }
+
+        let mut canonical_headers = self.tx.cursor_write::<tables::CanonicalHeaders>()?;
+        let mut headers = self.tx.cursor_write::<tables::Headers>()?;
+        let mut header_tds = self.tx.cursor_write::<tables::HeaderTD>()?;
+        let mut ommers = self.tx.cursor_write::<tables::BlockOmmers>()?;
+        let mut withdrawals = self.tx.cursor_write::<tables::BlockWithdrawals>()?;
+        let mut body_indices = self.tx.cursor_write::<tables::BlockBodyIndices>()?;
+        let mut transaction_blocks = self.tx.cursor_write::<tables::TransactionBlock>()?;
+        let mut transactions = self.tx.cursor_write::<tables::Transactions>()?;
+        let mut senders = self.tx.cursor_write::<tables::TxSenders>()?;
+
+        // Hash keyed entries can't be appended, collect them to insert them in sorted order.
+        let mut header_numbers = Vec::with_capacity(blocks.len());
+        let mut tx_hash_numbers = Vec::new();
+
+        for block in blocks {
+            let number = block.number;
+            td += block.difficulty;
+
+            write_with_mode(&mut canonical_headers, mode, number, block.hash())?;
+            write_with_mode(&mut headers, mode, number, block.header.as_ref().clone())?;
+            write_with_mode(&mut header_tds, mode, number, td.into())?;
+            header_numbers.push((block.hash(), number));
+
+            if !block.ommers.is_empty() {
+                let stored = StoredBlockOmmers { ommers: block.ommers.clone() };
+                write_with_mode(&mut ommers, mode, number, stored)?;
+            }
+
+            if let Some(block_withdrawals) = block.withdrawals.as_ref().filter(|w| !w.is_empty()) {
+                let stored = StoredBlockWithdrawals { withdrawals: block_withdrawals.clone() };
+                write_with_mode(&mut withdrawals, mode, number, stored)?;
+            }
+
+            let indices = StoredBlockBodyIndices {
+                first_tx_num: next_tx_num,
+                tx_count: block.body.len() as u64,
+            };
+            for (transaction, sender) in block.body.iter().zip(block.senders.iter()) {
+                write_with_mode(&mut senders, mode, next_tx_num, *sender)?;
+                write_with_mode(&mut transactions, mode, next_tx_num, transaction.clone().into())?;
+                tx_hash_numbers.push((transaction.hash(), next_tx_num));
+                next_tx_num += 1;
+            }
+
+            if !indices.is_empty() {
+                write_with_mode(&mut transaction_blocks, mode, indices.last_tx_num(), number)?;
+            }
+            write_with_mode(&mut body_indices, mode, number, indices)?;
+        }
+
+        let mut header_numbers_cursor = self.tx.cursor_write::<tables::HeaderNumbers>()?;
+        header_numbers.sort_unstable_by_key(|(hash, _)| *hash);
+        for (hash, number) in header_numbers {
+            header_numbers_cursor.upsert(hash, number)?;
+        }
+
+        let mut tx_hash_cursor = self.tx.cursor_write::<tables::TxHashNumber>()?;
+        tx_hash_numbers.sort_unstable_by_key(|(hash, _)| *hash);
+        for (hash, tx_num) in tx_hash_numbers {
+            tx_hash_cursor.upsert(hash, tx_num)?;
+        }
+
+        Ok(StageCheckpoint::new(blocks.last().expect("not empty").number))
+    }
+
     fn append_blocks_with_post_state(
         &self,
         blocks: Vec<SealedBlockWithSenders>,
@@ -1786,3 +2035,43 @@ impl<'this, TX: DbTxMut<'this> + DbTx<'this>> BlockWriter for DatabaseProvider<'
         Ok(())
     }
 }
+
+/// Removes the entries of the block at the given number that are not overwritten when a block
+/// with a different hash or fewer transactions is inserted at the same number: the hash lookups,
+/// the transactions and senders, and the ommers and withdrawals.
+fn remove_block_entries<'tx, TX: DbTx<'tx> + DbTxMut<'tx>>(
+    tx: &TX,
+    number: BlockNumber,
+) -> std::result::Result<(), DatabaseError> {
+    if let Some(hash) = tx.get::<tables::CanonicalHeaders>(number)? {
+        tx.delete::<tables::HeaderNumbers>(hash, None)?;
+    }
+    if let Some(indices) = tx.get::<tables::BlockBodyIndices>(number)? {
+        for tx_num in indices.tx_num_range() {
+            if let Some(transaction) = tx.get::<tables::Transactions>(tx_num)? {
+                tx.delete::<tables::TxHashNumber>(transaction.hash(), None)?;
+            }
+            tx.delete::<tables::Transactions>(tx_num, None)?;
+            tx.delete::<tables::TxSenders>(tx_num, None)?;
+        }
+        if !indices.is_empty() {
+            tx.delete::<tables::TransactionBlock>(indices.last_tx_num(), None)?;
+        }
+    }
+    tx.delete::<tables::BlockOmmers>(number, None)?;
+    tx.delete::<tables::BlockWithdrawals>(number, None)?;
+    Ok(())
+}
+
+/// Writes an entry with the given cursor as specified by the [StorageMode].
+fn write_with_mode<'tx, T: Table>(
+    cursor: &mut impl DbCursorRW<'tx, T>,
+    mode: StorageMode,
+    key: T::Key,
+    value: T::Value,
+) -> std::result::Result<(), DatabaseError> {
+    match mode {
+        StorageMode::Append => cursor.append(key, value),
+        StorageMode::Upsert => cursor.upsert(key, value),
+    }
+}
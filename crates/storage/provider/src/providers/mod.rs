@@ -1,10 +1,10 @@
 use crate::{
-    BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt,
+    BlockHashReader, BlockIdReader, BlockNumReader, BlockRangeIter, BlockReader, BlockReaderIdExt,
     BlockchainTreePendingStateProvider, CanonChainTracker, CanonStateNotifications,
-    CanonStateSubscriptions, ChainSpecProvider, EvmEnvProvider, HeaderProvider,
-    PostStateDataProvider, ProviderError, ReceiptProvider, ReceiptProviderIdExt,
-    StageCheckpointReader, StateProviderBox, StateProviderFactory, TransactionsProvider,
-    WithdrawalsProvider,
+    CanonStateSubscriptions, ChainSpecProvider, EvmEnvProvider, ForkchoiceSubscriptions,
+    HeaderProvider, PostStateDataProvider, ProviderError, PruneCheckpointReader, ReceiptProvider,
+    ReceiptProviderIdExt, StageCheckpointReader, StateProviderBox, StateProviderFactory,
+    TransactionsProvider, WithdrawalsProvider,
 };
 use reth_db::{database::Database, models::StoredBlockBodyIndices};
 use reth_interfaces::{
@@ -14,10 +14,11 @@ use reth_interfaces::{
 };
 use reth_primitives::{
     stage::{StageCheckpoint, StageId},
-    Address, Block, BlockHash, BlockHashOrNumber, BlockId, BlockNumHash, BlockNumber,
-    BlockNumberOrTag, BlockWithSenders, ChainInfo, ChainSpec, Header, Receipt, SealedBlock,
-    SealedBlockWithSenders, SealedHeader, TransactionMeta, TransactionSigned,
-    TransactionSignedNoHash, TxHash, TxNumber, Withdrawal, H256, U256,
+    Address, Block, BlockBody, BlockHash, BlockHashOrNumber, BlockId, BlockNumHash, BlockNumber,
+    BlockNumberOrTag, BlockWithSenders, ChainInfo, ChainSpec, Header, PruneCheckpoint,
+    PruneSegment, Receipt, ReceiptWithMeta, SealedBlock, SealedBlockWithSenders, SealedHeader,
+    TransactionMeta, TransactionSigned, TransactionSignedNoHash, TxHash, TxNumber, Withdrawal,
+    H256, U256,
 };
 use reth_revm_primitives::primitives::{BlockEnv, CfgEnv};
 pub use state::{
@@ -26,17 +27,23 @@ pub use state::{
 };
 use std::{
     collections::{BTreeMap, HashSet},
-    ops::RangeBounds,
+    ops::{RangeBounds, RangeInclusive},
     sync::Arc,
     time::Instant,
 };
+use tokio::sync::watch;
 use tracing::trace;
 
+mod block_number_cache;
 mod chain_info;
 mod database;
 mod post_state_provider;
 mod state;
-use crate::{providers::chain_info::ChainInfoTracker, traits::BlockSource};
+use crate::{
+    providers::{block_number_cache::BlockNumberCache, chain_info::ChainInfoTracker},
+    traits::BlockSource,
+};
+pub use block_number_cache::DEFAULT_BLOCK_NUMBER_CACHE_LEN;
 pub use database::*;
 pub use post_state_provider::PostStateProvider;
 use reth_interfaces::blockchain_tree::{
@@ -56,13 +63,31 @@ pub struct BlockchainProvider<DB, Tree> {
     tree: Tree,
     /// Tracks the chain info wrt forkchoice updates
     chain_info: ChainInfoTracker,
+    /// Optional cache of block hash to number lookups
+    block_numbers: Option<BlockNumberCache>,
 }
 
 impl<DB, Tree> BlockchainProvider<DB, Tree> {
     /// Create new  provider instance that wraps the database and the blockchain tree, using the
     /// provided latest header to initialize the chain info tracker.
     pub fn with_latest(database: ProviderFactory<DB>, tree: Tree, latest: SealedHeader) -> Self {
-        Self { database, tree, chain_info: ChainInfoTracker::new(latest) }
+        Self { database, tree, chain_info: ChainInfoTracker::new(latest), block_numbers: None }
+    }
+}
+
+impl<DB, Tree> BlockchainProvider<DB, Tree>
+where
+    Tree: CanonStateSubscriptions,
+{
+    /// Caches up to `max_len` block hash to number lookups, including hashes that are not known.
+    ///
+    /// This saves a database transaction for repeated lookups of the same hash, e.g. by RPC
+    /// clients polling for unknown blocks. Cached entries are evicted on canonical state changes.
+    /// A `max_len` of zero disables the cache.
+    pub fn with_block_number_cache(mut self, max_len: u32) -> Self {
+        self.block_numbers = (max_len > 0)
+            .then(|| BlockNumberCache::new(max_len, self.tree.subscribe_to_canonical_state()));
+        self
     }
 }
 
@@ -83,6 +108,18 @@ where
             None => Err(Error::Provider(ProviderError::HeaderNotFound(best.best_number.into()))),
         }
     }
+
+    /// Returns the number of the block with the given hash, using the block number cache if it is
+    /// enabled.
+    fn cached_block_number(
+        &self,
+        cache: &BlockNumberCache,
+        hash: BlockHash,
+    ) -> Result<Option<BlockNumber>> {
+        cache.get_or_fetch(hash, self.chain_info.get_canonical_block_number(), || {
+            self.database.provider()?.block_number(hash)
+        })
+    }
 }
 
 impl<DB, Tree> BlockchainProvider<DB, Tree>
@@ -115,7 +152,13 @@ where
     Tree: Send + Sync,
 {
     fn header(&self, block_hash: &BlockHash) -> Result<Option<Header>> {
-        self.database.provider()?.header(block_hash)
+        let Some(cache) = &self.block_numbers else {
+            return self.database.provider()?.header(block_hash)
+        };
+        match self.cached_block_number(cache, *block_hash)? {
+            Some(number) => self.header_by_number(number),
+            None => Ok(None),
+        }
     }
 
     fn header_by_number(&self, num: BlockNumber) -> Result<Option<Header>> {
@@ -178,7 +221,10 @@ where
     }
 
     fn block_number(&self, hash: H256) -> Result<Option<BlockNumber>> {
-        self.database.provider()?.block_number(hash)
+        match &self.block_numbers {
+            Some(cache) => self.cached_block_number(cache, hash),
+            None => self.database.provider()?.block_number(hash),
+        }
     }
 }
 
@@ -256,6 +302,17 @@ where
     fn block_with_senders(&self, number: BlockNumber) -> Result<Option<BlockWithSenders>> {
         self.database.provider()?.block_with_senders(number)
     }
+
+    fn block_range_iter(&self, range: RangeInclusive<BlockNumber>) -> Result<BlockRangeIter<'_>> {
+        self.database.block_range_iter(range)
+    }
+
+    fn block_body_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> Result<Vec<Option<BlockBody>>> {
+        self.database.block_body_range(range)
+    }
 }
 
 impl<DB, Tree> TransactionsProvider for BlockchainProvider<DB, Tree>
@@ -332,6 +389,10 @@ where
     fn receipts_by_block(&self, block: BlockHashOrNumber) -> Result<Option<Vec<Receipt>>> {
         self.database.provider()?.receipts_by_block(block)
     }
+
+    fn receipts_with_meta(&self, block: BlockHashOrNumber) -> Result<Option<Vec<ReceiptWithMeta>>> {
+        self.database.provider()?.receipts_with_meta(block)
+    }
 }
 impl<DB, Tree> ReceiptProviderIdExt for BlockchainProvider<DB, Tree>
 where
@@ -393,6 +454,16 @@ where
     }
 }
 
+impl<DB, Tree> PruneCheckpointReader for BlockchainProvider<DB, Tree>
+where
+    DB: Database,
+    Tree: Send + Sync,
+{
+    fn get_prune_checkpoint(&self, segment: PruneSegment) -> Result<Option<PruneCheckpoint>> {
+        self.database.provider()?.get_prune_checkpoint(segment)
+    }
+}
+
 impl<DB, Tree> EvmEnvProvider for BlockchainProvider<DB, Tree>
 where
     DB: Database,
@@ -521,7 +592,20 @@ where
 
     fn history_by_block_hash(&self, block_hash: BlockHash) -> Result<StateProviderBox<'_>> {
         trace!(target: "providers::blockchain", ?block_hash, "Getting history by block hash");
-        self.database.history_by_block_hash(block_hash)
+        match self.database.history_by_block_hash(block_hash) {
+            Err(Error::Provider(ProviderError::BlockHashNotFound(_))) => {
+                // the block may still be known as part of a side chain in the tree
+                if let Some(header) = self.tree.header_by_hash(block_hash) {
+                    return Err(ProviderError::BlockHashNotCanonical {
+                        hash: block_hash,
+                        number: Some(header.number),
+                    }
+                    .into())
+                }
+                Err(ProviderError::BlockHashNotFound(block_hash).into())
+            }
+            res => res,
+        }
     }
 
     fn state_by_block_hash(&self, block: BlockHash) -> Result<StateProviderBox<'_>> {
@@ -688,6 +772,10 @@ where
         self.chain_info.set_canonical_head(header);
     }
 
+    fn subscribe_to_canonical_head(&self) -> watch::Receiver<SealedHeader> {
+        self.chain_info.subscribe_to_canonical_head()
+    }
+
     fn set_safe(&self, header: SealedHeader) {
         self.chain_info.set_safe(header);
     }
@@ -697,6 +785,20 @@ where
     }
 }
 
+impl<DB, Tree> ForkchoiceSubscriptions for BlockchainProvider<DB, Tree>
+where
+    DB: Send + Sync,
+    Tree: Send + Sync,
+{
+    fn subscribe_to_safe_header(&self) -> watch::Receiver<Option<SealedHeader>> {
+        self.chain_info.subscribe_to_safe_header()
+    }
+
+    fn subscribe_to_finalized_header(&self) -> watch::Receiver<Option<SealedHeader>> {
+        self.chain_info.subscribe_to_finalized_header()
+    }
+}
+
 impl<DB, Tree> BlockReaderIdExt for BlockchainProvider<DB, Tree>
 where
     Self: BlockReader + BlockIdReader + ReceiptProviderIdExt,
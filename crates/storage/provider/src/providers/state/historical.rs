@@ -10,7 +10,12 @@ use reth_db::{
 };
 use reth_interfaces::Result;
 use reth_primitives::{
-    Account, Address, BlockNumber, Bytecode, Bytes, StorageKey, StorageValue, H256,
+    stage::StageId, Account, Address, BlockNumber, Bytecode, Bytes, StorageKey, StorageValue, H256,
+};
+use reth_trie::{
+    hashed_cursor::{HashedPostState, HashedPostStateCursorFactory},
+    prefix_set::PrefixSetLoader,
+    Proof,
 };
 use std::marker::PhantomData;
 
@@ -181,12 +186,55 @@ impl<'a, 'b, TX: DbTx<'a>> StateProvider for HistoricalStateProviderRef<'a, 'b,
     }
 
     /// Get account and storage proofs.
+    ///
+    /// The proofs are generated on top of the trie at the [StageId::MerkleExecute] checkpoint,
+    /// with the hashed state reverted by all changesets since this block. Returns an error if the
+    /// trie or the hashed state haven't reached this block yet, e.g. during sync.
     fn proof(
         &self,
-        _address: Address,
-        _keys: &[H256],
+        address: Address,
+        keys: &[H256],
     ) -> Result<(Vec<Bytes>, H256, Vec<Vec<Bytes>>)> {
-        Err(ProviderError::StateRootNotAvailableForHistoricalBlock.into())
+        let checkpoint = |stage_id: StageId| -> Result<BlockNumber> {
+            Ok(self
+                .tx
+                .get::<tables::SyncStage>(stage_id.to_string())?
+                .unwrap_or_default()
+                .block_number)
+        };
+        let tip = checkpoint(StageId::MerkleExecute)?;
+        // the hashed state is reverted from the trie tip, so both stages have to be at the same
+        // block
+        for hashing in [StageId::AccountHashing, StageId::StorageHashing] {
+            let hashed_block_number = checkpoint(hashing)?;
+            // `self.block_number` is the first block whose changesets are reverted
+            if hashed_block_number != tip || tip + 1 < self.block_number {
+                return Err(ProviderError::ProofNotAvailable {
+                    block_number: self.block_number.saturating_sub(1),
+                    trie_block_number: tip,
+                    hashed_block_number,
+                }
+                .into())
+            }
+        }
+        let revert_range = self.block_number..=tip;
+
+        let reverts = HashedPostState::from_reverts(self.tx, revert_range.clone())?;
+        let (account_prefixes, storage_prefixes) =
+            PrefixSetLoader::new(self.tx).load(revert_range)?;
+        let hashed_cursor_factory = HashedPostStateCursorFactory::new(self.tx, &reverts);
+
+        let proof = Proof::new(self.tx)
+            .with_hashed_cursor_factory(&hashed_cursor_factory)
+            .with_changed_account_prefixes(account_prefixes)
+            .with_changed_storage_prefixes(storage_prefixes)
+            .account_proof(address, keys)
+            .map_err(|err| reth_interfaces::Error::Database(err.into()))?;
+        Ok((
+            proof.proof,
+            proof.storage_root,
+            proof.storage_proofs.into_iter().map(|proof| proof.proof).collect(),
+        ))
     }
 }
 
@@ -219,7 +267,8 @@ delegate_provider_impls!(HistoricalStateProvider<'a, TX> where [TX: DbTx<'a>]);
 #[cfg(test)]
 mod tests {
     use crate::{
-        AccountReader, HistoricalStateProvider, HistoricalStateProviderRef, StateProvider,
+        AccountReader, HistoricalStateProvider, HistoricalStateProviderRef, ProviderError,
+        StateProvider,
     };
     use reth_db::{
         database::Database,
@@ -229,7 +278,13 @@ mod tests {
         transaction::{DbTx, DbTxMut},
         BlockNumberList,
     };
-    use reth_primitives::{hex_literal::hex, Account, StorageEntry, H160, H256, U256};
+    use reth_primitives::{
+        hex_literal::hex,
+        keccak256,
+        stage::{StageCheckpoint, StageId},
+        Account, StorageEntry, H160, H256, U256,
+    };
+    use reth_trie::{StateRoot, StorageRoot};
 
     const ADDRESS: H160 = H160(hex!("0000000000000000000000000000000000000001"));
     const HIGHER_ADDRESS: H160 = H160(hex!("0000000000000000000000000000000000000005"));
@@ -437,4 +492,86 @@ mod tests {
             Ok(Some(higher_entry_plain.value))
         );
     }
+
+    #[test]
+    fn history_provider_proof() {
+        let db = create_test_rw_db();
+        let tx = db.tx_mut().unwrap();
+
+        for idx in 1..=20 {
+            let address = H160::from_low_u64_be(idx);
+            let account = Account { nonce: idx, ..Default::default() };
+            tx.put::<tables::HashedAccount>(keccak256(address), account).unwrap();
+        }
+        let old_account = Account { nonce: 1, ..Default::default() };
+        let old_slot = StorageEntry { key: STORAGE, value: U256::from(1) };
+        tx.put::<tables::HashedStorage>(
+            keccak256(ADDRESS),
+            StorageEntry { key: keccak256(STORAGE), value: old_slot.value },
+        )
+        .unwrap();
+        let old_root = StateRoot::new(&tx).root().unwrap();
+        let old_storage_root = StorageRoot::new(&tx, ADDRESS).root().unwrap();
+
+        // block 2 changes the account and its storage slot
+        let new_account = Account { nonce: 2, ..old_account };
+        tx.put::<tables::HashedAccount>(keccak256(ADDRESS), new_account).unwrap();
+        tx.delete::<tables::HashedStorage>(keccak256(ADDRESS), None).unwrap();
+        tx.put::<tables::HashedStorage>(
+            keccak256(ADDRESS),
+            StorageEntry { key: keccak256(STORAGE), value: U256::from(2) },
+        )
+        .unwrap();
+        tx.put::<tables::AccountChangeSet>(
+            2,
+            AccountBeforeTx { address: ADDRESS, info: Some(old_account) },
+        )
+        .unwrap();
+        tx.put::<tables::StorageChangeSet>((2, ADDRESS).into(), old_slot).unwrap();
+        // headers are synced ahead of the trie
+        tx.put::<tables::CanonicalHeaders>(5, H256::random()).unwrap();
+
+        // the database trie and hashed state reflect the state after block 2
+        let (new_root, updates) = StateRoot::new(&tx).root_with_updates().unwrap();
+        updates.flush(&tx).unwrap();
+        assert_ne!(old_root, new_root);
+        for stage_id in [StageId::AccountHashing, StageId::StorageHashing, StageId::MerkleExecute] {
+            tx.put::<tables::SyncStage>(stage_id.to_string(), StageCheckpoint::new(2)).unwrap();
+        }
+
+        let (account_proof, storage_root, storage_proofs) =
+            HistoricalStateProviderRef::new(&tx, 2).proof(ADDRESS, &[STORAGE]).unwrap();
+        assert_eq!(keccak256(&account_proof[0]), old_root);
+        assert_eq!(storage_root, old_storage_root);
+        assert_eq!(keccak256(&storage_proofs[0][0]), old_storage_root);
+
+        let (account_proof, storage_root, _) =
+            HistoricalStateProviderRef::new(&tx, 3).proof(ADDRESS, &[STORAGE]).unwrap();
+        assert_eq!(keccak256(&account_proof[0]), new_root);
+        assert_ne!(storage_root, old_storage_root);
+
+        // the trie hasn't reached block 3 yet
+        assert_eq!(
+            HistoricalStateProviderRef::new(&tx, 4).proof(ADDRESS, &[STORAGE]).unwrap_err(),
+            ProviderError::ProofNotAvailable {
+                block_number: 3,
+                trie_block_number: 2,
+                hashed_block_number: 2
+            }
+            .into()
+        );
+
+        // the hashed state is ahead of the trie
+        tx.put::<tables::SyncStage>(StageId::AccountHashing.to_string(), StageCheckpoint::new(3))
+            .unwrap();
+        assert_eq!(
+            HistoricalStateProviderRef::new(&tx, 2).proof(ADDRESS, &[STORAGE]).unwrap_err(),
+            ProviderError::ProofNotAvailable {
+                block_number: 1,
+                trie_block_number: 2,
+                hashed_block_number: 3
+            }
+            .into()
+        );
+    }
 }
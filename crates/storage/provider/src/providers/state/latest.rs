@@ -7,10 +7,11 @@ use reth_db::{
     tables,
     transaction::DbTx,
 };
-use reth_interfaces::{provider::ProviderError, Result};
+use reth_interfaces::Result;
 use reth_primitives::{
-    keccak256, Account, Address, BlockNumber, Bytecode, Bytes, StorageKey, StorageValue, H256,
+    Account, Address, BlockNumber, Bytecode, Bytes, StorageKey, StorageValue, H256,
 };
+use reth_trie::Proof;
 use std::marker::PhantomData;
 
 /// State provider over latest state that takes tx reference.
@@ -33,6 +34,40 @@ impl<'a, 'b, TX: DbTx<'a>> AccountReader for LatestStateProviderRef<'a, 'b, TX>
     fn basic_account(&self, address: Address) -> Result<Option<Account>> {
         self.db.get::<tables::PlainAccountState>(address).map_err(Into::into)
     }
+
+    fn basic_accounts(&self, addresses: &[Address]) -> Result<Vec<(Address, Option<Account>)>> {
+        plain_accounts(self.db, addresses)
+    }
+}
+
+/// Looks up the plain state of all given accounts with a single cursor.
+///
+/// The addresses are visited in ascending order, so the cursor only ever moves forward and
+/// consecutive lookups hit neighbouring pages. The result preserves the order of `addresses`.
+pub(crate) fn plain_accounts<'a, TX: DbTx<'a>>(
+    tx: &TX,
+    addresses: &[Address],
+) -> Result<Vec<(Address, Option<Account>)>> {
+    let mut sorted = (0..addresses.len()).collect::<Vec<_>>();
+    sorted.sort_unstable_by_key(|idx| addresses[*idx]);
+
+    let mut cursor = tx.cursor_read::<tables::PlainAccountState>()?;
+    let mut accounts = vec![None; addresses.len()];
+    // The entry the cursor is positioned at, i.e. the first account at or after the last lookup.
+    let mut current: Option<(Address, Account)> = None;
+    for idx in sorted {
+        let address = addresses[idx];
+        if current.map_or(true, |(key, _)| key < address) {
+            current = cursor.seek(address)?;
+            if current.is_none() {
+                // no more accounts at or after this address
+                break
+            }
+        }
+        accounts[idx] = current.filter(|(key, _)| *key == address).map(|(_, account)| account);
+    }
+
+    Ok(addresses.iter().copied().zip(accounts).collect())
 }
 
 impl<'a, 'b, TX: DbTx<'a>> BlockHashReader for LatestStateProviderRef<'a, 'b, TX> {
@@ -83,18 +118,16 @@ impl<'a, 'b, TX: DbTx<'a>> StateProvider for LatestStateProviderRef<'a, 'b, TX>
     fn proof(
         &self,
         address: Address,
-        _keys: &[H256],
+        keys: &[H256],
     ) -> Result<(Vec<Bytes>, H256, Vec<Vec<Bytes>>)> {
-        let _hashed_address = keccak256(address);
-        let _root = self
-            .db
-            .cursor_read::<tables::Headers>()?
-            .last()?
-            .ok_or_else(|| ProviderError::HeaderNotFound(0.into()))?
-            .1
-            .state_root;
-
-        unimplemented!()
+        let proof = Proof::new(self.db)
+            .account_proof(address, keys)
+            .map_err(|err| reth_interfaces::Error::Database(err.into()))?;
+        Ok((
+            proof.proof,
+            proof.storage_root,
+            proof.storage_proofs.into_iter().map(|proof| proof.proof).collect(),
+        ))
     }
 }
 
@@ -131,4 +164,36 @@ mod tests {
     fn assert_latest_state_provider<'txn, T: DbTx<'txn> + 'txn>() {
         assert_state_provider::<LatestStateProvider<'txn, T>>();
     }
+
+    #[test]
+    fn latest_provider_basic_accounts() {
+        use reth_db::{database::Database, test_utils::create_test_rw_db, transaction::DbTxMut};
+
+        let db = create_test_rw_db();
+        let tx = db.tx_mut().unwrap();
+        let low = Address::from_low_u64_be(1);
+        let high = Address::from_low_u64_be(3);
+        tx.put::<tables::PlainAccountState>(low, Account { nonce: 1, ..Default::default() })
+            .unwrap();
+        tx.put::<tables::PlainAccountState>(high, Account { nonce: 3, ..Default::default() })
+            .unwrap();
+        tx.commit().unwrap();
+
+        let tx = db.tx().unwrap();
+        let provider = LatestStateProviderRef::new(&tx);
+        let missing = Address::from_low_u64_be(2);
+        let beyond = Address::from_low_u64_be(4);
+        let addresses = [high, missing, beyond, low, high];
+
+        let accounts = provider.basic_accounts(&addresses).unwrap();
+        let expected = addresses
+            .iter()
+            .map(|address| (*address, provider.basic_account(*address).unwrap()))
+            .collect::<Vec<_>>();
+        assert_eq!(accounts, expected);
+        assert_eq!(accounts[0].1.map(|account| account.nonce), Some(3));
+        assert_eq!(accounts[1].1, None);
+        assert_eq!(accounts[2].1, None);
+        assert_eq!(accounts[3].1.map(|account| account.nonce), Some(1));
+    }
 }
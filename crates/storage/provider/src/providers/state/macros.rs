@@ -35,6 +35,7 @@ macro_rules! delegate_provider_impls {
             }
             AccountReader $(where [$($generics)*])? {
                 fn basic_account(&self, address: reth_primitives::Address) -> reth_interfaces::Result<Option<reth_primitives::Account>>;
+                fn basic_accounts(&self, addresses: &[reth_primitives::Address]) -> reth_interfaces::Result<Vec<(reth_primitives::Address, Option<reth_primitives::Account>)>>;
             }
             BlockHashReader $(where [$($generics)*])? {
                 fn block_hash(&self, number: u64) -> reth_interfaces::Result<Option<reth_primitives::H256>>;
@@ -20,7 +20,7 @@ impl TestCanonStateSubscriptions {
     /// Adds reorg to the queue that can be consumed with
     /// [`TestCanonStateSubscriptions::subscribe_to_canonical_state`]
     pub fn add_next_reorg(&mut self, old: Arc<Chain>, new: Arc<Chain>) {
-        let event = CanonStateNotification::Reorg { old, new };
+        let event = CanonStateNotification::reorg(old, new);
         self.canon_notif_tx.lock().as_mut().unwrap().retain(|tx| tx.send(event.clone()).is_ok())
     }
 }
@@ -10,8 +10,9 @@ use reth_db::models::StoredBlockBodyIndices;
 use reth_interfaces::{provider::ProviderError, Result};
 use reth_primitives::{
     keccak256, Account, Address, Block, BlockHash, BlockHashOrNumber, BlockId, BlockNumber,
-    BlockWithSenders, Bytecode, Bytes, ChainInfo, Header, Receipt, SealedBlock, SealedHeader,
-    StorageKey, StorageValue, TransactionMeta, TransactionSigned, TxHash, TxNumber, H256, U256,
+    BlockWithSenders, Bytecode, Bytes, ChainInfo, Header, Receipt, ReceiptWithMeta, SealedBlock,
+    SealedHeader, StorageKey, StorageValue, TransactionMeta, TransactionSigned, TxHash, TxNumber,
+    H256, U256,
 };
 use reth_revm_primitives::primitives::{BlockEnv, CfgEnv};
 use std::{
@@ -237,6 +238,13 @@ impl ReceiptProvider for MockEthProvider {
     fn receipts_by_block(&self, _block: BlockHashOrNumber) -> Result<Option<Vec<Receipt>>> {
         Ok(None)
     }
+
+    fn receipts_with_meta(
+        &self,
+        _block: BlockHashOrNumber,
+    ) -> Result<Option<Vec<ReceiptWithMeta>>> {
+        Ok(None)
+    }
 }
 
 impl ReceiptProviderIdExt for MockEthProvider {}
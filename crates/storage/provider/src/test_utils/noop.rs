@@ -1,20 +1,23 @@
 use crate::{
     traits::{BlockSource, ReceiptProvider},
     AccountReader, BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt,
-    ChainSpecProvider, EvmEnvProvider, HeaderProvider, PostState, ReceiptProviderIdExt,
-    StageCheckpointReader, StateProvider, StateProviderBox, StateProviderFactory,
-    StateRootProvider, TransactionsProvider, WithdrawalsProvider,
+    ChainSpecProvider, EvmEnvProvider, ForkchoiceSubscriptions, HeaderProvider, PostState,
+    PruneCheckpointReader, ReceiptProviderIdExt, StageCheckpointReader, StateProvider,
+    StateProviderBox, StateProviderFactory, StateRootProvider, TransactionsProvider,
+    WithdrawalsProvider,
 };
 use reth_db::models::StoredBlockBodyIndices;
 use reth_interfaces::Result;
 use reth_primitives::{
     stage::{StageCheckpoint, StageId},
     Account, Address, Block, BlockHash, BlockHashOrNumber, BlockId, BlockNumber, Bytecode, Bytes,
-    ChainInfo, ChainSpec, Header, Receipt, SealedBlock, SealedHeader, StorageKey, StorageValue,
-    TransactionMeta, TransactionSigned, TxHash, TxNumber, H256, KECCAK_EMPTY, MAINNET, U256,
+    ChainInfo, ChainSpec, Header, PruneCheckpoint, PruneSegment, Receipt, ReceiptWithMeta,
+    SealedBlock, SealedHeader, StorageKey, StorageValue, TransactionMeta, TransactionSigned,
+    TxHash, TxNumber, H256, KECCAK_EMPTY, MAINNET, U256,
 };
 use reth_revm_primitives::primitives::{BlockEnv, CfgEnv};
 use std::{ops::RangeBounds, sync::Arc};
+use tokio::sync::watch;
 
 /// Supports various api interfaces for testing purposes.
 #[derive(Debug, Clone, Default, Copy)]
@@ -187,6 +190,13 @@ impl ReceiptProvider for NoopProvider {
     fn receipts_by_block(&self, _block: BlockHashOrNumber) -> Result<Option<Vec<Receipt>>> {
         Ok(None)
     }
+
+    fn receipts_with_meta(
+        &self,
+        _block: BlockHashOrNumber,
+    ) -> Result<Option<Vec<ReceiptWithMeta>>> {
+        Ok(None)
+    }
 }
 
 impl ReceiptProviderIdExt for NoopProvider {}
@@ -337,6 +347,12 @@ impl StageCheckpointReader for NoopProvider {
     }
 }
 
+impl PruneCheckpointReader for NoopProvider {
+    fn get_prune_checkpoint(&self, _segment: PruneSegment) -> Result<Option<PruneCheckpoint>> {
+        Ok(None)
+    }
+}
+
 impl WithdrawalsProvider for NoopProvider {
     fn latest_withdrawal(&self) -> Result<Option<reth_primitives::Withdrawal>> {
         Ok(None)
@@ -349,3 +365,13 @@ impl WithdrawalsProvider for NoopProvider {
         Ok(None)
     }
 }
+
+impl ForkchoiceSubscriptions for NoopProvider {
+    fn subscribe_to_safe_header(&self) -> watch::Receiver<Option<SealedHeader>> {
+        watch::channel(None).1
+    }
+
+    fn subscribe_to_finalized_header(&self) -> watch::Receiver<Option<SealedHeader>> {
+        watch::channel(None).1
+    }
+}
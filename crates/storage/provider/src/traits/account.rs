@@ -13,6 +13,15 @@ pub trait AccountReader: Send + Sync {
     ///
     /// Returns `None` if the account doesn't exist.
     fn basic_account(&self, address: Address) -> Result<Option<Account>>;
+
+    /// Get basic account information for multiple accounts. A more efficient version than calling
+    /// [`AccountReader::basic_account`] repeatedly.
+    ///
+    /// The accounts are returned in the same order as the given addresses, with `None` for every
+    /// account that doesn't exist.
+    fn basic_accounts(&self, addresses: &[Address]) -> Result<Vec<(Address, Option<Account>)>> {
+        addresses.iter().map(|address| Ok((*address, self.basic_account(*address)?))).collect()
+    }
 }
 
 /// Account reader
@@ -24,15 +33,6 @@ pub trait AccountExtReader: Send + Sync {
         _range: impl RangeBounds<BlockNumber>,
     ) -> Result<BTreeSet<Address>>;
 
-    /// Get basic account information for multiple accounts. A more efficient version than calling
-    /// [`AccountReader::basic_account`] repeatedly.
-    ///
-    /// Returns `None` if the account doesn't exist.
-    fn basic_accounts(
-        &self,
-        _iter: impl IntoIterator<Item = Address>,
-    ) -> Result<Vec<(Address, Option<Account>)>>;
-
     /// Iterate over account changesets and return all account addresses that were changed alongside
     /// each specific set of blocks.
     ///
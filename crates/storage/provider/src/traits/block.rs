@@ -4,10 +4,11 @@ use crate::{
 };
 use auto_impl::auto_impl;
 use reth_db::models::StoredBlockBodyIndices;
-use reth_interfaces::Result;
+use reth_interfaces::{provider::ProviderError, Result};
 use reth_primitives::{
-    Address, Block, BlockHashOrNumber, BlockId, BlockNumber, BlockNumberOrTag, BlockWithSenders,
-    ChainSpec, Header, Receipt, SealedBlock, SealedBlockWithSenders, SealedHeader, H256,
+    stage::StageCheckpoint, Address, Block, BlockBody, BlockHashOrNumber, BlockId, BlockNumber,
+    BlockNumberOrTag, BlockWithSenders, ChainSpec, Header, Receipt, SealedBlock,
+    SealedBlockWithSenders, SealedHeader, H256,
 };
 use std::ops::RangeInclusive;
 
@@ -42,6 +43,23 @@ impl BlockSource {
     }
 }
 
+/// Determines how [BlockWriter::insert_blocks] writes to the tables keyed by block or transaction
+/// number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum StorageMode {
+    /// Write with append-only cursors.
+    ///
+    /// This is the fastest option, but requires the blocks to directly follow the last block in
+    /// the database.
+    #[default]
+    Append,
+    /// Write with regular inserts, overwriting any existing entries.
+    ///
+    /// The blocks must reach at least the last block in the database, since the transactions of
+    /// any blocks above them would be overwritten.
+    Upsert,
+}
+
 /// Api trait for fetching `Block` related data.
 ///
 /// If not requested otherwise, implementers of this trait should prioritize fetching blocks from
@@ -105,6 +123,51 @@ pub trait BlockReader:
     ///
     /// Returns `None` if block is not found.
     fn block_with_senders(&self, number: BlockNumber) -> Result<Option<BlockWithSenders>>;
+
+    /// Returns a lazy iterator over the sealed blocks with senders in the given range.
+    ///
+    /// Blocks are only loaded once the iterator is advanced, so the whole range is never held in
+    /// memory. Yields an error for every block in the range that doesn't exist.
+    fn block_range_iter(&self, range: RangeInclusive<BlockNumber>) -> Result<BlockRangeIter<'_>> {
+        Ok(Box::new(range.map(move |number| sealed_block_with_senders(self, number))))
+    }
+
+    /// Returns the bodies of the canonical blocks in the given range, in order.
+    ///
+    /// The returned list has an entry for every block in the range, which is `None` if the block is
+    /// not in the database.
+    fn block_body_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> Result<Vec<Option<BlockBody>>> {
+        range
+            .map(|number| {
+                Ok(self.block(number.into())?.map(|block| BlockBody {
+                    transactions: block.body,
+                    ommers: block.ommers,
+                    withdrawals: block.withdrawals,
+                }))
+            })
+            .collect()
+    }
+}
+
+/// A lazy iterator over a range of blocks, see [BlockReader::block_range_iter].
+pub type BlockRangeIter<'a> = Box<dyn Iterator<Item = Result<SealedBlockWithSenders>> + 'a>;
+
+/// Loads the canonical block with the given number, sealed with its stored hash and with
+/// transaction hashes calculated.
+pub(crate) fn sealed_block_with_senders<P: BlockReader + ?Sized>(
+    provider: &P,
+    number: BlockNumber,
+) -> Result<SealedBlockWithSenders> {
+    let BlockWithSenders { mut block, senders } =
+        provider.block_with_senders(number)?.ok_or(ProviderError::BlockNotFound(number.into()))?;
+    let hash = provider.block_hash(number)?.ok_or(ProviderError::HeaderNotFound(number.into()))?;
+    for transaction in &mut block.body {
+        transaction.hash = transaction.recalculate_hash();
+    }
+    Ok(SealedBlockWithSenders { block: block.seal(hash), senders })
 }
 
 /// Trait extension for `BlockReader`, for types that implement `BlockId` conversion.
@@ -244,6 +307,19 @@ pub trait BlockWriter: Send + Sync {
         senders: Option<Vec<Address>>,
     ) -> Result<StoredBlockBodyIndices>;
 
+    /// Insert a contiguous range of pre-validated blocks and make them canonical.
+    ///
+    /// This writes headers, bodies, senders and all dependent indices in a single pass, but
+    /// doesn't execute the blocks or update any stage checkpoints.
+    ///
+    /// Returns the checkpoint of the last inserted block, or of the current database tip if no
+    /// blocks were given.
+    fn insert_blocks(
+        &self,
+        blocks: &[SealedBlockWithSenders],
+        mode: StorageMode,
+    ) -> Result<StageCheckpoint>;
+
     /// Append blocks and insert its post state.
     /// This will insert block data to all related tables and will update pipeline progress.
     fn append_blocks_with_post_state(
@@ -1,8 +1,9 @@
 //! Canonical chain state notification trait and types.
 use crate::{chain::BlockReceipts, Chain};
 use auto_impl::auto_impl;
-use reth_primitives::SealedBlockWithSenders;
+use reth_primitives::{Address, Log, SealedBlockWithSenders, TxHash, H256};
 use std::{
+    collections::HashSet,
     pin::Pin,
     sync::Arc,
     task::{ready, Context, Poll},
@@ -31,6 +32,18 @@ pub trait CanonStateSubscriptions: Send + Sync {
             st: BroadcastStream::new(self.subscribe_to_canonical_state()),
         }
     }
+
+    /// Convenience method to get a stream of [`CanonStateNotification`]s that only contain the
+    /// state changes of the given addresses.
+    ///
+    /// Notifications that don't change the state of any of the addresses are skipped, see
+    /// [CanonStateNotification::filter_accounts].
+    fn canonical_state_stream_for_accounts(
+        &self,
+        addresses: HashSet<Address>,
+    ) -> FilteredCanonStateNotificationStream {
+        FilteredCanonStateNotificationStream { st: self.canonical_state_stream(), addresses }
+    }
 }
 
 /// A Stream of [CanonStateNotification].
@@ -58,6 +71,33 @@ impl Stream for CanonStateNotificationStream {
     }
 }
 
+/// A Stream of [CanonStateNotification]s filtered by the addresses whose state changed, see
+/// [CanonStateSubscriptions::canonical_state_stream_for_accounts].
+#[derive(Debug)]
+#[pin_project::pin_project]
+pub struct FilteredCanonStateNotificationStream {
+    #[pin]
+    st: CanonStateNotificationStream,
+    addresses: HashSet<Address>,
+}
+
+impl Stream for FilteredCanonStateNotificationStream {
+    type Item = CanonStateNotification;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+        loop {
+            return match ready!(this.st.as_mut().poll_next(cx)) {
+                Some(notification) => match notification.filter_accounts(this.addresses) {
+                    Some(filtered) => Poll::Ready(Some(filtered)),
+                    None => continue,
+                },
+                None => Poll::Ready(None),
+            }
+        }
+    }
+}
+
 /// Chain action that is triggered when a new block is imported or old block is reverted.
 /// and will return all [`crate::PostState`] and [`reth_primitives::SealedBlockWithSenders`] of both
 /// reverted and committed blocks.
@@ -65,7 +105,10 @@ impl Stream for CanonStateNotificationStream {
 #[allow(missing_docs)]
 pub enum CanonStateNotification {
     /// Chain reorgs and both old and new chain are returned.
-    Reorg { old: Arc<Chain>, new: Arc<Chain> },
+    ///
+    /// The `diff` contains the transactions that were reverted and newly included by the reorg,
+    /// see [CanonStateNotification::reorg].
+    Reorg { old: Arc<Chain>, new: Arc<Chain>, diff: Arc<ReorgDiff> },
     /// Chain got reverted without reorg and only old chain is returned.
     ///
     /// This reverts the chain's tip to the first block of the chain.
@@ -79,9 +122,10 @@ pub enum CanonStateNotification {
 impl PartialEq for CanonStateNotification {
     fn eq(&self, other: &Self) -> bool {
         match (self, other) {
-            (Self::Reorg { old: old1, new: new1 }, Self::Reorg { old: old2, new: new2 }) => {
-                old1 == old2 && new1 == new2
-            }
+            (
+                Self::Reorg { old: old1, new: new1, .. },
+                Self::Reorg { old: old2, new: new2, .. },
+            ) => old1 == old2 && new1 == new2,
             (Self::Revert { old: old1 }, Self::Revert { old: old2 }) => old1 == old2,
             (Self::Commit { new: new1 }, Self::Commit { new: new2 }) => new1 == new2,
             _ => false,
@@ -90,6 +134,22 @@ impl PartialEq for CanonStateNotification {
 }
 
 impl CanonStateNotification {
+    /// Creates a [Self::Reorg] notification and computes the transaction diff of the two chains.
+    pub fn reorg(old: Arc<Chain>, new: Arc<Chain>) -> Self {
+        let diff = Arc::new(ReorgDiff::new(&old, &new));
+        Self::Reorg { old, new, diff }
+    }
+
+    /// Returns the transaction diff for [Self::Reorg] variant.
+    ///
+    /// Returns None for [Self::Revert] and [Self::Commit] variants.
+    pub fn reorg_diff(&self) -> Option<&ReorgDiff> {
+        match self {
+            Self::Reorg { diff, .. } => Some(diff),
+            Self::Revert { .. } | Self::Commit { .. } => None,
+        }
+    }
+
     /// Get old chain if any.
     pub fn reverted(&self) -> Option<Arc<Chain>> {
         match self {
@@ -125,6 +185,59 @@ impl CanonStateNotification {
         }
     }
 
+    /// Returns the notification with chains whose state only contains the changes of the given
+    /// addresses, see [Chain::filter_accounts].
+    ///
+    /// Returns `None` if none of the chains change the state of any of the addresses. A reorg is
+    /// kept as a whole if either the old or the new chain changes the state of one of them.
+    pub fn filter_accounts(&self, addresses: &HashSet<Address>) -> Option<Self> {
+        // the states are filtered first, so the blocks are only cloned if the notification is kept
+        let filter = |chain: &Arc<Chain>| chain.state.filter_accounts(addresses);
+        let with_state =
+            |chain: &Arc<Chain>, state| Arc::new(Chain { state, blocks: chain.blocks.clone() });
+
+        let notification = match self {
+            Self::Reorg { old, new, diff } => {
+                let (old_state, new_state) = (filter(old), filter(new));
+                if old_state.has_no_state_changes() && new_state.has_no_state_changes() {
+                    return None
+                }
+                Self::Reorg {
+                    old: with_state(old, old_state),
+                    new: with_state(new, new_state),
+                    diff: Arc::clone(diff),
+                }
+            }
+            Self::Revert { old } => {
+                let state = filter(old);
+                if state.has_no_state_changes() {
+                    return None
+                }
+                Self::Revert { old: with_state(old, state) }
+            }
+            Self::Commit { new } => {
+                let state = filter(new);
+                if state.has_no_state_changes() {
+                    return None
+                }
+                Self::Commit { new: with_state(new, state) }
+            }
+        };
+        Some(notification)
+    }
+
+    /// Returns the logs of the committed chain that match the given addresses and topics, see
+    /// [Chain::logs_matching].
+    ///
+    /// Returns an empty list for [Self::Revert] variant.
+    pub fn committed_logs_matching(
+        &self,
+        addresses: &HashSet<Address>,
+        topics: &[Option<H256>],
+    ) -> Vec<(TxHash, Log)> {
+        self.committed().map(|chain| chain.logs_matching(addresses, topics)).unwrap_or_default()
+    }
+
     /// Return receipt with its block number and transaction hash.
     ///
     /// Last boolean is true if receipt is from reverted block.
@@ -144,3 +257,79 @@ impl CanonStateNotification {
         receipts
     }
 }
+
+/// The transactions that changed between the old and the new chain of a reorg.
+///
+/// Transactions that are included in both chains are in neither set.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ReorgDiff {
+    /// Hashes of the transactions of the old chain that are not included in the new chain.
+    pub reverted: HashSet<TxHash>,
+    /// Hashes of the transactions of the new chain that were not included in the old chain.
+    pub added: HashSet<TxHash>,
+}
+
+impl ReorgDiff {
+    /// Computes the transaction diff of the old and the new chain.
+    pub fn new(old: &Chain, new: &Chain) -> Self {
+        let tx_hashes = |chain: &Chain| -> HashSet<TxHash> {
+            chain
+                .blocks()
+                .values()
+                .flat_map(|block| block.body.iter().map(|tx| tx.hash()))
+                .collect()
+        };
+        let old_txs = tx_hashes(old);
+        let new_txs = tx_hashes(new);
+        Self {
+            reverted: old_txs.difference(&new_txs).copied().collect(),
+            added: new_txs.difference(&old_txs).copied().collect(),
+        }
+    }
+
+    /// Returns `true` if both chains include the same transactions.
+    pub fn is_empty(&self) -> bool {
+        self.reverted.is_empty() && self.added.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PostState;
+    use reth_primitives::{
+        Header, SealedBlock, Signature, Transaction, TransactionSigned, TxLegacy,
+    };
+
+    fn transaction(nonce: u64) -> TransactionSigned {
+        TransactionSigned::from_transaction_and_signature(
+            Transaction::Legacy(TxLegacy { nonce, ..Default::default() }),
+            Signature::default(),
+        )
+    }
+
+    fn chain(nonces: &[u64]) -> Chain {
+        let body: Vec<_> = nonces.iter().copied().map(transaction).collect();
+        let block = SealedBlock {
+            header: Header { number: 1, ..Default::default() }.seal_slow(),
+            body,
+            ..Default::default()
+        };
+        let senders = vec![Address::zero(); block.body.len()];
+        Chain::new(vec![(SealedBlockWithSenders { block, senders }, PostState::default())])
+    }
+
+    #[test]
+    fn reorg_diff() {
+        let old = Arc::new(chain(&[0, 1]));
+        let new = Arc::new(chain(&[1, 2]));
+
+        let notification = CanonStateNotification::reorg(old.clone(), new);
+        let diff = notification.reorg_diff().unwrap();
+        assert_eq!(diff.reverted, HashSet::from([transaction(0).hash()]));
+        assert_eq!(diff.added, HashSet::from([transaction(2).hash()]));
+
+        assert!(ReorgDiff::new(&old, &old).is_empty());
+        assert!(CanonStateNotification::Commit { new: old }.reorg_diff().is_none());
+    }
+}
@@ -1,6 +1,7 @@
 use reth_interfaces::consensus::ForkchoiceState;
 use reth_primitives::SealedHeader;
 use std::time::Instant;
+use tokio::sync::watch;
 
 /// A type that can track updates related to fork choice updates.
 pub trait CanonChainTracker: Send + Sync {
@@ -21,9 +22,30 @@ pub trait CanonChainTracker: Send + Sync {
     /// Sets the canonical head of the chain.
     fn set_canonical_head(&self, header: SealedHeader);
 
+    /// Returns a receiver that yields the canonical head whenever it changes.
+    ///
+    /// This is cheaper than subscribing to the canonical state notifications if only the tip is
+    /// of interest. Intermediate heads may be skipped if the receiver doesn't keep up.
+    fn subscribe_to_canonical_head(&self) -> watch::Receiver<SealedHeader>;
+
     /// Sets the safe block of the chain.
     fn set_safe(&self, header: SealedHeader);
 
     /// Sets the finalized block of the chain.
     fn set_finalized(&self, header: SealedHeader);
 }
+
+/// A type that allows to subscribe to the safe and finalized headers of the chain, as reported by
+/// the consensus layer's forkchoice updates.
+#[auto_impl::auto_impl(&, Arc)]
+pub trait ForkchoiceSubscriptions: Send + Sync {
+    /// Returns a receiver that yields the safe header whenever it changes.
+    ///
+    /// The value is `None` until the first forkchoice update with a safe block was received.
+    fn subscribe_to_safe_header(&self) -> watch::Receiver<Option<SealedHeader>>;
+
+    /// Returns a receiver that yields the finalized header whenever it changes.
+    ///
+    /// The value is `None` until the first forkchoice update with a finalized block was received.
+    fn subscribe_to_finalized_header(&self) -> watch::Receiver<Option<SealedHeader>>;
+}
@@ -31,6 +31,27 @@ pub trait HeaderProvider: Send + Sync {
     /// Get total difficulty by block number.
     fn header_td_by_number(&self, number: BlockNumber) -> Result<Option<U256>>;
 
+    /// Get the stored total difficulty of a pre-merge block by block hash.
+    ///
+    /// Returns `None` for blocks with zero difficulty, i.e. blocks after the merge, where the total
+    /// difficulty no longer changes. The total difficulty is never recomputed.
+    fn total_difficulty_by_hash(&self, hash: &BlockHash) -> Result<Option<U256>> {
+        match self.header(hash)? {
+            Some(header) if !header.difficulty.is_zero() => self.header_td_by_number(header.number),
+            _ => Ok(None),
+        }
+    }
+
+    /// Get the stored total difficulty of a pre-merge block by block number.
+    ///
+    /// See [HeaderProvider::total_difficulty_by_hash] for the post-merge convention.
+    fn total_difficulty_by_number(&self, number: BlockNumber) -> Result<Option<U256>> {
+        match self.header_by_number(number)? {
+            Some(header) if !header.difficulty.is_zero() => self.header_td_by_number(number),
+            _ => Ok(None),
+        }
+    }
+
     /// Get headers in range of block numbers
     fn headers_range(&self, range: impl RangeBounds<BlockNumber>) -> Result<Vec<Header>>;
 
@@ -6,8 +6,11 @@ pub use account::{AccountExtReader, AccountReader};
 mod storage;
 pub use storage::StorageReader;
 
-mod block;
-pub use block::{BlockExecutionWriter, BlockReader, BlockReaderIdExt, BlockSource, BlockWriter};
+pub(crate) mod block;
+pub use block::{
+    BlockExecutionWriter, BlockRangeIter, BlockReader, BlockReaderIdExt, BlockSource, BlockWriter,
+    StorageMode,
+};
 
 mod block_hash;
 pub use block_hash::BlockHashReader;
@@ -19,7 +22,7 @@ mod evm_env;
 pub use evm_env::EvmEnvProvider;
 
 mod chain_info;
-pub use chain_info::CanonChainTracker;
+pub use chain_info::{CanonChainTracker, ForkchoiceSubscriptions};
 
 mod header;
 pub use header::HeaderProvider;
@@ -44,8 +47,9 @@ pub use executor::{BlockExecutor, ExecutorFactory};
 
 mod chain;
 pub use chain::{
-    CanonStateNotification, CanonStateNotificationSender, CanonStateNotifications,
-    CanonStateSubscriptions,
+    CanonStateNotification, CanonStateNotificationSender, CanonStateNotificationStream,
+    CanonStateNotifications, CanonStateSubscriptions, FilteredCanonStateNotificationStream,
+    ReorgDiff,
 };
 
 mod spec;
@@ -54,6 +58,9 @@ pub use spec::ChainSpecProvider;
 mod stage_checkpoint;
 pub use stage_checkpoint::{StageCheckpointReader, StageCheckpointWriter};
 
+mod prune_checkpoint;
+pub use prune_checkpoint::{PruneCheckpointReader, PruneCheckpointWriter};
+
 mod hashing;
 pub use hashing::HashingWriter;
 
@@ -0,0 +1,37 @@
+use reth_interfaces::Result;
+use reth_primitives::{BlockNumber, PruneCheckpoint, PruneSegment};
+
+/// The trait for fetching prune checkpoint related data.
+#[auto_impl::auto_impl(&, Arc)]
+pub trait PruneCheckpointReader: Send + Sync {
+    /// Fetch the checkpoint for the given prune segment.
+    fn get_prune_checkpoint(&self, segment: PruneSegment) -> Result<Option<PruneCheckpoint>>;
+
+    /// Returns the lowest block number for which the data of the given prune segment is still
+    /// available.
+    ///
+    /// The block of a prune checkpoint is the highest pruned block, so this is the block right
+    /// above it.
+    ///
+    /// Returns `0` if the segment has never been pruned.
+    fn earliest_available_block(&self, segment: PruneSegment) -> Result<BlockNumber> {
+        Ok(self.get_prune_checkpoint(segment)?.map_or(0, |checkpoint| checkpoint.block_number + 1))
+    }
+}
+
+/// The trait for updating prune checkpoint related data.
+#[auto_impl::auto_impl(&, Arc)]
+pub trait PruneCheckpointWriter: Send + Sync {
+    /// Save prune checkpoint.
+    fn save_prune_checkpoint(
+        &self,
+        segment: PruneSegment,
+        checkpoint: PruneCheckpoint,
+    ) -> Result<()>;
+
+    /// Lower all prune checkpoints that are above the given block number to that block number.
+    ///
+    /// Must be called when the chain is unwound below the already pruned blocks, so the blocks
+    /// re-synced after the unwind are pruned again.
+    fn unwind_prune_checkpoints(&self, block_number: BlockNumber) -> Result<()>;
+}
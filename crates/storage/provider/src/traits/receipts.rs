@@ -1,5 +1,7 @@
 use reth_interfaces::Result;
-use reth_primitives::{BlockHashOrNumber, BlockId, BlockNumberOrTag, Receipt, TxHash, TxNumber};
+use reth_primitives::{
+    BlockHashOrNumber, BlockId, BlockNumberOrTag, Receipt, ReceiptWithMeta, TxHash, TxNumber,
+};
 
 use crate::BlockIdReader;
 
@@ -20,6 +22,13 @@ pub trait ReceiptProvider: Send + Sync {
     ///
     /// Returns `None` if the block is not found.
     fn receipts_by_block(&self, block: BlockHashOrNumber) -> Result<Option<Vec<Receipt>>>;
+
+    /// Get receipts by block num or hash, together with the position of their transactions and
+    /// logs in the block.
+    ///
+    /// Returns `None` if the block is not found, or if any of its receipts are missing, e.g.
+    /// because they were pruned.
+    fn receipts_with_meta(&self, block: BlockHashOrNumber) -> Result<Option<Vec<ReceiptWithMeta>>>;
 }
 
 /// Trait extension for `ReceiptProvider`, for types that implement `BlockId` conversion.
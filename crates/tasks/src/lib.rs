@@ -15,7 +15,7 @@
 
 use crate::{
     metrics::TaskExecutorMetrics,
-    shutdown::{signal, Shutdown, Signal},
+    shutdown::{signal, GracefulShutdown, Shutdown, Signal},
 };
 use dyn_clone::DynClone;
 use futures_util::{
@@ -26,14 +26,19 @@ use std::{
     any::Any,
     fmt::{Display, Formatter},
     pin::Pin,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
     task::{ready, Context, Poll},
+    time::{Duration, Instant},
 };
 use tokio::{
     runtime::Handle,
     sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender},
     task::JoinHandle,
 };
-use tracing::error;
+use tracing::{debug, error};
 use tracing_futures::Instrument;
 
 pub mod metrics;
@@ -95,6 +100,16 @@ pub trait TaskSpawner: Send + Sync + Unpin + std::fmt::Debug + DynClone {
         name: &'static str,
         fut: BoxFuture<'static, ()>,
     ) -> JoinHandle<()>;
+
+    /// This spawns a critical blocking task onto the runtime that isn't aborted on shutdown.
+    ///
+    /// The task is expected to observe the shutdown itself, e.g. via a
+    /// [GracefulShutdown](shutdown::GracefulShutdown) signal.
+    fn spawn_critical_blocking_graceful(
+        &self,
+        name: &'static str,
+        fut: BoxFuture<'static, ()>,
+    ) -> JoinHandle<()>;
 }
 
 dyn_clone::clone_trait_object!(TaskSpawner);
@@ -124,6 +139,14 @@ impl TaskSpawner for TokioTaskExecutor {
     ) -> JoinHandle<()> {
         tokio::task::spawn_blocking(move || tokio::runtime::Handle::current().block_on(fut))
     }
+
+    fn spawn_critical_blocking_graceful(
+        &self,
+        _name: &'static str,
+        fut: BoxFuture<'static, ()>,
+    ) -> JoinHandle<()> {
+        tokio::task::spawn_blocking(move || tokio::runtime::Handle::current().block_on(fut))
+    }
 }
 
 /// Many reth components require to spawn tasks for long-running jobs. For example `discovery`
@@ -151,9 +174,11 @@ pub struct TaskManager {
     /// The [Signal] to fire when all tasks should be shutdown.
     ///
     /// This is fired on drop.
-    _signal: Signal,
+    signal: Option<Signal>,
     /// Receiver of the shutdown signal.
     on_shutdown: Shutdown,
+    /// The number of alive [GracefulShutdownGuard](shutdown::GracefulShutdownGuard)s.
+    graceful_tasks: Arc<AtomicUsize>,
 }
 
 // === impl TaskManager ===
@@ -162,8 +187,15 @@ impl TaskManager {
     /// Create a new instance connected to the given handle's tokio runtime.
     pub fn new(handle: Handle) -> Self {
         let (panicked_tasks_tx, panicked_tasks_rx) = unbounded_channel();
-        let (_signal, on_shutdown) = signal();
-        Self { handle, panicked_tasks_tx, panicked_tasks_rx, _signal, on_shutdown }
+        let (signal, on_shutdown) = signal();
+        Self {
+            handle,
+            panicked_tasks_tx,
+            panicked_tasks_rx,
+            signal: Some(signal),
+            on_shutdown,
+            graceful_tasks: Arc::new(AtomicUsize::new(0)),
+        }
     }
 
     /// Returns a new [`TaskExecutor`] that can spawn new tasks onto the tokio runtime this type is
@@ -174,7 +206,31 @@ impl TaskManager {
             on_shutdown: self.on_shutdown.clone(),
             panicked_tasks_tx: self.panicked_tasks_tx.clone(),
             metrics: Default::default(),
+            graceful_tasks: self.graceful_tasks.clone(),
+        }
+    }
+
+    /// Fires the shutdown signal and blocks until all tasks that hold a
+    /// [GracefulShutdownGuard](shutdown::GracefulShutdownGuard) have dropped it, or the timeout
+    /// elapsed.
+    ///
+    /// Returns `true` if all guards were dropped in time.
+    pub fn graceful_shutdown_with_timeout(mut self, timeout: Duration) -> bool {
+        if let Some(signal) = self.signal.take() {
+            signal.fire();
+        }
+
+        let deadline = Instant::now() + timeout;
+        while self.graceful_tasks.load(Ordering::SeqCst) > 0 {
+            if Instant::now() > deadline {
+                debug!("Graceful shutdown timed out");
+                return false
+            }
+            std::thread::sleep(Duration::from_millis(10));
         }
+
+        debug!("Gracefully shut down");
+        true
     }
 }
 
@@ -235,6 +291,8 @@ pub struct TaskExecutor {
     panicked_tasks_tx: UnboundedSender<PanickedTaskError>,
     // Task Executor Metrics
     metrics: TaskExecutorMetrics,
+    /// The number of alive [GracefulShutdownGuard](shutdown::GracefulShutdownGuard)s.
+    graceful_tasks: Arc<AtomicUsize>,
 }
 
 // === impl TaskExecutor ===
@@ -250,6 +308,12 @@ impl TaskExecutor {
         &self.on_shutdown
     }
 
+    /// Returns a [GracefulShutdown] signal that lets a task delay the shutdown until its current
+    /// unit of work is finished, see [TaskManager::graceful_shutdown_with_timeout].
+    pub fn on_graceful_shutdown_signal(&self) -> GracefulShutdown {
+        GracefulShutdown::new(self.on_shutdown.clone(), self.graceful_tasks.clone())
+    }
+
     /// Spawns a future on the tokio runtime depending on the [TaskKind]
     fn spawn_on_rt<F>(&self, fut: F, task_kind: TaskKind) -> JoinHandle<()>
     where
@@ -324,6 +388,7 @@ impl TaskExecutor {
         name: &'static str,
         fut: F,
         task_kind: TaskKind,
+        abort_on_shutdown: bool,
     ) -> JoinHandle<()>
     where
         F: Future<Output = ()> + Send + 'static,
@@ -343,7 +408,11 @@ impl TaskExecutor {
 
         let task = async move {
             pin_mut!(task);
-            let _ = select(on_shutdown, task).await;
+            if abort_on_shutdown {
+                let _ = select(on_shutdown, task).await;
+            } else {
+                let _ = task.await;
+            }
         };
 
         self.spawn_on_rt(task, task_kind)
@@ -357,7 +426,20 @@ impl TaskExecutor {
     where
         F: Future<Output = ()> + Send + 'static,
     {
-        self.spawn_critical_as(name, fut, TaskKind::Blocking)
+        self.spawn_critical_as(name, fut, TaskKind::Blocking, true)
+    }
+
+    /// This spawns a critical blocking task onto the runtime.
+    /// Unlike [TaskExecutor::spawn_critical_blocking], the given future isn't dropped when the
+    /// [Shutdown] signal is received, so it can finish its current unit of work while holding a
+    /// [GracefulShutdownGuard](shutdown::GracefulShutdownGuard).
+    ///
+    /// If this task panics, the [`TaskManager`] is notified.
+    pub fn spawn_critical_blocking_graceful<F>(&self, name: &'static str, fut: F) -> JoinHandle<()>
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        self.spawn_critical_as(name, fut, TaskKind::Blocking, false)
     }
 
     /// This spawns a critical task onto the runtime.
@@ -368,7 +450,7 @@ impl TaskExecutor {
     where
         F: Future<Output = ()> + Send + 'static,
     {
-        self.spawn_critical_as(name, fut, TaskKind::Default)
+        self.spawn_critical_as(name, fut, TaskKind::Default, true)
     }
 
     /// This spawns a critical task onto the runtime.
@@ -423,6 +505,14 @@ impl TaskSpawner for TaskExecutor {
     ) -> JoinHandle<()> {
         TaskExecutor::spawn_critical_blocking(self, name, fut)
     }
+
+    fn spawn_critical_blocking_graceful(
+        &self,
+        name: &'static str,
+        fut: BoxFuture<'static, ()>,
+    ) -> JoinHandle<()> {
+        TaskExecutor::spawn_critical_blocking_graceful(self, name, fut)
+    }
 }
 
 /// Determines how a task is spawned
@@ -513,4 +603,46 @@ mod tests {
 
         handle.block_on(shutdown);
     }
+
+    // Tests that a graceful shutdown waits for the guards of the spawned tasks
+    #[test]
+    fn test_graceful_shutdown() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let manager = TaskManager::new(runtime.handle().clone());
+        let executor = manager.executor();
+        let graceful = executor.on_graceful_shutdown_signal();
+        let guard = graceful.try_guard().unwrap();
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(100));
+            tx.send(()).unwrap();
+            drop(guard);
+        });
+
+        assert!(manager.graceful_shutdown_with_timeout(Duration::from_secs(10)));
+        // the guard was dropped after the message was sent
+        assert!(rx.try_recv().is_ok());
+        assert!(graceful.try_guard().is_none());
+    }
+
+    // Tests that a graceful critical task isn't dropped at an await point on shutdown
+    #[test]
+    fn test_graceful_critical_task_finishes() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let manager = TaskManager::new(runtime.handle().clone());
+        let executor = manager.executor();
+        let graceful = executor.on_graceful_shutdown_signal();
+        let guard = graceful.try_guard().unwrap();
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        executor.spawn_critical_blocking_graceful("graceful", async move {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            tx.send(()).unwrap();
+            drop(guard);
+        });
+
+        assert!(manager.graceful_shutdown_with_timeout(Duration::from_secs(10)));
+        assert!(rx.try_recv().is_ok());
+    }
 }
@@ -7,6 +7,10 @@ use futures_util::{
 use std::{
     future::Future,
     pin::Pin,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
     task::{Context, Poll},
 };
 use tokio::sync::oneshot;
@@ -28,6 +32,61 @@ impl Future for Shutdown {
     }
 }
 
+impl Shutdown {
+    /// Returns `true` if the shutdown event has been fired.
+    pub fn is_fired(&self) -> bool {
+        self.clone().now_or_never().is_some()
+    }
+}
+
+/// A shutdown signal that lets tasks finish their current unit of work, e.g. a database commit,
+/// before the process exits.
+///
+/// See [crate::TaskManager::graceful_shutdown_with_timeout].
+#[derive(Debug, Clone)]
+pub struct GracefulShutdown {
+    shutdown: Shutdown,
+    guards: Arc<AtomicUsize>,
+}
+
+impl GracefulShutdown {
+    pub(crate) fn new(shutdown: Shutdown, guards: Arc<AtomicUsize>) -> Self {
+        Self { shutdown, guards }
+    }
+
+    /// Returns `true` if the shutdown event has been fired.
+    pub fn is_fired(&self) -> bool {
+        self.shutdown.is_fired()
+    }
+
+    /// Returns a guard that delays the graceful shutdown until it's dropped, or `None` if the
+    /// shutdown event has been fired already.
+    pub fn try_guard(&self) -> Option<GracefulShutdownGuard> {
+        // The guard is registered before the signal is checked, so either the shutdown waits for
+        // the guard or the caller observes the signal.
+        let guard = GracefulShutdownGuard::new(self.guards.clone());
+        (!self.is_fired()).then_some(guard)
+    }
+}
+
+/// Delays a graceful shutdown until it's dropped, see [GracefulShutdown::try_guard].
+#[derive(Debug)]
+#[must_use = "the shutdown is only delayed while the guard is alive"]
+pub struct GracefulShutdownGuard(Arc<AtomicUsize>);
+
+impl GracefulShutdownGuard {
+    fn new(guards: Arc<AtomicUsize>) -> Self {
+        guards.fetch_add(1, Ordering::SeqCst);
+        Self(guards)
+    }
+}
+
+impl Drop for GracefulShutdownGuard {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
 /// Shutdown signal that fires either manually or on drop by closing the channel
 #[derive(Debug)]
 pub struct Signal(oneshot::Sender<()>);
@@ -86,6 +145,23 @@ mod tests {
         join_all(tasks).await;
     }
 
+    #[test]
+    fn test_graceful_shutdown_guard() {
+        let (signal, shutdown) = signal();
+        let guards = Arc::new(AtomicUsize::new(0));
+        let graceful = GracefulShutdown::new(shutdown, guards.clone());
+
+        let guard = graceful.try_guard().unwrap();
+        assert_eq!(guards.load(Ordering::SeqCst), 1);
+        drop(guard);
+        assert_eq!(guards.load(Ordering::SeqCst), 0);
+
+        signal.fire();
+        assert!(graceful.is_fired());
+        assert!(graceful.try_guard().is_none());
+        assert_eq!(guards.load(Ordering::SeqCst), 0);
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn test_drop_signal_from_thread() {
         let (signal, shutdown) = signal();
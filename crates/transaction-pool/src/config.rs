@@ -38,6 +38,11 @@ pub struct SubPoolLimit {
     pub max_txs: usize,
     /// Maximum combined size (in bytes) of transactions in the pool.
     pub max_size: usize,
+    /// Maximum amount of transactions of a single sender in the pool.
+    ///
+    /// If exceeded, the sender's transactions with the highest nonces are evicted. Unlimited if
+    /// `None`.
+    pub max_txs_per_sender: Option<usize>,
 }
 
 impl SubPoolLimit {
@@ -46,6 +51,12 @@ impl SubPoolLimit {
     pub fn is_exceeded(&self, txs: usize, size: usize) -> bool {
         self.max_txs < txs || self.max_size < size
     }
+
+    /// Sets the maximum amount of transactions of a single sender in the pool.
+    pub fn with_max_txs_per_sender(mut self, max_txs_per_sender: usize) -> Self {
+        self.max_txs_per_sender = Some(max_txs_per_sender);
+        self
+    }
 }
 
 impl Default for SubPoolLimit {
@@ -54,6 +65,7 @@ impl Default for SubPoolLimit {
         Self {
             max_txs: TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
             max_size: TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT * 1024 * 1024,
+            max_txs_per_sender: None,
         }
     }
 }
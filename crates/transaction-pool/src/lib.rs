@@ -156,7 +156,10 @@ pub use crate::{
     },
     error::PoolResult,
     ordering::{GasCostOrdering, TransactionOrdering},
-    pool::{AllTransactionsEvents, PoolTransactionEvent, TransactionEvent, TransactionEvents},
+    pool::{
+        AllTransactionsEvents, DiscardReason, PoolTransactionEvent, TransactionEvent,
+        TransactionEvents,
+    },
     traits::{
         AllPoolTransactions, BestTransactions, BlockInfo, CanonicalStateUpdate, ChangedAccount,
         NewTransactionEvent, PoolSize, PoolTransaction, PooledTransaction, PropagateKind,
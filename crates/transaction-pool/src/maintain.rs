@@ -70,7 +70,7 @@ where
         // based on the tracked state
 
         match event {
-            CanonStateNotification::Reorg { old, new } => {
+            CanonStateNotification::Reorg { old, new, .. } => {
                 let (old_blocks, old_state) = old.inner();
                 let (new_blocks, new_state) = new.inner();
                 let new_tip = new_blocks.tip();
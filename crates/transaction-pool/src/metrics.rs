@@ -15,6 +15,9 @@ pub struct TxPoolMetrics {
     pub(crate) invalid_transactions: Counter,
     /// Number of removed transactions from the pool
     pub(crate) removed_transactions: Counter,
+    /// Number of transactions evicted because their sender exceeded the per sender limit of a
+    /// sub-pool
+    pub(crate) sender_limit_evicted_transactions: Counter,
 
     /// Number of transactions in the pending sub-pool
     pub(crate) pending_pool_transactions: Gauge,
@@ -34,3 +37,11 @@ pub struct TxPoolMetrics {
     /// Number of all transactions of all sub-pools: pending + basefee + queued
     pub(crate) total_transactions: Gauge,
 }
+
+/// Metrics for the listeners of all transaction events
+#[derive(Metrics)]
+#[metrics(scope = "transaction_pool")]
+pub struct AllTransactionsEventsMetrics {
+    /// Number of transaction events that were dropped because a listener was full
+    pub(crate) dropped_events: Counter,
+}
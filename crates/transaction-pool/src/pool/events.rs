@@ -45,8 +45,8 @@ pub enum TransactionEvent {
     ///
     /// E.g. same (sender + nonce) pair
     Replaced(TxHash),
-    /// Transaction was dropped due to configured limits.
-    Discarded,
+    /// Transaction was removed from the pool for the given reason.
+    Discarded(DiscardReason),
     /// Transaction became invalid indefinitely.
     Invalid,
     /// Transaction was propagated to peers.
@@ -61,7 +61,22 @@ impl TransactionEvent {
             self,
             TransactionEvent::Replaced(_) |
                 TransactionEvent::Mined(_) |
-                TransactionEvent::Discarded
+                TransactionEvent::Discarded(_)
         )
     }
 }
+
+/// The reason a transaction was discarded from the pool.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum DiscardReason {
+    /// The transaction failed validation and was never added to the pool.
+    Invalid,
+    /// The transaction was evicted to enforce the configured pool size limits.
+    PoolSizeLimit,
+    /// The transaction can no longer be included after a state change, e.g. because its nonce was
+    /// used by another transaction.
+    Stale,
+    /// The transaction was removed from the pool on request.
+    Removed,
+}
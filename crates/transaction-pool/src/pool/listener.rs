@@ -1,7 +1,8 @@
 //! Listeners for the transaction-pool
 
 use crate::{
-    pool::events::{PoolTransactionEvent, TransactionEvent},
+    metrics::AllTransactionsEventsMetrics,
+    pool::events::{DiscardReason, PoolTransactionEvent, TransactionEvent},
     traits::PropagateKind,
 };
 use futures_util::Stream;
@@ -46,6 +47,8 @@ impl Stream for TransactionEvents {
 }
 
 /// A Stream that receives [PoolTransactionEvent] for _all_ transaction.
+///
+/// The stream is bounded, if the receiver can't keep up, new events are dropped.
 #[derive(Debug)]
 #[must_use = "streams do nothing unless polled"]
 pub struct AllTransactionsEvents {
@@ -121,8 +124,13 @@ impl PoolEventBroadcast {
     }
 
     /// Notify listeners about a transaction that was added to the queued pool.
-    pub(crate) fn queued(&mut self, tx: &TxHash) {
+    pub(crate) fn queued(&mut self, tx: &TxHash, replaced: Option<&TxHash>) {
         self.broadcast_event(tx, TransactionEvent::Queued);
+
+        if let Some(replaced) = replaced {
+            // notify listeners that this transaction was replaced
+            self.broadcast_event(replaced, TransactionEvent::Replaced(*tx));
+        }
     }
 
     /// Notify listeners about a transaction that was propagated.
@@ -131,8 +139,8 @@ impl PoolEventBroadcast {
     }
 
     /// Notify listeners about a transaction that was discarded.
-    pub(crate) fn discarded(&mut self, tx: &TxHash) {
-        self.broadcast_event(tx, TransactionEvent::Discarded);
+    pub(crate) fn discarded(&mut self, tx: &TxHash, reason: DiscardReason) {
+        self.broadcast_event(tx, TransactionEvent::Discarded(reason));
     }
 
     /// Notify listeners that the transaction was mined
@@ -148,6 +156,8 @@ impl PoolEventBroadcast {
 struct AllPoolEventsBroadcaster {
     /// Corresponding sender half(s) for event listener channel
     senders: Vec<Sender<PoolTransactionEvent>>,
+    /// Tracks the events that were dropped because a listener was full.
+    metrics: AllTransactionsEventsMetrics,
 }
 
 impl AllPoolEventsBroadcaster {
@@ -155,7 +165,11 @@ impl AllPoolEventsBroadcaster {
     fn broadcast(&mut self, tx_hash: TxHash, event: TransactionEvent) {
         self.senders.retain(|sender| {
             match sender.try_send(PoolTransactionEvent::new(tx_hash, event.clone())) {
-                Ok(_) | Err(TrySendError::Full(_)) => true,
+                Ok(_) => true,
+                Err(TrySendError::Full(_)) => {
+                    self.metrics.dropped_events.increment(1);
+                    true
+                }
                 Err(TrySendError::Closed(_)) => false,
             }
         })
@@ -93,7 +93,7 @@ use tokio::sync::mpsc;
 use tracing::debug;
 
 mod events;
-pub use events::{PoolTransactionEvent, TransactionEvent};
+pub use events::{DiscardReason, PoolTransactionEvent, TransactionEvent};
 
 mod listener;
 pub use listener::{AllTransactionsEvents, TransactionEvents};
@@ -304,12 +304,12 @@ where
             }
             TransactionValidationOutcome::Invalid(tx, err) => {
                 let mut listener = self.event_listener.write();
-                listener.discarded(tx.hash());
+                listener.discarded(tx.hash(), DiscardReason::Invalid);
                 Err(PoolError::InvalidTransaction(*tx.hash(), err))
             }
             TransactionValidationOutcome::Error(tx_hash, err) => {
                 let mut listener = self.event_listener.write();
-                listener.discarded(&tx_hash);
+                listener.discarded(&tx_hash, DiscardReason::Invalid);
                 Err(PoolError::Other(tx_hash, err))
             }
         }
@@ -406,7 +406,7 @@ where
 
         mined.iter().for_each(|tx| listener.mined(tx, block_hash));
         promoted.iter().for_each(|tx| listener.pending(tx, None));
-        discarded.iter().for_each(|tx| listener.discarded(tx));
+        discarded.iter().for_each(|tx| listener.discarded(tx, DiscardReason::Stale));
     }
 
     /// Fire events for the newly added transaction.
@@ -415,14 +415,14 @@ where
 
         match tx {
             AddedTransaction::Pending(tx) => {
-                let AddedPendingTransaction { transaction, promoted, discarded, .. } = tx;
+                let AddedPendingTransaction { transaction, replaced, promoted, discarded } = tx;
 
-                listener.pending(transaction.hash(), None);
+                listener.pending(transaction.hash(), replaced.as_ref());
                 promoted.iter().for_each(|tx| listener.pending(tx, None));
-                discarded.iter().for_each(|tx| listener.discarded(tx));
+                discarded.iter().for_each(|tx| listener.discarded(tx, DiscardReason::Stale));
             }
-            AddedTransaction::Parked { transaction, .. } => {
-                listener.queued(transaction.hash());
+            AddedTransaction::Parked { transaction, replaced, .. } => {
+                listener.queued(transaction.hash(), replaced.as_ref());
             }
         }
     }
@@ -460,7 +460,7 @@ where
 
         let mut listener = self.event_listener.write();
 
-        removed.iter().for_each(|tx| listener.discarded(tx.hash()));
+        removed.iter().for_each(|tx| listener.discarded(tx.hash(), DiscardReason::Removed));
 
         removed
     }
@@ -517,7 +517,18 @@ where
 
     /// Enforces the size limits of pool and returns the discarded transactions if violated.
     pub(crate) fn discard_worst(&self) -> HashSet<TxHash> {
-        self.pool.write().discard_worst().into_iter().map(|tx| *tx.hash()).collect()
+        let discarded = self
+            .pool
+            .write()
+            .discard_worst()
+            .into_iter()
+            .map(|tx| *tx.hash())
+            .collect::<HashSet<_>>();
+
+        let mut listener = self.event_listener.write();
+        discarded.iter().for_each(|tx| listener.discarded(tx, DiscardReason::PoolSizeLimit));
+
+        discarded
     }
 }
 
@@ -532,6 +543,8 @@ impl<V: TransactionValidator, T: TransactionOrdering> fmt::Debug for PoolInner<V
 pub struct AddedPendingTransaction<T: PoolTransaction> {
     /// Inserted transaction.
     transaction: Arc<ValidPoolTransaction<T>>,
+    /// The transaction that was replaced by the inserted transaction.
+    replaced: Option<TxHash>,
     /// transactions promoted to the ready queue
     promoted: Vec<TxHash>,
     /// transaction that failed and became discarded
@@ -548,6 +561,8 @@ pub enum AddedTransaction<T: PoolTransaction> {
     Parked {
         /// Inserted transaction.
         transaction: Arc<ValidPoolTransaction<T>>,
+        /// The transaction that was replaced by the inserted transaction.
+        replaced: Option<TxHash>,
         /// The subpool it was moved to.
         subpool: SubPool,
     },
@@ -577,7 +592,7 @@ impl<T: PoolTransaction> AddedTransaction<T> {
             AddedTransaction::Pending(tx) => {
                 NewTransactionEvent { subpool: SubPool::Pending, transaction: tx.transaction }
             }
-            AddedTransaction::Parked { transaction, subpool } => {
+            AddedTransaction::Parked { transaction, subpool, .. } => {
                 NewTransactionEvent { transaction, subpool }
             }
         }
@@ -314,6 +314,7 @@ impl<T: TransactionOrdering> TxPool<T> {
 
         match self.all_transactions.insert_tx(tx, on_chain_balance, on_chain_nonce) {
             Ok(InsertOk { transaction, move_to, replaced_tx, updates, .. }) => {
+                let replaced = replaced_tx.as_ref().map(|(tx, _)| *tx.hash());
                 self.add_new_transaction(transaction.clone(), replaced_tx, move_to);
                 // Update inserted transactions metric
                 self.metrics.inserted_transactions.increment(1);
@@ -323,11 +324,12 @@ impl<T: TransactionOrdering> TxPool<T> {
                 let res = if move_to.is_pending() {
                     AddedTransaction::Pending(AddedPendingTransaction {
                         transaction,
+                        replaced,
                         promoted,
                         discarded,
                     })
                 } else {
-                    AddedTransaction::Parked { transaction, subpool: move_to }
+                    AddedTransaction::Parked { transaction, replaced, subpool: move_to }
                 };
 
                 Ok(res)
@@ -556,6 +558,8 @@ impl<T: TransactionOrdering> TxPool<T> {
             };
         }
 
+        self.discard_sender_excess(&mut removed);
+
         discard_worst!(
             self, removed, [
                 pending_limit  => pending_pool,
@@ -567,6 +571,54 @@ impl<T: TransactionOrdering> TxPool<T> {
         removed
     }
 
+    /// Evicts the transactions of all senders that exceed the per sender limit of a sub-pool.
+    ///
+    /// The transactions of a sender with the highest nonces are evicted first, together with all
+    /// their descendants, so that no nonce gaps are introduced.
+    ///
+    /// All removed transactions are added to the `removed` vec.
+    fn discard_sender_excess(
+        &mut self,
+        removed: &mut Vec<Arc<ValidPoolTransaction<T::Transaction>>>,
+    ) {
+        let limits = [
+            (SubPool::Pending, self.config.pending_limit.max_txs_per_sender),
+            (SubPool::BaseFee, self.config.basefee_limit.max_txs_per_sender),
+            (SubPool::Queued, self.config.queued_limit.max_txs_per_sender),
+        ];
+        if limits.iter().all(|(_, limit)| limit.is_none()) {
+            return
+        }
+
+        // the first transaction of every sender that exceeds a limit, transactions are sorted by
+        // sender and nonce
+        let mut to_remove = Vec::new();
+        let mut counts = [0usize; 3];
+        let mut current_sender = None;
+        for (id, tx) in self.all_transactions.txs.iter() {
+            if current_sender != Some(id.sender) {
+                current_sender = Some(id.sender);
+                counts = [0; 3];
+            }
+            let Some(idx) = limits.iter().position(|(pool, _)| *pool == tx.subpool) else {
+                continue
+            };
+            counts[idx] += 1;
+            if limits[idx].1.map_or(false, |limit| counts[idx] == limit + 1) {
+                to_remove.push(*id);
+            }
+        }
+
+        let before = removed.len();
+        for id in to_remove {
+            if let Some(tx) = self.remove_transaction(&id) {
+                removed.push(tx);
+                self.remove_descendants(&id, removed);
+            }
+        }
+        self.metrics.sender_limit_evicted_transactions.increment((removed.len() - before) as u64);
+    }
+
     /// Number of transactions in the entire pool
     pub(crate) fn len(&self) -> usize {
         self.all_transactions.len()
@@ -1336,6 +1388,7 @@ mod tests {
     use crate::{
         test_utils::{MockOrdering, MockTransaction, MockTransactionFactory},
         traits::TransactionOrigin,
+        SubPoolLimit,
     };
 
     #[test]
@@ -1637,4 +1690,55 @@ mod tests {
 
         assert_eq!(pool.all_transactions.txs.get(&id).unwrap().subpool, SubPool::BaseFee)
     }
+
+    #[test]
+    fn add_transaction_replaced() {
+        let mut f = MockTransactionFactory::default();
+        let mut pool = TxPool::new(MockOrdering::default(), Default::default());
+
+        let tx = MockTransaction::eip1559().inc_price().inc_limit();
+        let first = f.validated(tx.clone());
+        pool.add_transaction(first.clone(), U256::from(1_000), 0).unwrap();
+
+        let replacement = f.validated(tx.rng_hash().inc_price());
+        let added = pool.add_transaction(replacement.clone(), U256::from(1_000), 0).unwrap();
+        let replaced = match added {
+            AddedTransaction::Pending(AddedPendingTransaction { replaced, .. }) => replaced,
+            AddedTransaction::Parked { replaced, .. } => replaced,
+        };
+        assert_eq!(replaced, Some(*first.hash()));
+        assert!(!pool.contains(first.hash()));
+        assert!(pool.contains(replacement.hash()));
+    }
+
+    #[test]
+    fn discard_sender_excess() {
+        let mut f = MockTransactionFactory::default();
+        let config = PoolConfig {
+            queued_limit: SubPoolLimit::default().with_max_txs_per_sender(2),
+            ..Default::default()
+        };
+        let mut pool = TxPool::new(MockOrdering::default(), config);
+
+        // nonce gap, all transactions are queued
+        let mut tx = MockTransaction::eip1559();
+        let mut hashes = Vec::new();
+        for _ in 0..4 {
+            tx = tx.next();
+            let validated = f.validated(tx.clone());
+            hashes.push(*validated.hash());
+            pool.add_transaction(validated, U256::from(1_000), 0).unwrap();
+        }
+        assert_eq!(pool.queued_pool.len(), 4);
+
+        let removed = pool.discard_worst();
+        let mut removed = removed.iter().map(|tx| *tx.hash()).collect::<Vec<_>>();
+        removed.sort();
+        let mut expected = hashes[2..].to_vec();
+        expected.sort();
+        assert_eq!(removed, expected);
+        assert_eq!(pool.queued_pool.len(), 2);
+        assert!(pool.contains(&hashes[0]));
+        assert!(pool.contains(&hashes[1]));
+    }
 }
@@ -0,0 +1,22 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use reth_trie::bench::bench_state_root;
+use std::time::Duration;
+
+/// Benchmarks the state root computation over tries of increasing size.
+///
+/// Only the root computation is measured, the database setup of every iteration is excluded.
+pub fn state_root(c: &mut Criterion) {
+    let mut group = c.benchmark_group("State Root");
+    group.sample_size(10);
+
+    for accounts in [1_000, 10_000, 100_000] {
+        group.bench_function(format!("accounts: {accounts}"), |b| {
+            b.iter_custom(|iters| {
+                (0..iters).map(|_| bench_state_root(accounts).elapsed).sum::<Duration>()
+            })
+        });
+    }
+}
+
+criterion_group!(state_root_benches, state_root);
+criterion_main!(state_root_benches);
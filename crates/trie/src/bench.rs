@@ -0,0 +1,82 @@
+use crate::StateRoot;
+use reth_db::{database::Database, tables, test_utils::create_test_rw_db, transaction::DbTxMut};
+use reth_primitives::{keccak256, Account, H256, U256};
+use std::time::{Duration, Instant};
+
+/// The outcome of [bench_state_root].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BenchResult {
+    /// The number of accounts in the trie.
+    pub accounts: usize,
+    /// The computed state root.
+    pub root: H256,
+    /// The time it took to compute the state root, excluding the setup of the database.
+    pub elapsed: Duration,
+    /// The number of trie nodes that were hashed and collected as updates, i.e. the branch nodes
+    /// of the account trie.
+    ///
+    /// Together with [BenchResult::accounts], the number of hashed leaves, this is the amount of
+    /// hashing work the computation did.
+    pub hashed_nodes: usize,
+}
+
+/// Builds the state trie over the given number of synthetic accounts and measures the state root
+/// computation.
+///
+/// The accounts are written to the hashed accounts table of a fresh temporary database, and the
+/// root is computed from scratch the same way the Merkle stage does, with [StateRoot]. The accounts
+/// are derived from their index, so the result is reproducible for the same number of accounts.
+///
+/// # Panics
+///
+/// If the temporary database can't be created, written to, or read from.
+pub fn bench_state_root(accounts: usize) -> BenchResult {
+    let db = create_test_rw_db();
+    db.update(|tx| {
+        for index in 0..accounts {
+            let (hashed_address, account) = synthetic_account(index);
+            tx.put::<tables::HashedAccount>(hashed_address, account)?;
+        }
+        Ok::<_, reth_db::DatabaseError>(())
+    })
+    .expect("failed to open transaction")
+    .expect("failed to write accounts");
+
+    let tx = db.tx().expect("failed to open transaction");
+    let started_at = Instant::now();
+    let (root, updates) = StateRoot::new(&tx).root_with_updates().expect("failed to compute root");
+    let elapsed = started_at.elapsed();
+
+    BenchResult { accounts, root, elapsed, hashed_nodes: updates.len() }
+}
+
+/// Returns the hashed address and the account with the given index.
+fn synthetic_account(index: usize) -> (H256, Account) {
+    let index = index as u64;
+    let account = Account {
+        nonce: index,
+        balance: U256::from(index) * U256::from(1_000),
+        bytecode_hash: None,
+    };
+    (keccak256(index.to_be_bytes()), account)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::state_root_prehashed;
+
+    #[test]
+    fn bench_matches_reference_root() {
+        let result = bench_state_root(100);
+        assert_eq!(result.accounts, 100);
+        assert!(result.hashed_nodes > 0);
+
+        let expected = state_root_prehashed(
+            (0..100)
+                .map(synthetic_account)
+                .map(|(key, account)| (key, (account, Vec::<(H256, U256)>::new()))),
+        );
+        assert_eq!(result.root, expected);
+    }
+}
@@ -2,11 +2,17 @@ use super::{HashedAccountCursor, HashedCursorFactory, HashedStorageCursor};
 use crate::prefix_set::{PrefixSet, PrefixSetMut};
 use reth_db::{
     cursor::{DbCursorRO, DbDupCursorRO},
+    models::{AccountBeforeTx, BlockNumberAddress},
     tables,
     transaction::{DbTx, DbTxGAT},
 };
-use reth_primitives::{trie::Nibbles, Account, StorageEntry, H256, U256};
-use std::collections::{HashMap, HashSet};
+use reth_primitives::{
+    keccak256, trie::Nibbles, Account, Address, BlockNumber, StorageEntry, H256, U256,
+};
+use std::{
+    collections::{HashMap, HashSet},
+    ops::RangeInclusive,
+};
 
 /// The post state account storage with hashed slots.
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -113,6 +119,55 @@ impl HashedPostState {
         self.storages.insert(hashed_address, hashed_storage);
     }
 
+    /// Reads the account and storage changesets of the given block range and returns the hashed
+    /// state that reverts all changes made in that range.
+    ///
+    /// Overlaying the current hashed state with the reverts yields the hashed state as it was
+    /// before the first block of the range.
+    pub fn from_reverts<'a, TX: DbTx<'a>>(
+        tx: &TX,
+        range: RangeInclusive<BlockNumber>,
+    ) -> Result<Self, reth_db::DatabaseError> {
+        // Only the first change of every account and slot in the range holds the value before
+        // the range.
+        let mut accounts = HashMap::<Address, Option<Account>>::default();
+        let mut account_changesets = tx.cursor_read::<tables::AccountChangeSet>()?;
+        for entry in account_changesets.walk_range(range.clone())? {
+            let (_, AccountBeforeTx { address, info }) = entry?;
+            accounts.entry(address).or_insert(info);
+        }
+
+        let mut storages = HashMap::<Address, HashMap<H256, U256>>::default();
+        let mut storage_changesets = tx.cursor_dup_read::<tables::StorageChangeSet>()?;
+        for entry in storage_changesets.walk_range(BlockNumberAddress::range(range))? {
+            let (BlockNumberAddress((_, address)), StorageEntry { key, value }) = entry?;
+            storages.entry(address).or_default().entry(key).or_insert(value);
+        }
+
+        let mut hashed_state = Self::default();
+        for (address, info) in accounts {
+            let hashed_address = keccak256(address);
+            match info {
+                Some(account) => hashed_state.insert_account(hashed_address, account),
+                None => hashed_state.insert_cleared_account(hashed_address),
+            }
+        }
+        for (address, slots) in storages {
+            let mut hashed_storage = HashedStorage::new(false);
+            for (slot, value) in slots {
+                let hashed_slot = keccak256(slot);
+                if value == U256::ZERO {
+                    hashed_storage.insert_zero_valued_slot(hashed_slot);
+                } else {
+                    hashed_storage.insert_non_zero_valued_storage(hashed_slot, value);
+                }
+            }
+            hashed_state.insert_hashed_storage(keccak256(address), hashed_storage);
+        }
+
+        Ok(hashed_state.sorted())
+    }
+
     /// Construct (PrefixSet)[PrefixSet] from hashed post state.
     /// The prefix sets contain the hashed account and storage keys that have been changed in the
     /// post state.
@@ -17,7 +17,7 @@
 //!
 //! ## Feature Flags
 //!
-//! - `test-utils`: Export utilities for testing
+//! - `test-utils`: Export utilities for testing and benchmarking
 
 /// The Ethereum account as represented in the trie.
 pub mod account;
@@ -42,6 +42,10 @@ pub use errors::{StateRootError, StorageRootError};
 mod trie;
 pub use trie::{StateRoot, StorageRoot};
 
+/// Merkle proofs of accounts and storage slots.
+mod proof;
+pub use proof::{AccountProof, Proof, StorageProof};
+
 /// Buffer for trie updates.
 pub mod updates;
 
@@ -49,6 +53,10 @@ pub mod updates;
 mod progress;
 pub use progress::{IntermediateStateRootState, StateRootProgress};
 
+/// Reproducible benchmarks of the state root computation.
+#[cfg(any(test, feature = "test-utils"))]
+pub mod bench;
+
 /// Collection of trie-related test utilities.
 #[cfg(any(test, feature = "test-utils"))]
 pub mod test_utils;
@@ -0,0 +1,360 @@
+use crate::{
+    account::EthAccount,
+    hashed_cursor::{HashedAccountCursor, HashedCursorFactory, HashedStorageCursor},
+    prefix_set::PrefixSetMut,
+    trie_cursor::{AccountTrieCursor, StorageTrieCursor},
+    walker::TrieWalker,
+    StateRootError, StorageRoot, StorageRootError,
+};
+use reth_db::{tables, transaction::DbTx};
+use reth_primitives::{
+    keccak256,
+    proofs::EMPTY_ROOT,
+    trie::{HashBuilder, Nibbles},
+    Account, Address, Bytes, StorageEntry, H256, U256,
+};
+use reth_rlp::Encodable;
+use std::collections::HashMap;
+
+/// The merkle proof of an account and a subset of its storage slots.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct AccountProof {
+    /// The address of the account.
+    pub address: Address,
+    /// The account, `None` if it doesn't exist.
+    pub info: Option<Account>,
+    /// The RLP encoded trie nodes on the path from the state root to the account.
+    pub proof: Vec<Bytes>,
+    /// The storage root of the account.
+    pub storage_root: H256,
+    /// The storage proofs, in the order of the requested slots.
+    pub storage_proofs: Vec<StorageProof>,
+}
+
+/// The merkle proof of a storage slot.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct StorageProof {
+    /// The storage slot.
+    pub key: H256,
+    /// The value of the storage slot, zero if the slot is not set.
+    pub value: U256,
+    /// The RLP encoded trie nodes on the path from the storage root to the slot.
+    pub proof: Vec<Bytes>,
+}
+
+impl StorageProof {
+    /// Creates an empty proof for the given slot.
+    pub fn new(key: H256) -> Self {
+        Self { key, ..Default::default() }
+    }
+}
+
+/// Proof is used to generate merkle proofs of accounts and their storage.
+///
+/// Like the [StateRoot](crate::StateRoot), it recomputes the trie nodes along the changed prefixes
+/// and on the paths to the proof targets, and reuses the intermediate nodes of the database trie
+/// everywhere else.
+pub struct Proof<'a, 'b, TX, H> {
+    /// A reference to the database transaction.
+    pub tx: &'a TX,
+    /// The factory for hashed cursors.
+    pub hashed_cursor_factory: &'b H,
+    /// A set of account prefixes that have changed.
+    pub changed_account_prefixes: PrefixSetMut,
+    /// A map containing storage changes with the hashed address as key and a set of storage key
+    /// prefixes as the value.
+    pub changed_storage_prefixes: HashMap<H256, PrefixSetMut>,
+}
+
+impl<'a, 'tx, TX> Proof<'a, 'a, TX, TX>
+where
+    TX: DbTx<'tx> + HashedCursorFactory<'a>,
+{
+    /// Create a new [Proof] instance.
+    pub fn new(tx: &'a TX) -> Self {
+        Self {
+            tx,
+            hashed_cursor_factory: tx,
+            changed_account_prefixes: PrefixSetMut::default(),
+            changed_storage_prefixes: HashMap::default(),
+        }
+    }
+}
+
+impl<'a, 'b, TX, H> Proof<'a, 'b, TX, H> {
+    /// Set the changed account prefixes.
+    pub fn with_changed_account_prefixes(mut self, prefixes: PrefixSetMut) -> Self {
+        self.changed_account_prefixes = prefixes;
+        self
+    }
+
+    /// Set the changed storage prefixes.
+    pub fn with_changed_storage_prefixes(mut self, prefixes: HashMap<H256, PrefixSetMut>) -> Self {
+        self.changed_storage_prefixes = prefixes;
+        self
+    }
+
+    /// Set the hashed cursor factory.
+    pub fn with_hashed_cursor_factory<'c, HF>(
+        self,
+        hashed_cursor_factory: &'c HF,
+    ) -> Proof<'a, 'c, TX, HF> {
+        Proof {
+            tx: self.tx,
+            hashed_cursor_factory,
+            changed_account_prefixes: self.changed_account_prefixes,
+            changed_storage_prefixes: self.changed_storage_prefixes,
+        }
+    }
+}
+
+impl<'a, 'b, 'tx, TX, H> Proof<'a, 'b, TX, H>
+where
+    TX: DbTx<'tx>,
+    H: HashedCursorFactory<'b>,
+{
+    /// Generates the merkle proof of the given account and its storage slots.
+    ///
+    /// If the account doesn't exist, the proof shows its absence and the storage proofs are
+    /// empty.
+    pub fn account_proof(
+        &self,
+        address: Address,
+        slots: &[H256],
+    ) -> Result<AccountProof, StateRootError> {
+        let hashed_address = keccak256(address);
+        let target = Nibbles::unpack(hashed_address);
+
+        // make sure the walker descends into every node on the path to the target
+        let mut prefixes = self.changed_account_prefixes.clone();
+        prefixes.insert(target.clone());
+
+        let mut hashed_account_cursor = self.hashed_cursor_factory.hashed_account_cursor()?;
+        let mut trie_cursor =
+            AccountTrieCursor::new(self.tx.cursor_read::<tables::AccountsTrie>()?);
+        let mut walker = TrieWalker::new(&mut trie_cursor, prefixes.freeze());
+        let mut hash_builder = HashBuilder::default().with_proof_retainer(vec![target]);
+
+        let mut account_proof = AccountProof {
+            address,
+            storage_root: EMPTY_ROOT,
+            storage_proofs: slots.iter().copied().map(StorageProof::new).collect(),
+            ..Default::default()
+        };
+
+        let mut account_rlp = Vec::with_capacity(128);
+        while let Some(key) = walker.key() {
+            if walker.can_skip_current_node {
+                hash_builder.add_branch(key, walker.hash().unwrap(), walker.children_are_in_trie());
+            }
+
+            let seek_key = match walker.next_unprocessed_key() {
+                Some(key) => key,
+                None => break, // no more keys
+            };
+
+            let next_key = walker.advance()?;
+            let mut next_account_entry = hashed_account_cursor.seek(seek_key)?;
+            while let Some((hashed_entry_address, account)) = next_account_entry {
+                let account_nibbles = Nibbles::unpack(hashed_entry_address);
+                if let Some(ref key) = next_key {
+                    if key < &account_nibbles {
+                        break
+                    }
+                }
+
+                let storage_root = if hashed_entry_address == hashed_address {
+                    let (storage_root, storage_proofs) =
+                        self.storage_proofs(hashed_address, slots)?;
+                    account_proof.info = Some(account);
+                    account_proof.storage_root = storage_root;
+                    account_proof.storage_proofs = storage_proofs;
+                    storage_root
+                } else {
+                    StorageRoot::new_hashed_with_factory(
+                        self.tx,
+                        self.hashed_cursor_factory,
+                        hashed_entry_address,
+                    )
+                    .with_changed_prefixes(
+                        self.changed_storage_prefixes
+                            .get(&hashed_entry_address)
+                            .cloned()
+                            .unwrap_or_default()
+                            .freeze(),
+                    )
+                    .root()?
+                };
+
+                let account = EthAccount::from(account).with_storage_root(storage_root);
+                account_rlp.clear();
+                account.encode(&mut &mut account_rlp);
+                hash_builder.add_leaf(account_nibbles, &account_rlp);
+
+                next_account_entry = hashed_account_cursor.next()?;
+            }
+        }
+
+        hash_builder.root();
+        account_proof.proof = hash_builder.take_proof_nodes().into_values().collect();
+
+        Ok(account_proof)
+    }
+
+    /// Computes the storage root of the given account and the proofs of the given slots.
+    fn storage_proofs(
+        &self,
+        hashed_address: H256,
+        slots: &[H256],
+    ) -> Result<(H256, Vec<StorageProof>), StorageRootError> {
+        let mut hashed_storage_cursor = self.hashed_cursor_factory.hashed_storage_cursor()?;
+        let mut proofs = slots.iter().copied().map(StorageProof::new).collect::<Vec<_>>();
+
+        // short circuit on empty storage
+        if hashed_storage_cursor.is_storage_empty(hashed_address)? {
+            return Ok((EMPTY_ROOT, proofs))
+        }
+
+        let targets = slots.iter().map(|slot| Nibbles::unpack(keccak256(slot))).collect::<Vec<_>>();
+        let mut prefixes =
+            self.changed_storage_prefixes.get(&hashed_address).cloned().unwrap_or_default();
+        for target in &targets {
+            prefixes.insert(target.clone());
+        }
+
+        let mut trie_cursor = StorageTrieCursor::new(
+            self.tx.cursor_dup_read::<tables::StoragesTrie>()?,
+            hashed_address,
+        );
+        let mut walker = TrieWalker::new(&mut trie_cursor, prefixes.freeze());
+        let mut hash_builder = HashBuilder::default().with_proof_retainer(targets.clone());
+
+        while let Some(key) = walker.key() {
+            if walker.can_skip_current_node {
+                hash_builder.add_branch(key, walker.hash().unwrap(), walker.children_are_in_trie());
+            }
+
+            let seek_key = match walker.next_unprocessed_key() {
+                Some(key) => key,
+                None => break, // no more keys
+            };
+
+            let next_key = walker.advance()?;
+            let mut storage = hashed_storage_cursor.seek(hashed_address, seek_key)?;
+            while let Some(StorageEntry { key: hashed_key, value }) = storage {
+                let storage_key_nibbles = Nibbles::unpack(hashed_key);
+                if let Some(ref key) = next_key {
+                    if key < &storage_key_nibbles {
+                        break
+                    }
+                }
+
+                for (proof, target) in proofs.iter_mut().zip(&targets) {
+                    if *target == storage_key_nibbles {
+                        proof.value = value;
+                    }
+                }
+
+                hash_builder
+                    .add_leaf(storage_key_nibbles, reth_rlp::encode_fixed_size(&value).as_ref());
+                storage = hashed_storage_cursor.next()?;
+            }
+        }
+
+        let root = hash_builder.root();
+        let nodes = hash_builder.take_proof_nodes();
+        for (proof, target) in proofs.iter_mut().zip(&targets) {
+            proof.proof = nodes
+                .iter()
+                .filter(|(path, _)| target.has_prefix(path))
+                .map(|(_, node)| node.clone())
+                .collect();
+        }
+
+        Ok((root, proofs))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::StateRoot;
+    use reth_db::{test_utils::create_test_rw_db, transaction::DbTxMut, DatabaseEnv};
+    use reth_primitives::MAINNET;
+    use reth_provider::{DatabaseProviderRW, ProviderFactory};
+
+    /// Asserts that the proof starts at the given root and every node is referenced by its parent.
+    fn assert_proof_path(root: H256, proof: &[Bytes]) {
+        assert_eq!(keccak256(&proof[0]), root);
+        for (parent, child) in proof.iter().zip(proof.iter().skip(1)) {
+            let reference = keccak256(child);
+            assert!(parent.windows(32).any(|window| window == reference.as_bytes()));
+        }
+    }
+
+    fn insert_state(provider: &DatabaseProviderRW<'_, &DatabaseEnv>) -> Vec<Address> {
+        let tx = provider.tx_ref();
+        let addresses = (1..=100).map(Address::from_low_u64_be).collect::<Vec<_>>();
+        for (idx, address) in addresses.iter().enumerate() {
+            let hashed_address = keccak256(address);
+            let account =
+                Account { nonce: idx as u64, balance: U256::from(idx), ..Default::default() };
+            tx.put::<tables::HashedAccount>(hashed_address, account).unwrap();
+            for slot in 1..=idx as u64 {
+                let entry = StorageEntry {
+                    key: keccak256(H256::from_low_u64_be(slot)),
+                    value: U256::from(slot),
+                };
+                tx.put::<tables::HashedStorage>(hashed_address, entry).unwrap();
+            }
+        }
+        addresses
+    }
+
+    #[test]
+    fn account_and_storage_proofs() {
+        let db = create_test_rw_db();
+        let factory = ProviderFactory::new(db.as_ref(), MAINNET.clone());
+        let provider = factory.provider_rw().unwrap();
+        let addresses = insert_state(&provider);
+
+        // persist the intermediate nodes, so the proof is built on top of the database trie
+        let (root, updates) = StateRoot::new(provider.tx_ref()).root_with_updates().unwrap();
+        updates.flush(provider.tx_ref()).unwrap();
+
+        let address = addresses[50];
+        let slots = [H256::from_low_u64_be(3), H256::from_low_u64_be(1000)];
+        let proof = Proof::new(provider.tx_ref()).account_proof(address, &slots).unwrap();
+
+        assert_eq!(proof.info.map(|account| account.nonce), Some(50));
+        assert_proof_path(root, &proof.proof);
+        assert_eq!(
+            proof.storage_root,
+            StorageRoot::new(provider.tx_ref(), address).root().unwrap()
+        );
+
+        let [existing, missing] = &proof.storage_proofs[..] else { panic!("two proofs") };
+        assert_eq!(existing.value, U256::from(3));
+        assert_proof_path(proof.storage_root, &existing.proof);
+        assert_eq!(missing.value, U256::ZERO);
+        assert_proof_path(proof.storage_root, &missing.proof);
+    }
+
+    #[test]
+    fn missing_account_proof() {
+        let db = create_test_rw_db();
+        let factory = ProviderFactory::new(db.as_ref(), MAINNET.clone());
+        let provider = factory.provider_rw().unwrap();
+        insert_state(&provider);
+        let root = StateRoot::new(provider.tx_ref()).root().unwrap();
+
+        let address = Address::from_low_u64_be(1000);
+        let slots = [H256::from_low_u64_be(1)];
+        let proof = Proof::new(provider.tx_ref()).account_proof(address, &slots).unwrap();
+
+        assert_eq!(proof.info, None);
+        assert_eq!(proof.storage_root, EMPTY_ROOT);
+        assert_proof_path(root, &proof.proof);
+        assert_eq!(proof.storage_proofs, vec![StorageProof::new(slots[0])]);
+    }
+}